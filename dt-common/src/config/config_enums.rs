@@ -26,6 +26,12 @@ pub enum DbType {
     Foxlake,
     #[strum(serialize = "tidb")]
     Tidb,
+    // also for OpenSearch
+    #[strum(serialize = "elasticsearch")]
+    Elasticsearch,
+    // local files or files in s3
+    #[strum(serialize = "file")]
+    File,
 }
 
 #[derive(Display, EnumString, IntoStaticStr, Debug, Clone)]
@@ -98,6 +104,8 @@ pub enum PipelineType {
     Basic,
     #[strum(serialize = "http_server")]
     HttpServer,
+    #[strum(serialize = "grpc_server")]
+    GrpcServer,
 }
 
 #[derive(Clone, Debug, EnumString, IntoStaticStr, PartialEq, Default)]
@@ -116,3 +124,144 @@ pub enum MetaCenterType {
     #[strum(serialize = "dbengine")]
     DbEngine,
 }
+
+#[derive(Clone, Debug, Display, EnumString, IntoStaticStr, PartialEq, Default)]
+pub enum DataMarkerType {
+    // write a marker row / key in each transaction
+    #[default]
+    #[strum(serialize = "table")]
+    Table,
+    // pg only, tag sinker sessions with replication origins
+    #[strum(serialize = "replication_origin")]
+    ReplicationOrigin,
+}
+
+#[derive(Clone, Debug, Display, EnumString, IntoStaticStr, PartialEq, Default)]
+pub enum PgInsertMethod {
+    #[default]
+    #[strum(serialize = "insert")]
+    Insert,
+    #[strum(serialize = "copy_text")]
+    CopyText,
+    #[strum(serialize = "copy_binary")]
+    CopyBinary,
+}
+
+#[derive(Clone, Debug, Display, EnumString, IntoStaticStr, PartialEq, Default)]
+pub enum FileFormat {
+    #[default]
+    #[strum(serialize = "csv")]
+    Csv,
+    // json lines, one json object per row
+    #[strum(serialize = "jsonl")]
+    Jsonl,
+    #[strum(serialize = "parquet")]
+    Parquet,
+}
+
+#[derive(Clone, Debug, Display, EnumString, IntoStaticStr, PartialEq, Default)]
+pub enum MessageFormat {
+    // ape-dts's own avro encoding
+    #[default]
+    #[strum(serialize = "avro")]
+    Avro,
+    #[strum(serialize = "debezium_json")]
+    DebeziumJson,
+    // Debezium AvroConverter with Confluent schema registry
+    #[strum(serialize = "debezium_avro")]
+    DebeziumAvro,
+    #[strum(serialize = "canal_json")]
+    CanalJson,
+    #[strum(serialize = "maxwell_json")]
+    MaxwellJson,
+}
+
+// subject names of schemas registered in schema registry
+#[derive(Clone, Debug, Display, EnumString, IntoStaticStr, PartialEq, Default)]
+pub enum SchemaSubjectStrategy {
+    // {topic}-key / {topic}-value
+    #[default]
+    #[strum(serialize = "topic_name")]
+    TopicName,
+    // full name of the record, e.g. ape_dts.test_db.tb_1.Envelope
+    #[strum(serialize = "record_name")]
+    RecordName,
+    // {topic}-{record name}
+    #[strum(serialize = "topic_record_name")]
+    TopicRecordName,
+}
+
+#[derive(Clone, Debug, Display, EnumString, IntoStaticStr, PartialEq, Default)]
+pub enum KafkaSecurityProtocol {
+    #[default]
+    #[strum(serialize = "plaintext")]
+    Plaintext,
+    #[strum(serialize = "ssl")]
+    Ssl,
+    #[strum(serialize = "sasl_plaintext")]
+    SaslPlaintext,
+    #[strum(serialize = "sasl_ssl")]
+    SaslSsl,
+}
+
+// key of kafka messages
+#[derive(Clone, Debug, Display, EnumString, IntoStaticStr, PartialEq, Default)]
+pub enum KafkaKeyFormat {
+    // the key of message_format
+    #[default]
+    #[strum(serialize = "default")]
+    Default,
+    // values of primary / unique key columns separated by ','
+    #[strum(serialize = "pk_string")]
+    PkString,
+    // {"id":1}
+    #[strum(serialize = "json")]
+    Json,
+    // the same as the key of debezium_avro, registered in schema registry
+    #[strum(serialize = "avro")]
+    Avro,
+}
+
+#[derive(Clone, Debug, Display, EnumString, IntoStaticStr, PartialEq, Default)]
+pub enum KafkaPartitionStrategy {
+    // chosen by the producer by the hash of the message key
+    #[default]
+    #[strum(serialize = "default")]
+    Default,
+    #[strum(serialize = "pk_hash")]
+    PkHash,
+    #[strum(serialize = "table")]
+    Table,
+    #[strum(serialize = "column")]
+    Column,
+    #[strum(serialize = "fixed")]
+    Fixed,
+}
+
+#[derive(Clone, Debug, Display, EnumString, IntoStaticStr, PartialEq)]
+pub enum KafkaHeader {
+    #[strum(serialize = "schema")]
+    Schema,
+    #[strum(serialize = "table")]
+    Table,
+    #[strum(serialize = "op")]
+    Op,
+    #[strum(serialize = "position")]
+    Position,
+    // id of the source transaction, refer to transaction_topic
+    #[strum(serialize = "transaction")]
+    Transaction,
+}
+
+#[derive(Clone, Debug, Display, EnumString, IntoStaticStr, PartialEq, Default)]
+pub enum PgSlotLimitPolicy {
+    // only log errors
+    #[default]
+    #[strum(serialize = "none")]
+    None,
+    #[strum(serialize = "fail")]
+    Fail,
+    // drop the slot to release the retained wal, then fail
+    #[strum(serialize = "drop_slot")]
+    DropSlot,
+}
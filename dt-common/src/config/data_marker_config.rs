@@ -1,3 +1,5 @@
+use super::config_enums::DataMarkerType;
+
 #[derive(Clone, Default)]
 pub struct DataMarkerConfig {
     pub topo_name: String,
@@ -7,4 +9,5 @@ pub struct DataMarkerConfig {
     pub do_nodes: String,
     pub ignore_nodes: String,
     pub marker: String,
+    pub marker_type: DataMarkerType,
 }
@@ -1,5 +1,6 @@
 use super::{
-    config_enums::{DbType, ExtractType},
+    config_enums::{DbType, ExtractType, FileFormat, MessageFormat, PgSlotLimitPolicy},
+    kafka_security_config::KafkaSecurityConfig,
     s3_config::S3Config,
 };
 
@@ -66,6 +67,12 @@ pub enum ExtractorConfig {
         ddl_meta_tb: String,
         start_time_utc: String,
         end_time_utc: String,
+        streaming: bool,
+        slot_check_interval_secs: u64,
+        slot_lag_warn_mb: u64,
+        slot_lag_limit_mb: u64,
+        slot_limit_policy: PgSlotLimitPolicy,
+        drop_slot_on_finish: bool,
     },
 
     PgCheck {
@@ -141,10 +148,16 @@ pub enum ExtractorConfig {
     Kafka {
         url: String,
         group: String,
-        topic: String,
-        partition: i32,
+        topics: Vec<String>,
+        // empty: all partitions of the topics
+        partitions: Vec<i32>,
+        // partitions are assigned by group rebalancing instead of explicitly
+        subscribe: bool,
         offset: i64,
         ack_interval_secs: u64,
+        message_format: MessageFormat,
+        schema_registry_url: String,
+        security_config: KafkaSecurityConfig,
     },
 
     FoxlakeS3 {
@@ -154,6 +167,20 @@ pub enum ExtractorConfig {
         s3_config: S3Config,
         batch_size: usize,
     },
+
+    FileSnapshot {
+        // root directory, or the key prefix in bucket if s3_bucket is set,
+        // files of a table are in {url}/{schema}/{tb}/
+        url: String,
+        schema: String,
+        tb: String,
+        file_format: FileFormat,
+        csv_delimiter: u8,
+        // if false, csv columns are in the same order as the target table
+        csv_has_header: bool,
+        s3_config: S3Config,
+        batch_size: usize,
+    },
 }
 
 #[derive(Clone, Debug)]
@@ -0,0 +1,16 @@
+#[derive(Clone, Debug, Default)]
+pub struct HttpServerSecurityConfig {
+    // clients must send "Authorization: Bearer {auth_token}" if not empty
+    pub auth_token: String,
+    // https if set, in PEM
+    pub tls_cert_file: String,
+    pub tls_key_file: String,
+    // mTLS if set, clients must present certificates signed by this CA
+    pub tls_client_ca_file: String,
+}
+
+impl HttpServerSecurityConfig {
+    pub fn is_tls(&self) -> bool {
+        !self.tls_cert_file.is_empty()
+    }
+}
@@ -1,4 +1,4 @@
-use std::{any::type_name, fmt::Debug, fs::File, io::Read, str::FromStr};
+use std::{any::type_name, collections::BTreeMap, fmt::Debug, fs::File, io::Read, str::FromStr};
 
 use anyhow::bail;
 use configparser::ini::Ini;
@@ -58,6 +58,21 @@ impl IniLoader {
         default
     }
 
+    /// All non-empty values in section whose keys start with prefix, keys are returned without prefix
+    pub fn get_with_prefix(&self, section: &str, prefix: &str) -> BTreeMap<String, String> {
+        let mut result = BTreeMap::new();
+        if let Some(map) = self.ini.get_map_ref().get(section) {
+            for (key, value) in map.iter() {
+                if let (Some(key), Some(value)) = (key.strip_prefix(prefix), value) {
+                    if !key.is_empty() && !value.is_empty() {
+                        result.insert(key.to_string(), value.to_string());
+                    }
+                }
+            }
+        }
+        result
+    }
+
     fn parse_value<T>(section: &str, key: &str, value: &str) -> anyhow::Result<T>
     where
         T: FromStr,
@@ -0,0 +1,127 @@
+use std::collections::BTreeMap;
+
+use super::config_enums::KafkaSecurityProtocol;
+
+#[derive(Clone, Debug, Default)]
+pub struct KafkaSecurityConfig {
+    pub security_protocol: KafkaSecurityProtocol,
+    // PLAIN / SCRAM-SHA-256 / SCRAM-SHA-512
+    pub sasl_mechanism: String,
+    pub sasl_username: String,
+    pub sasl_password: String,
+    pub ssl_ca_location: String,
+    pub ssl_certificate_location: String,
+    pub ssl_key_location: String,
+    pub ssl_key_password: String,
+    pub ssl_verify_hostname: bool,
+    // librdkafka properties passed through as is, set by keys prefixed with "rdkafka."
+    pub properties: BTreeMap<String, String>,
+}
+
+impl KafkaSecurityConfig {
+    pub fn is_sasl(&self) -> bool {
+        matches!(
+            self.security_protocol,
+            KafkaSecurityProtocol::SaslPlaintext | KafkaSecurityProtocol::SaslSsl
+        )
+    }
+
+    pub fn is_ssl(&self) -> bool {
+        matches!(
+            self.security_protocol,
+            KafkaSecurityProtocol::Ssl | KafkaSecurityProtocol::SaslSsl
+        )
+    }
+
+    /// librdkafka client properties, passthrough properties override the others
+    pub fn to_rdkafka_properties(&self) -> BTreeMap<String, String> {
+        let mut properties = BTreeMap::new();
+        let mut set = |key: &str, value: &str| {
+            if !value.is_empty() {
+                properties.insert(key.to_string(), value.to_string());
+            }
+        };
+
+        let security_protocol: &str = self.security_protocol.clone().into();
+        set("security.protocol", security_protocol);
+        if self.is_sasl() {
+            set("sasl.mechanism", &self.sasl_mechanism);
+            set("sasl.username", &self.sasl_username);
+            set("sasl.password", &self.sasl_password);
+        }
+        if self.is_ssl() {
+            set("ssl.ca.location", &self.ssl_ca_location);
+            set("ssl.certificate.location", &self.ssl_certificate_location);
+            set("ssl.key.location", &self.ssl_key_location);
+            set("ssl.key.password", &self.ssl_key_password);
+            if !self.ssl_verify_hostname {
+                set("ssl.endpoint.identification.algorithm", "none");
+            }
+        }
+
+        for (key, value) in self.properties.iter() {
+            properties.insert(key.clone(), value.clone());
+        }
+        properties
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_rdkafka_properties() {
+        let config = KafkaSecurityConfig::default();
+        assert_eq!(
+            config.to_rdkafka_properties(),
+            BTreeMap::from([("security.protocol".into(), "plaintext".into())])
+        );
+
+        let config = KafkaSecurityConfig {
+            security_protocol: KafkaSecurityProtocol::SaslSsl,
+            sasl_mechanism: "SCRAM-SHA-512".into(),
+            sasl_username: "user".into(),
+            sasl_password: "pwd".into(),
+            ssl_ca_location: "/certs/ca.pem".into(),
+            ssl_verify_hostname: true,
+            properties: BTreeMap::from([
+                ("sasl.mechanism".into(), "PLAIN".into()),
+                ("client.id".into(), "ape-dts".into()),
+            ]),
+            ..Default::default()
+        };
+        let expected: BTreeMap<String, String> = [
+            ("security.protocol", "sasl_ssl"),
+            ("sasl.mechanism", "PLAIN"),
+            ("sasl.username", "user"),
+            ("sasl.password", "pwd"),
+            ("ssl.ca.location", "/certs/ca.pem"),
+            ("client.id", "ape-dts"),
+        ]
+        .into_iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect();
+        assert_eq!(config.to_rdkafka_properties(), expected);
+
+        // sasl options are ignored without sasl protocols
+        let config = KafkaSecurityConfig {
+            security_protocol: KafkaSecurityProtocol::Ssl,
+            sasl_username: "user".into(),
+            ssl_certificate_location: "/certs/client.pem".into(),
+            ssl_key_location: "/certs/client.key".into(),
+            ssl_verify_hostname: false,
+            ..Default::default()
+        };
+        let expected: BTreeMap<String, String> = [
+            ("security.protocol", "ssl"),
+            ("ssl.certificate.location", "/certs/client.pem"),
+            ("ssl.key.location", "/certs/client.key"),
+            ("ssl.endpoint.identification.algorithm", "none"),
+        ]
+        .into_iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect();
+        assert_eq!(config.to_rdkafka_properties(), expected);
+    }
+}
@@ -3,7 +3,9 @@ pub mod config_token_parser;
 pub mod data_marker_config;
 pub mod extractor_config;
 pub mod filter_config;
+pub mod http_server_security_config;
 pub mod ini_loader;
+pub mod kafka_security_config;
 pub mod meta_center_config;
 pub mod monitor_config;
 pub mod parallelizer_config;
@@ -13,5 +15,6 @@ pub mod resumer_config;
 pub mod router_config;
 pub mod runtime_config;
 pub mod s3_config;
+pub mod sequence_config;
 pub mod sinker_config;
 pub mod task_config;
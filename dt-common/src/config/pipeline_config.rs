@@ -1,4 +1,4 @@
-use super::config_enums::PipelineType;
+use super::{config_enums::PipelineType, http_server_security_config::HttpServerSecurityConfig};
 
 #[derive(Clone)]
 pub struct PipelineConfig {
@@ -13,5 +13,11 @@ pub struct PipelineConfig {
     // used when pipeline_type == http_server
     pub http_host: String,
     pub http_port: u64,
+    pub http_session_timeout_secs: u64,
     pub with_field_defs: bool,
+    pub http_security_config: HttpServerSecurityConfig,
+    // used when pipeline_type == grpc_server
+    pub grpc_host: String,
+    pub grpc_port: u64,
+    pub grpc_auth_token: String,
 }
@@ -0,0 +1,7 @@
+#[derive(Clone, Default)]
+pub struct SequenceConfig {
+    // sync sequence values / auto_increment counters once the task finishes, used for cutover
+    pub sync_on_finish: bool,
+    // sync periodically while the task is running, 0 to disable
+    pub sync_interval_secs: u64,
+}
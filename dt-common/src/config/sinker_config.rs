@@ -1,5 +1,11 @@
+use std::collections::HashMap;
+
 use super::{
-    config_enums::{ConflictPolicyEnum, DbType},
+    config_enums::{
+        ConflictPolicyEnum, DbType, FileFormat, KafkaHeader, KafkaKeyFormat,
+        KafkaPartitionStrategy, MessageFormat, PgInsertMethod, SchemaSubjectStrategy,
+    },
+    kafka_security_config::KafkaSecurityConfig,
     s3_config::S3Config,
 };
 
@@ -11,12 +17,21 @@ pub enum SinkerConfig {
         url: String,
         batch_size: usize,
         replace: bool,
+        ddl_conflict_policy: ConflictPolicyEnum,
+        // for documents from Mongo
+        mongo_doc_col: String,
+        mongo_sample_size: usize,
     },
 
     Pg {
         url: String,
         batch_size: usize,
         replace: bool,
+        insert_method: PgInsertMethod,
+        ddl_conflict_policy: ConflictPolicyEnum,
+        // for documents from Mongo
+        mongo_doc_col: String,
+        mongo_sample_size: usize,
     },
 
     Mongo {
@@ -35,6 +50,7 @@ pub enum SinkerConfig {
         url: String,
         batch_size: usize,
         check_log_dir: String,
+        col_type_map: String,
     },
 
     MongoCheck {
@@ -52,6 +68,7 @@ pub enum SinkerConfig {
     PgStruct {
         url: String,
         conflict_policy: ConflictPolicyEnum,
+        col_type_map: String,
     },
 
     Kafka {
@@ -60,6 +77,32 @@ pub enum SinkerConfig {
         ack_timeout_secs: u64,
         required_acks: String,
         with_field_defs: bool,
+        message_format: MessageFormat,
+        // debezium_json only, include schema blocks in keys and values
+        with_schema: bool,
+        // debezium_json only, send a tombstone (null value) after each delete
+        with_tombstone: bool,
+        security_config: KafkaSecurityConfig,
+        // debezium_avro only
+        schema_registry_url: String,
+        schema_subject_strategy: SchemaSubjectStrategy,
+        // compatibility level of subjects, e.g. BACKWARD, empty: the default of schema registry
+        schema_compatibility: String,
+        // exactly-once mode if not empty, each batch is sent in a kafka transaction
+        transactional_id: String,
+        // topic of the positions committed with transactions
+        checkpoint_topic: String,
+        key_format: KafkaKeyFormat,
+        partition_strategy: KafkaPartitionStrategy,
+        // partition_strategy: column, key: (schema, tb), value: col
+        partition_cols: HashMap<(String, String), String>,
+        // partition_strategy: fixed
+        partition: i32,
+        headers: Vec<KafkaHeader>,
+        // BEGIN / END events of source transactions, disabled if empty
+        transaction_topic: String,
+        // topic of ddls, the topic routed by schema if empty
+        ddl_topic: String,
     },
 
     Redis {
@@ -110,6 +153,29 @@ pub enum SinkerConfig {
         engine: String,
     },
 
+    Elasticsearch {
+        url: String,
+        batch_size: usize,
+    },
+
+    ElasticsearchStruct {
+        url: String,
+        conflict_policy: ConflictPolicyEnum,
+    },
+
+    File {
+        // root directory, or the key prefix in bucket if s3_bucket is set
+        url: String,
+        file_format: FileFormat,
+        csv_delimiter: u8,
+        rolling_size_mb: usize,
+        rolling_interval_secs: u64,
+        // extra columns for cdc, disabled if empty
+        op_col: String,
+        ts_col: String,
+        s3_config: S3Config,
+    },
+
     Foxlake {
         url: String,
         batch_size: usize,
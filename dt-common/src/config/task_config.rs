@@ -1,6 +1,8 @@
 use std::{
+    collections::HashMap,
     fs::{self, File},
     io::Read,
+    str::FromStr,
 };
 
 use anyhow::{bail, Ok};
@@ -9,13 +11,15 @@ use crate::error::Error;
 
 use super::{
     config_enums::{
-        ConflictPolicyEnum, DbType, ExtractType, MetaCenterType, ParallelType, PipelineType,
-        SinkType,
+        ConflictPolicyEnum, DataMarkerType, DbType, ExtractType, KafkaHeader, KafkaKeyFormat,
+        MessageFormat, MetaCenterType, ParallelType, PipelineType, SinkType,
     },
     data_marker_config::DataMarkerConfig,
     extractor_config::{BasicExtractorConfig, ExtractorConfig},
     filter_config::FilterConfig,
+    http_server_security_config::HttpServerSecurityConfig,
     ini_loader::IniLoader,
+    kafka_security_config::KafkaSecurityConfig,
     meta_center_config::MetaCenterConfig,
     parallelizer_config::ParallelizerConfig,
     pipeline_config::PipelineConfig,
@@ -24,6 +28,7 @@ use super::{
     router_config::RouterConfig,
     runtime_config::RuntimeConfig,
     s3_config::S3Config,
+    sequence_config::SequenceConfig,
     sinker_config::{BasicSinkerConfig, SinkerConfig},
 };
 
@@ -42,6 +47,7 @@ pub struct TaskConfig {
     pub meta_center: Option<MetaCenterConfig>,
     pub data_marker: Option<DataMarkerConfig>,
     pub processor: Option<ProcessorConfig>,
+    pub sequence: Option<SequenceConfig>,
 }
 
 // sections
@@ -56,6 +62,7 @@ const RESUMER: &str = "resumer";
 const DATA_MARKER: &str = "data_marker";
 const PROCESSOR: &str = "processor";
 const META_CENTER: &str = "metacenter";
+const SEQUENCE: &str = "sequence";
 // keys
 const CHECK_LOG_DIR: &str = "check_log_dir";
 const DB_TYPE: &str = "db_type";
@@ -71,6 +78,8 @@ const REPL_PORT: &str = "repl_port";
 const PARALLEL_SIZE: &str = "parallel_size";
 const DDL_CONFLICT_POLICY: &str = "ddl_conflict_policy";
 const REPLACE: &str = "replace";
+const MONGO_DOC_COL: &str = "mongo_doc_col";
+const MONGO_SAMPLE_SIZE: &str = "mongo_sample_size";
 // default values
 const APE_DTS: &str = "APE_DTS";
 const ASTRISK: &str = "*";
@@ -79,7 +88,7 @@ impl TaskConfig {
     pub fn new(task_config_file: &str) -> anyhow::Result<Self> {
         let loader = IniLoader::new(task_config_file);
 
-        let pipeline = Self::load_pipeline_config(&loader);
+        let pipeline = Self::load_pipeline_config(&loader)?;
         let runtime = Self::load_runtime_config(&loader)?;
         let resumer = Self::load_resumer_config(&loader, &runtime)?;
         let (extractor_basic, extractor) = Self::load_extractor_config(&loader, &pipeline)?;
@@ -98,6 +107,7 @@ impl TaskConfig {
             data_marker: Self::load_data_marker_config(&loader)?,
             processor: Self::load_processor_config(&loader)?,
             meta_center: Self::load_meta_center_config(&loader)?,
+            sequence: Self::load_sequence_config(&loader)?,
         })
     }
 
@@ -170,15 +180,7 @@ impl TaskConfig {
                 },
 
                 ExtractType::FoxlakeS3 => {
-                    let s3_config = S3Config {
-                        bucket: loader.get_optional(EXTRACTOR, "s3_bucket"),
-                        access_key: loader.get_optional(EXTRACTOR, "s3_access_key"),
-                        secret_key: loader.get_optional(EXTRACTOR, "s3_secret_key"),
-                        region: loader.get_optional(EXTRACTOR, "s3_region"),
-                        endpoint: loader.get_optional(EXTRACTOR, "s3_endpoint"),
-                        root_dir: loader.get_optional(EXTRACTOR, "s3_root_dir"),
-                        root_url: loader.get_optional(EXTRACTOR, "s3_root_url"),
-                    };
+                    let s3_config = Self::load_s3_config(loader, EXTRACTOR);
                     ExtractorConfig::FoxlakeS3 {
                         url,
                         schema: String::new(),
@@ -213,6 +215,16 @@ impl TaskConfig {
                     ddl_meta_tb: loader.get_optional(EXTRACTOR, "ddl_meta_tb"),
                     start_time_utc: loader.get_optional(EXTRACTOR, "start_time_utc"),
                     end_time_utc: loader.get_optional(EXTRACTOR, "end_time_utc"),
+                    streaming: loader.get_optional(EXTRACTOR, "streaming"),
+                    slot_check_interval_secs: loader.get_with_default(
+                        EXTRACTOR,
+                        "slot_check_interval_secs",
+                        10,
+                    ),
+                    slot_lag_warn_mb: loader.get_optional(EXTRACTOR, "slot_lag_warn_mb"),
+                    slot_lag_limit_mb: loader.get_optional(EXTRACTOR, "slot_lag_limit_mb"),
+                    slot_limit_policy: loader.get_optional(EXTRACTOR, "slot_limit_policy"),
+                    drop_slot_on_finish: loader.get_optional(EXTRACTOR, "drop_slot_on_finish"),
                 },
 
                 ExtractType::CheckLog => ExtractorConfig::PgCheck {
@@ -309,15 +321,68 @@ impl TaskConfig {
                 _ => bail! { not_supported_err },
             },
 
-            DbType::Kafka => ExtractorConfig::Kafka {
-                url,
-                group: loader.get_required(EXTRACTOR, "group"),
-                topic: loader.get_required(EXTRACTOR, "topic"),
-                partition: loader.get_optional(EXTRACTOR, "partition"),
-                offset: loader.get_optional(EXTRACTOR, "offset"),
-                ack_interval_secs: loader.get_optional(EXTRACTOR, "ack_interval_secs"),
+            DbType::File => match extract_type {
+                ExtractType::Snapshot => ExtractorConfig::FileSnapshot {
+                    url,
+                    schema: String::new(),
+                    tb: String::new(),
+                    file_format: loader.get_optional(EXTRACTOR, "file_format"),
+                    csv_delimiter: Self::load_csv_delimiter(loader, EXTRACTOR)?,
+                    csv_has_header: loader.get_with_default(EXTRACTOR, "csv_has_header", true),
+                    s3_config: Self::load_s3_config(loader, EXTRACTOR),
+                    batch_size,
+                },
+
+                _ => bail! { not_supported_err },
             },
 
+            DbType::Kafka => {
+                let message_format: MessageFormat =
+                    loader.get_optional(EXTRACTOR, "message_format");
+                let schema_registry_url: String =
+                    loader.get_optional(EXTRACTOR, "schema_registry_url");
+                if message_format == MessageFormat::DebeziumAvro && schema_registry_url.is_empty() {
+                    bail! {Error::ConfigError(
+                        "schema_registry_url is required for message_format: debezium_avro".into()
+                    )}
+                }
+
+                let topic: String = loader.get_required(EXTRACTOR, "topic");
+                let topics: Vec<String> = topic
+                    .split(',')
+                    .map(|v| v.trim().to_string())
+                    .filter(|v| !v.is_empty())
+                    .collect();
+                let partition: String = loader.get_optional(EXTRACTOR, "partition");
+                let mut partitions: Vec<i32> = Vec::new();
+                for v in partition
+                    .split(',')
+                    .map(|v| v.trim())
+                    .filter(|v| !v.is_empty())
+                {
+                    match v.parse() {
+                        Ok(v) => partitions.push(v),
+                        Err(_) => bail! {Error::ConfigError(format!(
+                            "invalid kafka partition: {}",
+                            partition
+                        ))},
+                    }
+                }
+
+                ExtractorConfig::Kafka {
+                    url,
+                    group: loader.get_required(EXTRACTOR, "group"),
+                    topics,
+                    partitions,
+                    subscribe: loader.get_optional(EXTRACTOR, "subscribe"),
+                    offset: loader.get_optional(EXTRACTOR, "offset"),
+                    ack_interval_secs: loader.get_optional(EXTRACTOR, "ack_interval_secs"),
+                    message_format,
+                    schema_registry_url,
+                    security_config: Self::load_kafka_security_config(loader, EXTRACTOR),
+                }
+            }
+
             db_type => {
                 bail! {Error::ConfigError(format!(
                     "extractor db type: {} not supported",
@@ -356,6 +421,13 @@ impl TaskConfig {
                     url,
                     batch_size,
                     replace: loader.get_with_default(SINKER, REPLACE, true),
+                    ddl_conflict_policy: loader.get_with_default(
+                        SINKER,
+                        DDL_CONFLICT_POLICY,
+                        ConflictPolicyEnum::Interrupt,
+                    ),
+                    mongo_doc_col: loader.get_optional(SINKER, MONGO_DOC_COL),
+                    mongo_sample_size: loader.get_with_default(SINKER, MONGO_SAMPLE_SIZE, 1000),
                 },
 
                 SinkType::Check => SinkerConfig::MysqlCheck {
@@ -381,17 +453,27 @@ impl TaskConfig {
                     url,
                     batch_size,
                     replace: loader.get_with_default(SINKER, REPLACE, true),
+                    insert_method: loader.get_optional(SINKER, "insert_method"),
+                    ddl_conflict_policy: loader.get_with_default(
+                        SINKER,
+                        DDL_CONFLICT_POLICY,
+                        ConflictPolicyEnum::Interrupt,
+                    ),
+                    mongo_doc_col: loader.get_optional(SINKER, MONGO_DOC_COL),
+                    mongo_sample_size: loader.get_with_default(SINKER, MONGO_SAMPLE_SIZE, 1000),
                 },
 
                 SinkType::Check => SinkerConfig::PgCheck {
                     url,
                     batch_size,
                     check_log_dir: loader.get_optional(SINKER, CHECK_LOG_DIR),
+                    col_type_map: loader.get_optional(SINKER, "col_type_map"),
                 },
 
                 SinkType::Struct => SinkerConfig::PgStruct {
                     url,
                     conflict_policy,
+                    col_type_map: loader.get_optional(SINKER, "col_type_map"),
                 },
 
                 SinkType::Sql => SinkerConfig::Sql {
@@ -422,13 +504,86 @@ impl TaskConfig {
                 }
             }
 
-            DbType::Kafka => SinkerConfig::Kafka {
-                url,
-                batch_size,
-                ack_timeout_secs: loader.get_with_default(SINKER, "ack_timeout_secs", 5),
-                required_acks: loader.get_with_default(SINKER, "required_acks", "one".to_string()),
-                with_field_defs: loader.get_with_default(SINKER, "with_field_defs", true),
-            },
+            DbType::Kafka => {
+                let message_format: MessageFormat = loader.get_optional(SINKER, "message_format");
+                let schema_registry_url: String =
+                    loader.get_optional(SINKER, "schema_registry_url");
+                if message_format == MessageFormat::DebeziumAvro && schema_registry_url.is_empty() {
+                    bail! {Error::ConfigError(
+                        "schema_registry_url is required for message_format: debezium_avro".into()
+                    )}
+                }
+                let key_format: KafkaKeyFormat = loader.get_optional(SINKER, "key_format");
+                if key_format == KafkaKeyFormat::Avro && schema_registry_url.is_empty() {
+                    bail! {Error::ConfigError(
+                        "schema_registry_url is required for key_format: avro".into()
+                    )}
+                }
+
+                // partition_cols=test_db.tb_1:user_id,test_db.tb_2:tenant_id
+                let mut partition_cols = HashMap::new();
+                let partition_cols_str: String = loader.get_optional(SINKER, "partition_cols");
+                for v in partition_cols_str
+                    .split(',')
+                    .map(|v| v.trim())
+                    .filter(|v| !v.is_empty())
+                {
+                    let Some((tb, col)) = v.split_once(':') else {
+                        bail! {Error::ConfigError(format!("invalid partition_cols: {}", v))}
+                    };
+                    let Some((schema, tb)) = tb.split_once('.') else {
+                        bail! {Error::ConfigError(format!("invalid partition_cols: {}", v))}
+                    };
+                    partition_cols.insert((schema.to_string(), tb.to_string()), col.to_string());
+                }
+
+                let mut headers = Vec::new();
+                let headers_str: String = loader.get_optional(SINKER, "headers");
+                for v in headers_str
+                    .split(',')
+                    .map(|v| v.trim())
+                    .filter(|v| !v.is_empty())
+                {
+                    match KafkaHeader::from_str(v) {
+                        Ok(header) => headers.push(header),
+                        Err(_) => {
+                            bail! {Error::ConfigError(format!("invalid kafka header: {}", v))}
+                        }
+                    }
+                }
+
+                SinkerConfig::Kafka {
+                    url,
+                    batch_size,
+                    ack_timeout_secs: loader.get_with_default(SINKER, "ack_timeout_secs", 5),
+                    required_acks: loader.get_with_default(
+                        SINKER,
+                        "required_acks",
+                        "one".to_string(),
+                    ),
+                    with_field_defs: loader.get_with_default(SINKER, "with_field_defs", true),
+                    message_format,
+                    with_schema: loader.get_optional(SINKER, "with_schema"),
+                    with_tombstone: loader.get_with_default(SINKER, "with_tombstone", true),
+                    security_config: Self::load_kafka_security_config(loader, SINKER),
+                    schema_registry_url,
+                    schema_subject_strategy: loader.get_optional(SINKER, "schema_subject_strategy"),
+                    schema_compatibility: loader.get_optional(SINKER, "schema_compatibility"),
+                    transactional_id: loader.get_optional(SINKER, "transactional_id"),
+                    checkpoint_topic: loader.get_with_default(
+                        SINKER,
+                        "checkpoint_topic",
+                        "ape_dts_checkpoint".to_string(),
+                    ),
+                    key_format,
+                    partition_strategy: loader.get_optional(SINKER, "partition_strategy"),
+                    partition_cols,
+                    partition: loader.get_optional(SINKER, "partition"),
+                    headers,
+                    transaction_topic: loader.get_optional(SINKER, "transaction_topic"),
+                    ddl_topic: loader.get_optional(SINKER, "ddl_topic"),
+                }
+            }
 
             DbType::Redis => match sink_type {
                 SinkType::Write => SinkerConfig::Redis {
@@ -495,16 +650,38 @@ impl TaskConfig {
                 _ => bail! { not_supported_err },
             },
 
+            DbType::Elasticsearch => match sink_type {
+                SinkType::Write => SinkerConfig::Elasticsearch { url, batch_size },
+
+                SinkType::Struct => SinkerConfig::ElasticsearchStruct {
+                    url,
+                    conflict_policy,
+                },
+
+                _ => bail! { not_supported_err },
+            },
+
+            DbType::File => match sink_type {
+                SinkType::Write => SinkerConfig::File {
+                    url,
+                    file_format: loader.get_optional(SINKER, "file_format"),
+                    csv_delimiter: Self::load_csv_delimiter(loader, SINKER)?,
+                    rolling_size_mb: loader.get_with_default(SINKER, "rolling_size_mb", 128),
+                    rolling_interval_secs: loader.get_with_default(
+                        SINKER,
+                        "rolling_interval_secs",
+                        600,
+                    ),
+                    op_col: loader.get_optional(SINKER, "op_col"),
+                    ts_col: loader.get_optional(SINKER, "ts_col"),
+                    s3_config: Self::load_s3_config(loader, SINKER),
+                },
+
+                _ => bail! { not_supported_err },
+            },
+
             DbType::Foxlake => {
-                let s3_config = S3Config {
-                    bucket: loader.get_optional(SINKER, "s3_bucket"),
-                    access_key: loader.get_optional(SINKER, "s3_access_key"),
-                    secret_key: loader.get_optional(SINKER, "s3_secret_key"),
-                    region: loader.get_optional(SINKER, "s3_region"),
-                    endpoint: loader.get_optional(SINKER, "s3_endpoint"),
-                    root_dir: loader.get_optional(SINKER, "s3_root_dir"),
-                    root_url: loader.get_optional(SINKER, "s3_root_url"),
-                };
+                let s3_config = Self::load_s3_config(loader, SINKER);
 
                 match sink_type {
                     SinkType::Write => SinkerConfig::Foxlake {
@@ -541,6 +718,44 @@ impl TaskConfig {
         Ok((basic, sinker))
     }
 
+    fn load_s3_config(loader: &IniLoader, section: &str) -> S3Config {
+        S3Config {
+            bucket: loader.get_optional(section, "s3_bucket"),
+            access_key: loader.get_optional(section, "s3_access_key"),
+            secret_key: loader.get_optional(section, "s3_secret_key"),
+            region: loader.get_optional(section, "s3_region"),
+            endpoint: loader.get_optional(section, "s3_endpoint"),
+            root_dir: loader.get_optional(section, "s3_root_dir"),
+            root_url: loader.get_optional(section, "s3_root_url"),
+        }
+    }
+
+    fn load_kafka_security_config(loader: &IniLoader, section: &str) -> KafkaSecurityConfig {
+        KafkaSecurityConfig {
+            security_protocol: loader.get_optional(section, "security_protocol"),
+            sasl_mechanism: loader.get_optional(section, "sasl_mechanism"),
+            sasl_username: loader.get_optional(section, "sasl_username"),
+            sasl_password: loader.get_optional(section, "sasl_password"),
+            ssl_ca_location: loader.get_optional(section, "ssl_ca_location"),
+            ssl_certificate_location: loader.get_optional(section, "ssl_certificate_location"),
+            ssl_key_location: loader.get_optional(section, "ssl_key_location"),
+            ssl_key_password: loader.get_optional(section, "ssl_key_password"),
+            ssl_verify_hostname: loader.get_with_default(section, "ssl_verify_hostname", true),
+            properties: loader.get_with_prefix(section, "rdkafka."),
+        }
+    }
+
+    fn load_csv_delimiter(loader: &IniLoader, section: &str) -> anyhow::Result<u8> {
+        let csv_delimiter: String = loader.get_with_default(section, "csv_delimiter", ",".into());
+        if csv_delimiter.len() != 1 {
+            bail! {Error::ConfigError(format!(
+                "csv_delimiter should be a single byte, got: {}",
+                csv_delimiter
+            ))}
+        }
+        Ok(csv_delimiter.as_bytes()[0])
+    }
+
     fn load_parallelizer_config(loader: &IniLoader) -> anyhow::Result<ParallelizerConfig> {
         Ok(ParallelizerConfig {
             parallel_size: loader.get_with_default(PARALLELIZER, PARALLEL_SIZE, 1),
@@ -552,7 +767,7 @@ impl TaskConfig {
         })
     }
 
-    fn load_pipeline_config(loader: &IniLoader) -> PipelineConfig {
+    fn load_pipeline_config(loader: &IniLoader) -> anyhow::Result<PipelineConfig> {
         let mut config = PipelineConfig {
             buffer_size: loader.get_with_default(PIPELINE, "buffer_size", 16000),
             checkpoint_interval_secs: loader.get_with_default(
@@ -568,13 +783,35 @@ impl TaskConfig {
             pipeline_type: loader.get_with_default(PIPELINE, "pipeline_type", PipelineType::Basic),
             http_host: loader.get_with_default(PIPELINE, "http_host", "0.0.0.0".to_string()),
             http_port: loader.get_with_default(PIPELINE, "http_port", 10231),
+            http_session_timeout_secs: loader.get_optional(PIPELINE, "http_session_timeout_secs"),
             with_field_defs: loader.get_with_default(PIPELINE, "with_field_defs", true),
+            http_security_config: HttpServerSecurityConfig {
+                auth_token: loader.get_optional(PIPELINE, "http_auth_token"),
+                tls_cert_file: loader.get_optional(PIPELINE, "http_tls_cert_file"),
+                tls_key_file: loader.get_optional(PIPELINE, "http_tls_key_file"),
+                tls_client_ca_file: loader.get_optional(PIPELINE, "http_tls_client_ca_file"),
+            },
+            grpc_host: loader.get_with_default(PIPELINE, "grpc_host", "0.0.0.0".to_string()),
+            grpc_port: loader.get_with_default(PIPELINE, "grpc_port", 10232),
+            grpc_auth_token: loader.get_optional(PIPELINE, "grpc_auth_token"),
         };
 
         if config.counter_time_window_secs == 0 {
             config.counter_time_window_secs = config.checkpoint_interval_secs;
         }
-        config
+
+        let security_config = &config.http_security_config;
+        if security_config.tls_cert_file.is_empty() != security_config.tls_key_file.is_empty() {
+            bail! {Error::ConfigError(
+                "http_tls_cert_file and http_tls_key_file should be set together".into()
+            )}
+        }
+        if !security_config.tls_client_ca_file.is_empty() && !security_config.is_tls() {
+            bail! {Error::ConfigError(
+                "http_tls_client_ca_file requires http_tls_cert_file and http_tls_key_file".into()
+            )}
+        }
+        Ok(config)
     }
 
     fn load_runtime_config(loader: &IniLoader) -> anyhow::Result<RuntimeConfig> {
@@ -633,6 +870,13 @@ impl TaskConfig {
             return Ok(None);
         }
 
+        let marker_type: DataMarkerType = loader.get_optional(DATA_MARKER, "marker_type");
+        // replication origins are named by topo_name and nodes, no marker table needed
+        let marker = match marker_type {
+            DataMarkerType::Table => loader.get_required(DATA_MARKER, "marker"),
+            DataMarkerType::ReplicationOrigin => loader.get_optional(DATA_MARKER, "marker"),
+        };
+
         Ok(Some(DataMarkerConfig {
             topo_name: loader.get_required(DATA_MARKER, "topo_name"),
             topo_nodes: loader.get_optional(DATA_MARKER, "topo_nodes"),
@@ -640,7 +884,8 @@ impl TaskConfig {
             dst_node: loader.get_required(DATA_MARKER, "dst_node"),
             do_nodes: loader.get_required(DATA_MARKER, "do_nodes"),
             ignore_nodes: loader.get_optional(DATA_MARKER, "ignore_nodes"),
-            marker: loader.get_required(DATA_MARKER, "marker"),
+            marker,
+            marker_type,
         }))
     }
 
@@ -664,6 +909,17 @@ impl TaskConfig {
         }))
     }
 
+    fn load_sequence_config(loader: &IniLoader) -> anyhow::Result<Option<SequenceConfig>> {
+        if !loader.ini.sections().contains(&SEQUENCE.to_string()) {
+            return Ok(None);
+        }
+
+        Ok(Some(SequenceConfig {
+            sync_on_finish: loader.get_optional(SEQUENCE, "sync_on_finish"),
+            sync_interval_secs: loader.get_optional(SEQUENCE, "sync_interval_secs"),
+        }))
+    }
+
     fn load_meta_center_config(loader: &IniLoader) -> anyhow::Result<Option<MetaCenterConfig>> {
         let mut config = MetaCenterConfig::Basic;
         let db_type: DbType = loader.get_required(EXTRACTOR, DB_TYPE);
@@ -1,3 +1,6 @@
+pub mod mongo_col_value_convertor;
+pub mod mongo_doc_flattener;
 pub mod mysql_col_value_convertor;
 pub mod pg_col_value_convertor;
+pub mod pg_to_mysql_col_value_convertor;
 pub mod sqlx_ext;
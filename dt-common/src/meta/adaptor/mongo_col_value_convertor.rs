@@ -0,0 +1,240 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, NaiveDateTime, Utc};
+use mongodb::bson::{self, spec::BinarySubtype, Binary, Bson, Document};
+
+use crate::meta::{
+    col_value::ColValue, mongo::mongo_constant::MongoConstants, rdb_tb_meta::RdbTbMeta,
+};
+
+use super::pg_to_mysql_col_value_convertor::PgToMysqlColValueConvertor;
+
+const DATETIME_FORMAT: &str = "%Y-%m-%d %H:%M:%S%.f";
+const DATETIME_TZ_FORMAT: &str = "%Y-%m-%d %H:%M:%S%.f%#z";
+
+/// Converts col values extracted from MySQL / Postgres to BSON documents.
+///
+/// Decimals are kept as strings to avoid precision loss, datetimes without time zone are treated as UTC.
+pub struct MongoColValueConvertor {}
+
+impl MongoColValueConvertor {
+    /// Builds the document of a row, cols are ordered as in tb_meta and renamed by col_map,
+    /// _id is derived from tb_meta.id_cols.
+    pub fn to_doc(
+        col_values: &HashMap<String, ColValue>,
+        tb_meta: &RdbTbMeta,
+        col_map: Option<&HashMap<String, String>>,
+    ) -> Document {
+        let mut doc = Document::new();
+        doc.insert(
+            MongoConstants::ID,
+            Self::to_id(col_values, tb_meta, col_map),
+        );
+
+        for col in tb_meta.cols.iter() {
+            let dst_col = Self::get_dst_col(col, col_map);
+            if let Some(col_value) = col_values.get(dst_col) {
+                doc.insert(
+                    dst_col,
+                    Self::to_bson(col_value, tb_meta.col_origin_type_map.get(col)),
+                );
+            }
+        }
+        doc
+    }
+
+    /// single id col: the value itself, multiple id cols: { col_1: v_1, col_2: v_2 }
+    pub fn to_id(
+        col_values: &HashMap<String, ColValue>,
+        tb_meta: &RdbTbMeta,
+        col_map: Option<&HashMap<String, String>>,
+    ) -> Bson {
+        let get_bson = |col: &String| {
+            let dst_col = Self::get_dst_col(col, col_map);
+            let bson = col_values
+                .get(dst_col)
+                .map(|v| Self::to_bson(v, tb_meta.col_origin_type_map.get(col)))
+                .unwrap_or(Bson::Null);
+            (dst_col.to_string(), bson)
+        };
+
+        if tb_meta.id_cols.len() == 1 {
+            return get_bson(&tb_meta.id_cols[0]).1;
+        }
+
+        let mut id = Document::new();
+        for col in tb_meta.id_cols.iter() {
+            let (dst_col, bson) = get_bson(col);
+            id.insert(dst_col, bson);
+        }
+        Bson::Document(id)
+    }
+
+    pub fn to_bson(col_value: &ColValue, origin_type: Option<&String>) -> Bson {
+        match col_value {
+            ColValue::None => Bson::Null,
+            ColValue::Bool(v) => Bson::Boolean(*v),
+            ColValue::Tiny(v) => Bson::Int32(*v as i32),
+            ColValue::UnsignedTiny(v) => Bson::Int32(*v as i32),
+            ColValue::Short(v) => Bson::Int32(*v as i32),
+            ColValue::UnsignedShort(v) => Bson::Int32(*v as i32),
+            ColValue::Long(v) => Bson::Int32(*v),
+            ColValue::UnsignedLong(v) => Bson::Int64(*v as i64),
+            ColValue::LongLong(v) => Bson::Int64(*v),
+            ColValue::UnsignedLongLong(v) => match i64::try_from(*v) {
+                Ok(v) => Bson::Int64(v),
+                Err(_) => Bson::String(v.to_string()),
+            },
+            ColValue::Float(v) => Bson::Double(*v as f64),
+            ColValue::Double(v) => Bson::Double(*v),
+            ColValue::Year(v) => Bson::Int32(*v as i32),
+            ColValue::Bit(v) | ColValue::Set(v) => Bson::Int64(*v as i64),
+            ColValue::Enum(v) => Bson::Int64(*v as i64),
+
+            ColValue::DateTime(v) | ColValue::Timestamp(v) => match Self::parse_datetime(v) {
+                Some(millis) => Bson::DateTime(bson::DateTime::from_millis(millis)),
+                None => Bson::String(v.clone()),
+            },
+
+            ColValue::Decimal(v)
+            | ColValue::Time(v)
+            | ColValue::Date(v)
+            | ColValue::Set2(v)
+            | ColValue::Enum2(v) => Bson::String(v.clone()),
+
+            ColValue::String(v) => {
+                // Postgres arrays are extracted as text: {1,2,3}
+                if origin_type.is_some_and(|t| t.starts_with('_')) {
+                    if let Some(json) = PgToMysqlColValueConvertor::pg_array_to_json(v) {
+                        return Self::json_to_bson(&json);
+                    }
+                }
+                Bson::String(v.clone())
+            }
+
+            ColValue::RawString(v) => match String::from_utf8(v.clone()) {
+                Ok(s) => Bson::String(s),
+                Err(_) => Self::binary(v),
+            },
+            ColValue::Blob(v) => Self::binary(v),
+
+            ColValue::Json(v) => match serde_json::from_slice::<serde_json::Value>(v) {
+                Ok(json) => Self::json_to_bson(&json),
+                Err(_) => Self::binary(v),
+            },
+            ColValue::Json2(v) => match serde_json::from_str::<serde_json::Value>(v) {
+                Ok(json) => Self::json_to_bson(&json),
+                Err(_) => Bson::String(v.clone()),
+            },
+            ColValue::Json3(v) => Self::json_to_bson(v),
+
+            ColValue::MongoDoc(v) => Bson::Document(v.clone()),
+        }
+    }
+
+    fn json_to_bson(json: &serde_json::Value) -> Bson {
+        bson::to_bson(json).unwrap_or_else(|_| Bson::String(json.to_string()))
+    }
+
+    fn binary(v: &[u8]) -> Bson {
+        Bson::Binary(Binary {
+            subtype: BinarySubtype::Generic,
+            bytes: v.to_vec(),
+        })
+    }
+
+    fn parse_datetime(value: &str) -> Option<i64> {
+        let utc = if let Ok(v) = DateTime::parse_from_str(value, DATETIME_TZ_FORMAT) {
+            v.with_timezone(&Utc)
+        } else {
+            NaiveDateTime::parse_from_str(value, DATETIME_FORMAT)
+                .ok()?
+                .and_utc()
+        };
+        Some(utc.timestamp_millis())
+    }
+
+    fn get_dst_col<'a>(col: &'a str, col_map: Option<&'a HashMap<String, String>>) -> &'a str {
+        col_map
+            .and_then(|m| m.get(col))
+            .map(|c| c.as_str())
+            .unwrap_or(col)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mongodb::bson::doc;
+
+    #[test]
+    fn test_to_bson() {
+        let cases = [
+            (ColValue::None, Bson::Null),
+            (ColValue::Tiny(-1), Bson::Int32(-1)),
+            (
+                ColValue::UnsignedLong(u32::MAX),
+                Bson::Int64(u32::MAX as i64),
+            ),
+            (
+                ColValue::UnsignedLongLong(u64::MAX),
+                Bson::String(u64::MAX.to_string()),
+            ),
+            (
+                ColValue::Decimal("1.10".into()),
+                Bson::String("1.10".into()),
+            ),
+            (
+                ColValue::DateTime("2024-01-01 00:00:01.5".into()),
+                Bson::DateTime(bson::DateTime::from_millis(1704067201500)),
+            ),
+            (
+                ColValue::Timestamp("2024-01-01 08:00:00.5+08".into()),
+                Bson::DateTime(bson::DateTime::from_millis(1704067200500)),
+            ),
+            (
+                ColValue::Json2(r#"{"a":[1,"b"]}"#.into()),
+                Bson::Document(doc! {"a": [1_i64, "b"]}),
+            ),
+            (
+                ColValue::Blob(vec![1, 2]),
+                MongoColValueConvertor::binary(&[1, 2]),
+            ),
+        ];
+
+        for (col_value, expect) in cases {
+            assert_eq!(MongoColValueConvertor::to_bson(&col_value, None), expect);
+        }
+
+        let array_type = "_int4".to_string();
+        assert_eq!(
+            MongoColValueConvertor::to_bson(&ColValue::String("{1,2}".into()), Some(&array_type)),
+            Bson::Array(vec![Bson::Int64(1), Bson::Int64(2)])
+        );
+    }
+
+    #[test]
+    fn test_to_doc() {
+        let tb_meta = RdbTbMeta {
+            cols: vec!["id".into(), "name".into(), "value".into()],
+            id_cols: vec!["id".into(), "name".into()],
+            ..Default::default()
+        };
+        let col_values = HashMap::from([
+            ("id".to_string(), ColValue::Long(1)),
+            ("dst_name".to_string(), ColValue::String("a".into())),
+            ("value".to_string(), ColValue::None),
+        ]);
+        let col_map = HashMap::from([("name".to_string(), "dst_name".to_string())]);
+
+        assert_eq!(
+            MongoColValueConvertor::to_doc(&col_values, &tb_meta, Some(&col_map)),
+            doc! {
+                "_id": {"id": 1, "dst_name": "a"},
+                "id": 1,
+                "dst_name": "a",
+                "value": Bson::Null,
+            }
+        );
+    }
+}
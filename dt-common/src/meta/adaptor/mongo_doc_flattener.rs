@@ -0,0 +1,669 @@
+use std::collections::HashMap;
+
+use chrono::DateTime;
+use mongodb::bson::{Bson, Document};
+
+use crate::{
+    config::config_enums::DbType,
+    meta::{
+        col_value::ColValue, mongo::mongo_constant::MongoConstants, rdb_tb_meta::RdbTbMeta,
+        row_data::RowData, row_type::RowType,
+    },
+    utils::sql_util::SqlUtil,
+};
+
+const DATETIME_FORMAT: &str = "%Y-%m-%d %H:%M:%S%.3f";
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum ColKind {
+    Int32,
+    Int64,
+    Double,
+    Decimal,
+    Bool,
+    DateTime,
+    Binary,
+    Json,
+    Text,
+}
+
+/// Flattens documents from Mongo to rows of MySQL / Postgres tables.
+///
+/// Without col_map, each top-level field becomes a column. With col_map ({field_path: col}),
+/// only the mapped fields are kept, and a path like "a.b" or "a.0" is looked up in subdocuments / arrays.
+/// Subdocuments and arrays are stored as JSON, _id is stored as string.
+#[derive(Clone)]
+pub struct MongoDocFlattener {
+    // target, Mysql / Tidb / Pg
+    pub db_type: DbType,
+    // if not empty, the whole document is stored as JSON in this column
+    pub doc_col: String,
+}
+
+impl MongoDocFlattener {
+    /// Insert and full-document Update are converted to Insert (upserted by _id if replace enabled),
+    /// diff Update from oplog is converted to Update with only the changed cols.
+    /// Returns None if no col in target is changed.
+    pub fn flatten_row_data(
+        &self,
+        row_data: &RowData,
+        col_map: Option<&HashMap<String, String>>,
+        tb_meta: &RdbTbMeta,
+    ) -> Option<RowData> {
+        let get_doc = |col_values: &Option<HashMap<String, ColValue>>, key: &str| match col_values
+            .as_ref()
+            .and_then(|v| v.get(key))
+        {
+            Some(ColValue::MongoDoc(doc)) => Some(doc.clone()),
+            _ => None,
+        };
+
+        let (row_type, before, after) = match row_data.row_type {
+            RowType::Insert => {
+                let after = get_doc(&row_data.after, MongoConstants::DOC)?;
+                (
+                    RowType::Insert,
+                    None,
+                    Some(self.flatten_doc(&after, col_map)),
+                )
+            }
+
+            RowType::Delete => {
+                let before = get_doc(&row_data.before, MongoConstants::DOC)?;
+                (
+                    RowType::Delete,
+                    Some(self.flatten_id(&before, col_map)),
+                    None,
+                )
+            }
+
+            RowType::Update => {
+                if let Some(after) = get_doc(&row_data.after, MongoConstants::DOC) {
+                    (
+                        RowType::Insert,
+                        None,
+                        Some(self.flatten_doc(&after, col_map)),
+                    )
+                } else {
+                    let before = get_doc(&row_data.before, MongoConstants::DOC)?;
+                    let diff = get_doc(&row_data.after, MongoConstants::DIFF_DOC)?;
+                    (
+                        RowType::Update,
+                        Some(self.flatten_id(&before, col_map)),
+                        Some(self.flatten_diff_doc(&diff, col_map)),
+                    )
+                }
+            }
+        };
+
+        let before = before.map(|v| self.retain_tb_cols(v, tb_meta));
+        let after = after.map(|v| self.retain_tb_cols(v, tb_meta));
+        if row_type == RowType::Update && after.as_ref().is_some_and(|v| v.is_empty()) {
+            return None;
+        }
+
+        Some(RowData {
+            schema: row_data.schema.clone(),
+            tb: row_data.tb.clone(),
+            row_type,
+            before,
+            after,
+            data_size: row_data.data_size,
+        })
+    }
+
+    /// Whether a diff Update from oplog can NOT be applied by the changed cols,
+    /// which happens if doc_col is set or a field inside a col stored as JSON is changed,
+    /// the full document should be looked up and upserted instead.
+    pub fn need_full_doc(
+        &self,
+        diff_doc: &Document,
+        col_map: Option<&HashMap<String, String>>,
+    ) -> bool {
+        if !self.doc_col.is_empty() {
+            return true;
+        }
+
+        let mut keys = Vec::new();
+        for op in [MongoConstants::SET, MongoConstants::UNSET] {
+            if let Ok(doc) = diff_doc.get_document(op) {
+                keys.extend(doc.keys());
+            }
+        }
+        keys.iter().any(|key| match col_map {
+            Some(col_map) => col_map
+                .keys()
+                .any(|path| key.starts_with(&format!("{}.", path))),
+            None => key.contains('.'),
+        })
+    }
+
+    pub fn flatten_doc(
+        &self,
+        doc: &Document,
+        col_map: Option<&HashMap<String, String>>,
+    ) -> HashMap<String, ColValue> {
+        let mut col_values = HashMap::new();
+        match col_map {
+            Some(col_map) => {
+                for (path, col) in col_map.iter() {
+                    let col_value = Self::get_by_path(doc, path)
+                        .map(Self::to_col_value)
+                        .unwrap_or(ColValue::None);
+                    col_values.insert(col.clone(), col_value);
+                }
+            }
+            None => {
+                for (field, value) in doc.iter() {
+                    col_values.insert(field.clone(), Self::to_col_value(value));
+                }
+            }
+        }
+
+        col_values.extend(self.flatten_id(doc, col_map));
+        if !self.doc_col.is_empty() {
+            let json = Self::to_json(&Bson::Document(doc.clone()));
+            col_values.insert(self.doc_col.clone(), ColValue::Json2(json));
+        }
+        col_values
+    }
+
+    /// Builds CREATE DATABASE / SCHEMA and CREATE TABLE sqls, col types are inferred from sampled documents.
+    pub fn get_create_tb_sqls(
+        &self,
+        schema: &str,
+        tb: &str,
+        docs: &[Document],
+        col_map: Option<&HashMap<String, String>>,
+    ) -> Vec<String> {
+        let id_col = Self::get_id_col(col_map);
+        let mut cols: Vec<String> = Vec::new();
+        let mut col_kinds: HashMap<String, Option<ColKind>> = HashMap::new();
+        let mut add_col = |col: &str, value: Option<&Bson>| {
+            if col == id_col || col == self.doc_col {
+                return;
+            }
+            if !col_kinds.contains_key(col) {
+                cols.push(col.to_string());
+                col_kinds.insert(col.to_string(), None);
+            }
+            if let Some(kind) = value.and_then(Self::get_col_kind) {
+                let merged = match col_kinds[col] {
+                    Some(exist) => Self::merge_col_kind(exist, kind),
+                    None => kind,
+                };
+                col_kinds.insert(col.to_string(), Some(merged));
+            }
+        };
+
+        match col_map {
+            Some(col_map) => {
+                let mut path_cols: Vec<(&String, &String)> = col_map.iter().collect();
+                path_cols.sort_by(|a, b| a.1.cmp(b.1));
+                for (path, col) in path_cols {
+                    add_col(col, None);
+                    for doc in docs.iter() {
+                        add_col(col, Self::get_by_path(doc, path));
+                    }
+                }
+            }
+            None => {
+                for doc in docs.iter() {
+                    for (field, value) in doc.iter() {
+                        add_col(field, Some(value));
+                    }
+                }
+            }
+        }
+
+        let mut col_defs = vec![format!(
+            "{} {} NOT NULL",
+            self.escape(id_col),
+            self.get_id_col_type()
+        )];
+        for col in cols.iter() {
+            let kind = col_kinds[col].unwrap_or(ColKind::Text);
+            col_defs.push(format!("{} {}", self.escape(col), self.get_col_type(kind)));
+        }
+        if !self.doc_col.is_empty() {
+            col_defs.push(format!(
+                "{} {}",
+                self.escape(&self.doc_col),
+                self.get_col_type(ColKind::Json)
+            ));
+        }
+        col_defs.push(format!("PRIMARY KEY ({})", self.escape(id_col)));
+
+        let create_schema_sql = if self.db_type == DbType::Pg {
+            format!("CREATE SCHEMA IF NOT EXISTS {}", self.escape(schema))
+        } else {
+            format!("CREATE DATABASE IF NOT EXISTS {}", self.escape(schema))
+        };
+        let create_tb_sql = format!(
+            "CREATE TABLE IF NOT EXISTS {}.{} ({})",
+            self.escape(schema),
+            self.escape(tb),
+            col_defs.join(", ")
+        );
+        vec![create_schema_sql, create_tb_sql]
+    }
+
+    pub fn to_col_value(value: &Bson) -> ColValue {
+        match value {
+            Bson::Null | Bson::Undefined => ColValue::None,
+            Bson::Boolean(v) => ColValue::Bool(*v),
+            Bson::Int32(v) => ColValue::Long(*v),
+            Bson::Int64(v) => ColValue::LongLong(*v),
+            Bson::Double(v) => ColValue::Double(*v),
+            Bson::String(v) => ColValue::String(v.clone()),
+            Bson::ObjectId(v) => ColValue::String(v.to_hex()),
+            Bson::Binary(v) => ColValue::Blob(v.bytes.clone()),
+            Bson::DateTime(v) => match DateTime::from_timestamp_millis(v.timestamp_millis()) {
+                Some(dt) => ColValue::DateTime(dt.format(DATETIME_FORMAT).to_string()),
+                None => ColValue::String(Self::to_json(value)),
+            },
+            Bson::Decimal128(_) => {
+                // relaxed extended json: {"$numberDecimal": "1.23"}
+                let json = value.clone().into_relaxed_extjson();
+                match json.get("$numberDecimal").and_then(|v| v.as_str()) {
+                    Some(v) => ColValue::Decimal(v.to_string()),
+                    None => ColValue::String(json.to_string()),
+                }
+            }
+            Bson::Document(_) | Bson::Array(_) => ColValue::Json2(Self::to_json(value)),
+            _ => ColValue::String(Self::to_json(value)),
+        }
+    }
+
+    fn flatten_id(
+        &self,
+        doc: &Document,
+        col_map: Option<&HashMap<String, String>>,
+    ) -> HashMap<String, ColValue> {
+        let id = match doc.get(MongoConstants::ID) {
+            Some(Bson::ObjectId(v)) => ColValue::String(v.to_hex()),
+            Some(Bson::String(v)) => ColValue::String(v.clone()),
+            Some(v) => ColValue::String(Self::to_json(v)),
+            None => ColValue::None,
+        };
+        HashMap::from([(Self::get_id_col(col_map).to_string(), id)])
+    }
+
+    /// diff_doc: {"$set": {...}} or {"$unset": {...}}, keys may be paths like "a.b".
+    /// Partial changes inside a field stored as JSON (including doc_col) are ignored here,
+    /// refer to need_full_doc.
+    fn flatten_diff_doc(
+        &self,
+        diff_doc: &Document,
+        col_map: Option<&HashMap<String, String>>,
+    ) -> HashMap<String, ColValue> {
+        let mut col_values = HashMap::new();
+        if let Ok(set_doc) = diff_doc.get_document(MongoConstants::SET) {
+            match col_map {
+                Some(col_map) => {
+                    for (path, col) in col_map.iter() {
+                        if let Some(value) = Self::get_by_diff_path(set_doc, path) {
+                            col_values.insert(col.clone(), Self::to_col_value(value));
+                        }
+                    }
+                }
+                None => {
+                    for (key, value) in set_doc.iter() {
+                        if !key.contains('.') {
+                            col_values.insert(key.clone(), Self::to_col_value(value));
+                        }
+                    }
+                }
+            }
+        }
+
+        if let Ok(unset_doc) = diff_doc.get_document(MongoConstants::UNSET) {
+            for key in unset_doc.keys() {
+                match col_map {
+                    Some(col_map) => {
+                        for (path, col) in col_map.iter() {
+                            if path == key || path.starts_with(&format!("{}.", key)) {
+                                col_values.insert(col.clone(), ColValue::None);
+                            }
+                        }
+                    }
+                    None => {
+                        if !key.contains('.') {
+                            col_values.insert(key.clone(), ColValue::None);
+                        }
+                    }
+                }
+            }
+        }
+        col_values
+    }
+
+    fn retain_tb_cols(
+        &self,
+        col_values: HashMap<String, ColValue>,
+        tb_meta: &RdbTbMeta,
+    ) -> HashMap<String, ColValue> {
+        col_values
+            .into_iter()
+            .filter_map(|(col, col_value)| {
+                // column names in MySQL are case insensitive, and lowercased in tb_meta
+                let col = if self.db_type == DbType::Pg {
+                    col
+                } else {
+                    col.to_lowercase()
+                };
+                tb_meta.cols.contains(&col).then_some((col, col_value))
+            })
+            .collect()
+    }
+
+    fn get_by_path<'a>(doc: &'a Document, path: &str) -> Option<&'a Bson> {
+        let mut value = None;
+        for (i, field) in path.split('.').enumerate() {
+            value = match (i, value) {
+                (0, _) => doc.get(field),
+                (_, Some(Bson::Document(sub_doc))) => sub_doc.get(field),
+                (_, Some(Bson::Array(array))) => {
+                    field.parse::<usize>().ok().and_then(|i| array.get(i))
+                }
+                _ => None,
+            };
+            value?;
+        }
+        value
+    }
+
+    /// keys of $set may be either the full path "a.b" or its prefix "a" with a subdocument as value
+    fn get_by_diff_path<'a>(set_doc: &'a Document, path: &str) -> Option<&'a Bson> {
+        if let Some(value) = set_doc.get(path) {
+            return Some(value);
+        }
+        for (key, value) in set_doc.iter() {
+            if let Some(sub_path) = path
+                .strip_prefix(key.as_str())
+                .and_then(|p| p.strip_prefix('.'))
+            {
+                if let Bson::Document(sub_doc) = value {
+                    return Self::get_by_path(sub_doc, sub_path);
+                }
+            }
+        }
+        None
+    }
+
+    fn get_id_col(col_map: Option<&HashMap<String, String>>) -> &str {
+        col_map
+            .and_then(|m| m.get(MongoConstants::ID))
+            .map(|c| c.as_str())
+            .unwrap_or(MongoConstants::ID)
+    }
+
+    fn get_col_kind(value: &Bson) -> Option<ColKind> {
+        let kind = match value {
+            Bson::Null | Bson::Undefined => return None,
+            Bson::Int32(_) => ColKind::Int32,
+            Bson::Int64(_) => ColKind::Int64,
+            Bson::Double(_) => ColKind::Double,
+            Bson::Decimal128(_) => ColKind::Decimal,
+            Bson::Boolean(_) => ColKind::Bool,
+            Bson::DateTime(_) => ColKind::DateTime,
+            Bson::Binary(_) => ColKind::Binary,
+            Bson::Document(_) | Bson::Array(_) => ColKind::Json,
+            _ => ColKind::Text,
+        };
+        Some(kind)
+    }
+
+    fn merge_col_kind(a: ColKind, b: ColKind) -> ColKind {
+        use ColKind::*;
+        match (a, b) {
+            _ if a == b => a,
+            (Int32, Int64) | (Int64, Int32) => Int64,
+            (Int32 | Int64, Double) | (Double, Int32 | Int64) => Double,
+            (Int32 | Int64 | Double, Decimal) | (Decimal, Int32 | Int64 | Double) => Decimal,
+            // fields with conflicting types are stored as text
+            _ => Text,
+        }
+    }
+
+    fn get_col_type(&self, kind: ColKind) -> &'static str {
+        let is_pg = self.db_type == DbType::Pg;
+        match kind {
+            ColKind::Int32 if is_pg => "integer",
+            ColKind::Int32 => "INT",
+            ColKind::Int64 if is_pg => "bigint",
+            ColKind::Int64 => "BIGINT",
+            ColKind::Double if is_pg => "double precision",
+            ColKind::Double => "DOUBLE",
+            ColKind::Decimal if is_pg => "numeric",
+            ColKind::Decimal => "DECIMAL(65,30)",
+            ColKind::Bool if is_pg => "boolean",
+            ColKind::Bool => "TINYINT(1)",
+            ColKind::DateTime if is_pg => "timestamp(3)",
+            ColKind::DateTime => "DATETIME(3)",
+            ColKind::Binary if is_pg => "bytea",
+            ColKind::Binary => "LONGBLOB",
+            ColKind::Json if is_pg => "jsonb",
+            ColKind::Json => "JSON",
+            ColKind::Text if is_pg => "text",
+            ColKind::Text => "LONGTEXT",
+        }
+    }
+
+    fn get_id_col_type(&self) -> &'static str {
+        if self.db_type == DbType::Pg {
+            "varchar(255)"
+        } else {
+            "VARCHAR(255)"
+        }
+    }
+
+    fn escape(&self, token: &str) -> String {
+        if self.db_type == DbType::Pg {
+            SqlUtil::escape_by_db_type(token, &DbType::Pg)
+        } else {
+            SqlUtil::escape_by_db_type(token, &DbType::Mysql)
+        }
+    }
+
+    fn to_json(value: &Bson) -> String {
+        value.clone().into_relaxed_extjson().to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mongodb::bson::{doc, oid::ObjectId};
+
+    fn flattener(db_type: DbType, doc_col: &str) -> MongoDocFlattener {
+        MongoDocFlattener {
+            db_type,
+            doc_col: doc_col.into(),
+        }
+    }
+
+    #[test]
+    fn test_flatten_doc() {
+        let oid = ObjectId::parse_str("65a0d5b1e4b0a1b2c3d4e5f6").unwrap();
+        let doc = doc! {
+            "_id": oid,
+            "name": "a",
+            "count": 1_i64,
+            "address": {"city": "x", "tags": ["t1", "t2"]},
+            "deleted": Bson::Null,
+        };
+
+        let col_values = flattener(DbType::Mysql, "").flatten_doc(&doc, None);
+        assert_eq!(col_values.len(), 5);
+        assert_eq!(col_values["_id"], ColValue::String(oid.to_hex()));
+        assert_eq!(col_values["name"], ColValue::String("a".into()));
+        assert_eq!(col_values["count"], ColValue::LongLong(1));
+        assert_eq!(
+            col_values["address"],
+            ColValue::Json2(r#"{"city":"x","tags":["t1","t2"]}"#.into())
+        );
+        assert_eq!(col_values["deleted"], ColValue::None);
+
+        let col_map = HashMap::from([
+            ("_id".to_string(), "id".to_string()),
+            ("address.city".to_string(), "city".to_string()),
+            ("address.tags.1".to_string(), "tag_2".to_string()),
+            ("missing.field".to_string(), "missing".to_string()),
+        ]);
+        let col_values = flattener(DbType::Pg, "doc").flatten_doc(&doc, Some(&col_map));
+        assert_eq!(col_values.len(), 5);
+        assert_eq!(col_values["id"], ColValue::String(oid.to_hex()));
+        assert_eq!(col_values["city"], ColValue::String("x".into()));
+        assert_eq!(col_values["tag_2"], ColValue::String("t2".into()));
+        assert_eq!(col_values["missing"], ColValue::None);
+        assert!(matches!(col_values["doc"], ColValue::Json2(_)));
+    }
+
+    #[test]
+    fn test_flatten_row_data() {
+        let tb_meta = RdbTbMeta {
+            cols: vec!["_id".into(), "city".into(), "name".into()],
+            id_cols: vec!["_id".into()],
+            ..Default::default()
+        };
+        let col_map = HashMap::from([
+            ("address.city".to_string(), "city".to_string()),
+            ("name".to_string(), "name".to_string()),
+        ]);
+        let new_row_data = |row_type, before: Option<Document>, after: Option<(&str, Document)>| {
+            RowData::new(
+                "db_1".into(),
+                "tb_1".into(),
+                row_type,
+                before
+                    .map(|d| HashMap::from([(MongoConstants::DOC.into(), ColValue::MongoDoc(d))])),
+                after.map(|(k, d)| HashMap::from([(k.into(), ColValue::MongoDoc(d))])),
+            )
+        };
+        let flattener = flattener(DbType::Pg, "");
+
+        // full-document update is upserted
+        let row_data = new_row_data(
+            RowType::Update,
+            Some(doc! {"_id": 1}),
+            Some((
+                MongoConstants::DOC,
+                doc! {"_id": 1, "name": "a", "other": 1},
+            )),
+        );
+        let res = flattener
+            .flatten_row_data(&row_data, Some(&col_map), &tb_meta)
+            .unwrap();
+        assert_eq!(res.row_type, RowType::Insert);
+        assert_eq!(
+            res.after.unwrap(),
+            HashMap::from([
+                ("_id".to_string(), ColValue::String("1".into())),
+                ("city".to_string(), ColValue::None),
+                ("name".to_string(), ColValue::String("a".into())),
+            ])
+        );
+
+        // diff update from oplog
+        let row_data = new_row_data(
+            RowType::Update,
+            Some(doc! {"_id": "k"}),
+            Some((
+                MongoConstants::DIFF_DOC,
+                doc! {"$set": {"address": {"city": "y"}, "other": 2}},
+            )),
+        );
+        let res = flattener
+            .flatten_row_data(&row_data, Some(&col_map), &tb_meta)
+            .unwrap();
+        assert_eq!(res.row_type, RowType::Update);
+        assert_eq!(
+            res.before.unwrap(),
+            HashMap::from([("_id".to_string(), ColValue::String("k".into()))])
+        );
+        assert_eq!(
+            res.after.unwrap(),
+            HashMap::from([("city".to_string(), ColValue::String("y".into()))])
+        );
+
+        let row_data = new_row_data(
+            RowType::Update,
+            Some(doc! {"_id": "k"}),
+            Some((MongoConstants::DIFF_DOC, doc! {"$unset": {"address": true}})),
+        );
+        let res = flattener
+            .flatten_row_data(&row_data, Some(&col_map), &tb_meta)
+            .unwrap();
+        assert_eq!(
+            res.after.unwrap(),
+            HashMap::from([("city".to_string(), ColValue::None)])
+        );
+
+        // no target col changed
+        let row_data = new_row_data(
+            RowType::Update,
+            Some(doc! {"_id": "k"}),
+            Some((MongoConstants::DIFF_DOC, doc! {"$set": {"other": 3}})),
+        );
+        assert!(flattener
+            .flatten_row_data(&row_data, Some(&col_map), &tb_meta)
+            .is_none());
+    }
+
+    #[test]
+    fn test_need_full_doc() {
+        let diff_doc = doc! {"$set": {"name": "a", "addr.city": "b"}};
+        assert!(flattener(DbType::Mysql, "").need_full_doc(&diff_doc, None));
+        assert!(!flattener(DbType::Mysql, "").need_full_doc(&doc! {"$set": {"name": "a"}}, None));
+        assert!(flattener(DbType::Mysql, "doc").need_full_doc(&doc! {"$set": {"name": "a"}}, None));
+
+        // addr.city is mapped to a col
+        let col_map = HashMap::from([
+            ("name".to_string(), "name".to_string()),
+            ("addr.city".to_string(), "city".to_string()),
+            ("tags".to_string(), "tags".to_string()),
+        ]);
+        assert!(!flattener(DbType::Mysql, "").need_full_doc(&diff_doc, Some(&col_map)));
+        // tags is stored as JSON
+        assert!(flattener(DbType::Mysql, "")
+            .need_full_doc(&doc! {"$set": {"tags.0": "x"}}, Some(&col_map)));
+        assert!(flattener(DbType::Mysql, "")
+            .need_full_doc(&doc! {"$unset": {"tags.1": ""}}, Some(&col_map)));
+        assert!(!flattener(DbType::Mysql, "")
+            .need_full_doc(&doc! {"$set": {"tags_1.0": "x"}}, Some(&col_map)));
+    }
+
+    #[test]
+    fn test_get_create_tb_sqls() {
+        let docs = [
+            doc! {"_id": 1, "Name": "a", "n": 1, "attrs": {"k": "v"}},
+            doc! {"_id": 2, "n": 1.5, "created": mongodb::bson::DateTime::from_millis(0), "attrs": "v"},
+        ];
+
+        assert_eq!(
+            flattener(DbType::Mysql, "").get_create_tb_sqls("db_1", "tb_1", &docs, None),
+            vec![
+                "CREATE DATABASE IF NOT EXISTS `db_1`".to_string(),
+                "CREATE TABLE IF NOT EXISTS `db_1`.`tb_1` (`_id` VARCHAR(255) NOT NULL, \
+                 `Name` LONGTEXT, `n` DOUBLE, `attrs` LONGTEXT, `created` DATETIME(3), \
+                 PRIMARY KEY (`_id`))"
+                    .to_string(),
+            ]
+        );
+
+        let col_map = HashMap::from([
+            ("_id".to_string(), "id".to_string()),
+            ("attrs.k".to_string(), "k".to_string()),
+            ("n".to_string(), "n".to_string()),
+        ]);
+        assert_eq!(
+            flattener(DbType::Pg, "doc").get_create_tb_sqls("s_1", "tb_1", &docs, Some(&col_map)),
+            vec![
+                r#"CREATE SCHEMA IF NOT EXISTS "s_1""#.to_string(),
+                r#"CREATE TABLE IF NOT EXISTS "s_1"."tb_1" ("id" varchar(255) NOT NULL, "k" text, "n" double precision, "doc" jsonb, PRIMARY KEY ("id"))"#
+                    .to_string(),
+            ]
+        );
+    }
+}
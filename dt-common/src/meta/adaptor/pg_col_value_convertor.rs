@@ -10,20 +10,22 @@ pub struct PgColValueConvertor {}
 
 impl PgColValueConvertor {
     pub fn get_extract_type(col_type: &PgColType) -> String {
-        if col_type.alias == "oid" {
-            "int8".to_string()
-        } else {
-            match col_type.value_type {
-                PgValueType::Bytes
-                | PgValueType::Boolean
-                | PgValueType::Int16
-                | PgValueType::Int32
-                | PgValueType::Int64
-                | PgValueType::Float32
-                | PgValueType::Float64 => col_type.alias.to_string(),
-                _ => "text".to_string(),
-            }
+        // decided by value_type instead of alias, since domains, oid and
+        // extension types can not be decoded by sqlx with their own names
+        match col_type.value_type {
+            PgValueType::Bytes => "bytea",
+            PgValueType::Boolean => "bool",
+            PgValueType::Int16 => "int2",
+            PgValueType::Int32 => "int4",
+            PgValueType::Int64 => "int8",
+            PgValueType::Float32 => "float4",
+            PgValueType::Float64 => "float8",
+            // all others, including arrays, geometric, range, xml, bit and
+            // extension types (geometry, hstore...), are extracted by text format,
+            // which can be cast back losslessly by target
+            _ => "text",
         }
+        .to_string()
     }
 
     pub fn from_str(
@@ -0,0 +1,425 @@
+use std::{collections::HashMap, str::FromStr};
+
+use chrono::{DateTime, NaiveDateTime, Utc};
+
+use crate::meta::{
+    col_value::ColValue,
+    mysql::{mysql_col_type::MysqlColType, mysql_tb_meta::MysqlTbMeta},
+    row_data::RowData,
+    time::dt_utc_time::DtNaiveTime,
+};
+
+const DATETIME_FORMAT: &str = "%Y-%m-%d %H:%M:%S%.f";
+const DATETIME_TZ_FORMAT: &str = "%Y-%m-%d %H:%M:%S%.f%#z";
+
+/// Converts col values extracted from Postgres to the values that would be read from the target
+/// MySQL columns, so the sinker writes them correctly and the checker compares them as equal.
+pub struct PgToMysqlColValueConvertor {}
+
+impl PgToMysqlColValueConvertor {
+    pub fn convert_row_data(row_data: &mut RowData, tb_meta: &MysqlTbMeta) {
+        for col_values in [row_data.before.as_mut(), row_data.after.as_mut()]
+            .into_iter()
+            .flatten()
+        {
+            Self::convert_col_values(col_values, tb_meta);
+        }
+    }
+
+    fn convert_col_values(col_values: &mut HashMap<String, ColValue>, tb_meta: &MysqlTbMeta) {
+        for (col, col_value) in col_values.iter_mut() {
+            // cols not in target are ignored by the query builder
+            if let Some(col_type) = Self::get_col_type(tb_meta, col) {
+                let value = std::mem::replace(col_value, ColValue::None);
+                *col_value = Self::convert(value, col_type);
+            }
+        }
+    }
+
+    /// MySQL column names are case insensitive, the exact name is preferred
+    fn get_col_type<'a>(tb_meta: &'a MysqlTbMeta, col: &str) -> Option<&'a MysqlColType> {
+        if let Some(col_type) = tb_meta.col_type_map.get(col) {
+            return Some(col_type);
+        }
+        tb_meta
+            .col_type_map
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(col))
+            .map(|(_, v)| v)
+    }
+
+    pub fn convert(col_value: ColValue, col_type: &MysqlColType) -> ColValue {
+        if col_value == ColValue::None {
+            return col_value;
+        }
+
+        match col_type {
+            MysqlColType::TinyInt { .. }
+            | MysqlColType::SmallInt { .. }
+            | MysqlColType::MediumInt { .. }
+            | MysqlColType::Int { .. }
+            | MysqlColType::BigInt { .. } => Self::convert_integer(col_value, col_type),
+
+            MysqlColType::Float => match col_value {
+                ColValue::Double(v) => ColValue::Float(v as f32),
+                ColValue::Float(_) => col_value,
+                _ => Self::parse_or_keep(col_value, ColValue::Float),
+            },
+
+            MysqlColType::Double => match col_value {
+                ColValue::Float(v) => ColValue::Double(v as f64),
+                ColValue::Double(_) => col_value,
+                _ => Self::parse_or_keep(col_value, ColValue::Double),
+            },
+
+            MysqlColType::Year => Self::parse_or_keep(col_value, ColValue::Year),
+
+            MysqlColType::Bit => match &col_value {
+                // bit(n) in Postgres: '0101'
+                ColValue::String(v) => match u64::from_str_radix(v, 2) {
+                    Ok(v) => ColValue::Bit(v),
+                    Err(_) => col_value,
+                },
+                ColValue::Bool(v) => ColValue::Bit(*v as u64),
+                _ => col_value,
+            },
+
+            MysqlColType::Date { .. } => match col_value {
+                ColValue::String(v) | ColValue::Date(v) => ColValue::Date(v),
+                _ => col_value,
+            },
+
+            MysqlColType::Time { .. } => match &col_value {
+                ColValue::String(v) | ColValue::Time(v) => match DtNaiveTime::from_str(v) {
+                    Ok(time) => ColValue::Time(time.to_string()),
+                    Err(_) => col_value,
+                },
+                _ => col_value,
+            },
+
+            MysqlColType::DateTime { .. } => match &col_value {
+                ColValue::String(v) | ColValue::DateTime(v) | ColValue::Timestamp(v) => {
+                    match Self::normalize_datetime(v) {
+                        Some(v) => ColValue::DateTime(v),
+                        None => col_value,
+                    }
+                }
+                _ => col_value,
+            },
+
+            MysqlColType::Timestamp { .. } => match &col_value {
+                ColValue::String(v) | ColValue::DateTime(v) | ColValue::Timestamp(v) => {
+                    match Self::normalize_datetime(v) {
+                        Some(v) => ColValue::Timestamp(v),
+                        None => col_value,
+                    }
+                }
+                _ => col_value,
+            },
+
+            MysqlColType::Json => Self::convert_json(col_value),
+
+            MysqlColType::Binary { .. }
+            | MysqlColType::VarBinary { .. }
+            | MysqlColType::TinyBlob
+            | MysqlColType::MediumBlob
+            | MysqlColType::Blob
+            | MysqlColType::LongBlob => match col_value {
+                ColValue::Blob(_) => col_value,
+                _ => match col_value.to_option_string() {
+                    Some(v) => ColValue::Blob(v.into_bytes()),
+                    None => col_value,
+                },
+            },
+
+            MysqlColType::Enum { .. } => match col_value {
+                ColValue::String(v) => ColValue::Enum2(v),
+                _ => col_value,
+            },
+
+            MysqlColType::Set { .. } => match col_value {
+                ColValue::String(v) => ColValue::Set2(v),
+                _ => col_value,
+            },
+
+            _ => {
+                if col_type.is_string() {
+                    match col_value {
+                        ColValue::String(_) => col_value,
+                        _ => match col_value.to_option_string() {
+                            Some(v) => ColValue::String(v),
+                            None => col_value,
+                        },
+                    }
+                } else {
+                    col_value
+                }
+            }
+        }
+    }
+
+    fn convert_integer(col_value: ColValue, col_type: &MysqlColType) -> ColValue {
+        let value: Option<i128> = match &col_value {
+            ColValue::Bool(v) => Some(*v as i128),
+            ColValue::Tiny(v) => Some(*v as i128),
+            ColValue::Short(v) => Some(*v as i128),
+            ColValue::Long(v) => Some(*v as i128),
+            ColValue::LongLong(v) => Some(*v as i128),
+            ColValue::UnsignedTiny(v) => Some(*v as i128),
+            ColValue::UnsignedShort(v) => Some(*v as i128),
+            ColValue::UnsignedLong(v) => Some(*v as i128),
+            ColValue::UnsignedLongLong(v) => Some(*v as i128),
+            ColValue::Decimal(v) | ColValue::String(v) => v.parse().ok(),
+            _ => None,
+        };
+        let Some(v) = value else {
+            return col_value;
+        };
+
+        // the same variants as MysqlColValueConvertor::from_query
+        let converted = match col_type {
+            MysqlColType::TinyInt { unsigned: false } => i8::try_from(v).ok().map(ColValue::Tiny),
+            MysqlColType::TinyInt { unsigned: true } => {
+                u8::try_from(v).ok().map(ColValue::UnsignedTiny)
+            }
+            MysqlColType::SmallInt { unsigned: false } => {
+                i16::try_from(v).ok().map(ColValue::Short)
+            }
+            MysqlColType::SmallInt { unsigned: true } => {
+                u16::try_from(v).ok().map(ColValue::UnsignedShort)
+            }
+            MysqlColType::MediumInt { unsigned: false } | MysqlColType::Int { unsigned: false } => {
+                i32::try_from(v).ok().map(ColValue::Long)
+            }
+            MysqlColType::MediumInt { unsigned: true } | MysqlColType::Int { unsigned: true } => {
+                u32::try_from(v).ok().map(ColValue::UnsignedLong)
+            }
+            MysqlColType::BigInt { unsigned: false } => {
+                i64::try_from(v).ok().map(ColValue::LongLong)
+            }
+            MysqlColType::BigInt { unsigned: true } => {
+                u64::try_from(v).ok().map(ColValue::UnsignedLongLong)
+            }
+            _ => None,
+        };
+        // out of range values are kept and will be rejected by target
+        converted.unwrap_or(col_value)
+    }
+
+    fn parse_or_keep<T: FromStr>(col_value: ColValue, f: fn(T) -> ColValue) -> ColValue {
+        match col_value.to_option_string().and_then(|v| v.parse().ok()) {
+            Some(v) => f(v),
+            None => col_value,
+        }
+    }
+
+    /// timestamptz values are converted to UTC, since session.time_zone of target is always '+00:00'
+    fn normalize_datetime(value: &str) -> Option<String> {
+        let naive = if let Ok(v) = DateTime::parse_from_str(value, DATETIME_TZ_FORMAT) {
+            v.with_timezone(&Utc).naive_utc()
+        } else {
+            NaiveDateTime::parse_from_str(value, DATETIME_FORMAT).ok()?
+        };
+        Some(naive.format(DATETIME_FORMAT).to_string())
+    }
+
+    fn convert_json(col_value: ColValue) -> ColValue {
+        let json = match &col_value {
+            ColValue::Json3(v) => Some(v.clone()),
+            ColValue::Json2(v) => serde_json::from_str(v).ok(),
+            // arrays are extracted as text: {1,2,3}
+            ColValue::String(v) => serde_json::from_str(v)
+                .ok()
+                .or_else(|| Self::pg_array_to_json(v)),
+            _ => None,
+        };
+
+        match json {
+            // the same as MysqlColValueConvertor::from_query
+            Some(v) => ColValue::Json2(v.to_string()),
+            None => match col_value {
+                ColValue::Json3(v) => ColValue::Json2(v.to_string()),
+                _ => col_value,
+            },
+        }
+    }
+
+    /// {1,2,NULL} -> [1,2,null], {{"a b","c"},{d,e}} -> [["a b","c"],["d","e"]]
+    pub fn pg_array_to_json(value: &str) -> Option<serde_json::Value> {
+        let chars: Vec<char> = value.chars().collect();
+        let mut pos = 0;
+        let json = Self::parse_pg_array(&chars, &mut pos)?;
+        if pos != chars.len() {
+            return None;
+        }
+        Some(json)
+    }
+
+    fn parse_pg_array(chars: &[char], pos: &mut usize) -> Option<serde_json::Value> {
+        if chars.get(*pos) != Some(&'{') {
+            return None;
+        }
+        *pos += 1;
+
+        let mut items = Vec::new();
+        if chars.get(*pos) == Some(&'}') {
+            *pos += 1;
+            return Some(serde_json::Value::Array(items));
+        }
+
+        loop {
+            let item = match chars.get(*pos)? {
+                '{' => Self::parse_pg_array(chars, pos)?,
+
+                '"' => {
+                    *pos += 1;
+                    let mut item = String::new();
+                    loop {
+                        match chars.get(*pos)? {
+                            '\\' => {
+                                item.push(*chars.get(*pos + 1)?);
+                                *pos += 2;
+                            }
+                            '"' => {
+                                *pos += 1;
+                                break;
+                            }
+                            c => {
+                                item.push(*c);
+                                *pos += 1;
+                            }
+                        }
+                    }
+                    serde_json::Value::String(item)
+                }
+
+                _ => {
+                    let start = *pos;
+                    while !matches!(chars.get(*pos)?, ',' | '}') {
+                        *pos += 1;
+                    }
+                    let item: String = chars[start..*pos].iter().collect();
+                    let item = item.trim();
+                    if item.eq_ignore_ascii_case("NULL") {
+                        serde_json::Value::Null
+                    } else if let Ok(v) = item.parse::<i64>() {
+                        serde_json::Value::from(v)
+                    } else if let Some(v) = item
+                        .parse::<f64>()
+                        .ok()
+                        .and_then(serde_json::Number::from_f64)
+                    {
+                        serde_json::Value::Number(v)
+                    } else {
+                        serde_json::Value::String(item.to_string())
+                    }
+                }
+            };
+            items.push(item);
+
+            match chars.get(*pos)? {
+                ',' => *pos += 1,
+                '}' => {
+                    *pos += 1;
+                    return Some(serde_json::Value::Array(items));
+                }
+                _ => return None,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::meta::{rdb_tb_meta::RdbTbMeta, row_type::RowType};
+
+    #[test]
+    fn test_pg_array_to_json() {
+        let cases = [
+            ("{}", "[]"),
+            ("{1,2,NULL}", "[1,2,null]"),
+            ("{1.5,-2}", "[1.5,-2]"),
+            (r#"{"a b",c,"d\"e"}"#, r#"["a b","c","d\"e"]"#),
+            ("{{1,2},{3,4}}", "[[1,2],[3,4]]"),
+        ];
+        for (pg_array, json) in cases {
+            assert_eq!(
+                PgToMysqlColValueConvertor::pg_array_to_json(pg_array)
+                    .unwrap()
+                    .to_string(),
+                json
+            );
+        }
+        assert!(PgToMysqlColValueConvertor::pg_array_to_json("{1,2").is_none());
+    }
+
+    #[test]
+    fn test_convert_row_data() {
+        let tb_meta = MysqlTbMeta {
+            basic: RdbTbMeta::default(),
+            col_type_map: HashMap::from([
+                ("Id".to_string(), MysqlColType::TinyInt { unsigned: false }),
+                ("value".to_string(), MysqlColType::Json),
+            ]),
+        };
+        let after = HashMap::from([
+            ("Id".to_string(), ColValue::Bool(true)),
+            ("VALUE".to_string(), ColValue::String("{1}".into())),
+            ("other".to_string(), ColValue::Bool(true)),
+        ]);
+        let mut row_data = RowData::new(
+            "db_1".into(),
+            "tb_1".into(),
+            RowType::Insert,
+            None,
+            Some(after),
+        );
+        PgToMysqlColValueConvertor::convert_row_data(&mut row_data, &tb_meta);
+
+        let after = row_data.after.unwrap();
+        assert_eq!(after["Id"], ColValue::Tiny(1));
+        assert_eq!(after["VALUE"], ColValue::Json2("[1]".into()));
+        assert_eq!(after["other"], ColValue::Bool(true));
+    }
+
+    #[test]
+    fn test_convert() {
+        let int_type = MysqlColType::TinyInt { unsigned: false };
+        assert_eq!(
+            PgToMysqlColValueConvertor::convert(ColValue::Bool(true), &int_type),
+            ColValue::Tiny(1)
+        );
+
+        let datetime_type = MysqlColType::DateTime {
+            precision: 6,
+            is_nullable: true,
+        };
+        assert_eq!(
+            PgToMysqlColValueConvertor::convert(
+                ColValue::Timestamp("2023-01-01 08:00:00.12+08".into()),
+                &datetime_type
+            ),
+            ColValue::DateTime("2023-01-01 00:00:00.120".into())
+        );
+        assert_eq!(
+            PgToMysqlColValueConvertor::convert(
+                ColValue::DateTime("2023-01-01 08:00:00".into()),
+                &datetime_type
+            ),
+            ColValue::DateTime("2023-01-01 08:00:00".into())
+        );
+
+        assert_eq!(
+            PgToMysqlColValueConvertor::convert(
+                ColValue::String("{1,2}".into()),
+                &MysqlColType::Json
+            ),
+            ColValue::Json2("[1,2]".into())
+        );
+        assert_eq!(
+            PgToMysqlColValueConvertor::convert(ColValue::String("101".into()), &MysqlColType::Bit),
+            ColValue::Bit(5)
+        );
+    }
+}
@@ -119,6 +119,8 @@ impl<'q> SqlxMysqlExt<'q> for Query<'q, MySql, MySqlArguments> {
                 ColValue::Enum2(v) => self.bind(v),
                 ColValue::Json(v) => self.bind(v),
                 ColValue::Json2(v) => self.bind(v),
+                ColValue::Json3(v) => self.bind(v.to_string()),
+                ColValue::Bool(v) => self.bind(v),
                 _ => {
                     let none: Option<String> = Option::None;
                     self.bind(none)
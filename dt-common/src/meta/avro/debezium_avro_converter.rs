@@ -0,0 +1,853 @@
+use std::{
+    collections::{hash_map::DefaultHasher, HashMap},
+    hash::{Hash, Hasher},
+    sync::Arc,
+};
+
+use anyhow::{bail, Context};
+use apache_avro::{to_avro_datum, types::Value as AvroValue, Schema};
+use chrono::{DateTime, NaiveDate, NaiveDateTime, SecondsFormat, Utc};
+use serde_json::{json, Value};
+
+use crate::{
+    config::config_enums::{DbType, MessageFormat},
+    error::Error,
+    meta::{
+        col_value::ColValue,
+        ddl_meta::ddl_data::DdlData,
+        json::json_converter::{JsonConverter, SERVER_NAME},
+        position::Position,
+        rdb_meta_manager::RdbMetaManager,
+        rdb_tb_meta::RdbTbMeta,
+        row_data::RowData,
+        row_type::RowType,
+    },
+};
+
+// fields of the source block, all optional since they differ between connectors
+const SOURCE_FIELDS: [(&str, &str); 14] = [
+    ("version", "string"),
+    ("connector", "string"),
+    ("name", "string"),
+    ("ts_ms", "long"),
+    ("snapshot", "string"),
+    ("db", "string"),
+    ("schema", "string"),
+    ("table", "string"),
+    ("server_id", "long"),
+    ("gtid", "string"),
+    ("file", "string"),
+    ("pos", "long"),
+    ("row", "int"),
+    ("lsn", "long"),
+];
+
+// decimals with larger precision are sent as strings
+const MAX_DECIMAL_PRECISION: u32 = 38;
+
+/// Avro schema to be registered in schema registry
+pub struct AvroSchema {
+    // full name of the record, e.g. ape_dts.test_db.tb_1.Envelope
+    pub name: String,
+    pub json: String,
+    // hash of json, to identify schema versions
+    pub fingerprint: u64,
+    // parsed without logical types, the binary encodings are the same
+    schema: Schema,
+}
+
+/// Avro datum without the schema registry wire format header
+pub struct AvroDatum {
+    pub schema: Arc<AvroSchema>,
+    pub datum: Vec<u8>,
+}
+
+struct AvroCol {
+    col: String,
+    field: String,
+    col_type: AvroColType,
+}
+
+struct TbSchema {
+    cols: Vec<AvroCol>,
+    // indexes of the primary / unique key columns in cols
+    id_indexes: Vec<usize>,
+    key: Option<Arc<AvroSchema>>,
+    value: Arc<AvroSchema>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum AvroColType {
+    Boolean,
+    Int16,
+    Int32,
+    Int64,
+    Float,
+    Double,
+    Decimal { precision: u32, scale: u32 },
+    String,
+    Bytes,
+    Date,
+    MicroTimestamp,
+    ZonedTimestamp,
+    MicroTime,
+    Json,
+}
+
+/// Converts rows / ddls to messages compatible with Debezium AvroConverter,
+/// the key / value schemas are generated per table from the table meta of the source
+#[derive(Clone)]
+pub struct DebeziumAvroConverter {
+    pub json_converter: JsonConverter,
+    // key: (schema, tb)
+    tb_schemas: HashMap<(String, String), Arc<TbSchema>>,
+}
+
+impl DebeziumAvroConverter {
+    pub fn new(db_type: DbType, meta_manager: Option<RdbMetaManager>) -> Self {
+        Self {
+            json_converter: JsonConverter::new(
+                MessageFormat::DebeziumJson,
+                db_type,
+                false,
+                meta_manager,
+            ),
+            tb_schemas: HashMap::new(),
+        }
+    }
+
+    /// schemas of tables are regenerated after ddl
+    pub fn refresh_meta(&mut self, data: &[DdlData]) {
+        self.json_converter.refresh_meta(data);
+        if !data.is_empty() {
+            self.tb_schemas.clear();
+        }
+    }
+
+    /// Returns the key (None if the table has no primary / unique key) and the value
+    pub async fn row_data_to_avro(
+        &mut self,
+        row_data: &RowData,
+        position: &Position,
+    ) -> anyhow::Result<(Option<AvroDatum>, AvroDatum)> {
+        let tb_schema = self.get_tb_schema(&row_data.schema, &row_data.tb).await?;
+
+        let key = if let Some(key_schema) = &tb_schema.key {
+            let col_values = match row_data.row_type {
+                RowType::Insert => &row_data.after,
+                RowType::Update | RowType::Delete => &row_data.before,
+            };
+            let mut fields = Vec::new();
+            for i in tb_schema.id_indexes.iter() {
+                let col = &tb_schema.cols[*i];
+                fields.push((col.field.clone(), Self::to_avro_field(col, col_values)?));
+            }
+            Some(Self::to_avro_datum(key_schema, AvroValue::Record(fields))?)
+        } else {
+            None
+        };
+
+        let op = match row_data.row_type {
+            RowType::Insert if !JsonConverter::is_cdc(position) => "r",
+            RowType::Insert => "c",
+            RowType::Update => "u",
+            RowType::Delete => "d",
+        };
+        let source =
+            self.json_converter
+                .get_debezium_source(&row_data.schema, &row_data.tb, position);
+        let value = AvroValue::Record(vec![
+            (
+                "before".into(),
+                Self::to_avro_image(&tb_schema, &row_data.before)?,
+            ),
+            (
+                "after".into(),
+                Self::to_avro_image(&tb_schema, &row_data.after)?,
+            ),
+            ("source".into(), Self::to_avro_source(&source)),
+            ("op".into(), AvroValue::String(op.into())),
+            (
+                "ts_ms".into(),
+                AvroValue::Union(1, Box::new(AvroValue::Long(Utc::now().timestamp_millis()))),
+            ),
+        ]);
+        Ok((key, Self::to_avro_datum(&tb_schema.value, value)?))
+    }
+
+    /// schema change event: {"source": {...}, "databaseName": "test_db", "ddl": "..."}
+    pub fn ddl_data_to_avro(
+        &self,
+        ddl_data: &DdlData,
+        position: &Position,
+    ) -> anyhow::Result<AvroDatum> {
+        let (schema, tb) = ddl_data.get_schema_tb();
+        let connector = JsonConverter::get_debezium_connector(&self.json_converter.db_type);
+        let schema_json = json!({
+            "type": "record",
+            "name": "SchemaChangeValue",
+            "namespace": format!("io.debezium.connector.{}", connector),
+            "fields": [
+                {"name": "source", "type": Self::get_source_schema(connector)},
+                {"name": "databaseName", "type": "string"},
+                {"name": "ddl", "type": "string"},
+            ],
+            "connect.name": format!("io.debezium.connector.{}.SchemaChangeValue", connector),
+        });
+        let avro_schema = Arc::new(Self::parse_schema(schema_json)?);
+
+        let source = self
+            .json_converter
+            .get_debezium_source(&schema, &tb, position);
+        let value = AvroValue::Record(vec![
+            ("source".into(), Self::to_avro_source(&source)),
+            ("databaseName".into(), AvroValue::String(schema)),
+            ("ddl".into(), AvroValue::String(ddl_data.query.clone())),
+        ]);
+        Self::to_avro_datum(&avro_schema, value)
+    }
+
+    /// Key of ddl messages, refer to io.debezium.connector.*.SchemaChangeKey
+    pub fn ddl_key_to_avro(&self, schema: &str) -> anyhow::Result<AvroDatum> {
+        let connector = JsonConverter::get_debezium_connector(&self.json_converter.db_type);
+        let schema_json = json!({
+            "type": "record",
+            "name": "SchemaChangeKey",
+            "namespace": format!("io.debezium.connector.{}", connector),
+            "fields": [
+                {"name": "databaseName", "type": "string"},
+            ],
+            "connect.name": format!("io.debezium.connector.{}.SchemaChangeKey", connector),
+        });
+        let avro_schema = Arc::new(Self::parse_schema(schema_json)?);
+        let value = AvroValue::Record(vec![(
+            "databaseName".into(),
+            AvroValue::String(schema.to_string()),
+        )]);
+        Self::to_avro_datum(&avro_schema, value)
+    }
+
+    async fn get_tb_schema(&mut self, schema: &str, tb: &str) -> anyhow::Result<Arc<TbSchema>> {
+        let key = (schema.to_string(), tb.to_string());
+        if let Some(tb_schema) = self.tb_schemas.get(&key) {
+            return Ok(tb_schema.clone());
+        }
+
+        let Some(meta_manager) = self.json_converter.meta_manager.as_mut() else {
+            bail! {Error::Unexpected(
+                "table meta of the source is required by message_format: debezium_avro".into()
+            )}
+        };
+        let tb_meta = meta_manager.get_tb_meta(schema, tb).await?;
+        let tb_schema = Arc::new(Self::build_tb_schema(
+            &self.json_converter.db_type,
+            tb_meta,
+        )?);
+        self.tb_schemas.insert(key, tb_schema.clone());
+        Ok(tb_schema)
+    }
+
+    fn build_tb_schema(db_type: &DbType, tb_meta: &RdbTbMeta) -> anyhow::Result<TbSchema> {
+        let cols: Vec<AvroCol> = tb_meta
+            .cols
+            .iter()
+            .map(|col| {
+                let origin_type = tb_meta.col_origin_type_map.get(col);
+                AvroCol {
+                    col: col.clone(),
+                    field: Self::to_avro_name(col),
+                    col_type: Self::get_col_type(db_type, origin_type.map_or("", |v| v.as_str())),
+                }
+            })
+            .collect();
+        let id_indexes: Vec<usize> = tb_meta
+            .id_cols
+            .iter()
+            .filter_map(|id_col| cols.iter().position(|c| c.col == *id_col))
+            .collect();
+
+        let namespace = format!(
+            "{}.{}.{}",
+            SERVER_NAME,
+            Self::to_avro_name(&tb_meta.schema),
+            Self::to_avro_name(&tb_meta.tb)
+        );
+        let col_fields = |indexes: &mut dyn Iterator<Item = usize>| -> Vec<Value> {
+            indexes
+                .map(|i| {
+                    json!({
+                        "name": cols[i].field,
+                        "type": ["null", cols[i].col_type.to_avro_schema()],
+                        "default": null,
+                    })
+                })
+                .collect()
+        };
+
+        let key = if id_indexes.is_empty() {
+            None
+        } else {
+            let key_json = json!({
+                "type": "record",
+                "name": "Key",
+                "namespace": namespace,
+                "fields": col_fields(&mut id_indexes.iter().cloned()),
+                "connect.name": format!("{}.Key", namespace),
+            });
+            Some(Arc::new(Self::parse_schema(key_json)?))
+        };
+
+        let connector = JsonConverter::get_debezium_connector(db_type);
+        let value_json = json!({
+            "type": "record",
+            "name": "Envelope",
+            "namespace": namespace,
+            "fields": [
+                {
+                    "name": "before",
+                    "type": ["null", {
+                        "type": "record",
+                        "name": "Value",
+                        "fields": col_fields(&mut (0..cols.len())),
+                        "connect.name": format!("{}.Value", namespace),
+                    }],
+                    "default": null,
+                },
+                {"name": "after", "type": ["null", "Value"], "default": null},
+                {"name": "source", "type": Self::get_source_schema(connector)},
+                {"name": "op", "type": "string"},
+                {"name": "ts_ms", "type": ["null", "long"], "default": null},
+            ],
+            "connect.name": format!("{}.Envelope", namespace),
+        });
+        let value = Arc::new(Self::parse_schema(value_json)?);
+
+        Ok(TbSchema {
+            cols,
+            id_indexes,
+            key,
+            value,
+        })
+    }
+
+    fn get_source_schema(connector: &str) -> Value {
+        let fields: Vec<Value> = SOURCE_FIELDS
+            .iter()
+            .map(|(name, avro_type)| {
+                json!({
+                    "name": name,
+                    "type": ["null", avro_type],
+                    "default": null,
+                })
+            })
+            .collect();
+        json!({
+            "type": "record",
+            "name": "Source",
+            "namespace": format!("io.debezium.connector.{}", connector),
+            "fields": fields,
+            "connect.name": format!("io.debezium.connector.{}.Source", connector),
+        })
+    }
+
+    fn parse_schema(schema_json: Value) -> anyhow::Result<AvroSchema> {
+        let json = schema_json.to_string();
+        let mut hasher = DefaultHasher::new();
+        json.hash(&mut hasher);
+
+        let mut writer_json = schema_json;
+        Self::remove_logical_types(&mut writer_json);
+        let schema = Schema::parse(&writer_json)?;
+        Ok(AvroSchema {
+            name: format!(
+                "{}.{}",
+                writer_json["namespace"].as_str().unwrap_or_default(),
+                writer_json["name"].as_str().unwrap_or_default()
+            ),
+            json,
+            fingerprint: hasher.finish(),
+            schema,
+        })
+    }
+
+    fn remove_logical_types(schema: &mut Value) {
+        match schema {
+            Value::Object(obj) => {
+                obj.remove("logicalType");
+                obj.values_mut().for_each(Self::remove_logical_types);
+            }
+            Value::Array(values) => values.iter_mut().for_each(Self::remove_logical_types),
+            _ => {}
+        }
+    }
+
+    fn to_avro_datum(schema: &Arc<AvroSchema>, value: AvroValue) -> anyhow::Result<AvroDatum> {
+        Ok(AvroDatum {
+            schema: schema.clone(),
+            datum: to_avro_datum(&schema.schema, value)?,
+        })
+    }
+
+    fn to_avro_source(source: &Value) -> AvroValue {
+        let fields = SOURCE_FIELDS
+            .iter()
+            .map(|(name, avro_type)| {
+                let value = match (&source[name], *avro_type) {
+                    (Value::String(v), "string") => Some(AvroValue::String(v.clone())),
+                    (Value::Number(v), "long") => v.as_i64().map(AvroValue::Long),
+                    (Value::Number(v), "int") => v.as_i64().map(|v| AvroValue::Int(v as i32)),
+                    _ => None,
+                };
+                let value = match value {
+                    Some(v) => AvroValue::Union(1, Box::new(v)),
+                    None => AvroValue::Union(0, Box::new(AvroValue::Null)),
+                };
+                (name.to_string(), value)
+            })
+            .collect();
+        AvroValue::Record(fields)
+    }
+
+    fn to_avro_image(
+        tb_schema: &TbSchema,
+        col_values: &Option<HashMap<String, ColValue>>,
+    ) -> anyhow::Result<AvroValue> {
+        if col_values.is_none() {
+            return Ok(AvroValue::Union(0, Box::new(AvroValue::Null)));
+        }
+        let mut fields = Vec::new();
+        for col in tb_schema.cols.iter() {
+            fields.push((col.field.clone(), Self::to_avro_field(col, col_values)?));
+        }
+        Ok(AvroValue::Union(1, Box::new(AvroValue::Record(fields))))
+    }
+
+    fn to_avro_field(
+        col: &AvroCol,
+        col_values: &Option<HashMap<String, ColValue>>,
+    ) -> anyhow::Result<AvroValue> {
+        let col_value = col_values.as_ref().and_then(|v| v.get(&col.col));
+        let value = match col_value {
+            None | Some(ColValue::None) => None,
+            Some(col_value) => col
+                .col_type
+                .to_avro_value(col_value)
+                .with_context(|| format!("invalid value of {}: {:?}", col.col, col_value))?,
+        };
+        Ok(match value {
+            Some(v) => AvroValue::Union(1, Box::new(v)),
+            None => AvroValue::Union(0, Box::new(AvroValue::Null)),
+        })
+    }
+
+    // avro names: [A-Za-z_][A-Za-z0-9_]*
+    fn to_avro_name(name: &str) -> String {
+        let mut avro_name: String = name
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect();
+        if !avro_name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_') {
+            avro_name.insert(0, '_');
+        }
+        avro_name
+    }
+
+    // mysql: int(11) unsigned, decimal(10,2), pg: int4, numeric, timestamptz
+    fn get_col_type(db_type: &DbType, origin_type: &str) -> AvroColType {
+        let origin_type = origin_type.to_lowercase();
+        let unsigned = origin_type.contains("unsigned");
+        let base_type = origin_type
+            .split(['(', ' '])
+            .next()
+            .unwrap_or_default()
+            .to_string();
+
+        match base_type.as_str() {
+            "bool" | "boolean" => AvroColType::Boolean,
+            "smallint" if unsigned => AvroColType::Int32,
+            "tinyint" | "smallint" | "int2" => AvroColType::Int16,
+            "int" | "integer" if unsigned => AvroColType::Int64,
+            "mediumint" | "int" | "integer" | "int4" | "year" => AvroColType::Int32,
+            "bigint" if unsigned => AvroColType::Decimal {
+                precision: 20,
+                scale: 0,
+            },
+            "bigint" | "int8" | "bit" => AvroColType::Int64,
+            "float" | "float4" | "real" => AvroColType::Float,
+            "double" | "float8" => AvroColType::Double,
+            "decimal" | "numeric" => Self::get_decimal_type(&origin_type),
+            "date" => AvroColType::Date,
+            "datetime" => AvroColType::MicroTimestamp,
+            // mysql timestamps are in UTC
+            "timestamp" if *db_type == DbType::Mysql => AvroColType::ZonedTimestamp,
+            "timestamp" => AvroColType::MicroTimestamp,
+            "timestamptz" => AvroColType::ZonedTimestamp,
+            "time" => AvroColType::MicroTime,
+            "binary" | "varbinary" | "tinyblob" | "blob" | "mediumblob" | "longblob" | "bytea" => {
+                AvroColType::Bytes
+            }
+            "json" | "jsonb" => AvroColType::Json,
+            _ => AvroColType::String,
+        }
+    }
+
+    // decimal(10,2) -> Decimal, decimals without precision / scale (pg numeric) -> String
+    fn get_decimal_type(origin_type: &str) -> AvroColType {
+        let params = origin_type
+            .split_once('(')
+            .and_then(|(_, v)| v.split_once(')'))
+            .map(|(v, _)| v);
+        if let Some(params) = params {
+            let mut params = params.split(',').map(|v| v.trim().parse::<u32>());
+            if let Some(Ok(precision)) = params.next() {
+                let scale = match params.next() {
+                    Some(Ok(scale)) => scale,
+                    Some(Err(_)) => return AvroColType::String,
+                    None => 0,
+                };
+                if precision <= MAX_DECIMAL_PRECISION {
+                    return AvroColType::Decimal { precision, scale };
+                }
+            }
+        }
+        AvroColType::String
+    }
+
+    /// big-endian two's complement of the unscaled value, "-12.3" with scale 2 -> -1230
+    fn encode_decimal(value: &str, scale: u32) -> anyhow::Result<Vec<u8>> {
+        let (negative, digits) = match value.strip_prefix('-') {
+            Some(v) => (true, v),
+            None => (false, value),
+        };
+        let (int_part, frac_part) = digits.split_once('.').unwrap_or((digits, ""));
+        let scale = scale as usize;
+        let frac_part = &frac_part[..frac_part.len().min(scale)];
+        let unscaled: i128 = format!("{}{:0<scale$}", int_part, frac_part, scale = scale)
+            .parse()
+            .with_context(|| format!("invalid decimal: {}", value))?;
+        let unscaled = if negative { -unscaled } else { unscaled };
+
+        // remove redundant sign bytes
+        let bytes = unscaled.to_be_bytes();
+        let mut start = 0;
+        while start < bytes.len() - 1 {
+            let (b, next) = (bytes[start], bytes[start + 1]);
+            if (b == 0 && next & 0x80 == 0) || (b == 0xff && next & 0x80 != 0) {
+                start += 1;
+            } else {
+                break;
+            }
+        }
+        Ok(bytes[start..].to_vec())
+    }
+
+    /// mysql time may be negative or exceed 24 hours, e.g. -838:59:59.000000
+    fn parse_time_micros(value: &str) -> Option<i64> {
+        let (sign, value) = match value.strip_prefix('-') {
+            Some(v) => (-1, v),
+            None => (1, value),
+        };
+        let (hms, frac) = value.split_once('.').unwrap_or((value, ""));
+        let mut parts = hms.split(':');
+        let hours: i64 = parts.next()?.parse().ok()?;
+        let minutes: i64 = parts.next()?.parse().ok()?;
+        let seconds: i64 = parts.next()?.parse().ok()?;
+        let micros: i64 = if frac.is_empty() {
+            0
+        } else {
+            format!("{:0<6}", &frac[..frac.len().min(6)]).parse().ok()?
+        };
+        Some(sign * ((hours * 3600 + minutes * 60 + seconds) * 1_000_000 + micros))
+    }
+
+    /// timestamps with offsets (pg timestamptz) or in UTC (mysql timestamp)
+    fn parse_zoned_timestamp(value: &str) -> Option<DateTime<Utc>> {
+        if let Ok(v) = DateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S%.f%#z") {
+            return Some(v.to_utc());
+        }
+        NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S%.f")
+            .ok()
+            .map(|v| v.and_utc())
+    }
+}
+
+impl AvroColType {
+    fn to_avro_schema(&self) -> Value {
+        let connect_type = |avro_type: &str, connect_name: &str| {
+            json!({
+                "type": avro_type,
+                "connect.version": 1,
+                "connect.name": connect_name,
+            })
+        };
+        match self {
+            Self::Boolean => json!("boolean"),
+            Self::Int16 => json!({"type": "int", "connect.type": "int16"}),
+            Self::Int32 => json!("int"),
+            Self::Int64 => json!("long"),
+            Self::Float => json!("float"),
+            Self::Double => json!("double"),
+            Self::Decimal { precision, scale } => json!({
+                "type": "bytes",
+                "scale": scale,
+                "precision": precision,
+                "connect.version": 1,
+                "connect.parameters": {
+                    "scale": scale.to_string(),
+                    "connect.decimal.precision": precision.to_string(),
+                },
+                "connect.name": "org.apache.kafka.connect.data.Decimal",
+                "logicalType": "decimal",
+            }),
+            Self::String => json!("string"),
+            Self::Bytes => json!("bytes"),
+            Self::Date => connect_type("int", "io.debezium.time.Date"),
+            Self::MicroTimestamp => connect_type("long", "io.debezium.time.MicroTimestamp"),
+            Self::ZonedTimestamp => connect_type("string", "io.debezium.time.ZonedTimestamp"),
+            Self::MicroTime => connect_type("long", "io.debezium.time.MicroTime"),
+            Self::Json => connect_type("string", "io.debezium.data.Json"),
+        }
+    }
+
+    /// None for values can not be represented, e.g. zero dates of mysql
+    fn to_avro_value(&self, col_value: &ColValue) -> anyhow::Result<Option<AvroValue>> {
+        let str = col_value.to_option_string().unwrap_or_default();
+        let value = match self {
+            Self::Boolean => match col_value {
+                ColValue::Bool(v) => AvroValue::Boolean(*v),
+                _ => AvroValue::Boolean(matches!(str.as_str(), "1" | "t" | "true")),
+            },
+            Self::Int16 | Self::Int32 => AvroValue::Int(str.parse()?),
+            Self::Int64 => AvroValue::Long(str.parse()?),
+            Self::Float => AvroValue::Float(str.parse()?),
+            Self::Double => AvroValue::Double(str.parse()?),
+            Self::Decimal { scale, .. } => {
+                AvroValue::Bytes(DebeziumAvroConverter::encode_decimal(&str, *scale)?)
+            }
+            Self::String => match col_value {
+                ColValue::Blob(v) | ColValue::RawString(v) => {
+                    AvroValue::String(String::from_utf8_lossy(v).to_string())
+                }
+                _ => AvroValue::String(str),
+            },
+            Self::Bytes => match col_value {
+                ColValue::Blob(v) | ColValue::RawString(v) => AvroValue::Bytes(v.clone()),
+                _ => AvroValue::Bytes(str.into_bytes()),
+            },
+            Self::Date => {
+                let Ok(date) = NaiveDate::parse_from_str(&str, "%Y-%m-%d") else {
+                    return Ok(None);
+                };
+                let days = (date - NaiveDate::default()).num_days();
+                AvroValue::Int(days as i32)
+            }
+            Self::MicroTimestamp => {
+                let Ok(datetime) = NaiveDateTime::parse_from_str(&str, "%Y-%m-%d %H:%M:%S%.f")
+                else {
+                    return Ok(None);
+                };
+                AvroValue::Long(datetime.and_utc().timestamp_micros())
+            }
+            Self::ZonedTimestamp => {
+                let Some(datetime) = DebeziumAvroConverter::parse_zoned_timestamp(&str) else {
+                    return Ok(None);
+                };
+                AvroValue::String(datetime.to_rfc3339_opts(SecondsFormat::AutoSi, true))
+            }
+            Self::MicroTime => match DebeziumAvroConverter::parse_time_micros(&str) {
+                Some(micros) => AvroValue::Long(micros),
+                None => return Ok(None),
+            },
+            Self::Json => match col_value {
+                ColValue::Json(v) => AvroValue::String(String::from_utf8_lossy(v).to_string()),
+                _ => AvroValue::String(str),
+            },
+        };
+        Ok(Some(value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::meta::{dt_data::DtData, json::json_decoder::JsonDecoder};
+
+    fn get_tb_meta() -> RdbTbMeta {
+        let cols = [
+            ("id", "int(11)"),
+            ("f_1", "decimal(10,2)"),
+            ("f_2", "datetime(6)"),
+            ("f_3", "timestamp(3)"),
+            ("f_4", "date"),
+            ("f_5", "time(6)"),
+            ("f_6", "varbinary(10)"),
+            ("f_7", "json"),
+            ("f_8", "bigint unsigned"),
+            ("f-9", "varchar(255)"),
+        ];
+        RdbTbMeta {
+            schema: "test_db".into(),
+            tb: "tb_1".into(),
+            cols: cols.iter().map(|(c, _)| c.to_string()).collect(),
+            col_origin_type_map: cols
+                .iter()
+                .map(|(c, t)| (c.to_string(), t.to_string()))
+                .collect(),
+            id_cols: vec!["id".into()],
+            ..Default::default()
+        }
+    }
+
+    fn get_converter() -> DebeziumAvroConverter {
+        let mut converter = DebeziumAvroConverter::new(DbType::Mysql, None);
+        let tb_schema = DebeziumAvroConverter::build_tb_schema(&DbType::Mysql, &get_tb_meta());
+        converter.tb_schemas.insert(
+            ("test_db".into(), "tb_1".into()),
+            Arc::new(tb_schema.unwrap()),
+        );
+        converter
+    }
+
+    #[tokio::test]
+    async fn test_round_trip() {
+        let mut converter = get_converter();
+        let mut after = HashMap::new();
+        after.insert("id".to_string(), ColValue::Long(1));
+        after.insert("f_1".to_string(), ColValue::Decimal("-12.34".into()));
+        after.insert(
+            "f_2".to_string(),
+            ColValue::DateTime("2024-01-02 03:04:05.123456".into()),
+        );
+        after.insert(
+            "f_3".to_string(),
+            ColValue::Timestamp("2024-01-02 03:04:05.123".into()),
+        );
+        after.insert("f_4".to_string(), ColValue::Date("2024-01-02".into()));
+        after.insert("f_5".to_string(), ColValue::Time("-838:59:59.5".into()));
+        after.insert("f_6".to_string(), ColValue::Blob(vec![0, 255]));
+        after.insert("f_7".to_string(), ColValue::Json2("{\"a\": 1}".into()));
+        after.insert(
+            "f_8".to_string(),
+            ColValue::UnsignedLongLong(18446744073709551615),
+        );
+        after.insert("f-9".to_string(), ColValue::None);
+        let row_data = RowData::new(
+            "test_db".into(),
+            "tb_1".into(),
+            RowType::Insert,
+            None,
+            Some(after),
+        );
+
+        let (key, value) = converter
+            .row_data_to_avro(&row_data, &Position::None)
+            .await
+            .unwrap();
+        let key = key.unwrap();
+        assert_eq!(key.schema.name, "ape_dts.test_db.tb_1.Key");
+        assert_eq!(value.schema.name, "ape_dts.test_db.tb_1.Envelope");
+        // the registered schema keeps logical types
+        assert!(value.schema.json.contains("\"logicalType\":\"decimal\""));
+
+        let mut decoder = JsonDecoder::new(MessageFormat::DebeziumAvro);
+        decoder.add_avro_schema(1, &value.schema.json).unwrap();
+        let dt_datas = decoder.debezium_avro_to_dt_data(1, &value.datum).unwrap();
+        let DtData::Dml { row_data } = &dt_datas[0] else {
+            panic!()
+        };
+        assert_eq!(row_data.schema, "test_db");
+        assert_eq!(row_data.tb, "tb_1");
+        assert_eq!(row_data.row_type, RowType::Insert);
+        let after = row_data.after.as_ref().unwrap();
+        assert_eq!(after["id"], ColValue::LongLong(1));
+        assert_eq!(after["f_1"], ColValue::Decimal("-12.34".into()));
+        assert_eq!(
+            after["f_2"],
+            ColValue::DateTime("2024-01-02 03:04:05.123456".into())
+        );
+        assert_eq!(
+            after["f_3"],
+            ColValue::Timestamp("2024-01-02 03:04:05.123000".into())
+        );
+        assert_eq!(after["f_4"], ColValue::Date("2024-01-02".into()));
+        assert_eq!(after["f_5"], ColValue::Time("-838:59:59.500000".into()));
+        assert_eq!(after["f_6"], ColValue::Blob(vec![0, 255]));
+        assert_eq!(after["f_7"], ColValue::Json2("{\"a\": 1}".into()));
+        assert_eq!(
+            after["f_8"],
+            ColValue::Decimal("18446744073709551615".into())
+        );
+        assert_eq!(after["f_9"], ColValue::None);
+    }
+
+    #[test]
+    fn test_ddl() {
+        let converter = get_converter();
+        let ddl_data = DdlData {
+            default_schema: "test_db".into(),
+            query: "TRUNCATE TABLE tb_1".into(),
+            ..Default::default()
+        };
+        let value = converter
+            .ddl_data_to_avro(&ddl_data, &Position::None)
+            .unwrap();
+        assert_eq!(
+            value.schema.name,
+            "io.debezium.connector.mysql.SchemaChangeValue"
+        );
+
+        let mut decoder = JsonDecoder::new(MessageFormat::DebeziumAvro);
+        decoder.add_avro_schema(1, &value.schema.json).unwrap();
+        let dt_datas = decoder.debezium_avro_to_dt_data(1, &value.datum).unwrap();
+        let DtData::Ddl { ddl_data } = &dt_datas[0] else {
+            panic!()
+        };
+        assert_eq!(ddl_data.default_schema, "test_db");
+        assert_eq!(ddl_data.query, "TRUNCATE TABLE tb_1");
+    }
+
+    #[test]
+    fn test_get_col_type() {
+        let get =
+            |origin_type: &str| DebeziumAvroConverter::get_col_type(&DbType::Mysql, origin_type);
+        assert_eq!(get("tinyint(4)"), AvroColType::Int16);
+        assert_eq!(get("int(10) unsigned"), AvroColType::Int64);
+        assert_eq!(get("decimal(65,30)"), AvroColType::String);
+        assert_eq!(
+            get("decimal(10,2)"),
+            AvroColType::Decimal {
+                precision: 10,
+                scale: 2
+            }
+        );
+        assert_eq!(get("timestamp(6)"), AvroColType::ZonedTimestamp);
+        assert_eq!(get("enum('a','b')"), AvroColType::String);
+
+        let get = |origin_type: &str| DebeziumAvroConverter::get_col_type(&DbType::Pg, origin_type);
+        assert_eq!(get("numeric"), AvroColType::String);
+        assert_eq!(get("timestamp"), AvroColType::MicroTimestamp);
+        assert_eq!(get("timestamptz"), AvroColType::ZonedTimestamp);
+        assert_eq!(get("bytea"), AvroColType::Bytes);
+    }
+
+    #[test]
+    fn test_encode_decimal() {
+        assert_eq!(
+            DebeziumAvroConverter::encode_decimal("12.34", 2).unwrap(),
+            vec![0x04, 0xd2]
+        );
+        assert_eq!(
+            DebeziumAvroConverter::encode_decimal("-1", 0).unwrap(),
+            vec![0xff]
+        );
+        assert_eq!(
+            DebeziumAvroConverter::encode_decimal("128", 0).unwrap(),
+            vec![0x00, 0x80]
+        );
+        assert_eq!(
+            DebeziumAvroConverter::encode_decimal("0.5", 3).unwrap(),
+            vec![0x01, 0xf4]
+        );
+        assert!(DebeziumAvroConverter::encode_decimal("abc", 0).is_err());
+    }
+}
@@ -1,2 +1,3 @@
 pub mod avro_converter;
 pub mod avro_converter_schema;
+pub mod debezium_avro_converter;
@@ -5,6 +5,7 @@ use std::{
 
 use mongodb::bson::Document;
 use serde::{Deserialize, Serialize, Serializer};
+use serde_json::Value;
 
 use crate::utils::sql_util::SqlUtil;
 
@@ -102,6 +103,22 @@ impl ColValue {
         }
     }
 
+    /// Binaries are hex strings, json columns are json values
+    pub fn to_json_value(&self) -> Value {
+        match self {
+            ColValue::RawString(v) => Value::String(SqlUtil::binary_to_str(v).0),
+            ColValue::Blob(v) => Value::String(hex::encode(v)),
+            ColValue::Json(v) => serde_json::from_slice(v)
+                .unwrap_or_else(|_| Value::String(String::from_utf8_lossy(v).to_string())),
+            ColValue::Json2(v) => {
+                serde_json::from_str(v).unwrap_or_else(|_| Value::String(v.clone()))
+            }
+            ColValue::Json3(v) => v.clone(),
+            ColValue::MongoDoc(v) => Value::String(v.to_string()),
+            _ => serde_json::to_value(self).unwrap_or(Value::Null),
+        }
+    }
+
     pub fn is_nan(&self) -> bool {
         match &self {
             ColValue::Float(v) => v.is_nan(),
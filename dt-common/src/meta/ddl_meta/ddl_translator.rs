@@ -0,0 +1,2022 @@
+use anyhow::bail;
+
+use crate::{
+    config::config_enums::DbType,
+    error::Error,
+    log_warn,
+    meta::struct_meta::{
+        mysql_to_pg_converter::MysqlToPgConverter,
+        pg_to_mysql_converter::PgToMysqlConverter,
+        structure::column::{Column, ColumnDefault},
+    },
+    utils::sql_util::SqlUtil,
+};
+
+use super::{
+    ddl_data::DdlData,
+    ddl_statement::{DdlStatement, MysqlCreateIndexStatement, PgCreateIndexStatement},
+};
+
+const MYSQL_COL_KEYWORDS: [&str; 24] = [
+    "NOT",
+    "NULL",
+    "DEFAULT",
+    "AUTO_INCREMENT",
+    "PRIMARY",
+    "KEY",
+    "UNIQUE",
+    "COMMENT",
+    "CHARACTER",
+    "CHARSET",
+    "COLLATE",
+    "ON",
+    "CHECK",
+    "CONSTRAINT",
+    "REFERENCES",
+    "GENERATED",
+    "AS",
+    "VISIBLE",
+    "INVISIBLE",
+    "SRID",
+    "COLUMN_FORMAT",
+    "STORAGE",
+    "FIRST",
+    "AFTER",
+];
+
+const PG_COL_KEYWORDS: [&str; 10] = [
+    "NOT",
+    "NULL",
+    "DEFAULT",
+    "PRIMARY",
+    "UNIQUE",
+    "CHECK",
+    "CONSTRAINT",
+    "REFERENCES",
+    "GENERATED",
+    "COLLATE",
+];
+
+/// Translates ddl parsed from MySQL to Postgres sqls and vice versa,
+/// column types and defaults are mapped in the same way as structure migration.
+pub struct DdlTranslator {
+    default_schema: String,
+    schema: String,
+    tb: String,
+}
+
+#[derive(Default)]
+struct ColumnDef {
+    name: String,
+    col_type: String,
+    nullable: Option<bool>,
+    default: Option<String>,
+    auto_increment: bool,
+    primary_key: bool,
+    unique: bool,
+    comment: Option<String>,
+    checks: Vec<String>,
+    references: Option<String>,
+}
+
+enum KeyDef {
+    // part of CREATE TABLE / ALTER TABLE ADD
+    Inline(String),
+    // separate sqls, e.g. CREATE INDEX
+    Sqls(Vec<String>),
+    Skip,
+}
+
+impl DdlTranslator {
+    /// Renders the ddl for dst_db_type, one ddl may be translated to multiple sqls,
+    /// for example: indexes in a MySQL CREATE TABLE become CREATE INDEX sqls in Postgres.
+    /// Only MySQL <-> Postgres is translated, other ddl are rendered in the source dialect.
+    pub fn translate(ddl_data: &DdlData, dst_db_type: &DbType) -> anyhow::Result<Vec<String>> {
+        if !matches!(
+            (&ddl_data.db_type, dst_db_type),
+            (DbType::Mysql, DbType::Pg) | (DbType::Pg, DbType::Mysql)
+        ) {
+            return Ok(vec![ddl_data.to_sql()]);
+        }
+
+        let mut sqls = Vec::new();
+        for ddl in ddl_data.clone().split_to_multi() {
+            sqls.extend(Self::translate_single(&ddl, dst_db_type)?);
+        }
+        Ok(sqls)
+    }
+
+    fn translate_single(ddl_data: &DdlData, dst_db_type: &DbType) -> anyhow::Result<Vec<String>> {
+        let (schema, tb) = ddl_data.get_schema_tb();
+        let translator = Self {
+            default_schema: ddl_data.default_schema.clone(),
+            schema,
+            tb,
+        };
+
+        match (&ddl_data.db_type, dst_db_type) {
+            (DbType::Mysql, DbType::Pg) => translator.mysql_to_pg(&ddl_data.statement),
+            (DbType::Pg, DbType::Mysql) => translator.pg_to_mysql(&ddl_data.statement),
+            (src, dst) => bail! {Error::DdlTranslateError(format!(
+                "translating ddl from {} to {} is not supported, sql: {}",
+                src, dst, ddl_data.query
+            ))},
+        }
+    }
+
+    fn mysql_to_pg(&self, statement: &DdlStatement) -> anyhow::Result<Vec<String>> {
+        let sqls = match statement {
+            DdlStatement::CreateDatabase(s) => {
+                let if_not_exists = if s.if_not_exists {
+                    " IF NOT EXISTS"
+                } else {
+                    ""
+                };
+                vec![format!(
+                    "CREATE SCHEMA{} {}",
+                    if_not_exists,
+                    escape(&s.db, &DbType::Pg)
+                )]
+            }
+
+            DdlStatement::DropDatabase(s) => {
+                let if_exists = if s.if_exists { " IF EXISTS" } else { "" };
+                // tables are dropped along with the database in MySQL
+                vec![format!(
+                    "DROP SCHEMA{} {} CASCADE",
+                    if_exists,
+                    escape(&s.db, &DbType::Pg)
+                )]
+            }
+
+            // charset / collation changes, nothing to do in Postgres
+            DdlStatement::AlterDatabase(_) => vec![],
+
+            DdlStatement::MysqlCreateTable(s) => {
+                self.mysql_create_table_to_pg(s.if_not_exists, &s.unparsed)?
+            }
+
+            DdlStatement::MysqlAlterTable(s) => {
+                let mut sqls = Vec::new();
+                for spec in split_items(&trim_sql(&s.unparsed), &DbType::Mysql) {
+                    sqls.extend(self.mysql_alter_spec_to_pg(&spec)?);
+                }
+                sqls
+            }
+
+            DdlStatement::MysqlAlterTableRename(s) => self.rename_table_to_pg(&s.new_db, &s.new_tb),
+
+            DdlStatement::RenameTable(s) => self.rename_table_to_pg(&s.new_schema, &s.new_tb),
+
+            DdlStatement::MysqlTruncateTable(_) => {
+                vec![format!("TRUNCATE TABLE {}", self.tb_name(&DbType::Pg))]
+            }
+
+            DdlStatement::MysqlCreateIndex(s) => vec![self.mysql_create_index_to_pg(s)?],
+
+            DdlStatement::MysqlDropIndex(s) => {
+                vec![format!("DROP INDEX {}", self.pg_index_name(&s.index_name))]
+            }
+
+            DdlStatement::DropTable(_) => vec![statement.to_sql(&DbType::Pg)],
+
+            _ => bail! {self.error("ddl not supported")},
+        };
+        Ok(sqls)
+    }
+
+    fn pg_to_mysql(&self, statement: &DdlStatement) -> anyhow::Result<Vec<String>> {
+        let sqls = match statement {
+            DdlStatement::CreateSchema(s) => {
+                let if_not_exists = if s.if_not_exists {
+                    " IF NOT EXISTS"
+                } else {
+                    ""
+                };
+                vec![format!(
+                    "CREATE DATABASE{} {}",
+                    if_not_exists,
+                    escape(&s.schema, &DbType::Mysql)
+                )]
+            }
+
+            DdlStatement::DropSchema(s) => {
+                let if_exists = if s.if_exists { " IF EXISTS" } else { "" };
+                vec![format!(
+                    "DROP DATABASE{} {}",
+                    if_exists,
+                    escape(&s.schema, &DbType::Mysql)
+                )]
+            }
+
+            DdlStatement::AlterSchema(s) => {
+                if s.unparsed.trim().to_uppercase().starts_with("OWNER") {
+                    vec![]
+                } else {
+                    bail! {self.error("ALTER SCHEMA not supported")}
+                }
+            }
+
+            DdlStatement::PgCreateTable(s) => {
+                if s.temporary.is_some() {
+                    bail! {self.error("CREATE TEMPORARY TABLE not supported")}
+                }
+                self.pg_create_table_to_mysql(s.if_not_exists, &s.unparsed)?
+            }
+
+            DdlStatement::PgAlterTable(s) => {
+                let mut sqls = Vec::new();
+                for spec in split_items(&trim_sql(&s.unparsed), &DbType::Pg) {
+                    sqls.extend(self.pg_alter_spec_to_mysql(&spec)?);
+                }
+                sqls
+            }
+
+            DdlStatement::PgAlterTableRename(s) => {
+                // the table stays in the same schema in Postgres
+                let new_schema = if s.new_schema.is_empty() {
+                    &self.schema
+                } else {
+                    &s.new_schema
+                };
+                vec![format!(
+                    "ALTER TABLE {} RENAME TO {}",
+                    self.tb_name(&DbType::Mysql),
+                    tb_name(new_schema, &s.new_tb, &DbType::Mysql)
+                )]
+            }
+
+            DdlStatement::PgAlterTableSetSchema(s) => {
+                vec![format!(
+                    "RENAME TABLE {} TO {}",
+                    self.tb_name(&DbType::Mysql),
+                    tb_name(&s.new_schema, &self.tb, &DbType::Mysql)
+                )]
+            }
+
+            DdlStatement::PgTruncateTable(s) => {
+                if s.unparsed.contains(',') {
+                    bail! {self.error("TRUNCATE of multiple tables not supported")}
+                }
+                vec![format!("TRUNCATE TABLE {}", self.tb_name(&DbType::Mysql))]
+            }
+
+            DdlStatement::PgCreateIndex(s) => vec![self.pg_create_index_to_mysql(s)?],
+
+            DdlStatement::PgDropIndex(_) => {
+                bail! {self.error("DROP INDEX not supported since the table of the index is unknown")}
+            }
+
+            DdlStatement::DropTable(_) => vec![statement.to_sql(&DbType::Mysql)],
+
+            _ => bail! {self.error("ddl not supported")},
+        };
+        Ok(sqls)
+    }
+
+    fn mysql_create_table_to_pg(
+        &self,
+        if_not_exists: bool,
+        unparsed: &str,
+    ) -> anyhow::Result<Vec<String>> {
+        let unparsed = trim_sql(unparsed);
+        let if_not_exists = if if_not_exists { " IF NOT EXISTS" } else { "" };
+        let tb_name = self.tb_name(&DbType::Pg);
+
+        // CREATE TABLE tb_1 LIKE tb_2, CREATE TABLE tb_1 (LIKE tb_2)
+        let like = unparsed.trim_start_matches('(').trim_start();
+        if like.to_uppercase().starts_with("LIKE") {
+            let like_tb = like[4..].trim().trim_end_matches(')').trim();
+            let like_tb = convert_quotes(like_tb, &DbType::Mysql, &DbType::Pg);
+            return Ok(vec![format!(
+                "CREATE TABLE{} {} (LIKE {} INCLUDING ALL)",
+                if_not_exists, tb_name, like_tb
+            )]);
+        }
+
+        let Some((body, options)) = split_body(&unparsed, &DbType::Mysql) else {
+            bail! {self.error("CREATE TABLE without column definitions not supported")}
+        };
+        let option_tokens = tokenize(&options, &DbType::Mysql);
+        if option_tokens
+            .iter()
+            .any(|i| matches!(upper(i).as_str(), "SELECT" | "AS"))
+        {
+            bail! {self.error("CREATE TABLE ... SELECT not supported")}
+        }
+
+        let mut defs = Vec::new();
+        let mut post_sqls = Vec::new();
+        for item in split_items(&body, &DbType::Mysql) {
+            let tokens = tokenize(&item, &DbType::Mysql);
+            if is_mysql_key(&tokens) {
+                match self.mysql_key_to_pg(&tokens)? {
+                    KeyDef::Inline(def) => defs.push(def),
+                    KeyDef::Sqls(sqls) => post_sqls.extend(sqls),
+                    KeyDef::Skip => {}
+                }
+            } else {
+                let col = self.parse_mysql_col(&tokens)?;
+                post_sqls.extend(self.pg_comment_sqls(&col));
+                defs.push(render_pg_col(&col));
+            }
+        }
+
+        if let Some(comment) = find_option_value(&option_tokens, "COMMENT") {
+            post_sqls.push(format!(
+                "COMMENT ON TABLE {} IS '{}'",
+                tb_name,
+                escape_pg_literal(&unquote_mysql(&comment))
+            ));
+        }
+        if find_option_value(&option_tokens, "PARTITION").is_some() {
+            log_warn!(
+                "partitions of table: {}.{} not migrated",
+                self.schema,
+                self.tb
+            );
+        }
+
+        let mut sqls = vec![format!(
+            "CREATE TABLE{} {} ({})",
+            if_not_exists,
+            tb_name,
+            defs.join(", ")
+        )];
+        sqls.extend(post_sqls);
+        Ok(sqls)
+    }
+
+    fn mysql_alter_spec_to_pg(&self, spec: &str) -> anyhow::Result<Vec<String>> {
+        let tokens = tokenize(spec, &DbType::Mysql);
+        let alter =
+            |action: String| format!("ALTER TABLE {} {}", self.tb_name(&DbType::Pg), action);
+        let word = |i: usize| tokens.get(i).map(|t| upper(t)).unwrap_or_default();
+
+        let sqls = match word(0).as_str() {
+            "ADD" => {
+                let start = if word(1) == "COLUMN" { 2 } else { 1 };
+                let rest = &tokens[start.min(tokens.len())..];
+                if rest.is_empty() {
+                    bail! {self.error(&format!("invalid alter spec: {}", spec))}
+                }
+
+                if start == 1 && is_mysql_key(rest) {
+                    match self.mysql_key_to_pg(rest)? {
+                        KeyDef::Inline(def) => vec![alter(format!("ADD {}", def))],
+                        KeyDef::Sqls(sqls) => sqls,
+                        KeyDef::Skip => vec![],
+                    }
+                } else if rest[0].starts_with('(') {
+                    // ADD COLUMN (col_1 int, col_2 int)
+                    let mut sqls = Vec::new();
+                    for item in split_items(&inner(&rest[0]), &DbType::Mysql) {
+                        let col = self.parse_mysql_col(&tokenize(&item, &DbType::Mysql))?;
+                        sqls.push(alter(format!("ADD COLUMN {}", render_pg_col(&col))));
+                        sqls.extend(self.pg_comment_sqls(&col));
+                    }
+                    sqls
+                } else {
+                    let col = self.parse_mysql_col(rest)?;
+                    let mut sqls = vec![alter(format!("ADD COLUMN {}", render_pg_col(&col)))];
+                    sqls.extend(self.pg_comment_sqls(&col));
+                    sqls
+                }
+            }
+
+            "DROP" => match word(1).as_str() {
+                "PRIMARY" => vec![alter(format!(
+                    "DROP CONSTRAINT {}",
+                    escape(&format!("{}_pkey", self.tb), &DbType::Pg)
+                ))],
+                "INDEX" | "KEY" => {
+                    let name = ident_name(self.token(&tokens, 2, spec)?, &DbType::Mysql);
+                    vec![format!("DROP INDEX {}", self.pg_index_name(&name))]
+                }
+                "FOREIGN" | "CONSTRAINT" | "CHECK" => {
+                    // DROP FOREIGN KEY fk_1, DROP CONSTRAINT c_1
+                    let i = if word(1) == "FOREIGN" { 3 } else { 2 };
+                    let name = self.token(&tokens, i, spec)?;
+                    vec![alter(format!(
+                        "DROP CONSTRAINT {}",
+                        convert_ident(name, &DbType::Mysql, &DbType::Pg)
+                    ))]
+                }
+                "COLUMN" => vec![alter(format!(
+                    "DROP COLUMN {}",
+                    convert_ident(self.token(&tokens, 2, spec)?, &DbType::Mysql, &DbType::Pg)
+                ))],
+                _ => vec![alter(format!(
+                    "DROP COLUMN {}",
+                    convert_ident(self.token(&tokens, 1, spec)?, &DbType::Mysql, &DbType::Pg)
+                ))],
+            },
+
+            "MODIFY" => {
+                let start = if word(1) == "COLUMN" { 2 } else { 1 };
+                let col = self.parse_mysql_col(tokens_from(&tokens, start))?;
+                self.modify_pg_col(&col)
+            }
+
+            "CHANGE" => {
+                let start = if word(1) == "COLUMN" { 2 } else { 1 };
+                let old_name = ident_name(self.token(&tokens, start, spec)?, &DbType::Mysql);
+                let col = self.parse_mysql_col(tokens_from(&tokens, start + 1))?;
+                let mut sqls = Vec::new();
+                if old_name != col.name {
+                    sqls.push(alter(format!(
+                        "RENAME COLUMN {} TO {}",
+                        escape(&old_name, &DbType::Pg),
+                        escape(&col.name, &DbType::Pg)
+                    )));
+                }
+                sqls.extend(self.modify_pg_col(&col));
+                sqls
+            }
+
+            "RENAME" => match word(1).as_str() {
+                "COLUMN" => vec![alter(format!(
+                    "RENAME COLUMN {} TO {}",
+                    convert_ident(self.token(&tokens, 2, spec)?, &DbType::Mysql, &DbType::Pg),
+                    convert_ident(self.token(&tokens, 4, spec)?, &DbType::Mysql, &DbType::Pg)
+                ))],
+                "INDEX" | "KEY" => {
+                    let old_name = ident_name(self.token(&tokens, 2, spec)?, &DbType::Mysql);
+                    let new_name = ident_name(self.token(&tokens, 4, spec)?, &DbType::Mysql);
+                    vec![format!(
+                        "ALTER INDEX {} RENAME TO {}",
+                        self.pg_index_name(&old_name),
+                        escape(&format!("{}_{}", self.tb, new_name), &DbType::Pg)
+                    )]
+                }
+                w => {
+                    // RENAME [TO | AS] new_tb
+                    let i = if w == "TO" || w == "AS" { 2 } else { 1 };
+                    let (new_schema, new_tb) =
+                        split_tb_name(self.token(&tokens, i, spec)?, &DbType::Mysql);
+                    self.rename_table_to_pg(&new_schema, &new_tb)
+                }
+            },
+
+            "ALTER" => {
+                let start = if word(1) == "COLUMN" { 2 } else { 1 };
+                let col = convert_ident(
+                    self.token(&tokens, start, spec)?,
+                    &DbType::Mysql,
+                    &DbType::Pg,
+                );
+                match (word(start + 1).as_str(), word(start + 2).as_str()) {
+                    ("SET", "DEFAULT") => {
+                        let raw = join_tokens(tokens_from(&tokens, start + 3));
+                        let column = Column {
+                            column_default: mysql_default(&raw),
+                            ..Default::default()
+                        };
+                        match MysqlToPgConverter::get_pg_col_default(&column, "") {
+                            Some(ColumnDefault::Literal(v) | ColumnDefault::Expression(v)) => {
+                                vec![alter(format!("ALTER COLUMN {} SET DEFAULT {}", col, v))]
+                            }
+                            None => vec![alter(format!("ALTER COLUMN {} DROP DEFAULT", col))],
+                        }
+                    }
+                    ("DROP", "DEFAULT") => {
+                        vec![alter(format!("ALTER COLUMN {} DROP DEFAULT", col))]
+                    }
+                    // ALTER INDEX idx_1 [IN]VISIBLE, ALTER COLUMN col_1 SET [IN]VISIBLE
+                    _ if matches!(word(1).as_str(), "INDEX" | "CHECK" | "CONSTRAINT")
+                        || word(start + 2).ends_with("VISIBLE") =>
+                    {
+                        vec![]
+                    }
+                    _ => bail! {self.error(&format!("alter spec not supported: {}", spec))},
+                }
+            }
+
+            "COMMENT" => {
+                let comment = find_option_value(&tokens, "COMMENT").unwrap_or_default();
+                vec![format!(
+                    "COMMENT ON TABLE {} IS '{}'",
+                    self.tb_name(&DbType::Pg),
+                    escape_pg_literal(&unquote_mysql(&comment))
+                )]
+            }
+
+            // table options without Postgres equivalents
+            w if [
+                "ENGINE",
+                "CONVERT",
+                "DEFAULT",
+                "CHARACTER",
+                "CHARSET",
+                "COLLATE",
+                "ALGORITHM",
+                "LOCK",
+                "AUTO_INCREMENT",
+                "ROW_FORMAT",
+                "FORCE",
+                "KEY_BLOCK_SIZE",
+                "STATS_",
+                "AVG_ROW_LENGTH",
+                "CHECKSUM",
+                "PACK_KEYS",
+            ]
+            .iter()
+            .any(|i| w.starts_with(i)) =>
+            {
+                vec![]
+            }
+
+            _ => bail! {self.error(&format!("alter spec not supported: {}", spec))},
+        };
+        Ok(sqls)
+    }
+
+    fn modify_pg_col(&self, col: &ColumnDef) -> Vec<String> {
+        let name = escape(&col.name, &DbType::Pg);
+        let mut actions = vec![format!(
+            "ALTER COLUMN {} TYPE {} USING {}::{}",
+            name, col.col_type, name, col.col_type
+        )];
+        // columns are nullable if NOT NULL is not specified in MySQL
+        if col.nullable == Some(false) || col.primary_key {
+            actions.push(format!("ALTER COLUMN {} SET NOT NULL", name));
+        } else {
+            actions.push(format!("ALTER COLUMN {} DROP NOT NULL", name));
+        }
+        // identity is kept by Postgres
+        if !col.auto_increment {
+            match &col.default {
+                Some(v) => actions.push(format!("ALTER COLUMN {} SET DEFAULT {}", name, v)),
+                None => actions.push(format!("ALTER COLUMN {} DROP DEFAULT", name)),
+            }
+        }
+
+        let mut sqls = vec![format!(
+            "ALTER TABLE {} {}",
+            self.tb_name(&DbType::Pg),
+            actions.join(", ")
+        )];
+        sqls.extend(self.pg_comment_sqls(col));
+        sqls
+    }
+
+    fn mysql_key_to_pg(&self, tokens: &[String]) -> anyhow::Result<KeyDef> {
+        let (constraint_name, start) = parse_constraint_name(tokens, &DbType::Mysql);
+        let kind = upper(&tokens[start]);
+        let cols = tokens[start..].iter().find(|i| i.starts_with('('));
+
+        let def = match kind.as_str() {
+            "PRIMARY" => {
+                let cols = self.mysql_index_cols_to_pg(cols)?;
+                KeyDef::Inline(format!("PRIMARY KEY ({})", cols))
+            }
+
+            "UNIQUE" | "KEY" | "INDEX" => {
+                let cols_str = self.mysql_index_cols_to_pg(cols)?;
+                // name of the index, or the first column if not specified
+                let name = tokens[start + 1..]
+                    .iter()
+                    .find(|i| !matches!(upper(i).as_str(), "KEY" | "INDEX"))
+                    .filter(|i| !i.starts_with('(') && upper(i) != "USING")
+                    .map(|i| ident_name(i, &DbType::Mysql))
+                    .or(constraint_name)
+                    .or_else(|| {
+                        let first_col = tokenize(&inner(cols?), &DbType::Mysql).into_iter().next();
+                        first_col.map(|i| ident_name(&i, &DbType::Mysql))
+                    });
+                let Some(name) = name else {
+                    bail! {self.error(&format!("index name not found: {}", join_tokens(tokens)))}
+                };
+                let unique = if kind == "UNIQUE" { "UNIQUE " } else { "" };
+                KeyDef::Sqls(vec![format!(
+                    "CREATE {}INDEX {} ON {} ({})",
+                    unique,
+                    escape(&format!("{}_{}", self.tb, name), &DbType::Pg),
+                    self.tb_name(&DbType::Pg),
+                    cols_str
+                )])
+            }
+
+            "FULLTEXT" | "SPATIAL" => {
+                log_warn!(
+                    "{} index of table: {}.{} not migrated",
+                    kind,
+                    self.schema,
+                    self.tb
+                );
+                KeyDef::Skip
+            }
+
+            "FOREIGN" | "CHECK" => {
+                let def =
+                    convert_quotes(&join_tokens(&tokens[start..]), &DbType::Mysql, &DbType::Pg);
+                match constraint_name {
+                    Some(name) => {
+                        KeyDef::Inline(format!("CONSTRAINT {} {}", escape(&name, &DbType::Pg), def))
+                    }
+                    None => KeyDef::Inline(def),
+                }
+            }
+
+            _ => bail! {self.error(&format!("key not supported: {}", join_tokens(tokens)))},
+        };
+        Ok(def)
+    }
+
+    fn mysql_index_cols_to_pg(&self, cols: Option<&String>) -> anyhow::Result<String> {
+        let Some(cols) = cols else {
+            bail! {self.error("index columns not found")}
+        };
+
+        let mut res = Vec::new();
+        for item in split_items(&inner(cols), &DbType::Mysql) {
+            let tokens = tokenize(&item, &DbType::Mysql);
+            let Some(first) = tokens.first() else {
+                bail! {self.error(&format!("invalid index columns: {}", cols))}
+            };
+            if first.starts_with('(') {
+                bail! {self.error(&format!("index on expression not supported: {}", item))}
+            }
+            // col_1(10) DESC, prefix length is not supported in Postgres
+            let mut col = convert_ident(first, &DbType::Mysql, &DbType::Pg);
+            if let Some(order) = tokens
+                .iter()
+                .skip(1)
+                .find(|i| matches!(upper(i).as_str(), "ASC" | "DESC"))
+            {
+                col = format!("{} {}", col, upper(order));
+            }
+            res.push(col);
+        }
+        if res.is_empty() {
+            bail! {self.error("index columns not found")}
+        }
+        Ok(res.join(", "))
+    }
+
+    fn mysql_create_index_to_pg(&self, s: &MysqlCreateIndexStatement) -> anyhow::Result<String> {
+        let kind = s.index_kind.as_deref().unwrap_or_default().to_uppercase();
+        if kind == "FULLTEXT" || kind == "SPATIAL" {
+            bail! {self.error(&format!("{} index not supported", kind))}
+        }
+
+        let tokens = tokenize(&trim_sql(&s.unparsed), &DbType::Mysql);
+        let cols = self.mysql_index_cols_to_pg(tokens.iter().find(|i| i.starts_with('(')))?;
+        let method = match s.index_type.as_deref().map(|i| i.to_lowercase()) {
+            Some(i) if i == "hash" => " USING hash",
+            _ => "",
+        };
+        let unique = if kind == "UNIQUE" { "UNIQUE " } else { "" };
+        Ok(format!(
+            "CREATE {}INDEX {} ON {}{} ({})",
+            unique,
+            escape(&format!("{}_{}", self.tb, s.index_name), &DbType::Pg),
+            self.tb_name(&DbType::Pg),
+            method,
+            cols
+        ))
+    }
+
+    fn parse_mysql_col(&self, tokens: &[String]) -> anyhow::Result<ColumnDef> {
+        if tokens.len() < 2 {
+            bail! {self.error(&format!("invalid column definition: {}", join_tokens(tokens)))}
+        }
+
+        let mut col = ColumnDef {
+            name: ident_name(&tokens[0], &DbType::Mysql),
+            ..Default::default()
+        };
+        let mut i = 1;
+        let mysql_col_type = take_until_keyword(tokens, &mut i, &MYSQL_COL_KEYWORDS);
+        let mut raw_default = None;
+
+        while i < tokens.len() {
+            let word = upper(&tokens[i]);
+            let next = tokens.get(i + 1).map(|t| upper(t)).unwrap_or_default();
+            i += 1;
+            match word.as_str() {
+                "NOT" if next == "NULL" => {
+                    col.nullable = Some(false);
+                    i += 1;
+                }
+                "NULL" => col.nullable = Some(true),
+                "DEFAULT" => {
+                    raw_default = Some(take_until_keyword(tokens, &mut i, &MYSQL_COL_KEYWORDS))
+                }
+                "AUTO_INCREMENT" => col.auto_increment = true,
+                "PRIMARY" | "KEY" => {
+                    col.primary_key = true;
+                    if next == "KEY" {
+                        i += 1;
+                    }
+                }
+                "UNIQUE" => {
+                    col.unique = true;
+                    if next == "KEY" {
+                        i += 1;
+                    }
+                }
+                "COMMENT" => {
+                    col.comment = tokens.get(i).map(|t| unquote_mysql(t));
+                    i += 1;
+                }
+                // CHARACTER SET utf8mb4
+                "CHARACTER" => i += 2,
+                "CHARSET" | "COLLATE" | "SRID" | "COLUMN_FORMAT" | "STORAGE" | "AFTER" => i += 1,
+                "FIRST" | "VISIBLE" | "INVISIBLE" => {}
+                "ON" => {
+                    // ON UPDATE CURRENT_TIMESTAMP needs a trigger in Postgres
+                    log_warn!(
+                        "ON UPDATE of column: {}.{}.{} not migrated",
+                        self.schema,
+                        self.tb,
+                        col.name
+                    );
+                    i += 1;
+                    take_until_keyword(tokens, &mut i, &MYSQL_COL_KEYWORDS);
+                }
+                "CHECK" => {
+                    if let Some(expr) = tokens.get(i) {
+                        col.checks.push(format!(
+                            "CHECK {}",
+                            convert_quotes(expr, &DbType::Mysql, &DbType::Pg)
+                        ));
+                    }
+                    i += 1;
+                }
+                "CONSTRAINT" => {
+                    if next != "CHECK" {
+                        i += 1;
+                    }
+                }
+                // inline references are ignored by MySQL
+                "REFERENCES" => {
+                    take_until_keyword(tokens, &mut i, &MYSQL_COL_KEYWORDS);
+                }
+                _ => bail! {self.error(&format!(
+                    "column attribute not supported: {}, column: {}",
+                    tokens[i - 1], col.name
+                ))},
+            }
+        }
+
+        col.col_type = MysqlToPgConverter::get_pg_col_type(&mysql_col_type);
+        if col.auto_increment {
+            // identity columns must be smallint / integer / bigint
+            if col.col_type == "numeric(20,0)" {
+                col.col_type = "bigint".into();
+            }
+        } else if let Some(raw) = raw_default {
+            let column = Column {
+                column_name: col.name.clone(),
+                column_default: mysql_default(&raw),
+                ..Default::default()
+            };
+            col.default = match MysqlToPgConverter::get_pg_col_default(&column, &col.col_type) {
+                Some(ColumnDefault::Literal(v) | ColumnDefault::Expression(v)) => Some(v),
+                None => None,
+            };
+        }
+        Ok(col)
+    }
+
+    fn pg_comment_sqls(&self, col: &ColumnDef) -> Vec<String> {
+        match &col.comment {
+            Some(comment) => vec![format!(
+                "COMMENT ON COLUMN {}.{} IS '{}'",
+                self.tb_name(&DbType::Pg),
+                escape(&col.name, &DbType::Pg),
+                escape_pg_literal(comment)
+            )],
+            None => vec![],
+        }
+    }
+
+    fn rename_table_to_pg(&self, new_schema: &str, new_tb: &str) -> Vec<String> {
+        // tables without schema are renamed to the current database in MySQL
+        let new_schema = if new_schema.is_empty() {
+            &self.default_schema
+        } else {
+            new_schema
+        };
+
+        let mut sqls = Vec::new();
+        let mut schema = self.schema.as_str();
+        if !new_schema.is_empty() && new_schema != schema {
+            sqls.push(format!(
+                "ALTER TABLE {} SET SCHEMA {}",
+                self.tb_name(&DbType::Pg),
+                escape(new_schema, &DbType::Pg)
+            ));
+            schema = new_schema;
+        }
+        if new_tb != self.tb {
+            sqls.push(format!(
+                "ALTER TABLE {} RENAME TO {}",
+                tb_name(schema, &self.tb, &DbType::Pg),
+                escape(new_tb, &DbType::Pg)
+            ));
+        }
+        sqls
+    }
+
+    fn pg_create_table_to_mysql(
+        &self,
+        if_not_exists: bool,
+        unparsed: &str,
+    ) -> anyhow::Result<Vec<String>> {
+        let unparsed = trim_sql(unparsed);
+        let Some((body, options)) = split_body(&unparsed, &DbType::Pg) else {
+            bail! {self.error("CREATE TABLE without column definitions not supported")}
+        };
+        let option_tokens = tokenize(&options, &DbType::Pg);
+        if let Some(option) = option_tokens
+            .iter()
+            .find(|i| matches!(upper(i).as_str(), "AS" | "INHERITS" | "PARTITION" | "OF"))
+        {
+            bail! {self.error(&format!("CREATE TABLE with {} not supported", upper(option)))}
+        }
+
+        let mut defs = Vec::new();
+        for item in split_items(&body, &DbType::Pg) {
+            let tokens = tokenize(&item, &DbType::Pg);
+            if is_pg_key(&tokens) {
+                if let Some(def) = self.pg_key_to_mysql(&tokens)? {
+                    defs.push(def);
+                }
+            } else {
+                let col = self.parse_pg_col(&tokens)?;
+                defs.push(render_mysql_col(&col));
+                if let Some(references) = &col.references {
+                    defs.push(format!(
+                        "FOREIGN KEY ({}) {}",
+                        escape(&col.name, &DbType::Mysql),
+                        references
+                    ));
+                }
+            }
+        }
+
+        let if_not_exists = if if_not_exists { " IF NOT EXISTS" } else { "" };
+        Ok(vec![format!(
+            "CREATE TABLE{} {} ({})",
+            if_not_exists,
+            self.tb_name(&DbType::Mysql),
+            defs.join(", ")
+        )])
+    }
+
+    fn pg_alter_spec_to_mysql(&self, spec: &str) -> anyhow::Result<Vec<String>> {
+        let tokens = tokenize(spec, &DbType::Pg);
+        let alter =
+            |action: String| format!("ALTER TABLE {} {}", self.tb_name(&DbType::Mysql), action);
+        let word = |i: usize| tokens.get(i).map(|t| upper(t)).unwrap_or_default();
+        // skip optional keywords: COLUMN, IF [NOT] EXISTS
+        let skip_optional = |mut i: usize| {
+            if word(i) == "COLUMN" {
+                i += 1;
+            }
+            if word(i) == "IF" {
+                i += if word(i + 1) == "NOT" { 3 } else { 2 };
+            }
+            i
+        };
+
+        let sqls = match word(0).as_str() {
+            "ADD" => {
+                let start = skip_optional(1);
+                let rest = &tokens[start.min(tokens.len())..];
+                if rest.is_empty() {
+                    bail! {self.error(&format!("invalid alter spec: {}", spec))}
+                }
+
+                if start == 1 && is_pg_key(rest) {
+                    match self.pg_key_to_mysql(rest)? {
+                        Some(def) => vec![alter(format!("ADD {}", def))],
+                        None => vec![],
+                    }
+                } else {
+                    let col = self.parse_pg_col(rest)?;
+                    let mut sqls = vec![alter(format!("ADD COLUMN {}", render_mysql_col(&col)))];
+                    if let Some(references) = &col.references {
+                        sqls.push(alter(format!(
+                            "ADD FOREIGN KEY ({}) {}",
+                            escape(&col.name, &DbType::Mysql),
+                            references
+                        )));
+                    }
+                    sqls
+                }
+            }
+
+            "DROP" if word(1) == "CONSTRAINT" => {
+                let i = if word(2) == "IF" { 4 } else { 2 };
+                let name = ident_name(self.token(&tokens, i, spec)?, &DbType::Pg);
+                // primary keys are named {tb}_pkey by default in Postgres
+                if name.ends_with("_pkey") {
+                    vec![alter("DROP PRIMARY KEY".into())]
+                } else {
+                    vec![alter(format!(
+                        "DROP CONSTRAINT {}",
+                        escape(&name, &DbType::Mysql)
+                    ))]
+                }
+            }
+
+            "DROP" => {
+                let i = skip_optional(1);
+                vec![alter(format!(
+                    "DROP COLUMN {}",
+                    convert_ident(self.token(&tokens, i, spec)?, &DbType::Pg, &DbType::Mysql)
+                ))]
+            }
+
+            "RENAME" if word(1) == "CONSTRAINT" => {
+                bail! {self.error("RENAME CONSTRAINT not supported")}
+            }
+
+            "RENAME" => {
+                let i = if word(1) == "COLUMN" { 2 } else { 1 };
+                vec![alter(format!(
+                    "RENAME COLUMN {} TO {}",
+                    convert_ident(self.token(&tokens, i, spec)?, &DbType::Pg, &DbType::Mysql),
+                    convert_ident(
+                        self.token(&tokens, i + 2, spec)?,
+                        &DbType::Pg,
+                        &DbType::Mysql
+                    )
+                ))]
+            }
+
+            "ALTER" if word(1) != "CONSTRAINT" => {
+                let start = if word(1) == "COLUMN" { 2 } else { 1 };
+                let col_name = self.token(&tokens, start, spec)?;
+                let col = convert_ident(col_name, &DbType::Pg, &DbType::Mysql);
+                match (word(start + 1).as_str(), word(start + 2).as_str()) {
+                    ("SET", "DEFAULT") => {
+                        let raw = join_tokens(tokens_from(&tokens, start + 3));
+                        let column = Column {
+                            column_name: ident_name(col_name, &DbType::Pg),
+                            column_default: Some(ColumnDefault::Literal(raw)),
+                            ..Default::default()
+                        };
+                        match PgToMysqlConverter::get_mysql_col_default(
+                            &self.schema,
+                            &self.tb,
+                            &column,
+                            "",
+                        ) {
+                            Some(default) => vec![alter(format!(
+                                "ALTER COLUMN {} SET DEFAULT {}",
+                                col,
+                                render_mysql_default(&default)
+                            ))],
+                            None => vec![alter(format!("ALTER COLUMN {} DROP DEFAULT", col))],
+                        }
+                    }
+                    ("DROP", "DEFAULT") => {
+                        vec![alter(format!("ALTER COLUMN {} DROP DEFAULT", col))]
+                    }
+                    ("SET", "STATISTICS" | "STORAGE" | "COMPRESSION" | "(")
+                    | ("RESET", _)
+                    | ("OPTIONS", _) => vec![],
+                    _ => {
+                        // MODIFY in MySQL needs the full column definition which is unknown here
+                        bail! {self.error(&format!("alter spec not supported: {}", spec))}
+                    }
+                }
+            }
+
+            // table properties without MySQL equivalents
+            "OWNER" | "SET" | "RESET" | "ENABLE" | "DISABLE" | "REPLICA" | "CLUSTER"
+            | "INHERIT" | "NO" | "VALIDATE" | "FORCE" | "ALTER" => vec![],
+
+            _ => bail! {self.error(&format!("alter spec not supported: {}", spec))},
+        };
+        Ok(sqls)
+    }
+
+    fn pg_key_to_mysql(&self, tokens: &[String]) -> anyhow::Result<Option<String>> {
+        let (constraint_name, start) = parse_constraint_name(tokens, &DbType::Pg);
+        let kind = upper(&tokens[start]);
+        let cols = tokens[start..].iter().find(|i| i.starts_with('('));
+
+        let def = match kind.as_str() {
+            "PRIMARY" => {
+                let cols = self.pg_index_cols_to_mysql(cols)?;
+                format!("PRIMARY KEY ({})", cols)
+            }
+
+            "UNIQUE" => {
+                let cols = self.pg_index_cols_to_mysql(cols)?;
+                match constraint_name {
+                    Some(name) => {
+                        format!("UNIQUE KEY {} ({})", escape(&name, &DbType::Mysql), cols)
+                    }
+                    None => format!("UNIQUE KEY ({})", cols),
+                }
+            }
+
+            "FOREIGN" | "CHECK" => {
+                let def = join_tokens(&tokens[start..]);
+                if kind == "CHECK" && def.contains("::") {
+                    log_warn!(
+                        "check constraint: {} of table: {}.{} not migrated",
+                        def,
+                        self.schema,
+                        self.tb
+                    );
+                    return Ok(None);
+                }
+                let def = strip_pg_constraint_options(&def);
+                let def = convert_quotes(&def, &DbType::Pg, &DbType::Mysql);
+                match constraint_name {
+                    Some(name) => format!("CONSTRAINT {} {}", escape(&name, &DbType::Mysql), def),
+                    None => def,
+                }
+            }
+
+            _ => bail! {self.error(&format!("constraint not supported: {}", join_tokens(tokens)))},
+        };
+        Ok(Some(def))
+    }
+
+    fn pg_index_cols_to_mysql(&self, cols: Option<&String>) -> anyhow::Result<String> {
+        let Some(cols) = cols else {
+            bail! {self.error("index columns not found")}
+        };
+
+        let mut res = Vec::new();
+        for item in split_items(&inner(cols), &DbType::Pg) {
+            let tokens = tokenize(&item, &DbType::Pg);
+            let Some(first) = tokens.first() else {
+                bail! {self.error(&format!("invalid index columns: {}", cols))}
+            };
+            if first.starts_with('(') || tokens.get(1).is_some_and(|i| i.starts_with('(')) {
+                bail! {self.error(&format!("index on expression not supported: {}", item))}
+            }
+            // operator classes, COLLATE and NULLS FIRST / LAST are ignored
+            let mut col = convert_ident(first, &DbType::Pg, &DbType::Mysql);
+            if let Some(order) = tokens
+                .iter()
+                .skip(1)
+                .find(|i| matches!(upper(i).as_str(), "ASC" | "DESC"))
+            {
+                col = format!("{} {}", col, upper(order));
+            }
+            res.push(col);
+        }
+        if res.is_empty() {
+            bail! {self.error("index columns not found")}
+        }
+        Ok(res.join(", "))
+    }
+
+    fn pg_create_index_to_mysql(&self, s: &PgCreateIndexStatement) -> anyhow::Result<String> {
+        let Some(index_name) = &s.index_name else {
+            bail! {self.error("CREATE INDEX without index name not supported")}
+        };
+
+        // [USING method] (cols) [INCLUDE (cols)] [WITH (options)] [TABLESPACE name] [WHERE predicate]
+        let tokens = tokenize(&trim_sql(&s.unparsed), &DbType::Pg);
+        let mut method = "";
+        if tokens.first().is_some_and(|i| upper(i) == "USING") {
+            method = match tokens.get(1).map(|i| upper(i)).as_deref() {
+                Some("BTREE") => " USING BTREE",
+                Some("HASH") => " USING HASH",
+                _ => bail! {self.error(&format!("index method not supported: {}", s.unparsed))},
+            };
+        }
+        if let Some(clause) = tokens
+            .iter()
+            .find(|i| matches!(upper(i).as_str(), "INCLUDE" | "WHERE"))
+        {
+            bail! {self.error(&format!("index with {} not supported", upper(clause)))}
+        }
+
+        let cols = self.pg_index_cols_to_mysql(tokens.iter().find(|i| i.starts_with('(')))?;
+        let unique = if s.is_unique { "UNIQUE " } else { "" };
+        Ok(format!(
+            "CREATE {}INDEX {}{} ON {} ({})",
+            unique,
+            escape(index_name, &DbType::Mysql),
+            method,
+            self.tb_name(&DbType::Mysql),
+            cols
+        ))
+    }
+
+    fn parse_pg_col(&self, tokens: &[String]) -> anyhow::Result<ColumnDef> {
+        if tokens.len() < 2 {
+            bail! {self.error(&format!("invalid column definition: {}", join_tokens(tokens)))}
+        }
+
+        let mut col = ColumnDef {
+            name: ident_name(&tokens[0], &DbType::Pg),
+            ..Default::default()
+        };
+        let mut i = 1;
+        let (pg_col_type, serial) =
+            normalize_pg_type(&take_until_keyword(tokens, &mut i, &PG_COL_KEYWORDS));
+        col.auto_increment = serial;
+        let mut raw_default = None;
+
+        while i < tokens.len() {
+            let word = upper(&tokens[i]);
+            let next = tokens.get(i + 1).map(|t| upper(t)).unwrap_or_default();
+            i += 1;
+            match word.as_str() {
+                "NOT" if next == "NULL" => {
+                    col.nullable = Some(false);
+                    i += 1;
+                }
+                "NULL" => col.nullable = Some(true),
+                "DEFAULT" => {
+                    raw_default = Some(take_until_keyword(tokens, &mut i, &PG_COL_KEYWORDS))
+                }
+                "GENERATED" => {
+                    // GENERATED { ALWAYS | BY DEFAULT } AS IDENTITY [ ( sequence_options ) ]
+                    let Some(pos) = tokens[i..].iter().position(|t| upper(t) == "IDENTITY") else {
+                        bail! {self.error(&format!("generated column not supported: {}", col.name))}
+                    };
+                    i += pos + 1;
+                    if tokens.get(i).is_some_and(|t| t.starts_with('(')) {
+                        i += 1;
+                    }
+                    col.auto_increment = true;
+                }
+                "PRIMARY" => {
+                    col.primary_key = true;
+                    i += 1;
+                }
+                "UNIQUE" => {
+                    col.unique = true;
+                    // UNIQUE NULLS [NOT] DISTINCT
+                    if next == "NULLS" {
+                        i += if tokens.get(i + 1).is_some_and(|t| upper(t) == "NOT") {
+                            3
+                        } else {
+                            2
+                        };
+                    }
+                }
+                "CHECK" => {
+                    if let Some(expr) = tokens.get(i) {
+                        if expr.contains("::") {
+                            log_warn!(
+                                "check constraint: {} of column: {}.{}.{} not migrated",
+                                expr,
+                                self.schema,
+                                self.tb,
+                                col.name
+                            );
+                        } else {
+                            col.checks.push(format!(
+                                "CHECK {}",
+                                convert_quotes(expr, &DbType::Pg, &DbType::Mysql)
+                            ));
+                        }
+                    }
+                    i += 1;
+                }
+                "CONSTRAINT" | "COLLATE" => i += 1,
+                "REFERENCES" => {
+                    let references = format!(
+                        "REFERENCES {}",
+                        take_until_keyword(tokens, &mut i, &PG_COL_KEYWORDS)
+                    );
+                    col.references = Some(convert_quotes(
+                        &strip_pg_constraint_options(&references),
+                        &DbType::Pg,
+                        &DbType::Mysql,
+                    ));
+                }
+                _ => bail! {self.error(&format!(
+                    "column attribute not supported: {}, column: {}",
+                    tokens[i - 1], col.name
+                ))},
+            }
+        }
+
+        col.col_type = PgToMysqlConverter::get_mysql_col_type(&pg_col_type);
+        if !col.auto_increment {
+            if let Some(raw) = raw_default {
+                let column = Column {
+                    column_name: col.name.clone(),
+                    column_default: Some(ColumnDefault::Literal(raw)),
+                    ..Default::default()
+                };
+                col.default = PgToMysqlConverter::get_mysql_col_default(
+                    &self.schema,
+                    &self.tb,
+                    &column,
+                    &col.col_type,
+                )
+                .map(|i| render_mysql_default(&i));
+            }
+        }
+        Ok(col)
+    }
+
+    fn tb_name(&self, db_type: &DbType) -> String {
+        tb_name(&self.schema, &self.tb, db_type)
+    }
+
+    /// indexes are named {tb}_{index} in Postgres, the same as structure migration
+    fn pg_index_name(&self, index_name: &str) -> String {
+        tb_name(
+            &self.schema,
+            &format!("{}_{}", self.tb, index_name),
+            &DbType::Pg,
+        )
+    }
+
+    /// tokens[i] of the alter spec, an incomplete spec is an error rather than a panic
+    fn token<'a>(&self, tokens: &'a [String], i: usize, spec: &str) -> anyhow::Result<&'a String> {
+        match tokens.get(i) {
+            Some(token) => Ok(token),
+            None => bail! {self.error(&format!("invalid alter spec: {}", spec))},
+        }
+    }
+
+    fn error(&self, message: &str) -> Error {
+        Error::DdlTranslateError(format!(
+            "{}, schema: {}, tb: {}",
+            message, self.schema, self.tb
+        ))
+    }
+}
+
+fn render_pg_col(col: &ColumnDef) -> String {
+    let mut sql = format!("{} {}", escape(&col.name, &DbType::Pg), col.col_type);
+    if col.auto_increment {
+        sql = format!("{} GENERATED BY DEFAULT AS IDENTITY", sql);
+    }
+    match col.nullable {
+        Some(false) => sql = format!("{} NOT NULL", sql),
+        Some(true) => sql = format!("{} NULL", sql),
+        None => {}
+    }
+    if let Some(default) = &col.default {
+        sql = format!("{} DEFAULT {}", sql, default);
+    }
+    if col.primary_key {
+        sql = format!("{} PRIMARY KEY", sql);
+    }
+    if col.unique {
+        sql = format!("{} UNIQUE", sql);
+    }
+    for check in col.checks.iter() {
+        sql = format!("{} {}", sql, check);
+    }
+    sql
+}
+
+fn render_mysql_col(col: &ColumnDef) -> String {
+    let mut sql = format!("{} {}", escape(&col.name, &DbType::Mysql), col.col_type);
+    match col.nullable {
+        Some(false) => sql = format!("{} NOT NULL", sql),
+        Some(true) => sql = format!("{} NULL", sql),
+        None => {}
+    }
+    if let Some(default) = &col.default {
+        sql = format!("{} DEFAULT {}", sql, default);
+    }
+    if col.auto_increment {
+        sql = format!("{} AUTO_INCREMENT", sql);
+    }
+    if col.unique {
+        sql = format!("{} UNIQUE KEY", sql);
+    }
+    if col.primary_key {
+        sql = format!("{} PRIMARY KEY", sql);
+    }
+    for check in col.checks.iter() {
+        sql = format!("{} {}", sql, check);
+    }
+    sql
+}
+
+fn render_mysql_default(default: &ColumnDefault) -> String {
+    match default {
+        // b'101'
+        ColumnDefault::Literal(v) if v.starts_with("b'") => v.clone(),
+        ColumnDefault::Literal(v) => format!("'{}'", escape_mysql_literal(v)),
+        ColumnDefault::Expression(v) => v.clone(),
+    }
+}
+
+/// MySQL default in ddl -> default in information_schema.columns
+fn mysql_default(raw: &str) -> Option<ColumnDefault> {
+    let upper = raw.to_uppercase();
+    if raw.is_empty() || upper == "NULL" {
+        None
+    } else if raw.starts_with('\'') || raw.starts_with('"') {
+        Some(ColumnDefault::Literal(unquote_mysql(raw)))
+    } else if upper.starts_with("B'") || raw.trim_start_matches(['-', '+']).parse::<f64>().is_ok() {
+        Some(ColumnDefault::Literal(raw.to_string()))
+    } else if upper == "TRUE" || upper == "FALSE" {
+        let v = if upper == "TRUE" { "1" } else { "0" };
+        Some(ColumnDefault::Literal(v.into()))
+    } else {
+        Some(ColumnDefault::Expression(raw.to_string()))
+    }
+}
+
+/// Maps Postgres type names in ddl to pg_catalog.format_type,
+/// for example: int4 -> integer, timestamptz(3) -> timestamp(3) with time zone.
+/// returns: (type, is_serial)
+fn normalize_pg_type(pg_type: &str) -> (String, bool) {
+    let col_type = pg_type.trim().to_lowercase();
+    if col_type.contains('[') || col_type.ends_with(" array") {
+        return ("array[]".into(), false);
+    }
+
+    let base_end = col_type.find('(').unwrap_or(col_type.len());
+    let base = col_type[..base_end]
+        .split_whitespace()
+        .collect::<Vec<&str>>()
+        .join(" ");
+    let (args, suffix) = match (col_type.find('('), col_type.find(')')) {
+        (Some(start), Some(end)) if start < end => (
+            col_type[start + 1..end].replace(' ', ""),
+            col_type[end + 1..].trim().to_string(),
+        ),
+        _ => (String::new(), String::new()),
+    };
+    let with_tz = base.contains("with time zone") || suffix.contains("with time zone");
+
+    let (name, serial) = match base.as_str() {
+        "int" | "int4" | "integer" => ("integer", false),
+        "serial" | "serial4" => ("integer", true),
+        "int2" | "smallint" => ("smallint", false),
+        "smallserial" | "serial2" => ("smallint", true),
+        "int8" | "bigint" => ("bigint", false),
+        "bigserial" | "serial8" => ("bigint", true),
+        "float4" | "real" => ("real", false),
+        "float8" | "double precision" => ("double precision", false),
+        "float" => {
+            // float(p): real if p <= 24
+            let name = match args.parse::<u32>() {
+                Ok(p) if p <= 24 => "real",
+                _ => "double precision",
+            };
+            return (name.into(), false);
+        }
+        "decimal" | "numeric" => ("numeric", false),
+        "bool" | "boolean" => ("boolean", false),
+        "varchar" | "character varying" => ("character varying", false),
+        "char" | "character" | "bpchar" => ("character", false),
+        "timestamptz" => ("timestamp with time zone", false),
+        b if b.starts_with("timestamp") && with_tz => ("timestamp with time zone", false),
+        b if b.starts_with("timestamp") => ("timestamp without time zone", false),
+        "timetz" => ("time with time zone", false),
+        b if b.starts_with("time") && with_tz => ("time with time zone", false),
+        b if b.starts_with("time") => ("time without time zone", false),
+        "varbit" | "bit varying" => ("bit varying", false),
+        b => (b, false),
+    };
+
+    let args = match (name, args.is_empty()) {
+        ("character" | "bit", true) => "1".to_string(),
+        _ => args,
+    };
+    let col_type = if args.is_empty() {
+        name.to_string()
+    } else if let Some((first, rest)) = name.split_once(' ').filter(|_| name.starts_with("time")) {
+        // timestamp(3) with time zone
+        format!("{}({}) {}", first, args, rest)
+    } else {
+        format!("{}({})", name, args)
+    };
+    (col_type, serial)
+}
+
+fn is_mysql_key(tokens: &[String]) -> bool {
+    matches!(
+        tokens
+            .first()
+            .map(|i| upper(i))
+            .unwrap_or_default()
+            .as_str(),
+        "PRIMARY"
+            | "UNIQUE"
+            | "KEY"
+            | "INDEX"
+            | "FULLTEXT"
+            | "SPATIAL"
+            | "CONSTRAINT"
+            | "FOREIGN"
+            | "CHECK"
+    )
+}
+
+fn is_pg_key(tokens: &[String]) -> bool {
+    matches!(
+        tokens
+            .first()
+            .map(|i| upper(i))
+            .unwrap_or_default()
+            .as_str(),
+        "PRIMARY" | "UNIQUE" | "CONSTRAINT" | "FOREIGN" | "CHECK" | "EXCLUDE" | "LIKE"
+    )
+}
+
+/// CONSTRAINT [name] PRIMARY KEY ... -> (name, index of PRIMARY)
+fn parse_constraint_name(tokens: &[String], db_type: &DbType) -> (Option<String>, usize) {
+    if tokens.len() < 2 || upper(&tokens[0]) != "CONSTRAINT" {
+        return (None, 0);
+    }
+    if matches!(
+        upper(&tokens[1]).as_str(),
+        "PRIMARY" | "UNIQUE" | "FOREIGN" | "CHECK"
+    ) {
+        return (None, 1);
+    }
+    (
+        Some(ident_name(&tokens[1], db_type)),
+        2.min(tokens.len() - 1),
+    )
+}
+
+/// DEFERRABLE / INITIALLY DEFERRED / NOT VALID are not supported in MySQL
+fn strip_pg_constraint_options(def: &str) -> String {
+    let mut res = def.to_string();
+    for option in [
+        " NOT DEFERRABLE",
+        " DEFERRABLE",
+        " INITIALLY DEFERRED",
+        " INITIALLY IMMEDIATE",
+        " NOT VALID",
+        " NO INHERIT",
+    ] {
+        if let Some(pos) = res.to_uppercase().find(option) {
+            res.replace_range(pos..pos + option.len(), "");
+        }
+    }
+    res
+}
+
+/// (cols) ENGINE=InnoDB -> (cols, ENGINE=InnoDB)
+fn split_body(sql: &str, db_type: &DbType) -> Option<(String, String)> {
+    let tokens = tokenize(sql, db_type);
+    let body = tokens.first().filter(|i| i.starts_with('('))?;
+    let options = sql.trim_start()[body.len()..].trim().to_string();
+    Some((inner(body), options))
+}
+
+/// COMMENT='abc' / COMMENT = 'abc' / COMMENT 'abc' -> 'abc'
+fn find_option_value(tokens: &[String], option: &str) -> Option<String> {
+    let pos = tokens.iter().position(|i| upper(i).starts_with(option))?;
+    let value = tokens[pos][option.len()..].trim_start_matches('=');
+    if !value.is_empty() {
+        return Some(value.to_string());
+    }
+    tokens[pos + 1..]
+        .iter()
+        .find(|i| i.as_str() != "=")
+        .map(|i| i.trim_start_matches('=').to_string())
+}
+
+/// joins tokens until a keyword is met, the first token is always taken
+fn take_until_keyword(tokens: &[String], i: &mut usize, keywords: &[&str]) -> String {
+    let start = *i;
+    while *i < tokens.len() && (*i == start || !keywords.contains(&upper(&tokens[*i]).as_str())) {
+        *i += 1;
+    }
+    join_tokens(&tokens[start..*i])
+}
+
+/// tokens[start..], empty if start is out of range
+fn tokens_from(tokens: &[String], start: usize) -> &[String] {
+    tokens.get(start..).unwrap_or_default()
+}
+
+fn join_tokens(tokens: &[String]) -> String {
+    let mut res = String::new();
+    for token in tokens {
+        if !res.is_empty() && !token.starts_with('(') {
+            res.push(' ');
+        }
+        res.push_str(token);
+    }
+    res
+}
+
+/// splits by whitespaces, quoted identifiers / strings and parenthesized groups are kept,
+/// a parenthesized group is always a separate token: varchar(10) -> [varchar, (10)]
+fn tokenize(text: &str, db_type: &DbType) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut token = String::new();
+    let mut scanner = QuoteScanner::new(db_type);
+    let mut depth = 0;
+
+    for c in text.chars() {
+        if scanner.in_quote(c) {
+            token.push(c);
+            continue;
+        }
+        match c {
+            '(' => {
+                if depth == 0 && !token.is_empty() {
+                    tokens.push(std::mem::take(&mut token));
+                }
+                depth += 1;
+                token.push(c);
+            }
+            ')' => {
+                token.push(c);
+                depth -= 1;
+                if depth == 0 {
+                    tokens.push(std::mem::take(&mut token));
+                }
+            }
+            c if c.is_whitespace() && depth == 0 => {
+                if !token.is_empty() {
+                    tokens.push(std::mem::take(&mut token));
+                }
+            }
+            _ => token.push(c),
+        }
+    }
+    if !token.is_empty() {
+        tokens.push(token);
+    }
+    tokens
+}
+
+/// splits by top level commas
+fn split_items(text: &str, db_type: &DbType) -> Vec<String> {
+    let mut items = Vec::new();
+    let mut item = String::new();
+    let mut scanner = QuoteScanner::new(db_type);
+    let mut depth = 0;
+
+    for c in text.chars() {
+        if !scanner.in_quote(c) {
+            match c {
+                '(' => depth += 1,
+                ')' => depth -= 1,
+                ',' if depth == 0 => {
+                    items.push(std::mem::take(&mut item).trim().to_string());
+                    continue;
+                }
+                _ => {}
+            }
+        }
+        item.push(c);
+    }
+    items.push(item.trim().to_string());
+    items.retain(|i| !i.is_empty());
+    items
+}
+
+struct QuoteScanner {
+    quote: Option<char>,
+    escaped: bool,
+    // backslash escapes in MySQL strings
+    backslash_escape: bool,
+}
+
+impl QuoteScanner {
+    fn new(db_type: &DbType) -> Self {
+        Self {
+            quote: None,
+            escaped: false,
+            backslash_escape: *db_type != DbType::Pg,
+        }
+    }
+
+    fn in_quote(&mut self, c: char) -> bool {
+        if let Some(quote) = self.quote {
+            if self.escaped {
+                self.escaped = false;
+            } else if c == '\\' && self.backslash_escape && quote != '`' {
+                self.escaped = true;
+            } else if c == quote {
+                self.quote = None;
+            }
+            return true;
+        }
+
+        if matches!(c, '\'' | '"' | '`') {
+            self.quote = Some(c);
+            return true;
+        }
+        false
+    }
+}
+
+/// replaces identifier quotes of src with those of dst, strings are kept
+fn convert_quotes(text: &str, src_db_type: &DbType, dst_db_type: &DbType) -> String {
+    let src_quote = SqlUtil::get_escape_pairs(src_db_type)[0].0;
+    let dst_quote = SqlUtil::get_escape_pairs(dst_db_type)[0].0;
+    let mut res = String::new();
+    let mut in_string = false;
+    for c in text.chars() {
+        if c == '\'' {
+            in_string = !in_string;
+        }
+        if c == src_quote && !in_string {
+            res.push(dst_quote);
+        } else {
+            res.push(c);
+        }
+    }
+    res
+}
+
+/// `a` -> a, "a" -> a, unquoted identifiers are folded to lower case in Postgres
+fn ident_name(token: &str, db_type: &DbType) -> String {
+    let escape_pair = SqlUtil::get_escape_pairs(db_type)[0];
+    if SqlUtil::is_escaped(token, &escape_pair) && token.len() > 1 {
+        SqlUtil::unescape(token, &escape_pair)
+    } else if *db_type == DbType::Pg {
+        token.to_lowercase()
+    } else {
+        token.to_string()
+    }
+}
+
+fn convert_ident(token: &str, src_db_type: &DbType, dst_db_type: &DbType) -> String {
+    escape(&ident_name(token, src_db_type), dst_db_type)
+}
+
+/// db.tb / `db`.`tb` / tb -> (db, tb)
+fn split_tb_name(token: &str, db_type: &DbType) -> (String, String) {
+    let items: Vec<String> = split_by_dot(token, db_type);
+    match items.as_slice() {
+        [schema, tb] => (ident_name(schema, db_type), ident_name(tb, db_type)),
+        _ => (String::new(), ident_name(token, db_type)),
+    }
+}
+
+fn split_by_dot(token: &str, db_type: &DbType) -> Vec<String> {
+    let mut items = Vec::new();
+    let mut item = String::new();
+    let mut scanner = QuoteScanner::new(db_type);
+    for c in token.chars() {
+        if !scanner.in_quote(c) && c == '.' {
+            items.push(std::mem::take(&mut item));
+            continue;
+        }
+        item.push(c);
+    }
+    items.push(item);
+    items
+}
+
+fn tb_name(schema: &str, tb: &str, db_type: &DbType) -> String {
+    if schema.is_empty() {
+        escape(tb, db_type)
+    } else {
+        format!("{}.{}", escape(schema, db_type), escape(tb, db_type))
+    }
+}
+
+fn escape(identifier: &str, db_type: &DbType) -> String {
+    SqlUtil::escape_by_db_type(identifier, db_type)
+}
+
+/// 'it''s' / 'it\'s' -> it's
+fn unquote_mysql(text: &str) -> String {
+    let text = text.trim();
+    let Some(quote) = text.chars().next().filter(|c| *c == '\'' || *c == '"') else {
+        return text.to_string();
+    };
+    let inner = text
+        .strip_prefix(quote)
+        .and_then(|i| i.strip_suffix(quote))
+        .unwrap_or(text);
+
+    let mut res = String::new();
+    let mut chars = inner.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                Some('n') => res.push('\n'),
+                Some('t') => res.push('\t'),
+                Some('r') => res.push('\r'),
+                Some('0') => res.push('\0'),
+                Some(c) => res.push(c),
+                None => {}
+            },
+            c if c == quote && chars.peek() == Some(&quote) => {
+                res.push(c);
+                chars.next();
+            }
+            c => res.push(c),
+        }
+    }
+    res
+}
+
+fn escape_pg_literal(text: &str) -> String {
+    text.replace('\'', "''")
+}
+
+fn escape_mysql_literal(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('\'', "''")
+}
+
+fn inner(group: &str) -> String {
+    group
+        .strip_prefix('(')
+        .and_then(|i| i.strip_suffix(')'))
+        .unwrap_or(group)
+        .to_string()
+}
+
+fn trim_sql(sql: &str) -> String {
+    sql.trim().trim_end_matches(';').trim().to_string()
+}
+
+fn upper(token: &str) -> String {
+    token.to_uppercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::meta::ddl_meta::ddl_parser::DdlParser;
+
+    fn translate(sql: &str, src_db_type: DbType, dst_db_type: DbType) -> Vec<String> {
+        let mut ddl_data = DdlParser::new(src_db_type).parse(sql).unwrap();
+        ddl_data.default_schema = "db_1".into();
+        DdlTranslator::translate(&ddl_data, &dst_db_type).unwrap()
+    }
+
+    #[test]
+    fn test_mysql_create_table_to_pg() {
+        let sqls = translate(
+            "CREATE TABLE `db_1`.`tb_1` (
+                `id` bigint unsigned NOT NULL AUTO_INCREMENT,
+                `name` varchar(64) CHARACTER SET utf8mb4 NOT NULL DEFAULT 'it\\'s' COMMENT 'user name',
+                `created_at` datetime(3) DEFAULT CURRENT_TIMESTAMP(3) ON UPDATE CURRENT_TIMESTAMP(3),
+                `flag` tinyint(1) DEFAULT '0',
+                PRIMARY KEY (`id`),
+                UNIQUE KEY `uk_name` (`name`(10)),
+                KEY `idx_created_at` (`created_at` DESC)
+            ) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COMMENT='users';",
+            DbType::Mysql,
+            DbType::Pg,
+        );
+
+        assert_eq!(
+            sqls,
+            vec![
+                r#"CREATE TABLE "db_1"."tb_1" ("id" bigint GENERATED BY DEFAULT AS IDENTITY NOT NULL, "name" character varying(64) NOT NULL DEFAULT 'it''s', "created_at" timestamp(3) without time zone DEFAULT CURRENT_TIMESTAMP, "flag" smallint DEFAULT '0', PRIMARY KEY ("id"))"#,
+                r#"COMMENT ON COLUMN "db_1"."tb_1"."name" IS 'user name'"#,
+                r#"CREATE UNIQUE INDEX "tb_1_uk_name" ON "db_1"."tb_1" ("name")"#,
+                r#"CREATE INDEX "tb_1_idx_created_at" ON "db_1"."tb_1" ("created_at" DESC)"#,
+                r#"COMMENT ON TABLE "db_1"."tb_1" IS 'users'"#,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_mysql_alter_table_to_pg() {
+        let sqls = translate(
+            "ALTER TABLE tb_1 ADD COLUMN c_1 int unsigned NOT NULL DEFAULT 1 AFTER id, \
+            MODIFY c_2 varchar(10) NULL, CHANGE c_3 c_4 json, DROP COLUMN c_5, \
+            DROP INDEX idx_1, ADD INDEX idx_2 (c_1, c_4), DROP PRIMARY KEY, \
+            RENAME COLUMN c_6 TO c_7, ALTER c_8 SET DEFAULT 'a', ENGINE=InnoDB",
+            DbType::Mysql,
+            DbType::Pg,
+        );
+
+        assert_eq!(
+            sqls,
+            vec![
+                r#"ALTER TABLE "db_1"."tb_1" ADD COLUMN "c_1" bigint NOT NULL DEFAULT '1'"#,
+                r#"ALTER TABLE "db_1"."tb_1" ALTER COLUMN "c_2" TYPE character varying(10) USING "c_2"::character varying(10), ALTER COLUMN "c_2" DROP NOT NULL, ALTER COLUMN "c_2" DROP DEFAULT"#,
+                r#"ALTER TABLE "db_1"."tb_1" RENAME COLUMN "c_3" TO "c_4""#,
+                r#"ALTER TABLE "db_1"."tb_1" ALTER COLUMN "c_4" TYPE jsonb USING "c_4"::jsonb, ALTER COLUMN "c_4" DROP NOT NULL, ALTER COLUMN "c_4" DROP DEFAULT"#,
+                r#"ALTER TABLE "db_1"."tb_1" DROP COLUMN "c_5""#,
+                r#"DROP INDEX "db_1"."tb_1_idx_1""#,
+                r#"CREATE INDEX "tb_1_idx_2" ON "db_1"."tb_1" ("c_1", "c_4")"#,
+                r#"ALTER TABLE "db_1"."tb_1" DROP CONSTRAINT "tb_1_pkey""#,
+                r#"ALTER TABLE "db_1"."tb_1" RENAME COLUMN "c_6" TO "c_7""#,
+                r#"ALTER TABLE "db_1"."tb_1" ALTER COLUMN "c_8" SET DEFAULT 'a'"#,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_mysql_other_ddl_to_pg() {
+        let cases = [
+            (
+                "CREATE DATABASE IF NOT EXISTS db_2",
+                vec![r#"CREATE SCHEMA IF NOT EXISTS "db_2""#],
+            ),
+            ("DROP DATABASE db_2", vec![r#"DROP SCHEMA "db_2" CASCADE"#]),
+            (
+                "TRUNCATE TABLE tb_1",
+                vec![r#"TRUNCATE TABLE "db_1"."tb_1""#],
+            ),
+            (
+                "CREATE UNIQUE INDEX idx_1 USING HASH ON tb_1 (c_1)",
+                vec![r#"CREATE UNIQUE INDEX "tb_1_idx_1" ON "db_1"."tb_1" USING hash ("c_1")"#],
+            ),
+            (
+                "DROP INDEX idx_1 ON tb_1",
+                vec![r#"DROP INDEX "db_1"."tb_1_idx_1""#],
+            ),
+            (
+                "RENAME TABLE db_1.tb_1 TO db_2.tb_2",
+                vec![
+                    r#"ALTER TABLE "db_1"."tb_1" SET SCHEMA "db_2""#,
+                    r#"ALTER TABLE "db_2"."tb_1" RENAME TO "tb_2""#,
+                ],
+            ),
+            (
+                "ALTER TABLE tb_1 RENAME TO tb_2",
+                vec![r#"ALTER TABLE "db_1"."tb_1" RENAME TO "tb_2""#],
+            ),
+        ];
+
+        for (sql, expect_sqls) in cases {
+            assert_eq!(translate(sql, DbType::Mysql, DbType::Pg), expect_sqls);
+        }
+    }
+
+    #[test]
+    fn test_pg_create_table_to_mysql() {
+        let sqls = translate(
+            r#"CREATE TABLE public.tb_1 (
+                id serial PRIMARY KEY,
+                "Name" varchar(64) NOT NULL DEFAULT 'a\b'::character varying,
+                amount numeric(10, 2) CHECK (amount > 0),
+                tags text[],
+                created_at timestamptz(3) DEFAULT now(),
+                parent_id int REFERENCES tb_2(id) ON DELETE CASCADE,
+                flag bool DEFAULT true,
+                CONSTRAINT uk_name UNIQUE ("Name")
+            );"#,
+            DbType::Pg,
+            DbType::Mysql,
+        );
+
+        assert_eq!(
+            sqls,
+            vec![
+                "CREATE TABLE `public`.`tb_1` (`id` int AUTO_INCREMENT PRIMARY KEY, \
+                `Name` varchar(64) NOT NULL DEFAULT 'a\\\\b', \
+                `amount` decimal(10,2) CHECK (amount > 0), \
+                `tags` json, \
+                `created_at` datetime(3) DEFAULT CURRENT_TIMESTAMP(3), \
+                `parent_id` int, FOREIGN KEY (`parent_id`) REFERENCES tb_2(id) ON DELETE CASCADE, \
+                `flag` tinyint(1) DEFAULT '1', \
+                UNIQUE KEY `uk_name` (`Name`))"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_pg_alter_table_to_mysql() {
+        let sqls = translate(
+            r#"ALTER TABLE tb_1 ADD COLUMN IF NOT EXISTS c_1 bigint NOT NULL DEFAULT 0,
+            DROP COLUMN IF EXISTS c_2 CASCADE,
+            ALTER COLUMN c_5 DROP DEFAULT, DROP CONSTRAINT tb_1_pkey,
+            ADD CONSTRAINT fk_1 FOREIGN KEY (c_1) REFERENCES "tb_2"(id) NOT VALID,
+            OWNER TO postgres"#,
+            DbType::Pg,
+            DbType::Mysql,
+        );
+
+        assert_eq!(
+            sqls,
+            vec![
+                "ALTER TABLE `db_1`.`tb_1` ADD COLUMN `c_1` bigint NOT NULL DEFAULT '0'",
+                "ALTER TABLE `db_1`.`tb_1` DROP COLUMN `c_2`",
+                "ALTER TABLE `db_1`.`tb_1` ALTER COLUMN `c_5` DROP DEFAULT",
+                "ALTER TABLE `db_1`.`tb_1` DROP PRIMARY KEY",
+                "ALTER TABLE `db_1`.`tb_1` ADD CONSTRAINT `fk_1` FOREIGN KEY(c_1) REFERENCES `tb_2`(id)",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_pg_other_ddl_to_mysql() {
+        let cases = [
+            ("CREATE SCHEMA s_1", vec!["CREATE DATABASE `s_1`"]),
+            (
+                "ALTER TABLE tb_1 RENAME COLUMN c_1 TO c_2",
+                vec!["ALTER TABLE `db_1`.`tb_1` RENAME COLUMN `c_1` TO `c_2`"],
+            ),
+            ("DROP SCHEMA IF EXISTS s_1 CASCADE", vec!["DROP DATABASE IF EXISTS `s_1`"]),
+            ("TRUNCATE TABLE ONLY tb_1 RESTART IDENTITY", vec!["TRUNCATE TABLE `db_1`.`tb_1`"]),
+            (
+                "CREATE UNIQUE INDEX CONCURRENTLY idx_1 ON tb_1 USING btree (c_1 DESC NULLS LAST, c_2)",
+                vec!["CREATE UNIQUE INDEX `idx_1` USING BTREE ON `db_1`.`tb_1` (`c_1` DESC, `c_2`)"],
+            ),
+            (
+                "ALTER TABLE s_1.tb_1 RENAME TO tb_2",
+                vec!["ALTER TABLE `s_1`.`tb_1` RENAME TO `s_1`.`tb_2`"],
+            ),
+            (
+                "ALTER TABLE s_1.tb_1 SET SCHEMA s_2",
+                vec!["RENAME TABLE `s_1`.`tb_1` TO `s_2`.`tb_1`"],
+            ),
+        ];
+
+        for (sql, expect_sqls) in cases {
+            assert_eq!(translate(sql, DbType::Pg, DbType::Mysql), expect_sqls);
+        }
+    }
+
+    #[test]
+    fn test_translate_not_supported() {
+        let sqls = [
+            "ALTER TABLE tb_1 ALTER COLUMN c_1 TYPE bigint",
+            "ALTER TABLE tb_1 ALTER COLUMN c_1 SET NOT NULL",
+            "CREATE INDEX idx_1 ON tb_1 (lower(c_1))",
+            "CREATE INDEX idx_1 ON tb_1 (c_1) WHERE c_1 > 0",
+            "DROP INDEX idx_1",
+        ];
+        for sql in sqls {
+            let ddl_data = DdlParser::new(DbType::Pg).parse(sql).unwrap();
+            assert!(DdlTranslator::translate(&ddl_data, &DbType::Mysql).is_err());
+        }
+    }
+
+    #[test]
+    fn test_translate_incomplete_alter_spec() {
+        let cases = [
+            (DbType::Mysql, DbType::Pg, "ALTER TABLE tb_1 DROP INDEX"),
+            (
+                DbType::Mysql,
+                DbType::Pg,
+                "ALTER TABLE tb_1 DROP FOREIGN KEY",
+            ),
+            (DbType::Mysql, DbType::Pg, "ALTER TABLE tb_1 DROP COLUMN"),
+            (DbType::Mysql, DbType::Pg, "ALTER TABLE tb_1 CHANGE COLUMN"),
+            (DbType::Mysql, DbType::Pg, "ALTER TABLE tb_1 MODIFY COLUMN"),
+            (
+                DbType::Mysql,
+                DbType::Pg,
+                "ALTER TABLE tb_1 RENAME COLUMN c_1 TO",
+            ),
+            (
+                DbType::Mysql,
+                DbType::Pg,
+                "ALTER TABLE tb_1 RENAME INDEX idx_1",
+            ),
+            (DbType::Mysql, DbType::Pg, "ALTER TABLE tb_1 RENAME TO"),
+            (DbType::Mysql, DbType::Pg, "ALTER TABLE tb_1 ALTER COLUMN"),
+            (DbType::Mysql, DbType::Pg, "ALTER TABLE tb_1 ADD INDEX ()"),
+            (
+                DbType::Pg,
+                DbType::Mysql,
+                "ALTER TABLE tb_1 DROP CONSTRAINT IF EXISTS",
+            ),
+            (
+                DbType::Pg,
+                DbType::Mysql,
+                "ALTER TABLE tb_1 DROP COLUMN IF EXISTS",
+            ),
+            (
+                DbType::Pg,
+                DbType::Mysql,
+                "ALTER TABLE tb_1 RENAME COLUMN c_1 TO",
+            ),
+            (DbType::Pg, DbType::Mysql, "ALTER TABLE tb_1 ALTER COLUMN"),
+            (
+                DbType::Pg,
+                DbType::Mysql,
+                "ALTER TABLE tb_1 ADD PRIMARY KEY ()",
+            ),
+        ];
+        for (src_db_type, dst_db_type, sql) in cases {
+            let mut ddl_data = DdlParser::new(src_db_type).parse(sql).unwrap();
+            ddl_data.default_schema = "db_1".into();
+            assert!(DdlTranslator::translate(&ddl_data, &dst_db_type).is_err());
+        }
+    }
+
+    #[test]
+    fn test_normalize_pg_type() {
+        let cases = [
+            ("int4", ("integer", false)),
+            ("bigserial", ("bigint", true)),
+            ("varchar(10)", ("character varying(10)", false)),
+            ("char", ("character(1)", false)),
+            ("timestamptz(3)", ("timestamp(3) with time zone", false)),
+            (
+                "timestamp (6) with time zone",
+                ("timestamp(6) with time zone", false),
+            ),
+            ("timestamp", ("timestamp without time zone", false)),
+            ("time(3)", ("time(3) without time zone", false)),
+            ("numeric(10, 2)", ("numeric(10,2)", false)),
+            ("float(10)", ("real", false)),
+            ("integer[]", ("array[]", false)),
+        ];
+        for (pg_type, (expect_type, expect_serial)) in cases {
+            assert_eq!(
+                normalize_pg_type(pg_type),
+                (expect_type.to_string(), expect_serial)
+            );
+        }
+    }
+}
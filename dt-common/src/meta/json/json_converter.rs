@@ -0,0 +1,818 @@
+use std::collections::HashMap;
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use chrono::Utc;
+use serde_json::{json, Map, Value};
+
+use crate::{
+    config::config_enums::{DbType, MessageFormat},
+    meta::{
+        col_value::ColValue,
+        ddl_meta::{ddl_data::DdlData, ddl_type::DdlType},
+        position::Position,
+        rdb_meta_manager::RdbMetaManager,
+        rdb_tb_meta::RdbTbMeta,
+        row_data::RowData,
+        row_type::RowType,
+    },
+    utils::sql_util::SqlUtil,
+};
+
+// the logical server name in Debezium messages
+pub const SERVER_NAME: &str = "ape_dts";
+
+/// Converts rows / ddls to json messages compatible with other CDC tools: Debezium, Canal and Maxwell
+#[derive(Clone)]
+pub struct JsonConverter {
+    pub message_format: MessageFormat,
+    // db_type of the source
+    pub db_type: DbType,
+    pub with_schema: bool,
+    pub meta_manager: Option<RdbMetaManager>,
+}
+
+impl JsonConverter {
+    pub fn new(
+        message_format: MessageFormat,
+        db_type: DbType,
+        with_schema: bool,
+        meta_manager: Option<RdbMetaManager>,
+    ) -> Self {
+        Self {
+            message_format,
+            db_type,
+            with_schema,
+            meta_manager,
+        }
+    }
+
+    pub fn refresh_meta(&mut self, data: &[DdlData]) {
+        if let Some(meta_manager) = &mut self.meta_manager {
+            for ddl_data in data.iter() {
+                meta_manager.invalidate_cache_by_ddl_data(ddl_data);
+            }
+        }
+    }
+
+    /// json of the primary / unique key values, empty if the table meta is unknown
+    pub async fn row_data_to_json_key(&mut self, row_data: &RowData) -> anyhow::Result<String> {
+        let id_cols = match self.get_tb_meta(row_data).await? {
+            Some(tb_meta) => tb_meta.id_cols.clone(),
+            None => return Ok(String::new()),
+        };
+        let col_values = match row_data.row_type {
+            RowType::Insert => &row_data.after,
+            RowType::Update | RowType::Delete => &row_data.before,
+        };
+        let Some(col_values) = col_values else {
+            return Ok(String::new());
+        };
+
+        let mut key = Map::new();
+        let mut fields = Vec::new();
+        if self.message_format == MessageFormat::MaxwellJson {
+            // {"database":"test_db","table":"tb_1","pk.id":1}
+            key.insert("database".into(), json!(row_data.schema));
+            key.insert("table".into(), json!(row_data.tb));
+        }
+        for col in id_cols.iter() {
+            let col_value = col_values.get(col).unwrap_or(&ColValue::None);
+            let key_col = match self.message_format {
+                MessageFormat::MaxwellJson => format!("pk.{}", col),
+                _ => col.to_owned(),
+            };
+            key.insert(key_col, self.to_json_value(col_value));
+            fields.push(Self::get_field_schema(col, Self::get_col_type(col_value)));
+        }
+
+        let key = if self.message_format == MessageFormat::DebeziumJson && self.with_schema {
+            json!({
+                "schema": {
+                    "type": "struct",
+                    "fields": fields,
+                    "optional": false,
+                    "name": format!("{}.{}.{}.Key", SERVER_NAME, row_data.schema, row_data.tb),
+                },
+                "payload": key,
+            })
+        } else {
+            Value::Object(key)
+        };
+        Ok(key.to_string())
+    }
+
+    /// primary / unique key columns and values, empty if the table meta is unknown
+    pub async fn get_id_col_values(
+        &mut self,
+        row_data: &RowData,
+    ) -> anyhow::Result<Vec<(String, ColValue)>> {
+        let id_cols = match self.get_tb_meta(row_data).await? {
+            Some(tb_meta) => tb_meta.id_cols.clone(),
+            None => return Ok(Vec::new()),
+        };
+        let col_values = match row_data.row_type {
+            RowType::Insert => &row_data.after,
+            RowType::Update | RowType::Delete => &row_data.before,
+        };
+        let Some(col_values) = col_values else {
+            return Ok(Vec::new());
+        };
+        Ok(id_cols
+            .into_iter()
+            .map(|col| {
+                let col_value = col_values.get(&col).cloned().unwrap_or(ColValue::None);
+                (col, col_value)
+            })
+            .collect())
+    }
+
+    /// {"id":1}, values are converted in the same way as message_format
+    pub fn id_col_values_to_json(&self, id_col_values: &[(String, ColValue)]) -> String {
+        let mut key = Map::new();
+        for (col, col_value) in id_col_values {
+            key.insert(col.to_owned(), self.to_json_value(col_value));
+        }
+        Value::Object(key).to_string()
+    }
+
+    pub async fn row_data_to_json_value(
+        &mut self,
+        row_data: &RowData,
+        position: &Position,
+    ) -> anyhow::Result<Vec<u8>> {
+        let tb_meta = self.get_tb_meta(row_data).await?.cloned();
+        let cols = Self::get_cols(row_data, tb_meta.as_ref());
+        let value = match self.message_format {
+            MessageFormat::CanalJson => {
+                self.row_data_to_canal(row_data, position, &cols, tb_meta.as_ref())
+            }
+            MessageFormat::MaxwellJson => self.row_data_to_maxwell(row_data, position, &cols),
+            _ => self.row_data_to_debezium(row_data, position, &cols),
+        };
+        Ok(serde_json::to_vec(&value)?)
+    }
+
+    pub fn ddl_data_to_json_value(
+        &self,
+        ddl_data: &DdlData,
+        position: &Position,
+    ) -> anyhow::Result<Vec<u8>> {
+        let (schema, tb) = ddl_data.get_schema_tb();
+        let value = match self.message_format {
+            MessageFormat::CanalJson => json!({
+                "data": null,
+                "database": schema,
+                "table": tb,
+                "pkNames": null,
+                "isDdl": true,
+                "type": Self::get_canal_ddl_type(&ddl_data.ddl_type),
+                "es": Self::get_event_ts_millis(position),
+                "ts": Utc::now().timestamp_millis(),
+                "sql": ddl_data.query,
+                "mysqlType": null,
+                "old": null,
+                "id": 0,
+            }),
+
+            MessageFormat::MaxwellJson => {
+                let mut value = json!({
+                    "type": Self::get_maxwell_ddl_type(&ddl_data.ddl_type),
+                    "database": schema,
+                    "table": tb,
+                    "ts": Self::get_event_ts_millis(position) / 1000,
+                    "sql": ddl_data.query,
+                });
+                Self::fill_maxwell_position(&mut value, position);
+                value
+            }
+
+            _ => {
+                // schema change event
+                let payload = json!({
+                    "source": self.get_debezium_source(&schema, &tb, position),
+                    "databaseName": schema,
+                    "ddl": ddl_data.query,
+                    "tableChanges": [],
+                });
+                if self.with_schema {
+                    let mut schema = Self::infer_schema(&payload, None);
+                    schema["optional"] = json!(false);
+                    schema["name"] = json!("io.debezium.connector.schema.Change");
+                    json!({ "schema": schema, "payload": payload })
+                } else {
+                    payload
+                }
+            }
+        };
+        Ok(serde_json::to_vec(&value)?)
+    }
+
+    fn row_data_to_debezium(
+        &self,
+        row_data: &RowData,
+        position: &Position,
+        cols: &[String],
+    ) -> Value {
+        let op = match row_data.row_type {
+            RowType::Insert if !Self::is_cdc(position) => "r",
+            RowType::Insert => "c",
+            RowType::Update => "u",
+            RowType::Delete => "d",
+        };
+        let source = self.get_debezium_source(&row_data.schema, &row_data.tb, position);
+        let payload = json!({
+            "before": self.to_json_object(&row_data.before, cols),
+            "after": self.to_json_object(&row_data.after, cols),
+            "source": source,
+            "op": op,
+            "ts_ms": Utc::now().timestamp_millis(),
+        });
+        if !self.with_schema {
+            return payload;
+        }
+
+        let name = format!("{}.{}.{}", SERVER_NAME, row_data.schema, row_data.tb);
+        let mut value_fields = Vec::new();
+        for col in cols.iter() {
+            let get_type = |col_values: &Option<HashMap<String, ColValue>>| {
+                col_values
+                    .as_ref()
+                    .and_then(|v| v.get(col))
+                    .and_then(Self::get_col_type)
+            };
+            let col_type = get_type(&row_data.after).or_else(|| get_type(&row_data.before));
+            value_fields.push(Self::get_field_schema(col, col_type));
+        }
+        let image_schema = |field: &str| {
+            json!({
+                "type": "struct",
+                "fields": value_fields,
+                "optional": true,
+                "name": format!("{}.Value", name),
+                "field": field,
+            })
+        };
+        let mut source_schema = Self::infer_schema(&payload["source"], Some("source"));
+        source_schema["optional"] = json!(false);
+        source_schema["name"] = json!(format!(
+            "io.debezium.connector.{}.Source",
+            Self::get_debezium_connector(&self.db_type)
+        ));
+
+        let schema = json!({
+            "type": "struct",
+            "fields": [
+                image_schema("before"),
+                image_schema("after"),
+                source_schema,
+                {"type": "string", "optional": false, "field": "op"},
+                {"type": "int64", "optional": true, "field": "ts_ms"},
+            ],
+            "optional": false,
+            "name": format!("{}.Envelope", name),
+        });
+        json!({ "schema": schema, "payload": payload })
+    }
+
+    fn row_data_to_canal(
+        &self,
+        row_data: &RowData,
+        position: &Position,
+        cols: &[String],
+        tb_meta: Option<&RdbTbMeta>,
+    ) -> Value {
+        let (data, old) = match row_data.row_type {
+            RowType::Insert => (self.to_json_object(&row_data.after, cols), Value::Null),
+            RowType::Update => (
+                self.to_json_object(&row_data.after, cols),
+                json!([self.get_changed_before(row_data, cols)]),
+            ),
+            RowType::Delete => (self.to_json_object(&row_data.before, cols), Value::Null),
+        };
+
+        let (pk_names, mysql_type) = if let Some(tb_meta) = tb_meta {
+            let mysql_type: Map<String, Value> = cols
+                .iter()
+                .filter_map(|col| {
+                    tb_meta
+                        .col_origin_type_map
+                        .get(col)
+                        .map(|t| (col.to_owned(), json!(t)))
+                })
+                .collect();
+            (json!(tb_meta.id_cols), Value::Object(mysql_type))
+        } else {
+            (Value::Null, Value::Null)
+        };
+
+        json!({
+            "data": [data],
+            "database": row_data.schema,
+            "table": row_data.tb,
+            "pkNames": pk_names,
+            "isDdl": false,
+            "type": row_data.row_type.to_string().to_uppercase(),
+            "es": Self::get_event_ts_millis(position),
+            "ts": Utc::now().timestamp_millis(),
+            "sql": "",
+            "mysqlType": mysql_type,
+            "old": old,
+            "id": 0,
+        })
+    }
+
+    fn row_data_to_maxwell(
+        &self,
+        row_data: &RowData,
+        position: &Position,
+        cols: &[String],
+    ) -> Value {
+        let mut value = json!({
+            "database": row_data.schema,
+            "table": row_data.tb,
+            "type": row_data.row_type.to_string(),
+            "ts": Self::get_event_ts_millis(position) / 1000,
+        });
+        match row_data.row_type {
+            RowType::Insert => value["data"] = self.to_json_object(&row_data.after, cols),
+            RowType::Update => {
+                value["data"] = self.to_json_object(&row_data.after, cols);
+                value["old"] = self.get_changed_before(row_data, cols);
+            }
+            RowType::Delete => value["data"] = self.to_json_object(&row_data.before, cols),
+        }
+        Self::fill_maxwell_position(&mut value, position);
+        value
+    }
+
+    fn fill_maxwell_position(value: &mut Value, position: &Position) {
+        if let Position::MysqlCdc {
+            server_id,
+            binlog_filename,
+            next_event_position,
+            gtid_set,
+            ..
+        } = position
+        {
+            value["position"] = json!(format!("{}:{}", binlog_filename, next_event_position));
+            value["server_id"] = json!(server_id.parse::<u64>().unwrap_or_default());
+            if !gtid_set.is_empty() {
+                value["gtid"] = json!(gtid_set);
+            }
+        }
+    }
+
+    pub fn get_debezium_source(&self, schema: &str, tb: &str, position: &Position) -> Value {
+        let mut source = json!({
+            "version": env!("CARGO_PKG_VERSION"),
+            "connector": Self::get_debezium_connector(&self.db_type),
+            "name": SERVER_NAME,
+            "ts_ms": Self::get_event_ts_millis(position),
+            "snapshot": (!Self::is_cdc(position)).to_string(),
+        });
+        match self.db_type {
+            DbType::Pg => {
+                source["schema"] = json!(schema);
+                source["table"] = json!(tb);
+            }
+            DbType::Mongo => {
+                source["db"] = json!(schema);
+                source["collection"] = json!(tb);
+            }
+            _ => {
+                source["db"] = json!(schema);
+                source["table"] = json!(tb);
+            }
+        }
+
+        match position {
+            Position::MysqlCdc {
+                server_id,
+                binlog_filename,
+                next_event_position,
+                gtid_set,
+                ..
+            } => {
+                source["server_id"] = json!(server_id.parse::<u64>().unwrap_or_default());
+                source["gtid"] = if gtid_set.is_empty() {
+                    Value::Null
+                } else {
+                    json!(gtid_set)
+                };
+                source["file"] = json!(binlog_filename);
+                source["pos"] = json!(next_event_position);
+                source["row"] = json!(0);
+            }
+            Position::PgCdc { lsn, .. } => {
+                source["lsn"] = match Self::lsn_to_u64(lsn) {
+                    Some(lsn) => json!(lsn),
+                    None => Value::Null,
+                };
+            }
+            _ => {}
+        }
+        source
+    }
+
+    pub fn get_debezium_connector(db_type: &DbType) -> &'static str {
+        match db_type {
+            DbType::Pg => "postgresql",
+            DbType::Mongo => "mongodb",
+            _ => "mysql",
+        }
+    }
+
+    fn get_canal_ddl_type(ddl_type: &DdlType) -> &'static str {
+        match ddl_type {
+            DdlType::CreateTable => "CREATE",
+            DdlType::AlterTable => "ALTER",
+            DdlType::DropTable => "ERASE",
+            DdlType::RenameTable => "RENAME",
+            DdlType::TruncateTable => "TRUNCATE",
+            DdlType::CreateIndex => "CINDEX",
+            DdlType::DropIndex => "DINDEX",
+            _ => "QUERY",
+        }
+    }
+
+    fn get_maxwell_ddl_type(ddl_type: &DdlType) -> &'static str {
+        match ddl_type {
+            DdlType::CreateDatabase | DdlType::CreateSchema => "database-create",
+            DdlType::DropDatabase | DdlType::DropSchema => "database-drop",
+            DdlType::AlterDatabase | DdlType::AlterSchema => "database-alter",
+            DdlType::CreateTable => "table-create",
+            DdlType::DropTable => "table-drop",
+            _ => "table-alter",
+        }
+    }
+
+    pub fn is_cdc(position: &Position) -> bool {
+        matches!(
+            position,
+            Position::MysqlCdc { .. } | Position::PgCdc { .. } | Position::MongoCdc { .. }
+        )
+    }
+
+    // the time when the change happened in source, or now for snapshot data
+    fn get_event_ts_millis(position: &Position) -> i64 {
+        let timestamp = match position {
+            Position::MysqlCdc { timestamp, .. }
+            | Position::PgCdc { timestamp, .. }
+            | Position::MongoCdc { timestamp, .. } => Position::parse_timestamp_millis(timestamp),
+            _ => None,
+        };
+        timestamp.unwrap_or_else(|| Utc::now().timestamp_millis())
+    }
+
+    // 0/16B3748 -> 23803720
+    fn lsn_to_u64(lsn: &str) -> Option<u64> {
+        let (high, low) = lsn.split_once('/')?;
+        let high = u64::from_str_radix(high, 16).ok()?;
+        let low = u64::from_str_radix(low, 16).ok()?;
+        Some((high << 32) + low)
+    }
+
+    // columns in the order of table, if the table meta is unknown, in the order of names
+    fn get_cols(row_data: &RowData, tb_meta: Option<&RdbTbMeta>) -> Vec<String> {
+        let mut row_cols: Vec<&String> = Vec::new();
+        for col_values in [&row_data.before, &row_data.after].into_iter().flatten() {
+            for col in col_values.keys() {
+                if !row_cols.contains(&col) {
+                    row_cols.push(col);
+                }
+            }
+        }
+        row_cols.sort();
+
+        let mut cols = Vec::new();
+        if let Some(tb_meta) = tb_meta {
+            for col in tb_meta.cols.iter() {
+                if row_cols.contains(&col) {
+                    cols.push(col.to_owned());
+                }
+            }
+        }
+        for col in row_cols {
+            if !cols.contains(col) {
+                cols.push(col.to_owned());
+            }
+        }
+        cols
+    }
+
+    // before values of the columns changed by update
+    fn get_changed_before(&self, row_data: &RowData, cols: &[String]) -> Value {
+        let (Some(before), Some(after)) = (&row_data.before, &row_data.after) else {
+            return Value::Null;
+        };
+        let mut changed = Map::new();
+        for col in cols.iter() {
+            if let Some(col_value) = before.get(col) {
+                if after.get(col) != Some(col_value) {
+                    changed.insert(col.to_owned(), self.to_json_value(col_value));
+                }
+            }
+        }
+        Value::Object(changed)
+    }
+
+    fn to_json_object(
+        &self,
+        col_values: &Option<HashMap<String, ColValue>>,
+        cols: &[String],
+    ) -> Value {
+        let Some(col_values) = col_values else {
+            return Value::Null;
+        };
+        let mut object = Map::new();
+        for col in cols.iter() {
+            if let Some(col_value) = col_values.get(col) {
+                object.insert(col.to_owned(), self.to_json_value(col_value));
+            }
+        }
+        Value::Object(object)
+    }
+
+    fn to_json_value(&self, col_value: &ColValue) -> Value {
+        match self.message_format {
+            // all values are strings in Canal, binaries are decoded as ISO-8859-1
+            MessageFormat::CanalJson => match col_value {
+                ColValue::Blob(v) => Value::String(v.iter().map(|b| *b as char).collect()),
+                ColValue::Json(v) => Value::String(String::from_utf8_lossy(v).to_string()),
+                _ => col_value
+                    .to_option_string()
+                    .map_or(Value::Null, Value::String),
+            },
+
+            // binaries are base64 strings, json columns are json strings in Debezium and json values in Maxwell
+            _ => {
+                let json_as_str = self.message_format == MessageFormat::DebeziumJson;
+                match col_value {
+                    ColValue::Blob(v) => Value::String(STANDARD.encode(v)),
+                    ColValue::RawString(v) => Value::String(SqlUtil::binary_to_str(v).0),
+                    ColValue::Json(v) if json_as_str => {
+                        Value::String(String::from_utf8_lossy(v).to_string())
+                    }
+                    ColValue::Json(v) => serde_json::from_slice(v)
+                        .unwrap_or_else(|_| Value::String(String::from_utf8_lossy(v).to_string())),
+                    ColValue::Json2(v) if json_as_str => Value::String(v.clone()),
+                    ColValue::Json2(v) => {
+                        serde_json::from_str(v).unwrap_or_else(|_| Value::String(v.clone()))
+                    }
+                    ColValue::Json3(v) if json_as_str => Value::String(v.to_string()),
+                    ColValue::Json3(v) => v.clone(),
+                    ColValue::MongoDoc(v) => Value::String(v.to_string()),
+                    _ => serde_json::to_value(col_value).unwrap_or(Value::Null),
+                }
+            }
+        }
+    }
+
+    // Kafka Connect schema type of a column value, None for null
+    fn get_col_type(col_value: &ColValue) -> Option<&'static str> {
+        let col_type = match col_value {
+            ColValue::None => return None,
+            ColValue::Bool(_) => "boolean",
+            ColValue::Tiny(_) => "int8",
+            ColValue::UnsignedTiny(_) | ColValue::Short(_) => "int16",
+            ColValue::UnsignedShort(_) | ColValue::Long(_) | ColValue::Year(_) => "int32",
+            ColValue::UnsignedLong(_)
+            | ColValue::LongLong(_)
+            | ColValue::UnsignedLongLong(_)
+            | ColValue::Bit(_)
+            | ColValue::Set(_)
+            | ColValue::Enum(_) => "int64",
+            ColValue::Float(_) => "float",
+            ColValue::Double(_) => "double",
+            ColValue::Blob(_) => "bytes",
+            _ => "string",
+        };
+        Some(col_type)
+    }
+
+    fn get_field_schema(col: &str, col_type: Option<&str>) -> Value {
+        json!({
+            "type": col_type.unwrap_or("string"),
+            "optional": true,
+            "field": col,
+        })
+    }
+
+    // Kafka Connect schema of a json value
+    fn infer_schema(value: &Value, field: Option<&str>) -> Value {
+        let mut schema = match value {
+            Value::Object(object) => {
+                let fields: Vec<Value> = object
+                    .iter()
+                    .map(|(k, v)| Self::infer_schema(v, Some(k)))
+                    .collect();
+                json!({ "type": "struct", "fields": fields })
+            }
+            Value::Array(array) => {
+                let items = match array.first() {
+                    Some(v) => Self::infer_schema(v, None),
+                    None => json!({ "type": "string", "optional": true }),
+                };
+                json!({ "type": "array", "items": items })
+            }
+            Value::Bool(_) => json!({ "type": "boolean" }),
+            Value::Number(v) if v.is_f64() => json!({ "type": "double" }),
+            Value::Number(_) => json!({ "type": "int64" }),
+            Value::String(_) | Value::Null => json!({ "type": "string" }),
+        };
+        schema["optional"] = json!(true);
+        if let Some(field) = field {
+            schema["field"] = json!(field);
+        }
+        schema
+    }
+
+    async fn get_tb_meta<'a>(
+        &'a mut self,
+        row_data: &RowData,
+    ) -> anyhow::Result<Option<&'a RdbTbMeta>> {
+        if let Some(meta_manager) = self.meta_manager.as_mut() {
+            let tb_meta = meta_manager
+                .get_tb_meta(&row_data.schema, &row_data.tb)
+                .await?;
+            return Ok(Some(tb_meta));
+        }
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_converter(message_format: MessageFormat, with_schema: bool) -> JsonConverter {
+        JsonConverter::new(message_format, DbType::Mysql, with_schema, None)
+    }
+
+    fn get_position() -> Position {
+        Position::MysqlCdc {
+            server_id: "1".into(),
+            binlog_filename: "mysql-bin.000001".into(),
+            next_event_position: 100,
+            gtid_set: String::new(),
+            timestamp: "2023-03-28 05:33:48.396".into(),
+        }
+    }
+
+    fn get_update_row_data() -> RowData {
+        let mut before = HashMap::new();
+        before.insert("id".to_string(), ColValue::Long(1));
+        before.insert("name".to_string(), ColValue::String("a".into()));
+        before.insert("bin".to_string(), ColValue::Blob(vec![1, 2]));
+        let mut after = before.clone();
+        after.insert("name".to_string(), ColValue::String("b".into()));
+        RowData::new(
+            "test_db".into(),
+            "tb_1".into(),
+            RowType::Update,
+            Some(before),
+            Some(after),
+        )
+    }
+
+    async fn to_json(
+        converter: &mut JsonConverter,
+        row_data: &RowData,
+        position: &Position,
+    ) -> Value {
+        let value = converter
+            .row_data_to_json_value(row_data, position)
+            .await
+            .unwrap();
+        serde_json::from_slice(&value).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_debezium() {
+        let mut converter = get_converter(MessageFormat::DebeziumJson, false);
+        let row_data = get_update_row_data();
+        let value = to_json(&mut converter, &row_data, &get_position()).await;
+
+        assert_eq!(value["op"], "u");
+        assert_eq!(value["before"]["name"], "a");
+        assert_eq!(value["after"]["name"], "b");
+        assert_eq!(value["after"]["id"], 1);
+        assert_eq!(value["after"]["bin"], "AQI=");
+        assert_eq!(value["source"]["connector"], "mysql");
+        assert_eq!(value["source"]["db"], "test_db");
+        assert_eq!(value["source"]["table"], "tb_1");
+        assert_eq!(value["source"]["file"], "mysql-bin.000001");
+        assert_eq!(value["source"]["pos"], 100);
+        assert_eq!(value["source"]["server_id"], 1);
+        assert_eq!(value["source"]["gtid"], Value::Null);
+        assert_eq!(value["source"]["snapshot"], "false");
+        assert_eq!(value["source"]["ts_ms"], 1679981628396i64);
+
+        // snapshot
+        let row_data = RowData::new(
+            "test_db".into(),
+            "tb_1".into(),
+            RowType::Insert,
+            None,
+            row_data.after,
+        );
+        let value = to_json(&mut converter, &row_data, &Position::None).await;
+        assert_eq!(value["op"], "r");
+        assert_eq!(value["before"], Value::Null);
+        assert_eq!(value["source"]["snapshot"], "true");
+    }
+
+    #[tokio::test]
+    async fn test_debezium_with_schema() {
+        let mut converter = get_converter(MessageFormat::DebeziumJson, true);
+        let row_data = get_update_row_data();
+        let value = to_json(&mut converter, &row_data, &get_position()).await;
+
+        assert_eq!(value["payload"]["op"], "u");
+        let fields = value["schema"]["fields"].as_array().unwrap();
+        assert_eq!(fields[0]["field"], "before");
+        assert_eq!(
+            fields[1]["fields"],
+            json!([
+                {"type": "bytes", "optional": true, "field": "bin"},
+                {"type": "int32", "optional": true, "field": "id"},
+                {"type": "string", "optional": true, "field": "name"},
+            ])
+        );
+        assert_eq!(fields[2]["name"], "io.debezium.connector.mysql.Source");
+        assert_eq!(value["schema"]["name"], "ape_dts.test_db.tb_1.Envelope");
+    }
+
+    #[tokio::test]
+    async fn test_canal() {
+        let mut converter = get_converter(MessageFormat::CanalJson, false);
+        let row_data = get_update_row_data();
+        let value = to_json(&mut converter, &row_data, &get_position()).await;
+
+        assert_eq!(value["type"], "UPDATE");
+        assert_eq!(value["isDdl"], false);
+        assert_eq!(value["database"], "test_db");
+        assert_eq!(value["table"], "tb_1");
+        assert_eq!(
+            value["data"],
+            json!([{"bin": "\u{1}\u{2}", "id": "1", "name": "b"}])
+        );
+        assert_eq!(value["old"], json!([{"name": "a"}]));
+        assert_eq!(value["es"], 1679981628396i64);
+    }
+
+    #[tokio::test]
+    async fn test_maxwell() {
+        let mut converter = get_converter(MessageFormat::MaxwellJson, false);
+        let row_data = get_update_row_data().reverse();
+        let row_data = RowData::new(
+            row_data.schema,
+            row_data.tb,
+            RowType::Delete,
+            row_data.before,
+            None,
+        );
+        let value = to_json(&mut converter, &row_data, &get_position()).await;
+
+        assert_eq!(value["type"], "delete");
+        assert_eq!(value["data"], json!({"bin": "AQI=", "id": 1, "name": "b"}));
+        assert_eq!(value["old"], Value::Null);
+        assert_eq!(value["ts"], 1679981628);
+        assert_eq!(value["position"], "mysql-bin.000001:100");
+    }
+
+    #[test]
+    fn test_ddl() {
+        let ddl_data = DdlData {
+            default_schema: "test_db".into(),
+            query: "TRUNCATE TABLE tb_1".into(),
+            ddl_type: DdlType::TruncateTable,
+            ..Default::default()
+        };
+
+        let converter = get_converter(MessageFormat::CanalJson, false);
+        let value = converter
+            .ddl_data_to_json_value(&ddl_data, &get_position())
+            .unwrap();
+        let value: Value = serde_json::from_slice(&value).unwrap();
+        assert_eq!(value["isDdl"], true);
+        assert_eq!(value["type"], "TRUNCATE");
+        assert_eq!(value["sql"], "TRUNCATE TABLE tb_1");
+        assert_eq!(value["database"], "test_db");
+
+        let converter = get_converter(MessageFormat::DebeziumJson, false);
+        let value = converter
+            .ddl_data_to_json_value(&ddl_data, &get_position())
+            .unwrap();
+        let value: Value = serde_json::from_slice(&value).unwrap();
+        assert_eq!(value["ddl"], "TRUNCATE TABLE tb_1");
+        assert_eq!(value["databaseName"], "test_db");
+    }
+
+    #[test]
+    fn test_lsn_to_u64() {
+        assert_eq!(JsonConverter::lsn_to_u64("0/16B3748"), Some(23803720));
+        assert_eq!(JsonConverter::lsn_to_u64("1/0"), Some(1 << 32));
+        assert_eq!(JsonConverter::lsn_to_u64(""), None);
+    }
+}
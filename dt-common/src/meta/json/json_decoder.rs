@@ -0,0 +1,800 @@
+use std::collections::HashMap;
+
+use anyhow::{bail, Context};
+use apache_avro::{from_avro_datum, types::Value as AvroValue, Schema};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use chrono::{DateTime, NaiveDate, TimeDelta};
+use serde_json::{json, Map, Value};
+
+use crate::{
+    config::config_enums::{DbType, MessageFormat},
+    error::Error,
+    meta::{
+        col_value::ColValue, ddl_meta::ddl_data::DdlData, dt_data::DtData, row_data::RowData,
+        row_type::RowType,
+    },
+};
+
+/// Decodes messages produced by other CDC tools: Debezium (json / avro), Canal and Maxwell.
+/// Ddls are returned with db_type, default_schema and query only, they should be parsed by the caller.
+#[derive(Clone)]
+pub struct JsonDecoder {
+    pub message_format: MessageFormat,
+    // debezium avro schemas fetched from schema registry, key: schema id,
+    // value: (avro schema, schemas of before / after columns in Kafka Connect style)
+    avro_schemas: HashMap<u32, (Schema, HashMap<String, Value>)>,
+}
+
+impl JsonDecoder {
+    pub fn new(message_format: MessageFormat) -> Self {
+        Self {
+            message_format,
+            avro_schemas: HashMap::new(),
+        }
+    }
+
+    /// Returns empty for tombstones and events other than dml / ddl / transaction END,
+    /// for example: heartbeats
+    pub fn json_value_to_dt_data(&self, payload: &[u8]) -> anyhow::Result<Vec<DtData>> {
+        let value: Value = serde_json::from_slice(payload)
+            .with_context(|| format!("invalid {} message", self.message_format))?;
+        // transaction metadata is in json for all message formats
+        if let Some(dt_datas) = Self::transaction_to_dt_data(&value) {
+            return Ok(dt_datas);
+        }
+        match self.message_format {
+            MessageFormat::CanalJson => Self::canal_to_dt_data(value),
+            MessageFormat::MaxwellJson => Self::maxwell_to_dt_data(value),
+            _ => {
+                // with_schema: {"schema": {...}, "payload": {...}}
+                if let (Some(schema), Some(payload)) = (value.get("schema"), value.get("payload")) {
+                    let col_schemas = Self::get_debezium_col_schemas(schema);
+                    Self::debezium_to_dt_data(payload.clone(), &col_schemas)
+                } else {
+                    Self::debezium_to_dt_data(value, &HashMap::new())
+                }
+            }
+        }
+    }
+
+    pub fn contains_avro_schema(&self, schema_id: u32) -> bool {
+        self.avro_schemas.contains_key(&schema_id)
+    }
+
+    /// schema: the writer schema registered by Debezium AvroConverter
+    pub fn add_avro_schema(&mut self, schema_id: u32, schema: &str) -> anyhow::Result<()> {
+        let mut schema: Value = serde_json::from_str(schema)?;
+        // decimals are decoded by Kafka Connect type names like in json messages
+        Self::remove_logical_types(&mut schema);
+        let col_schemas = Self::get_avro_col_schemas(&schema);
+        let avro_schema = Schema::parse(&schema)?;
+        self.avro_schemas
+            .insert(schema_id, (avro_schema, col_schemas));
+        Ok(())
+    }
+
+    /// datum: avro value without the schema registry wire format header
+    pub fn debezium_avro_to_dt_data(
+        &self,
+        schema_id: u32,
+        datum: &[u8],
+    ) -> anyhow::Result<Vec<DtData>> {
+        let Some((avro_schema, col_schemas)) = self.avro_schemas.get(&schema_id) else {
+            bail! {Error::Unexpected(format!("avro schema not found, id: {}", schema_id))}
+        };
+        let mut reader = datum;
+        let value = from_avro_datum(avro_schema, &mut reader, None)?;
+        Self::debezium_to_dt_data(Self::avro_to_json(value), col_schemas)
+    }
+
+    /// Transaction metadata: {"status": "BEGIN" / "END", "id": "..", ..}, END is decoded as Commit
+    /// so that the transactions are committed to target as in source, BEGIN is ignored
+    fn transaction_to_dt_data(value: &Value) -> Option<Vec<DtData>> {
+        let id = value["id"].as_str()?;
+        match value["status"].as_str()? {
+            "BEGIN" => Some(Vec::new()),
+            "END" => {
+                // xid is only in messages produced by ape-dts
+                let xid = value["xid"].as_str().unwrap_or(id).to_string();
+                Some(vec![DtData::Commit { xid }])
+            }
+            _ => None,
+        }
+    }
+
+    fn debezium_to_dt_data(
+        payload: Value,
+        col_schemas: &HashMap<String, Value>,
+    ) -> anyhow::Result<Vec<DtData>> {
+        // tombstone
+        if payload.is_null() {
+            return Ok(Vec::new());
+        }
+
+        let source = &payload["source"];
+        let connector = source["connector"].as_str().unwrap_or_default();
+        let db_type = match connector {
+            "postgresql" => DbType::Pg,
+            "mongodb" => {
+                bail! {Error::Unexpected("debezium mongodb messages are not supported".into())}
+            }
+            _ => DbType::Mysql,
+        };
+
+        if let Some(dt_datas) = Self::transaction_to_dt_data(&payload) {
+            return Ok(dt_datas);
+        }
+
+        // schema change event
+        if let Some(query) = payload["ddl"].as_str() {
+            let schema = payload["databaseName"]
+                .as_str()
+                .or(source["db"].as_str())
+                .unwrap_or_default();
+            return Ok(vec![Self::ddl_dt_data(db_type, schema, query)]);
+        }
+
+        let row_type = match payload["op"].as_str() {
+            Some("c") | Some("r") => RowType::Insert,
+            Some("u") => RowType::Update,
+            Some("d") => RowType::Delete,
+            // truncate, message
+            _ => return Ok(Vec::new()),
+        };
+
+        // pg: source.schema, mysql: source.db
+        let schema = source["schema"]
+            .as_str()
+            .or(source["db"].as_str())
+            .unwrap_or_default();
+        let tb = source["table"].as_str().unwrap_or_default();
+
+        let get_col_values = |key: &str| -> anyhow::Result<Option<HashMap<String, ColValue>>> {
+            let Value::Object(obj) = &payload[key] else {
+                return Ok(None);
+            };
+            let mut col_values = HashMap::new();
+            for (col, v) in obj {
+                let col_value = Self::debezium_to_col_value(v, col_schemas.get(col))
+                    .with_context(|| format!("invalid value of {}.{}.{}", schema, tb, col))?;
+                col_values.insert(col.clone(), col_value);
+            }
+            Ok(Some(col_values))
+        };
+        let before = get_col_values("before")?;
+        let after = get_col_values("after")?;
+        let row_data = RowData::new(schema.into(), tb.into(), row_type, before, after);
+        Ok(vec![DtData::Dml { row_data }])
+    }
+
+    fn canal_to_dt_data(value: Value) -> anyhow::Result<Vec<DtData>> {
+        let schema = value["database"].as_str().unwrap_or_default();
+        if value["isDdl"].as_bool() == Some(true) {
+            let query = value["sql"].as_str().unwrap_or_default();
+            return Ok(vec![Self::ddl_dt_data(DbType::Mysql, schema, query)]);
+        }
+
+        let row_type = match value["type"].as_str() {
+            Some("INSERT") => RowType::Insert,
+            Some("UPDATE") => RowType::Update,
+            Some("DELETE") => RowType::Delete,
+            _ => return Ok(Vec::new()),
+        };
+
+        let tb = value["table"].as_str().unwrap_or_default();
+        let mysql_types = value["mysqlType"].as_object();
+        let to_col_values = |obj: &Map<String, Value>| -> HashMap<String, ColValue> {
+            obj.iter()
+                .map(|(col, v)| {
+                    let col_type = mysql_types.and_then(|types| types.get(col)?.as_str());
+                    (col.clone(), Self::canal_to_col_value(v, col_type))
+                })
+                .collect()
+        };
+
+        let empty = Vec::new();
+        let data = value["data"].as_array().unwrap_or(&empty);
+        let old = value["old"].as_array().unwrap_or(&empty);
+        let mut dt_datas = Vec::new();
+        for (i, row) in data.iter().enumerate() {
+            let Value::Object(row) = row else { continue };
+            let (before, after) = match row_type {
+                RowType::Insert => (None, Some(to_col_values(row))),
+                RowType::Delete => (Some(to_col_values(row)), None),
+                _ => {
+                    // old contains the changed columns only
+                    let mut before = row.clone();
+                    if let Some(Value::Object(changed)) = old.get(i) {
+                        before.extend(changed.clone());
+                    }
+                    (Some(to_col_values(&before)), Some(to_col_values(row)))
+                }
+            };
+            let row_data = RowData::new(schema.into(), tb.into(), row_type.clone(), before, after);
+            dt_datas.push(DtData::Dml { row_data });
+        }
+        Ok(dt_datas)
+    }
+
+    fn maxwell_to_dt_data(value: Value) -> anyhow::Result<Vec<DtData>> {
+        let schema = value["database"].as_str().unwrap_or_default();
+        let row_type = match value["type"].as_str().unwrap_or_default() {
+            "insert" | "bootstrap-insert" => RowType::Insert,
+            "update" => RowType::Update,
+            "delete" => RowType::Delete,
+            ddl_type if ddl_type.starts_with("database-") || ddl_type.starts_with("table-") => {
+                let query = value["sql"].as_str().unwrap_or_default();
+                return Ok(vec![Self::ddl_dt_data(DbType::Mysql, schema, query)]);
+            }
+            // bootstrap-start, bootstrap-complete
+            _ => return Ok(Vec::new()),
+        };
+
+        let tb = value["table"].as_str().unwrap_or_default();
+        let to_col_values = |obj: &Map<String, Value>| -> HashMap<String, ColValue> {
+            obj.iter()
+                .map(|(col, v)| (col.clone(), Self::json_to_col_value(v)))
+                .collect()
+        };
+
+        let Value::Object(data) = &value["data"] else {
+            return Ok(Vec::new());
+        };
+        let (before, after) = match row_type {
+            RowType::Insert => (None, Some(to_col_values(data))),
+            RowType::Delete => (Some(to_col_values(data)), None),
+            _ => {
+                // old contains the changed columns only
+                let mut before = data.clone();
+                if let Value::Object(changed) = &value["old"] {
+                    before.extend(changed.clone());
+                }
+                (Some(to_col_values(&before)), Some(to_col_values(data)))
+            }
+        };
+        let row_data = RowData::new(schema.into(), tb.into(), row_type, before, after);
+        Ok(vec![DtData::Dml { row_data }])
+    }
+
+    fn ddl_dt_data(db_type: DbType, schema: &str, query: &str) -> DtData {
+        let ddl_data = DdlData {
+            db_type,
+            default_schema: schema.into(),
+            query: query.into(),
+            ..Default::default()
+        };
+        DtData::Ddl { ddl_data }
+    }
+
+    fn canal_to_col_value(value: &Value, mysql_type: Option<&str>) -> ColValue {
+        match value {
+            Value::Null => ColValue::None,
+            // binary values are encoded as latin1 (ISO-8859-1) strings
+            Value::String(v)
+                if mysql_type.is_some_and(|t| {
+                    let t = t.to_lowercase();
+                    t.contains("blob") || t.contains("binary")
+                }) =>
+            {
+                ColValue::Blob(v.chars().map(|c| c as u8).collect())
+            }
+            Value::String(v) => ColValue::String(v.clone()),
+            _ => Self::json_to_col_value(value),
+        }
+    }
+
+    fn json_to_col_value(value: &Value) -> ColValue {
+        match value {
+            Value::Null => ColValue::None,
+            Value::Bool(v) => ColValue::Bool(*v),
+            Value::Number(v) => {
+                if let Some(v) = v.as_i64() {
+                    ColValue::LongLong(v)
+                } else if let Some(v) = v.as_u64() {
+                    ColValue::UnsignedLongLong(v)
+                } else {
+                    ColValue::Double(v.as_f64().unwrap_or_default())
+                }
+            }
+            Value::String(v) => ColValue::String(v.clone()),
+            // json columns
+            _ => ColValue::Json2(value.to_string()),
+        }
+    }
+
+    /// col_schema: Kafka Connect schema of the column, only exists if the message contains schema
+    fn debezium_to_col_value(
+        value: &Value,
+        col_schema: Option<&Value>,
+    ) -> anyhow::Result<ColValue> {
+        if value.is_null() {
+            return Ok(ColValue::None);
+        }
+        let Some(col_schema) = col_schema else {
+            return Ok(Self::json_to_col_value(value));
+        };
+
+        let as_i64 = || {
+            value
+                .as_i64()
+                .with_context(|| format!("expect integer, got: {}", value))
+        };
+        let format_datetime = |v: Option<DateTime<chrono::Utc>>, fmt: &str| {
+            v.map(|v| v.format(fmt).to_string())
+                .with_context(|| format!("datetime out of range: {}", value))
+        };
+
+        let col_value = match col_schema["name"].as_str().unwrap_or_default() {
+            "org.apache.kafka.connect.data.Decimal" => {
+                let scale = match &col_schema["parameters"]["scale"] {
+                    Value::String(v) => v.parse()?,
+                    v => v.as_u64().unwrap_or_default() as u32,
+                };
+                ColValue::Decimal(Self::decode_decimal(value, scale)?)
+            }
+            "io.debezium.data.VariableScaleDecimal" => {
+                let scale = value["scale"].as_u64().unwrap_or_default() as u32;
+                ColValue::Decimal(Self::decode_decimal(&value["value"], scale)?)
+            }
+            "io.debezium.time.Date" | "org.apache.kafka.connect.data.Date" => {
+                let date = NaiveDate::default()
+                    .checked_add_signed(TimeDelta::days(as_i64()?))
+                    .with_context(|| format!("date out of range: {}", value))?;
+                ColValue::Date(date.format("%Y-%m-%d").to_string())
+            }
+            "io.debezium.time.Timestamp" | "org.apache.kafka.connect.data.Timestamp" => {
+                let v = DateTime::from_timestamp_millis(as_i64()?);
+                ColValue::DateTime(format_datetime(v, "%Y-%m-%d %H:%M:%S%.3f")?)
+            }
+            "io.debezium.time.MicroTimestamp" => {
+                let v = DateTime::from_timestamp_micros(as_i64()?);
+                ColValue::DateTime(format_datetime(v, "%Y-%m-%d %H:%M:%S%.6f")?)
+            }
+            "io.debezium.time.NanoTimestamp" => {
+                let v = Some(DateTime::from_timestamp_nanos(as_i64()?));
+                ColValue::DateTime(format_datetime(v, "%Y-%m-%d %H:%M:%S%.9f")?)
+            }
+            "io.debezium.time.ZonedTimestamp" => {
+                let str = value.as_str().unwrap_or_default();
+                let v = DateTime::parse_from_rfc3339(str)
+                    .with_context(|| format!("invalid zoned timestamp: {}", str))?
+                    .to_utc();
+                ColValue::Timestamp(format_datetime(Some(v), "%Y-%m-%d %H:%M:%S%.6f")?)
+            }
+            "io.debezium.time.Time" | "org.apache.kafka.connect.data.Time" => {
+                ColValue::Time(Self::format_time_micros(as_i64()? * 1000))
+            }
+            "io.debezium.time.MicroTime" => ColValue::Time(Self::format_time_micros(as_i64()?)),
+            "io.debezium.time.NanoTime" => {
+                ColValue::Time(Self::format_time_micros(as_i64()? / 1000))
+            }
+            "io.debezium.data.Json" => ColValue::Json2(value.as_str().unwrap_or_default().into()),
+            _ if col_schema["type"] == "bytes" => {
+                let str = value.as_str().unwrap_or_default();
+                ColValue::Blob(STANDARD.decode(str)?)
+            }
+            _ => Self::json_to_col_value(value),
+        };
+        Ok(col_value)
+    }
+
+    /// value: base64 of the big-endian two's complement unscaled value
+    fn decode_decimal(value: &Value, scale: u32) -> anyhow::Result<String> {
+        let bytes = STANDARD.decode(value.as_str().unwrap_or_default())?;
+        if bytes.is_empty() || bytes.len() > 16 {
+            bail! {Error::Unexpected(format!("unsupported decimal bytes: {}", value))}
+        }
+        // sign extension
+        let mut buf = if bytes[0] & 0x80 != 0 {
+            [0xff_u8; 16]
+        } else {
+            [0_u8; 16]
+        };
+        buf[16 - bytes.len()..].copy_from_slice(&bytes);
+        let unscaled = i128::from_be_bytes(buf);
+
+        let digits = unscaled.unsigned_abs().to_string();
+        let sign = if unscaled < 0 { "-" } else { "" };
+        let scale = scale as usize;
+        if scale == 0 {
+            return Ok(format!("{}{}", sign, digits));
+        }
+        let digits = format!("{:0>width$}", digits, width = scale + 1);
+        let (int_part, frac_part) = digits.split_at(digits.len() - scale);
+        Ok(format!("{}{}.{}", sign, int_part, frac_part))
+    }
+
+    /// mysql time may be negative or exceed 24 hours
+    fn format_time_micros(micros: i64) -> String {
+        let sign = if micros < 0 { "-" } else { "" };
+        let micros = micros.unsigned_abs();
+        let secs = micros / 1_000_000;
+        format!(
+            "{}{:02}:{:02}:{:02}.{:06}",
+            sign,
+            secs / 3600,
+            secs / 60 % 60,
+            secs % 60,
+            micros % 1_000_000
+        )
+    }
+
+    /// schema: {"type": "struct", "fields": [{"field": "before", "fields": [...]}, ...]}
+    fn get_debezium_col_schemas(schema: &Value) -> HashMap<String, Value> {
+        let mut col_schemas = HashMap::new();
+        for field in schema["fields"].as_array().into_iter().flatten() {
+            if field["field"] != "before" && field["field"] != "after" {
+                continue;
+            }
+            for col_schema in field["fields"].as_array().into_iter().flatten() {
+                if let Some(col) = col_schema["field"].as_str() {
+                    col_schemas.insert(col.to_string(), col_schema.clone());
+                }
+            }
+        }
+        col_schemas
+    }
+
+    /// converts avro schemas of before / after columns to Kafka Connect style,
+    /// field: {"name": "f_1", "type": ["null", {"type": "int", "connect.name": "io.debezium.time.Date"}]}
+    /// to: {"field": "f_1", "type": "int32", "name": "io.debezium.time.Date"}
+    fn get_avro_col_schemas(schema: &Value) -> HashMap<String, Value> {
+        let non_null_type = |avro_type: &Value| -> Value {
+            match avro_type {
+                Value::Array(types) => types
+                    .iter()
+                    .find(|t| *t != "null")
+                    .cloned()
+                    .unwrap_or(Value::Null),
+                _ => avro_type.clone(),
+            }
+        };
+
+        let mut col_schemas = HashMap::new();
+        for field in schema["fields"].as_array().into_iter().flatten() {
+            if field["name"] != "before" && field["name"] != "after" {
+                continue;
+            }
+            let record = non_null_type(&field["type"]);
+            for col_field in record["fields"].as_array().into_iter().flatten() {
+                let Some(col) = col_field["name"].as_str() else {
+                    continue;
+                };
+                let col_type = non_null_type(&col_field["type"]);
+                let col_schema = match &col_type {
+                    Value::Object(obj) => json!({
+                        "field": col,
+                        "type": obj.get("type"),
+                        "name": obj.get("connect.name"),
+                        "parameters": obj.get("connect.parameters"),
+                    }),
+                    _ => json!({ "field": col, "type": col_type }),
+                };
+                col_schemas.insert(col.to_string(), col_schema);
+            }
+        }
+        col_schemas
+    }
+
+    fn remove_logical_types(schema: &mut Value) {
+        match schema {
+            Value::Object(obj) => {
+                obj.remove("logicalType");
+                obj.values_mut().for_each(Self::remove_logical_types);
+            }
+            Value::Array(values) => values.iter_mut().for_each(Self::remove_logical_types),
+            _ => {}
+        }
+    }
+
+    /// bytes are encoded as base64 strings, the same as Debezium JsonConverter
+    fn avro_to_json(value: AvroValue) -> Value {
+        match value {
+            AvroValue::Null => Value::Null,
+            AvroValue::Boolean(v) => json!(v),
+            AvroValue::Int(v) => json!(v),
+            AvroValue::Long(v) => json!(v),
+            AvroValue::Float(v) => json!(v),
+            AvroValue::Double(v) => json!(v),
+            AvroValue::Bytes(v) | AvroValue::Fixed(_, v) => json!(STANDARD.encode(v)),
+            AvroValue::String(v) | AvroValue::Enum(_, v) => json!(v),
+            AvroValue::Union(_, v) => Self::avro_to_json(*v),
+            AvroValue::Array(v) => Value::Array(v.into_iter().map(Self::avro_to_json).collect()),
+            AvroValue::Map(v) => Value::Object(
+                v.into_iter()
+                    .map(|(k, v)| (k, Self::avro_to_json(v)))
+                    .collect(),
+            ),
+            AvroValue::Record(v) => Value::Object(
+                v.into_iter()
+                    .map(|(k, v)| (k, Self::avro_to_json(v)))
+                    .collect(),
+            ),
+            // logical types are removed from schemas
+            v => json!(format!("{:?}", v)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use apache_avro::{to_avro_datum, types::Record};
+
+    use super::*;
+    use crate::meta::{json::json_converter::JsonConverter, position::Position};
+
+    fn get_row_data(dt_datas: Vec<DtData>) -> Vec<RowData> {
+        dt_datas
+            .into_iter()
+            .map(|dt_data| match dt_data {
+                DtData::Dml { row_data } => row_data,
+                _ => panic!(),
+            })
+            .collect()
+    }
+
+    fn get_update_row_data() -> RowData {
+        let mut before = HashMap::new();
+        before.insert("id".to_string(), ColValue::LongLong(1));
+        before.insert("name".to_string(), ColValue::String("a".into()));
+        before.insert("bin".to_string(), ColValue::Blob(vec![1, 2, 255]));
+        let mut after = before.clone();
+        after.insert("name".to_string(), ColValue::String("b".into()));
+        RowData::new(
+            "test_db".into(),
+            "tb_1".into(),
+            RowType::Update,
+            Some(before),
+            Some(after),
+        )
+    }
+
+    async fn round_trip(message_format: MessageFormat, with_schema: bool) -> RowData {
+        let mut converter =
+            JsonConverter::new(message_format.clone(), DbType::Mysql, with_schema, None);
+        let payload = converter
+            .row_data_to_json_value(&get_update_row_data(), &Position::None)
+            .await
+            .unwrap();
+        let decoder = JsonDecoder::new(message_format);
+        let mut row_datas = get_row_data(decoder.json_value_to_dt_data(&payload).unwrap());
+        assert_eq!(row_datas.len(), 1);
+        row_datas.remove(0)
+    }
+
+    #[tokio::test]
+    async fn test_debezium_round_trip() {
+        let row_data = round_trip(MessageFormat::DebeziumJson, true).await;
+        assert_eq!(row_data, get_update_row_data());
+
+        // without schema, binary values are kept as base64 strings
+        let row_data = round_trip(MessageFormat::DebeziumJson, false).await;
+        assert_eq!(
+            row_data.after.unwrap()["bin"],
+            ColValue::String("AQL/".into())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_canal_round_trip() {
+        let row_data = round_trip(MessageFormat::CanalJson, false).await;
+        let before = row_data.before.unwrap();
+        let after = row_data.after.unwrap();
+        // no mysqlType without meta, all values are strings
+        assert_eq!(before["id"], ColValue::String("1".into()));
+        assert_eq!(before["name"], ColValue::String("a".into()));
+        assert_eq!(after["name"], ColValue::String("b".into()));
+    }
+
+    #[tokio::test]
+    async fn test_maxwell_round_trip() {
+        let row_data = round_trip(MessageFormat::MaxwellJson, false).await;
+        let before = row_data.before.unwrap();
+        let after = row_data.after.unwrap();
+        assert_eq!(row_data.row_type, RowType::Update);
+        assert_eq!(before["id"], ColValue::LongLong(1));
+        assert_eq!(before["name"], ColValue::String("a".into()));
+        assert_eq!(after["name"], ColValue::String("b".into()));
+    }
+
+    #[test]
+    fn test_debezium_pg() {
+        let payload = r#"{
+            "before": null,
+            "after": {"id": 1, "f_1": 19000, "f_2": "BNI=", "f_3": 1679981628396543, "f_4": 3723000000},
+            "source": {"connector": "postgresql", "db": "postgres", "schema": "public", "table": "tb_1"},
+            "op": "c"
+        }"#;
+        let schema = r#"{"type": "struct", "fields": [{"field": "after", "type": "struct", "fields": [
+            {"field": "id", "type": "int32"},
+            {"field": "f_1", "type": "int32", "name": "io.debezium.time.Date"},
+            {"field": "f_2", "type": "bytes", "name": "org.apache.kafka.connect.data.Decimal", "parameters": {"scale": "2"}},
+            {"field": "f_3", "type": "int64", "name": "io.debezium.time.MicroTimestamp"},
+            {"field": "f_4", "type": "int64", "name": "io.debezium.time.MicroTime"}
+        ]}]}"#;
+        let message = format!(r#"{{"schema": {}, "payload": {}}}"#, schema, payload);
+
+        let decoder = JsonDecoder::new(MessageFormat::DebeziumJson);
+        let row_data = get_row_data(decoder.json_value_to_dt_data(message.as_bytes()).unwrap());
+        let row_data = &row_data[0];
+        assert_eq!(row_data.schema, "public");
+        assert_eq!(row_data.tb, "tb_1");
+        assert_eq!(row_data.row_type, RowType::Insert);
+        assert!(row_data.before.is_none());
+        let after = row_data.after.as_ref().unwrap();
+        assert_eq!(after["id"], ColValue::LongLong(1));
+        assert_eq!(after["f_1"], ColValue::Date("2022-01-08".into()));
+        assert_eq!(after["f_2"], ColValue::Decimal("12.34".into()));
+        assert_eq!(
+            after["f_3"],
+            ColValue::DateTime("2023-03-28 05:33:48.396543".into())
+        );
+        assert_eq!(after["f_4"], ColValue::Time("01:02:03.000000".into()));
+
+        // tombstone
+        assert!(decoder.json_value_to_dt_data(b"null").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_decode_decimal() {
+        let decode = |bytes: &[u8], scale| {
+            JsonDecoder::decode_decimal(&json!(STANDARD.encode(bytes)), scale).unwrap()
+        };
+        assert_eq!(decode(&[0x04, 0xd2], 2), "12.34");
+        assert_eq!(decode(&[0xfb, 0x2e], 2), "-12.34");
+        assert_eq!(decode(&[0x05], 3), "0.005");
+        assert_eq!(decode(&[0xff], 0), "-1");
+    }
+
+    #[test]
+    fn test_debezium_avro() {
+        let schema = r#"{
+            "type": "record", "name": "Envelope", "namespace": "ape_dts.test_db.tb_1",
+            "fields": [
+                {"name": "before", "type": ["null", {"type": "record", "name": "Value", "fields": [
+                    {"name": "id", "type": "int"},
+                    {"name": "f_1", "type": ["null", {"type": "bytes", "scale": 2, "precision": 10,
+                        "connect.name": "org.apache.kafka.connect.data.Decimal",
+                        "connect.parameters": {"scale": "2"}, "logicalType": "decimal"}], "default": null},
+                    {"name": "f_2", "type": ["null", "bytes"], "default": null}
+                ]}], "default": null},
+                {"name": "after", "type": ["null", "Value"], "default": null},
+                {"name": "source", "type": {"type": "record", "name": "Source", "fields": [
+                    {"name": "connector", "type": "string"},
+                    {"name": "db", "type": "string"},
+                    {"name": "table", "type": ["null", "string"], "default": null}
+                ]}},
+                {"name": "op", "type": "string"}
+            ]
+        }"#;
+
+        let mut decoder = JsonDecoder::new(MessageFormat::DebeziumAvro);
+        decoder.add_avro_schema(1, schema).unwrap();
+        assert!(decoder.contains_avro_schema(1));
+
+        // encode by the writer schema without logical types
+        let avro_schema = &decoder.avro_schemas[&1].0;
+        let Schema::Record(record_schema) = avro_schema else {
+            panic!()
+        };
+        let Schema::Union(value_schema) = &record_schema.fields[0].schema else {
+            panic!()
+        };
+        let mut value = Record::new(&value_schema.variants()[1]).unwrap();
+        value.put("id", AvroValue::Int(1));
+        value.put(
+            "f_1",
+            AvroValue::Union(1, Box::new(AvroValue::Bytes(vec![0x04, 0xd2]))),
+        );
+        value.put(
+            "f_2",
+            AvroValue::Union(1, Box::new(AvroValue::Bytes(vec![1, 2]))),
+        );
+        let mut source = Record::new(&record_schema.fields[2].schema).unwrap();
+        source.put("connector", AvroValue::String("mysql".into()));
+        source.put("db", AvroValue::String("test_db".into()));
+        source.put(
+            "table",
+            AvroValue::Union(1, Box::new(AvroValue::String("tb_1".into()))),
+        );
+        let mut envelope = Record::new(avro_schema).unwrap();
+        envelope.put("before", AvroValue::Union(0, Box::new(AvroValue::Null)));
+        envelope.put("after", AvroValue::Union(1, Box::new(value.into())));
+        envelope.put("source", source);
+        envelope.put("op", AvroValue::String("r".into()));
+        let datum = to_avro_datum(avro_schema, envelope).unwrap();
+
+        let row_data = get_row_data(decoder.debezium_avro_to_dt_data(1, &datum).unwrap());
+        let row_data = &row_data[0];
+        assert_eq!(row_data.schema, "test_db");
+        assert_eq!(row_data.tb, "tb_1");
+        assert_eq!(row_data.row_type, RowType::Insert);
+        let after = row_data.after.as_ref().unwrap();
+        assert_eq!(after["id"], ColValue::LongLong(1));
+        assert_eq!(after["f_1"], ColValue::Decimal("12.34".into()));
+        assert_eq!(after["f_2"], ColValue::Blob(vec![1, 2]));
+
+        assert!(decoder.debezium_avro_to_dt_data(2, &datum).is_err());
+    }
+
+    #[test]
+    fn test_canal() {
+        let message = r#"{
+            "data": [{"id": "1", "name": "b", "bin": "\u0001ÿ"}, {"id": "2", "name": "b", "bin": null}],
+            "database": "test_db", "table": "tb_1", "isDdl": false, "type": "UPDATE",
+            "mysqlType": {"id": "int", "name": "varchar(10)", "bin": "varbinary(10)"},
+            "old": [{"name": "a"}, {"name": null}]
+        }"#;
+        let decoder = JsonDecoder::new(MessageFormat::CanalJson);
+        let row_datas = get_row_data(decoder.json_value_to_dt_data(message.as_bytes()).unwrap());
+        assert_eq!(row_datas.len(), 2);
+        let before = row_datas[0].before.as_ref().unwrap();
+        let after = row_datas[0].after.as_ref().unwrap();
+        assert_eq!(before["name"], ColValue::String("a".into()));
+        assert_eq!(after["name"], ColValue::String("b".into()));
+        assert_eq!(after["bin"], ColValue::Blob(vec![1, 255]));
+        assert_eq!(
+            row_datas[1].before.as_ref().unwrap()["name"],
+            ColValue::None
+        );
+    }
+
+    #[test]
+    fn test_ddl() {
+        let messages = [
+            (
+                MessageFormat::CanalJson,
+                r#"{"data": null, "database": "test_db", "table": "tb_1", "isDdl": true, "type": "ALTER",
+                    "sql": "ALTER TABLE tb_1 ADD COLUMN f_1 INT"}"#,
+            ),
+            (
+                MessageFormat::MaxwellJson,
+                r#"{"type": "table-alter", "database": "test_db", "table": "tb_1",
+                    "sql": "ALTER TABLE tb_1 ADD COLUMN f_1 INT"}"#,
+            ),
+            (
+                MessageFormat::DebeziumJson,
+                r#"{"source": {"connector": "mysql", "db": "test_db", "table": "tb_1"},
+                    "databaseName": "test_db", "ddl": "ALTER TABLE tb_1 ADD COLUMN f_1 INT", "tableChanges": []}"#,
+            ),
+        ];
+
+        for (message_format, message) in messages {
+            let decoder = JsonDecoder::new(message_format);
+            let mut dt_datas = decoder.json_value_to_dt_data(message.as_bytes()).unwrap();
+            let DtData::Ddl { ddl_data } = dt_datas.remove(0) else {
+                panic!()
+            };
+            assert_eq!(ddl_data.db_type, DbType::Mysql);
+            assert_eq!(ddl_data.default_schema, "test_db");
+            assert_eq!(ddl_data.query, "ALTER TABLE tb_1 ADD COLUMN f_1 INT");
+        }
+    }
+
+    #[test]
+    fn test_transaction() {
+        let begin = r#"{"status": "BEGIN", "id": "binlog.000001:1024", "event_count": null,
+            "data_collections": null, "ts_ms": 1700000000000}"#;
+        let end = r#"{"status": "END", "id": "binlog.000001:1024", "xid": "100", "event_count": 2,
+            "data_collections": [{"data_collection": "test_db.tb_1", "event_count": 2}],
+            "ts_ms": 1700000000000}"#;
+        let debezium_end = r#"{"schema": {}, "payload": {"status": "END", "id": "571:53195832",
+            "event_count": 1, "data_collections": [], "ts_ms": 1700000000000}}"#;
+
+        for message_format in [MessageFormat::DebeziumJson, MessageFormat::CanalJson] {
+            let decoder = JsonDecoder::new(message_format);
+            assert!(decoder
+                .json_value_to_dt_data(begin.as_bytes())
+                .unwrap()
+                .is_empty());
+            let dt_datas = decoder.json_value_to_dt_data(end.as_bytes()).unwrap();
+            assert!(matches!(&dt_datas[..], [DtData::Commit { xid }] if xid == "100"));
+        }
+
+        let decoder = JsonDecoder::new(MessageFormat::DebeziumJson);
+        let dt_datas = decoder
+            .json_value_to_dt_data(debezium_end.as_bytes())
+            .unwrap();
+        assert!(matches!(&dt_datas[..], [DtData::Commit { xid }] if xid == "571:53195832"));
+    }
+}
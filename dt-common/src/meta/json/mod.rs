@@ -0,0 +1,2 @@
+pub mod json_converter;
+pub mod json_decoder;
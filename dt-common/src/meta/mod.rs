@@ -8,6 +8,7 @@ pub mod dt_data;
 pub mod dt_queue;
 pub mod foreign_key;
 pub mod foxlake;
+pub mod json;
 pub mod kafka;
 pub mod mongo;
 pub mod mysql;
@@ -12,6 +12,9 @@ pub struct PgColType {
     pub element_oid: i32,
     pub category: String,
     pub enum_values: Option<Vec<String>>,
+    // namespace of the type, empty for mocked types
+    #[serde(default)]
+    pub schema: String,
 }
 
 impl std::fmt::Display for PgColType {
@@ -33,4 +36,26 @@ impl PgColType {
     pub fn is_user_defined(&self) -> bool {
         "U" == self.category
     }
+
+    pub fn is_builtin(&self) -> bool {
+        self.schema.is_empty() || self.schema == "pg_catalog"
+    }
+
+    /// The type name used to cast bound parameters, e.g. $1::int4
+    pub fn get_cast_name(&self) -> String {
+        // TODO: workaround for types like bit(3), casting to bit without length truncates to bit(1)
+        match self.alias.as_str() {
+            "bit" => return "varbit".to_string(),
+            "_bit" => return "_varbit".to_string(),
+            _ => {}
+        }
+
+        if self.is_builtin() {
+            self.alias.clone()
+        } else {
+            // types of extensions / users may not be in search_path,
+            // and their names may be case sensitive
+            format!(r#""{}"."{}""#, self.schema, self.name)
+        }
+    }
 }
@@ -1,63 +1,67 @@
 use serde::{Deserialize, Serialize};
 
+// refer to: https://github.com/postgres/postgres/blob/master/src/include/catalog/pg_type.dat
 const BOOL_OID: i32 = 16;
 const BYTEA_OID: i32 = 17;
 const CHAR_OID: i32 = 18;
-// const NAME_OID: i32 = 19;
+const NAME_OID: i32 = 19;
 const INT8_OID: i32 = 20;
 const INT2_OID: i32 = 21;
 const INT4_OID: i32 = 23;
 const TEXT_OID: i32 = 25;
 const OID_OID: i32 = 26;
-// const TID_OID: i32 = 27;
-// const XID_OID: i32 = 28;
-// const CID_OID: i32 = 29;
+const TID_OID: i32 = 27;
+const XID_OID: i32 = 28;
+const CID_OID: i32 = 29;
 const JSON_OID: i32 = 114;
-// const XML_OID: i32 = 142;
-// const XML_ARRAY_OID: i32 = 143;
-// const JSON_ARRAY_OID: i32 = 199;
+const XML_OID: i32 = 142;
+const XML_ARRAY_OID: i32 = 143;
+const JSON_ARRAY_OID: i32 = 199;
 const POINT_OID: i32 = 600;
-// const LSEG_OID: i32 = 601;
-// const PATH_OID: i32 = 602;
-// const BOX_OID: i32 = 603;
-// const POLYGON_OID: i32 = 604;
-// const LINE_OID: i32 = 628;
-// const LINE_ARRAY_OID: i32 = 629;
+const LSEG_OID: i32 = 601;
+const PATH_OID: i32 = 602;
+const BOX_OID: i32 = 603;
+const POLYGON_OID: i32 = 604;
+const LINE_OID: i32 = 628;
+const LINE_ARRAY_OID: i32 = 629;
 const CIDR_OID: i32 = 650;
-// const CIDR_ARRAY_OID: i32 = 651;
+const CIDR_ARRAY_OID: i32 = 651;
 const FLOAT4_OID: i32 = 700;
 const FLOAT8_OID: i32 = 701;
-// const CIRCLE_OID: i32 = 718;
-// const CIRCLE_ARRAY_OID: i32 = 719;
-// const UNKNOWN_OID: i32 = 705;
-// const MACADDR8_OID: i32 = 774;
+const CIRCLE_OID: i32 = 718;
+const CIRCLE_ARRAY_OID: i32 = 719;
+const UNKNOWN_OID: i32 = 705;
+const MACADDR8_OID: i32 = 774;
+const MACADDR8_ARRAY_OID: i32 = 775;
+const MONEY_OID: i32 = 790;
+const MONEY_ARRAY_OID: i32 = 791;
 const MACADDR_OID: i32 = 829;
 const INET_OID: i32 = 869;
 const BOOL_ARRAY_OID: i32 = 1000;
-// const QCHAR_ARRAY_OID: i32 = 1002;
-// const NAME_ARRAY_OID: i32 = 1003;
+const BYTEA_ARRAY_OID: i32 = 1001;
+const QCHAR_ARRAY_OID: i32 = 1002;
+const NAME_ARRAY_OID: i32 = 1003;
 const INT2_ARRAY_OID: i32 = 1005;
 const INT4_ARRAY_OID: i32 = 1007;
 const TEXT_ARRAY_OID: i32 = 1009;
-// const TID_ARRAY_OID: i32 = 1010;
-// const BYTEA_ARRAY_OID: i32 = 1001;
-// const XID_ARRAY_OID: i32 = 1011;
-// const CID_ARRAY_OID: i32 = 1012;
+const TID_ARRAY_OID: i32 = 1010;
+const XID_ARRAY_OID: i32 = 1011;
+const CID_ARRAY_OID: i32 = 1012;
 const BPCHAR_ARRAY_OID: i32 = 1014;
 const VARCHAR_ARRAY_OID: i32 = 1015;
 const INT8_ARRAY_OID: i32 = 1016;
-// const POINT_ARRAY_OID: i32 = 1017;
-// const LSEG_ARRAY_OID: i32 = 1018;
-// const PATH_ARRAY_OID: i32 = 1019;
-// const BOX_ARRAY_OID: i32 = 1020;
+const POINT_ARRAY_OID: i32 = 1017;
+const LSEG_ARRAY_OID: i32 = 1018;
+const PATH_ARRAY_OID: i32 = 1019;
+const BOX_ARRAY_OID: i32 = 1020;
 const FLOAT4_ARRAY_OID: i32 = 1021;
 const FLOAT8_ARRAY_OID: i32 = 1022;
-// const POLYGON_ARRAY_OID: i32 = 1027;
-// const OID_ARRAY_OID: i32 = 1028;
-// const ACLITEM_OID: i32 = 1033;
-// const ACLITEM_ARRAY_OID: i32 = 1034;
-// const MACADDR_ARRAY_OID: i32 = 1040;
-// const INET_ARRAY_OID: i32 = 1041;
+const POLYGON_ARRAY_OID: i32 = 1027;
+const OID_ARRAY_OID: i32 = 1028;
+const ACLITEM_OID: i32 = 1033;
+const ACLITEM_ARRAY_OID: i32 = 1034;
+const MACADDR_ARRAY_OID: i32 = 1040;
+const INET_ARRAY_OID: i32 = 1041;
 const BPCHAR_OID: i32 = 1042;
 const VARCHAR_OID: i32 = 1043;
 const DATE_OID: i32 = 1082;
@@ -65,51 +69,59 @@ const TIME_OID: i32 = 1083;
 const TIMESTAMP_OID: i32 = 1114;
 const TIMESTAMP_ARRAY_OID: i32 = 1115;
 const DATE_ARRAY_OID: i32 = 1182;
-// const TIME_ARRAY_OID: i32 = 1183;
+const TIME_ARRAY_OID: i32 = 1183;
 const TIMESTAMPTZ_OID: i32 = 1184;
 const TIMESTAMPTZ_ARRAY_OID: i32 = 1185;
 const INTERVAL_OID: i32 = 1186;
-// const INTERVAL_ARRAY_OID: i32 = 1187;
-// const NUMERIC_ARRAY_OID: i32 = 1231;
+const INTERVAL_ARRAY_OID: i32 = 1187;
+const NUMERIC_ARRAY_OID: i32 = 1231;
 const TIMETZ_OID: i32 = 1266;
-// const TIMETZ_ARRAY_OID: i32 = 1270;
-// const BIT_OID: i32 = 1560;
-// const BIT_ARRAY_OID: i32 = 1561;
-// const VARBIT_OID: i32 = 1562;
-// const VARBIT_ARRAY_OID: i32 = 1563;
+const TIMETZ_ARRAY_OID: i32 = 1270;
+const BIT_OID: i32 = 1560;
+const BIT_ARRAY_OID: i32 = 1561;
+const VARBIT_OID: i32 = 1562;
+const VARBIT_ARRAY_OID: i32 = 1563;
 const NUMERIC_OID: i32 = 1700;
-// const RECORD_OID: i32 = 2249;
-// const RECORD_ARRAY_OID: i32 = 2287;
+const RECORD_OID: i32 = 2249;
+const RECORD_ARRAY_OID: i32 = 2287;
 const UUID_OID: i32 = 2950;
-// const UUID_ARRAY_OID: i32 = 2951;
+const UUID_ARRAY_OID: i32 = 2951;
+const TSVECTOR_OID: i32 = 3614;
+const TSQUERY_OID: i32 = 3615;
+const TSVECTOR_ARRAY_OID: i32 = 3643;
+const TSQUERY_ARRAY_OID: i32 = 3645;
 const JSONB_OID: i32 = 3802;
-// const JSONB_ARRAY_OID: i32 = 3807;
-// const DATERANGE_OID: i32 = 3912;
-// const DATERANGE_ARRAY_OID: i32 = 3913;
-// const INT4RANGE_OID: i32 = 3904;
-// const INT4RANGE_ARRAY_OID: i32 = 3905;
-// const NUMRANGE_OID: i32 = 3906;
-// const NUMRANGE_ARRAY_OID: i32 = 3907;
-// const TSRANGE_OID: i32 = 3908;
-// const TSRANGE_ARRAY_OID: i32 = 3909;
-// const TSTZRANGE_OID: i32 = 3910;
-// const TSTZRANGE_ARRAY_OID: i32 = 3911;
-// const INT8RANGE_OID: i32 = 3926;
-// const INT8RANGE_ARRAY_OID: i32 = 3927;
-// const JSONPATH_OID: i32 = 4072;
-// const JSONPATH_ARRAY_OID: i32 = 4073;
-// const INT4MULTIRANGE_OID: i32 = 4451;
-// const NUMMULTIRANGE_OID: i32 = 4532;
-// const TSMULTIRANGE_OID: i32 = 4533;
-// const TSTZMULTIRANGE_OID: i32 = 4534;
-// const DATEMULTIRANGE_OID: i32 = 4535;
-// const INT8MULTIRANGE_OID: i32 = 4536;
-// const INT4MULTIRANGE_ARRAY_OID: i32 = 6150;
-// const NUMMULTIRANGE_ARRAY_OID: i32 = 6151;
-// const TSMULTIRANGE_ARRAY_OID: i32 = 6152;
-// const TSTZMULTIRANGE_ARRAY_OID: i32 = 6153;
-// const DATEMULTIRANGE_ARRAY_OID: i32 = 6155;
-// const INT8MULTIRANGE_ARRAY_OID: i32 = 6157;
+const JSONB_ARRAY_OID: i32 = 3807;
+const DATERANGE_OID: i32 = 3912;
+const DATERANGE_ARRAY_OID: i32 = 3913;
+const INT4RANGE_OID: i32 = 3904;
+const INT4RANGE_ARRAY_OID: i32 = 3905;
+const NUMRANGE_OID: i32 = 3906;
+const NUMRANGE_ARRAY_OID: i32 = 3907;
+const TSRANGE_OID: i32 = 3908;
+const TSRANGE_ARRAY_OID: i32 = 3909;
+const TSTZRANGE_OID: i32 = 3910;
+const TSTZRANGE_ARRAY_OID: i32 = 3911;
+const INT8RANGE_OID: i32 = 3926;
+const INT8RANGE_ARRAY_OID: i32 = 3927;
+const JSONPATH_OID: i32 = 4072;
+const JSONPATH_ARRAY_OID: i32 = 4073;
+const INT4MULTIRANGE_OID: i32 = 4451;
+const NUMMULTIRANGE_OID: i32 = 4532;
+const TSMULTIRANGE_OID: i32 = 4533;
+const TSTZMULTIRANGE_OID: i32 = 4534;
+const DATEMULTIRANGE_OID: i32 = 4535;
+const INT8MULTIRANGE_OID: i32 = 4536;
+const INT4MULTIRANGE_ARRAY_OID: i32 = 6150;
+const NUMMULTIRANGE_ARRAY_OID: i32 = 6151;
+const TSMULTIRANGE_ARRAY_OID: i32 = 6152;
+const TSTZMULTIRANGE_ARRAY_OID: i32 = 6153;
+const DATEMULTIRANGE_ARRAY_OID: i32 = 6155;
+const INT8MULTIRANGE_ARRAY_OID: i32 = 6157;
+
+// oids below this are assigned to built-in objects,
+// types created by extensions (postgis, hstore...) and users get oids above it
+pub const FIRST_NORMAL_OBJECT_OID: i32 = 16384;
 
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
 pub enum PgValueType {
@@ -129,16 +141,32 @@ pub enum PgValueType {
     TimeTZ,
     Interval,
     Numeric,
+    Money,
     Bytes,
     UUID,
     JSON,
+    Xml,
     HStore,
     Geography,
     Geometry,
     Point,
+    Lseg,
+    Path,
+    Box,
+    Polygon,
+    Line,
+    Circle,
     CIDR,
     INET,
     Macaddr,
+    Macaddr8,
+    Bit,
+    Varbit,
+    TsVector,
+    TsQuery,
+    JsonPath,
+    Range,
+    MultiRange,
     ArrayFloat32,
     ArrayFloat64,
     ArrayInt16,
@@ -146,9 +174,28 @@ pub enum PgValueType {
     ArrayInt64,
     ArrayString,
     ArrayDate,
+    ArrayTime,
+    ArrayTimeTZ,
     ArrayTimestamp,
     ArrayTimestampTZ,
+    ArrayInterval,
     ArrayBoolean,
+    ArrayNumeric,
+    ArrayMoney,
+    ArrayBytes,
+    ArrayUUID,
+    ArrayJSON,
+    ArrayXml,
+    ArrayPoint,
+    ArrayGeometric,
+    ArrayCIDR,
+    ArrayINET,
+    ArrayMacaddr,
+    ArrayBit,
+    ArrayRange,
+    ArrayMultiRange,
+    // arrays of any other element type, including extension types
+    Array,
 }
 
 impl PgValueType {
@@ -161,12 +208,15 @@ impl PgValueType {
             FLOAT4_OID => PgValueType::Float32,
             FLOAT8_OID => PgValueType::Float64,
             CHAR_OID => PgValueType::Char,
-            TEXT_OID | VARCHAR_OID | BPCHAR_OID => PgValueType::String,
+            TEXT_OID | VARCHAR_OID | BPCHAR_OID | NAME_OID | UNKNOWN_OID => PgValueType::String,
+            TID_OID | XID_OID | CID_OID | ACLITEM_OID => PgValueType::String,
             BYTEA_OID => PgValueType::Bytes,
             JSON_OID | JSONB_OID => PgValueType::JSON,
+            XML_OID => PgValueType::Xml,
             UUID_OID => PgValueType::UUID,
             CIDR_OID => PgValueType::CIDR,
             MACADDR_OID => PgValueType::Macaddr,
+            MACADDR8_OID => PgValueType::Macaddr8,
             INET_OID => PgValueType::INET,
             INTERVAL_OID => PgValueType::Interval,
             DATE_OID => PgValueType::Date,
@@ -175,7 +225,24 @@ impl PgValueType {
             TIMESTAMP_OID => PgValueType::Timestamp,
             TIMESTAMPTZ_OID => PgValueType::TimestampTZ,
             NUMERIC_OID => PgValueType::Numeric,
+            MONEY_OID => PgValueType::Money,
             POINT_OID => PgValueType::Point,
+            LSEG_OID => PgValueType::Lseg,
+            PATH_OID => PgValueType::Path,
+            BOX_OID => PgValueType::Box,
+            POLYGON_OID => PgValueType::Polygon,
+            LINE_OID => PgValueType::Line,
+            CIRCLE_OID => PgValueType::Circle,
+            BIT_OID => PgValueType::Bit,
+            VARBIT_OID => PgValueType::Varbit,
+            TSVECTOR_OID => PgValueType::TsVector,
+            TSQUERY_OID => PgValueType::TsQuery,
+            JSONPATH_OID => PgValueType::JsonPath,
+            RECORD_OID => PgValueType::Struct,
+            INT4RANGE_OID | INT8RANGE_OID | NUMRANGE_OID | TSRANGE_OID | TSTZRANGE_OID
+            | DATERANGE_OID => PgValueType::Range,
+            INT4MULTIRANGE_OID | INT8MULTIRANGE_OID | NUMMULTIRANGE_OID | TSMULTIRANGE_OID
+            | TSTZMULTIRANGE_OID | DATEMULTIRANGE_OID => PgValueType::MultiRange,
             INT2_ARRAY_OID => PgValueType::ArrayInt16,
             INT4_ARRAY_OID => PgValueType::ArrayInt32,
             INT8_ARRAY_OID => PgValueType::ArrayInt64,
@@ -183,9 +250,38 @@ impl PgValueType {
             FLOAT8_ARRAY_OID => PgValueType::ArrayFloat64,
             BOOL_ARRAY_OID => PgValueType::ArrayBoolean,
             DATE_ARRAY_OID => PgValueType::ArrayDate,
+            TIME_ARRAY_OID => PgValueType::ArrayTime,
+            TIMETZ_ARRAY_OID => PgValueType::ArrayTimeTZ,
             TIMESTAMP_ARRAY_OID => PgValueType::ArrayTimestamp,
             TIMESTAMPTZ_ARRAY_OID => PgValueType::ArrayTimestampTZ,
-            TEXT_ARRAY_OID | VARCHAR_ARRAY_OID | BPCHAR_ARRAY_OID => PgValueType::ArrayString,
+            INTERVAL_ARRAY_OID => PgValueType::ArrayInterval,
+            NUMERIC_ARRAY_OID => PgValueType::ArrayNumeric,
+            MONEY_ARRAY_OID => PgValueType::ArrayMoney,
+            BYTEA_ARRAY_OID => PgValueType::ArrayBytes,
+            UUID_ARRAY_OID => PgValueType::ArrayUUID,
+            JSON_ARRAY_OID | JSONB_ARRAY_OID => PgValueType::ArrayJSON,
+            XML_ARRAY_OID => PgValueType::ArrayXml,
+            POINT_ARRAY_OID => PgValueType::ArrayPoint,
+            LSEG_ARRAY_OID | PATH_ARRAY_OID | BOX_ARRAY_OID | POLYGON_ARRAY_OID
+            | LINE_ARRAY_OID | CIRCLE_ARRAY_OID => PgValueType::ArrayGeometric,
+            CIDR_ARRAY_OID => PgValueType::ArrayCIDR,
+            INET_ARRAY_OID => PgValueType::ArrayINET,
+            MACADDR_ARRAY_OID | MACADDR8_ARRAY_OID => PgValueType::ArrayMacaddr,
+            BIT_ARRAY_OID | VARBIT_ARRAY_OID => PgValueType::ArrayBit,
+            TEXT_ARRAY_OID | VARCHAR_ARRAY_OID | BPCHAR_ARRAY_OID | NAME_ARRAY_OID
+            | QCHAR_ARRAY_OID => PgValueType::ArrayString,
+            INT4RANGE_ARRAY_OID | INT8RANGE_ARRAY_OID | NUMRANGE_ARRAY_OID | TSRANGE_ARRAY_OID
+            | TSTZRANGE_ARRAY_OID | DATERANGE_ARRAY_OID => PgValueType::ArrayRange,
+            INT4MULTIRANGE_ARRAY_OID
+            | INT8MULTIRANGE_ARRAY_OID
+            | NUMMULTIRANGE_ARRAY_OID
+            | TSMULTIRANGE_ARRAY_OID
+            | TSTZMULTIRANGE_ARRAY_OID
+            | DATEMULTIRANGE_ARRAY_OID => PgValueType::ArrayMultiRange,
+            TID_ARRAY_OID | XID_ARRAY_OID | CID_ARRAY_OID | OID_ARRAY_OID | ACLITEM_ARRAY_OID
+            | TSVECTOR_ARRAY_OID | TSQUERY_ARRAY_OID | JSONPATH_ARRAY_OID | RECORD_ARRAY_OID => {
+                PgValueType::Array
+            }
             _ => PgValueType::String,
         }
     }
@@ -199,12 +295,14 @@ impl PgValueType {
             "float4" => PgValueType::Float32,
             "float8" => PgValueType::Float64,
             "char" => PgValueType::Char,
-            "text" | "varchar" | "bpchar" => PgValueType::String,
+            "text" | "varchar" | "bpchar" | "name" | "citext" => PgValueType::String,
             "bytea" => PgValueType::Bytes,
             "json" | "jsonb" => PgValueType::JSON,
+            "xml" => PgValueType::Xml,
             "uuid" => PgValueType::UUID,
             "cidr" => PgValueType::CIDR,
             "macaddr" => PgValueType::Macaddr,
+            "macaddr8" => PgValueType::Macaddr8,
             "inet" => PgValueType::INET,
             "interval" => PgValueType::Interval,
             "date" => PgValueType::Date,
@@ -213,7 +311,29 @@ impl PgValueType {
             "timestamp" => PgValueType::Timestamp,
             "timestamptz" => PgValueType::TimestampTZ,
             "numeric" => PgValueType::Numeric,
+            "money" => PgValueType::Money,
             "point" => PgValueType::Point,
+            "lseg" => PgValueType::Lseg,
+            "path" => PgValueType::Path,
+            "box" => PgValueType::Box,
+            "polygon" => PgValueType::Polygon,
+            "line" => PgValueType::Line,
+            "circle" => PgValueType::Circle,
+            "bit" => PgValueType::Bit,
+            "varbit" => PgValueType::Varbit,
+            "tsvector" => PgValueType::TsVector,
+            "tsquery" => PgValueType::TsQuery,
+            "jsonpath" => PgValueType::JsonPath,
+            "record" => PgValueType::Struct,
+            "int4range" | "int8range" | "numrange" | "tsrange" | "tstzrange" | "daterange" => {
+                PgValueType::Range
+            }
+            "int4multirange" | "int8multirange" | "nummultirange" | "tsmultirange"
+            | "tstzmultirange" | "datemultirange" => PgValueType::MultiRange,
+            // extension types, their oids are assigned on CREATE EXTENSION
+            "hstore" => PgValueType::HStore,
+            "geometry" | "box2d" | "box3d" => PgValueType::Geometry,
+            "geography" => PgValueType::Geography,
             "_int2" => PgValueType::ArrayInt16,
             "_int4" => PgValueType::ArrayInt32,
             "_int8" => PgValueType::ArrayInt64,
@@ -221,10 +341,133 @@ impl PgValueType {
             "_float8" => PgValueType::ArrayFloat64,
             "_bool" => PgValueType::ArrayBoolean,
             "_date" => PgValueType::ArrayDate,
+            "_time" => PgValueType::ArrayTime,
+            "_timetz" => PgValueType::ArrayTimeTZ,
             "_timestamp" => PgValueType::ArrayTimestamp,
             "_timestamptz" => PgValueType::ArrayTimestampTZ,
-            "_text" | "_varchar" | "_bpchar" => PgValueType::ArrayString,
-            _ => PgValueType::String,
+            "_interval" => PgValueType::ArrayInterval,
+            "_numeric" => PgValueType::ArrayNumeric,
+            "_money" => PgValueType::ArrayMoney,
+            "_bytea" => PgValueType::ArrayBytes,
+            "_uuid" => PgValueType::ArrayUUID,
+            "_json" | "_jsonb" => PgValueType::ArrayJSON,
+            "_xml" => PgValueType::ArrayXml,
+            "_point" => PgValueType::ArrayPoint,
+            "_lseg" | "_path" | "_box" | "_polygon" | "_line" | "_circle" => {
+                PgValueType::ArrayGeometric
+            }
+            "_cidr" => PgValueType::ArrayCIDR,
+            "_inet" => PgValueType::ArrayINET,
+            "_macaddr" | "_macaddr8" => PgValueType::ArrayMacaddr,
+            "_bit" | "_varbit" => PgValueType::ArrayBit,
+            "_text" | "_varchar" | "_bpchar" | "_name" | "_char" | "_citext" => {
+                PgValueType::ArrayString
+            }
+            "_int4range" | "_int8range" | "_numrange" | "_tsrange" | "_tstzrange"
+            | "_daterange" => PgValueType::ArrayRange,
+            "_int4multirange" | "_int8multirange" | "_nummultirange" | "_tsmultirange"
+            | "_tstzmultirange" | "_datemultirange" => PgValueType::ArrayMultiRange,
+            _ => {
+                if alias.starts_with('_') {
+                    PgValueType::Array
+                } else {
+                    PgValueType::String
+                }
+            }
+        }
+    }
+
+    pub fn is_array(&self) -> bool {
+        matches!(
+            self,
+            PgValueType::ArrayFloat32
+                | PgValueType::ArrayFloat64
+                | PgValueType::ArrayInt16
+                | PgValueType::ArrayInt32
+                | PgValueType::ArrayInt64
+                | PgValueType::ArrayString
+                | PgValueType::ArrayDate
+                | PgValueType::ArrayTime
+                | PgValueType::ArrayTimeTZ
+                | PgValueType::ArrayTimestamp
+                | PgValueType::ArrayTimestampTZ
+                | PgValueType::ArrayInterval
+                | PgValueType::ArrayBoolean
+                | PgValueType::ArrayNumeric
+                | PgValueType::ArrayMoney
+                | PgValueType::ArrayBytes
+                | PgValueType::ArrayUUID
+                | PgValueType::ArrayJSON
+                | PgValueType::ArrayXml
+                | PgValueType::ArrayPoint
+                | PgValueType::ArrayGeometric
+                | PgValueType::ArrayCIDR
+                | PgValueType::ArrayINET
+                | PgValueType::ArrayMacaddr
+                | PgValueType::ArrayBit
+                | PgValueType::ArrayRange
+                | PgValueType::ArrayMultiRange
+                | PgValueType::Array
+        )
+    }
+
+    /// Whether values of this type can be matched by "=" in WHERE clauses.
+    /// Some types have no equality operator at all (json, xml, point, polygon...),
+    /// others have one with surprising semantics (box / circle compare areas),
+    /// so they are matched by their text representation instead.
+    pub fn has_eq_operator(&self) -> bool {
+        !matches!(
+            self,
+            PgValueType::JSON
+                | PgValueType::Xml
+                | PgValueType::Point
+                | PgValueType::Lseg
+                | PgValueType::Path
+                | PgValueType::Box
+                | PgValueType::Polygon
+                | PgValueType::Line
+                | PgValueType::Circle
+                | PgValueType::Geometry
+                | PgValueType::Geography
+                | PgValueType::ArrayJSON
+                | PgValueType::ArrayXml
+                | PgValueType::ArrayPoint
+                | PgValueType::ArrayGeometric
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_from_oid_and_alias() {
+        let cases = [
+            (XML_OID, "xml", PgValueType::Xml),
+            (BOX_OID, "box", PgValueType::Box),
+            (MACADDR8_OID, "macaddr8", PgValueType::Macaddr8),
+            (TSTZRANGE_OID, "tstzrange", PgValueType::Range),
+            (
+                DATEMULTIRANGE_OID,
+                "datemultirange",
+                PgValueType::MultiRange,
+            ),
+            (JSON_ARRAY_OID, "_json", PgValueType::ArrayJSON),
+            (INET_ARRAY_OID, "_inet", PgValueType::ArrayINET),
+            (CIRCLE_ARRAY_OID, "_circle", PgValueType::ArrayGeometric),
+            (INT8RANGE_ARRAY_OID, "_int8range", PgValueType::ArrayRange),
+        ];
+        for (oid, alias, value_type) in cases {
+            assert_eq!(PgValueType::from_oid(oid), value_type);
+            assert_eq!(PgValueType::from_alias(alias), value_type);
+            assert_eq!(value_type.is_array(), alias.starts_with('_'));
         }
+
+        // extension types
+        assert_eq!(PgValueType::from_alias("geometry"), PgValueType::Geometry);
+        assert_eq!(PgValueType::from_alias("hstore"), PgValueType::HStore);
+        assert_eq!(PgValueType::from_alias("_geometry"), PgValueType::Array);
+        assert_eq!(PgValueType::from_alias("ltree"), PgValueType::String);
     }
 }
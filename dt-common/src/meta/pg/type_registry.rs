@@ -2,7 +2,10 @@ use futures::TryStreamExt;
 use sqlx::{postgres::PgRow, Pool, Postgres, Row};
 use std::collections::HashMap;
 
-use super::{pg_col_type::PgColType, pg_value_type::PgValueType};
+use super::{
+    pg_col_type::PgColType,
+    pg_value_type::{PgValueType, FIRST_NORMAL_OBJECT_OID},
+};
 
 #[derive(Clone)]
 pub struct TypeRegistry {
@@ -26,6 +29,7 @@ impl TypeRegistry {
                     t.typbasetype AS parentoid,
                     t.typtypmod AS modifiers,
                     t.typcategory AS category,
+                    n.nspname AS schema,
                     e.values AS enum_values
             FROM pg_catalog.pg_type t
             JOIN pg_catalog.pg_namespace n
@@ -41,14 +45,49 @@ impl TypeRegistry {
             let col_type = self.parse_col_meta(&row)?;
             self.oid_to_type.insert(col_type.oid, col_type.clone());
         }
+        self.resolve_domain_types();
         Ok(self)
     }
 
+    fn resolve_domain_types(&mut self) {
+        // domains share the value type of their base types so that values
+        // extracted by snapshot (text cast) and cdc (wal) are the same
+        let mut resolved = Vec::new();
+        for col_type in self.oid_to_type.values() {
+            if col_type.parent_oid == 0 {
+                continue;
+            }
+
+            // domains may be nested
+            let mut base_type = col_type;
+            while let Some(parent) = self.oid_to_type.get(&base_type.parent_oid) {
+                base_type = parent;
+            }
+
+            if base_type.value_type != col_type.value_type {
+                resolved.push((col_type.oid, base_type.value_type.clone()));
+            }
+        }
+
+        for (oid, value_type) in resolved {
+            if let Some(col_type) = self.oid_to_type.get_mut(&oid) {
+                col_type.value_type = value_type;
+            }
+        }
+    }
+
     fn parse_col_meta(&mut self, row: &PgRow) -> anyhow::Result<PgColType> {
         let oid: i32 = row.get_unchecked("oid");
-        let value_type = PgValueType::from_oid(oid);
         let name: String = row.try_get("name")?;
         let alias = Self::name_to_alias(&name);
+        let schema: String = row.try_get("schema")?;
+        // built-in types are identified by fixed oids, while extension types
+        // like postgis geometry / hstore can only be identified by names
+        let value_type = if oid < FIRST_NORMAL_OBJECT_OID {
+            PgValueType::from_oid(oid)
+        } else {
+            PgValueType::from_alias(&alias)
+        };
         let element_oid: i32 = row.get_unchecked("element");
         let parent_oid: i32 = row.get_unchecked("parentoid");
         let category: String = row.get_unchecked("category");
@@ -69,6 +108,7 @@ impl TypeRegistry {
             parent_oid,
             category,
             enum_values,
+            schema,
         })
     }
 
@@ -1,7 +1,7 @@
-use std::str::FromStr;
+use std::{collections::BTreeMap, str::FromStr};
 
 use anyhow::Context;
-use chrono::DateTime;
+use chrono::{DateTime, NaiveDateTime};
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 
@@ -13,9 +13,13 @@ pub enum Position {
     #[default]
     None,
     Kafka {
+        // the message of this position
         topic: String,
         partition: i32,
         offset: i64,
+        // next offsets to consume of all consumed partitions, key: {topic}:{partition}
+        #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+        offsets: BTreeMap<String, i64>,
     },
     RdbSnapshot {
         db_type: String,
@@ -57,6 +61,14 @@ pub enum Position {
         tb: String,
         s3_meta_file: String,
     },
+    File {
+        schema: String,
+        tb: String,
+        // file name in the directory of table
+        file: String,
+        // count of rows extracted from the file
+        offset: u64,
+    },
 }
 
 impl Position {
@@ -67,6 +79,12 @@ impl Position {
             String::new()
         }
     }
+
+    pub fn parse_timestamp_millis(timestamp: &str) -> Option<i64> {
+        NaiveDateTime::parse_from_str(timestamp, "%Y-%m-%d %H:%M:%S%.3f")
+            .ok()
+            .map(|v| v.and_utc().timestamp_millis())
+    }
 }
 
 impl std::fmt::Display for Position {
@@ -123,11 +141,22 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_parse_timestamp() {
+        assert_eq!(
+            Some(1679981628396),
+            Position::parse_timestamp_millis("2023-03-28 05:33:48.396")
+        );
+        assert_eq!(None, Position::parse_timestamp_millis(""));
+    }
+
     #[test]
     fn test_from_str() {
         let strs = [
             r#"{"type":"None"}"#,
             r#"{"type":"RdbSnapshot","db_type":"mysql","schema":"test_db_1","tb":"numeric_table","order_col":"f_0","value":"127"}"#,
+            r#"{"type":"Kafka","topic":"test","partition":0,"offset":10}"#,
+            r#"{"type":"Kafka","topic":"test","partition":1,"offset":5,"offsets":{"test2:0":3,"test:0":11,"test:1":6}}"#,
         ];
 
         for str in strs {
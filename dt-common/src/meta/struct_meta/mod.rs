@@ -1,3 +1,5 @@
+pub mod mysql_to_pg_converter;
+pub mod pg_to_mysql_converter;
 pub mod statement;
 pub mod struct_data;
 pub mod structure;
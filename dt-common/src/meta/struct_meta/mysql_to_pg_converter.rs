@@ -0,0 +1,667 @@
+use std::collections::HashMap;
+
+use regex::{Captures, Regex};
+use serde::{Deserialize, Serialize};
+
+use crate::log_warn;
+
+use super::{
+    statement::{
+        mysql_create_database_statement::MysqlCreateDatabaseStatement,
+        mysql_create_table_statement::MysqlCreateTableStatement,
+        pg_create_schema_statement::PgCreateSchemaStatement,
+        pg_create_table_statement::PgCreateTableStatement,
+    },
+    structure::{
+        column::{Column, ColumnDefault},
+        comment::{Comment, CommentType},
+        constraint::{Constraint, ConstraintType},
+        index::{Index, IndexKind, IndexType},
+        schema::Schema,
+        table::Table,
+    },
+};
+
+type TbColTypeMap = HashMap<(String, String), HashMap<String, String>>;
+
+const JSON_PREFIX: &str = "json:";
+const AUTO_INCREMENT: &str = "auto_increment";
+// keywords quoted by Postgres quote_ident, which are all but the unreserved ones
+const PG_KEYWORDS: &str =
+    "all analyse analyze and any array as asc asymmetric authorization between bigint binary \
+    bit boolean both case cast char character check coalesce collate collation column \
+    concurrently constraint create cross current_catalog current_date current_role \
+    current_schema current_time current_timestamp current_user dec decimal default deferrable \
+    desc distinct do else end except exists extract false fetch float for foreign freeze from \
+    full grant greatest group grouping having ilike in initially inner inout int integer \
+    intersect interval into is isnull join json lateral leading least left like limit \
+    localtime localtimestamp national natural nchar none normalize not notnull null nullif \
+    numeric offset on only or order out outer overlaps overlay placing position precision \
+    primary real references returning right row select session_user setof similar smallint \
+    some substring symmetric system_user table tablesample then time timestamp to trailing \
+    treat trim true union unique user using values varchar variadic verbose when where window \
+    with xmlattributes xmlconcat xmlelement xmlexists xmlforest xmlnamespaces xmlparse xmlpi \
+    xmlroot xmlserialize xmltable";
+
+/// Converts MySQL structures into Postgres structures, a MySQL database becomes a Postgres schema.
+///
+/// Column types are mapped by get_pg_col_type, the results are in the same format as
+/// pg_catalog.format_type so they can be compared with the target directly.
+#[derive(Debug, Clone, Default)]
+pub struct MysqlToPgConverter {
+    // HashMap<(src_db, src_tb), HashMap<col, pg_col_type>>
+    pub col_type_map: TbColTypeMap,
+}
+
+impl MysqlToPgConverter {
+    pub fn from_config(col_type_map: &str) -> anyhow::Result<Self> {
+        let mut results = TbColTypeMap::new();
+        if col_type_map.trim().is_empty() {
+            return Ok(Self::default());
+        }
+
+        #[derive(Serialize, Deserialize)]
+        struct TbColTypeMapType {
+            db: String,
+            tb: String,
+            col_type_map: HashMap<String, String>,
+        }
+        // col_type_map=json:[{"db":"db_1","tb":"tb_1","col_type_map":{"f_0":"text","f_1":"numeric(30,0)"}}]
+        let config: Vec<TbColTypeMapType> =
+            serde_json::from_str(col_type_map.trim_start_matches(JSON_PREFIX))?;
+        for i in config {
+            results.insert((i.db, i.tb), i.col_type_map);
+        }
+        Ok(Self {
+            col_type_map: results,
+        })
+    }
+
+    pub fn convert_database(statement: MysqlCreateDatabaseStatement) -> PgCreateSchemaStatement {
+        // charset / collation are database level in Postgres
+        PgCreateSchemaStatement {
+            schema: Schema {
+                name: statement.database.name,
+            },
+        }
+    }
+
+    /// src_db / src_tb: names in source, used to find col type overrides,
+    /// statement: already routed,
+    /// route_tb: maps a source table to the target, used for foreign key references
+    pub fn convert_table(
+        &self,
+        src_db: &str,
+        src_tb: &str,
+        statement: MysqlCreateTableStatement,
+        route_tb: impl Fn(&str, &str) -> (String, String),
+    ) -> PgCreateTableStatement {
+        let mysql_table = statement.table;
+        let (schema, tb) = (mysql_table.database_name, mysql_table.table_name);
+        let col_type_overrides = self
+            .col_type_map
+            .get(&(src_db.to_string(), src_tb.to_string()));
+
+        let mut columns = Vec::new();
+        let mut column_comments = Vec::new();
+        let mut constraints = Vec::new();
+        let mut pk_cols = Vec::new();
+
+        let mut mysql_columns = mysql_table.columns;
+        mysql_columns.sort_by_key(|i| i.ordinal_position);
+        for mysql_column in mysql_columns {
+            let col = mysql_column.column_name.clone();
+            let auto_increment = mysql_column.extra.to_lowercase().contains(AUTO_INCREMENT);
+
+            let mut column_type = Self::get_pg_col_type(&mysql_column.column_type);
+            // identity columns must be smallint / integer / bigint
+            if auto_increment && column_type == "numeric(20,0)" {
+                column_type = "bigint".into();
+            }
+            if let Some(override_type) = col_type_overrides.and_then(|i| i.get(&col)) {
+                column_type = override_type.clone();
+            }
+
+            if mysql_column.column_type.to_lowercase().starts_with("enum") {
+                let items: Vec<String> = Self::parse_items(&mysql_column.column_type)
+                    .iter()
+                    .map(|i| format!("'{}'", Self::escape(i)))
+                    .collect();
+                constraints.push(Constraint {
+                    database_name: String::new(),
+                    schema_name: schema.clone(),
+                    table_name: tb.clone(),
+                    constraint_name: format!("{}_{}_check", tb, col),
+                    constraint_type: ConstraintType::Check,
+                    definition: format!(r#"CHECK ("{}" IN ({}))"#, col, items.join(", ")),
+                });
+            }
+
+            if !mysql_column.column_comment.is_empty() {
+                column_comments.push(Comment {
+                    comment_type: CommentType::Column,
+                    database_name: String::new(),
+                    schema_name: schema.clone(),
+                    table_name: tb.clone(),
+                    column_name: col.clone(),
+                    comment: Self::escape(&mysql_column.column_comment),
+                });
+            }
+
+            if mysql_column.column_key == "PRI" {
+                pk_cols.push(Self::quote_pg_identifier(&col));
+            }
+
+            let column_default = if auto_increment {
+                None
+            } else {
+                Self::get_pg_col_default(&mysql_column, &column_type)
+            };
+            columns.push(Column {
+                column_name: col,
+                ordinal_position: mysql_column.ordinal_position,
+                column_default,
+                is_nullable: mysql_column.is_nullable,
+                column_type,
+                // GENERATED BY DEFAULT AS IDENTITY, explicit values in inserts are still allowed
+                generated: auto_increment.then(|| "BY DEFAULT".to_string()),
+                ..Default::default()
+            });
+        }
+
+        if !pk_cols.is_empty() {
+            constraints.insert(
+                0,
+                Constraint {
+                    database_name: String::new(),
+                    schema_name: schema.clone(),
+                    table_name: tb.clone(),
+                    constraint_name: format!("{}_pkey", tb),
+                    constraint_type: ConstraintType::Primary,
+                    definition: format!("PRIMARY KEY ({})", pk_cols.join(", ")),
+                },
+            );
+        }
+
+        for constraint in statement.constraints {
+            let definition = match constraint.constraint_type {
+                ConstraintType::Check => {
+                    let definition = Self::strip_charset_introducers(&constraint.definition);
+                    format!("CHECK ({})", Self::quote_identifiers(&definition))
+                }
+                ConstraintType::Foregin => {
+                    let definition = Self::route_reference(&constraint.definition, &route_tb);
+                    format!("FOREIGN KEY {}", Self::quote_identifiers(&definition))
+                }
+                _ => continue,
+            };
+            constraints.push(Constraint {
+                database_name: String::new(),
+                schema_name: schema.clone(),
+                table_name: tb.clone(),
+                constraint_name: constraint.constraint_name,
+                constraint_type: constraint.constraint_type,
+                definition,
+            });
+        }
+
+        let mut indexes = Vec::new();
+        for index in statement.indexes {
+            if let Some(index) = Self::convert_index(&schema, &tb, index) {
+                indexes.push(index);
+            }
+        }
+
+        let mut table_comments = Vec::new();
+        if !mysql_table.table_comment.is_empty() {
+            table_comments.push(Comment {
+                comment_type: CommentType::Table,
+                database_name: String::new(),
+                schema_name: schema.clone(),
+                table_name: tb.clone(),
+                column_name: String::new(),
+                comment: Self::escape(&mysql_table.table_comment),
+            });
+        }
+
+        PgCreateTableStatement {
+            table: Table {
+                schema_name: schema,
+                table_name: tb,
+                columns,
+                ..Default::default()
+            },
+            table_comments,
+            column_comments,
+            constraints,
+            indexes,
+            sequences: vec![],
+            sequence_owners: vec![],
+        }
+    }
+
+    /// Maps MySQL information_schema.columns.COLUMN_TYPE to Postgres type,
+    /// the result is the same as pg_catalog.format_type of the created column.
+    pub fn get_pg_col_type(mysql_col_type: &str) -> String {
+        let col_type = mysql_col_type.trim().to_lowercase();
+        let unsigned = col_type.contains("unsigned");
+        let base_end = col_type.find(['(', ' ']).unwrap_or(col_type.len());
+        let base = &col_type[..base_end];
+        let args = match (col_type.find('('), col_type.find(')')) {
+            (Some(start), Some(end)) if start < end => col_type[start + 1..end].replace(' ', ""),
+            _ => String::new(),
+        };
+        let args_or = |default: &str| {
+            if args.is_empty() {
+                default.to_string()
+            } else {
+                args.clone()
+            }
+        };
+
+        match base {
+            "tinyint" => "smallint".into(),
+            "smallint" if unsigned => "integer".into(),
+            "smallint" => "smallint".into(),
+            "mediumint" => "integer".into(),
+            "int" | "integer" if unsigned => "bigint".into(),
+            "int" | "integer" => "integer".into(),
+            "bigint" if unsigned => "numeric(20,0)".into(),
+            "bigint" => "bigint".into(),
+
+            "float" => "real".into(),
+            "double" | "real" => "double precision".into(),
+            "decimal" | "numeric" | "dec" | "fixed" => format!("numeric({})", args_or("10,0")),
+            "bit" => format!("bit({})", args_or("1")),
+
+            "date" => "date".into(),
+            "time" => format!("time({}) without time zone", args_or("0")),
+            "datetime" => format!("timestamp({}) without time zone", args_or("0")),
+            "timestamp" => format!("timestamp({}) with time zone", args_or("0")),
+            "year" => "smallint".into(),
+
+            "char" => format!("character({})", args_or("1")),
+            "varchar" => format!("character varying({})", args),
+            "tinytext" | "text" | "mediumtext" | "longtext" => "text".into(),
+
+            "binary" | "varbinary" | "tinyblob" | "blob" | "mediumblob" | "longblob" => {
+                "bytea".into()
+            }
+
+            "enum" => {
+                let max_len = Self::parse_items(mysql_col_type)
+                    .iter()
+                    .map(|i| i.chars().count())
+                    .max()
+                    .unwrap_or(1);
+                format!("character varying({})", max_len.max(1))
+            }
+            "set" => "text".into(),
+            "json" => "jsonb".into(),
+
+            // geometry types and others without Postgres builtin equivalents
+            _ => "text".into(),
+        }
+    }
+
+    pub(crate) fn get_pg_col_default(column: &Column, pg_col_type: &str) -> Option<ColumnDefault> {
+        match &column.column_default {
+            Some(ColumnDefault::Literal(v)) => {
+                let default = if pg_col_type.starts_with("bit") {
+                    // b'101'
+                    v.to_string()
+                } else {
+                    format!("'{}'", Self::escape(v))
+                };
+                Some(ColumnDefault::Literal(default))
+            }
+
+            Some(ColumnDefault::Expression(v)) => {
+                let lower = v.to_lowercase();
+                let default = if lower.starts_with("current_timestamp")
+                    || lower.starts_with("now(")
+                    || lower.starts_with("localtimestamp")
+                {
+                    // ON UPDATE CURRENT_TIMESTAMP needs a trigger in Postgres, not migrated
+                    "CURRENT_TIMESTAMP".to_string()
+                } else if lower == "curdate()" || lower.starts_with("current_date") {
+                    "CURRENT_DATE".to_string()
+                } else if lower == "curtime()" || lower.starts_with("current_time") {
+                    "CURRENT_TIME".to_string()
+                } else if lower == "uuid()" || lower == "(uuid())" {
+                    "gen_random_uuid()".to_string()
+                } else {
+                    Self::quote_identifiers(v)
+                };
+                Some(ColumnDefault::Expression(default))
+            }
+
+            None => None,
+        }
+    }
+
+    fn convert_index(schema: &str, tb: &str, mut index: Index) -> Option<Index> {
+        let method = match index.index_type {
+            IndexType::Btree | IndexType::Unknown => "btree",
+            IndexType::Hash => "hash",
+            IndexType::FullText | IndexType::Spatial => {
+                log_warn!(
+                    "index: {} of table: {}.{} not migrated, type: {} not supported in Postgres",
+                    index.index_name,
+                    schema,
+                    tb,
+                    index.index_type
+                );
+                return None;
+            }
+        };
+
+        index.columns.sort_by_key(|i| i.seq_in_index);
+        let cols: Vec<String> = index
+            .columns
+            .iter()
+            .filter(|i| !i.column_name.is_empty())
+            .map(|i| Self::quote_pg_identifier(&i.column_name))
+            .collect();
+        // index names are unique per table in MySQL but per schema in Postgres
+        let index_name = format!("{}_{}", tb, index.index_name);
+        let unique = if index.index_kind == IndexKind::Unique {
+            "UNIQUE "
+        } else {
+            ""
+        };
+
+        let definition = format!(
+            r#"CREATE {}INDEX "{}" ON "{}"."{}" USING {} ({})"#,
+            unique,
+            index_name,
+            schema,
+            tb,
+            method,
+            cols.join(", ")
+        );
+        Some(Index {
+            database_name: String::new(),
+            schema_name: schema.to_string(),
+            table_name: tb.to_string(),
+            index_name,
+            index_kind: index.index_kind,
+            index_type: index.index_type,
+            comment: index.comment,
+            table_space: "pg_default".to_string(),
+            definition,
+            columns: index.columns,
+        })
+    }
+
+    /// enum('a','b''c') -> [a, b'c]
+    fn parse_items(mysql_col_type: &str) -> Vec<String> {
+        let (Some(start), Some(end)) = (mysql_col_type.find('('), mysql_col_type.rfind(')')) else {
+            return vec![];
+        };
+
+        let mut items = Vec::new();
+        let mut item = String::new();
+        let mut in_quote = false;
+        let mut chars = mysql_col_type[start + 1..end].chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '\'' if in_quote && chars.peek() == Some(&'\'') => {
+                    item.push('\'');
+                    chars.next();
+                }
+                '\'' if in_quote => {
+                    items.push(std::mem::take(&mut item));
+                    in_quote = false;
+                }
+                '\'' => in_quote = true,
+                _ if in_quote => item.push(c),
+                _ => {}
+            }
+        }
+        items
+    }
+
+    /// Quotes the identifier only if needed, the same as pg_get_constraintdef / pg_get_indexdef,
+    /// so the converted definitions can be compared with those fetched from target
+    fn quote_pg_identifier(name: &str) -> String {
+        let simple = name.starts_with(|c: char| c.is_ascii_lowercase() || c == '_')
+            && name
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_' || c == '$');
+        if simple && !PG_KEYWORDS.split_whitespace().any(|i| i == name) {
+            name.to_string()
+        } else {
+            format!(r#""{}""#, name.replace('"', r#""""#))
+        }
+    }
+
+    /// (`f_1`) REFERENCES `db_1`.`tb_2`(`id`) -> (`f_1`) REFERENCES `dst_db`.`dst_tb`(`id`)
+    fn route_reference(
+        definition: &str,
+        route_tb: &impl Fn(&str, &str) -> (String, String),
+    ) -> String {
+        let regex = Regex::new(r"REFERENCES `([^`]+)`\.`([^`]+)`").unwrap();
+        regex
+            .replace(definition, |caps: &Captures| {
+                let (dst_db, dst_tb) = route_tb(&caps[1], &caps[2]);
+                format!("REFERENCES `{}`.`{}`", dst_db, dst_tb)
+            })
+            .to_string()
+    }
+
+    /// Removes charset introducers of string literals which Postgres does not support,
+    /// (`f_1` in (_utf8mb4'a',_utf8mb4'b')) -> (`f_1` in ('a','b'))
+    fn strip_charset_introducers(text: &str) -> String {
+        let chars: Vec<char> = text.chars().collect();
+        let is_ident_char = |c: char| c.is_ascii_alphanumeric() || c == '_';
+        let mut result = String::new();
+        let mut quote: Option<char> = None;
+        let mut i = 0;
+        while i < chars.len() {
+            let c = chars[i];
+            match quote {
+                Some(_) if c == '\\' && i + 1 < chars.len() => {
+                    result.push(c);
+                    result.push(chars[i + 1]);
+                    i += 2;
+                    continue;
+                }
+                Some(q) if c == q => quote = None,
+                Some(_) => {}
+                None if c == '\'' || c == '`' => quote = Some(c),
+                None if c == '_' && (i == 0 || !is_ident_char(chars[i - 1])) => {
+                    let mut end = i + 1;
+                    while end < chars.len() && chars[end].is_ascii_alphanumeric() {
+                        end += 1;
+                    }
+                    if end > i + 1 && end < chars.len() && chars[end] == '\'' {
+                        i = end;
+                        continue;
+                    }
+                }
+                None => {}
+            }
+            result.push(c);
+            i += 1;
+        }
+        result
+    }
+
+    fn quote_identifiers(text: &str) -> String {
+        text.replace('`', "\"")
+    }
+
+    fn escape(text: &str) -> String {
+        text.replace('\'', "''")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_pg_col_type() {
+        let cases = [
+            ("tinyint(4)", "smallint"),
+            ("smallint(5) unsigned", "integer"),
+            ("int(11)", "integer"),
+            ("int unsigned", "bigint"),
+            ("bigint(20) unsigned", "numeric(20,0)"),
+            ("decimal(10,4)", "numeric(10,4)"),
+            ("float(6,2)", "real"),
+            ("double", "double precision"),
+            ("bit(64)", "bit(64)"),
+            ("datetime(6)", "timestamp(6) without time zone"),
+            ("datetime", "timestamp(0) without time zone"),
+            ("timestamp(3)", "timestamp(3) with time zone"),
+            ("time", "time(0) without time zone"),
+            ("year", "smallint"),
+            ("char(255)", "character(255)"),
+            ("varchar(100)", "character varying(100)"),
+            ("longtext", "text"),
+            ("varbinary(255)", "bytea"),
+            ("enum('x-small','small','medium')", "character varying(7)"),
+            ("set('a','b')", "text"),
+            ("json", "jsonb"),
+            ("geometry", "text"),
+        ];
+        for (mysql_col_type, pg_col_type) in cases {
+            assert_eq!(
+                MysqlToPgConverter::get_pg_col_type(mysql_col_type),
+                pg_col_type
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_items() {
+        assert_eq!(
+            MysqlToPgConverter::parse_items("enum('a','b''c','d,e')"),
+            vec!["a", "b'c", "d,e"]
+        );
+    }
+
+    #[test]
+    fn test_convert_table() {
+        let columns = vec![
+            Column {
+                column_name: "id".into(),
+                ordinal_position: 1,
+                column_type: "bigint unsigned".into(),
+                column_key: "PRI".into(),
+                extra: "auto_increment".into(),
+                ..Default::default()
+            },
+            Column {
+                column_name: "name".into(),
+                ordinal_position: 2,
+                column_type: "varchar(64)".into(),
+                is_nullable: true,
+                column_default: Some(ColumnDefault::Literal("it's".into())),
+                column_comment: "user name".into(),
+                ..Default::default()
+            },
+            Column {
+                column_name: "amount".into(),
+                ordinal_position: 3,
+                column_type: "bigint unsigned".into(),
+                is_nullable: true,
+                ..Default::default()
+            },
+        ];
+        let statement = MysqlCreateTableStatement {
+            table: Table {
+                database_name: "dst_db".into(),
+                table_name: "tb_1".into(),
+                columns,
+                ..Default::default()
+            },
+            constraints: vec![
+                Constraint {
+                    constraint_name: "tb_1_fk".into(),
+                    constraint_type: ConstraintType::Foregin,
+                    definition: "(`amount`) REFERENCES `db_1`.`tb_2`(`id`)".into(),
+                    database_name: "dst_db".into(),
+                    schema_name: String::new(),
+                    table_name: "tb_1".into(),
+                },
+                Constraint {
+                    constraint_name: "tb_1_chk".into(),
+                    constraint_type: ConstraintType::Check,
+                    definition: "(`name` in (_utf8mb4'a',_utf8mb4'b'))".into(),
+                    database_name: "dst_db".into(),
+                    schema_name: String::new(),
+                    table_name: "tb_1".into(),
+                },
+            ],
+            indexes: vec![],
+        };
+
+        let converter = MysqlToPgConverter::from_config(
+            r#"json:[{"db":"db_1","tb":"tb_1","col_type_map":{"amount":"numeric(30,0)"}}]"#,
+        )
+        .unwrap();
+        let route_tb = |db: &str, tb: &str| match (db, tb) {
+            ("db_1", "tb_2") => ("dst_db".to_string(), "dst_tb_2".to_string()),
+            _ => ("dst_db".to_string(), tb.to_string()),
+        };
+        let pg_statement = converter.convert_table("db_1", "tb_1", statement, route_tb);
+
+        assert_eq!(pg_statement.table.schema_name, "dst_db");
+        let columns = &pg_statement.table.columns;
+        assert_eq!(columns[0].column_type, "bigint");
+        assert_eq!(columns[0].generated, Some("BY DEFAULT".to_string()));
+        assert_eq!(columns[1].column_type, "character varying(64)");
+        assert_eq!(
+            columns[1].column_default,
+            Some(ColumnDefault::Literal("'it''s'".into()))
+        );
+        assert_eq!(columns[2].column_type, "numeric(30,0)");
+        assert_eq!(pg_statement.constraints[0].definition, "PRIMARY KEY (id)");
+        assert_eq!(pg_statement.column_comments[0].comment, "user name");
+        assert_eq!(
+            pg_statement.constraints[1].definition,
+            r#"FOREIGN KEY ("amount") REFERENCES "dst_db"."dst_tb_2"("id")"#
+        );
+        assert_eq!(
+            pg_statement.constraints[2].definition,
+            r#"CHECK (("name" in ('a','b')))"#
+        );
+    }
+
+    #[test]
+    fn test_strip_charset_introducers() {
+        let cases = [
+            ("(`f_1` = _utf8mb4'a')", "(`f_1` = 'a')"),
+            ("(`f_1` in (_latin1'a',_binary'b'))", "(`f_1` in ('a','b'))"),
+            // not introducers
+            ("(`f_1` = 'x _utf8mb4''a')", "(`f_1` = 'x _utf8mb4''a')"),
+            ("(`f_utf8` > 0)", "(`f_utf8` > 0)"),
+            ("(`_f` > 0)", "(`_f` > 0)"),
+        ];
+        for (text, expected) in cases {
+            assert_eq!(
+                MysqlToPgConverter::strip_charset_introducers(text),
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn test_quote_pg_identifier() {
+        assert_eq!(MysqlToPgConverter::quote_pg_identifier("id"), "id");
+        assert_eq!(MysqlToPgConverter::quote_pg_identifier("f_1$"), "f_1$");
+        assert_eq!(MysqlToPgConverter::quote_pg_identifier("Id"), r#""Id""#);
+        assert_eq!(MysqlToPgConverter::quote_pg_identifier("1f"), r#""1f""#);
+        assert_eq!(
+            MysqlToPgConverter::quote_pg_identifier("order"),
+            r#""order""#
+        );
+        assert_eq!(
+            MysqlToPgConverter::quote_pg_identifier(r#"a"b"#),
+            r#""a""b""#
+        );
+    }
+}
@@ -0,0 +1,554 @@
+use crate::{log_warn, meta::struct_meta::structure::index::IndexColumn};
+
+use super::{
+    statement::{
+        mysql_create_database_statement::MysqlCreateDatabaseStatement,
+        mysql_create_table_statement::MysqlCreateTableStatement,
+        pg_create_schema_statement::PgCreateSchemaStatement,
+        pg_create_table_statement::PgCreateTableStatement,
+    },
+    structure::{
+        column::{Column, ColumnDefault},
+        constraint::{Constraint, ConstraintType},
+        database::Database,
+        index::{Index, IndexKind, IndexType},
+        table::Table,
+    },
+};
+
+const AUTO_INCREMENT: &str = "auto_increment";
+const CHARSET: &str = "utf8mb4";
+// the max length of varchar(n) in utf8mb4 within the 65535 bytes row limit
+const MAX_VARCHAR_LENGTH: u64 = 16383;
+
+/// Converts Postgres structures into MySQL structures, a Postgres schema becomes a MySQL database.
+///
+/// Column types are mapped by get_mysql_col_type from pg_catalog.format_type.
+pub struct PgToMysqlConverter {}
+
+impl PgToMysqlConverter {
+    pub fn convert_schema(statement: PgCreateSchemaStatement) -> MysqlCreateDatabaseStatement {
+        MysqlCreateDatabaseStatement {
+            database: Database {
+                name: statement.schema.name,
+                default_character_set_name: CHARSET.into(),
+                default_collation_name: String::new(),
+            },
+        }
+    }
+
+    /// src_schema: name in source, used to resolve foreign key references,
+    /// statement: already routed
+    pub fn convert_table(
+        src_schema: &str,
+        statement: PgCreateTableStatement,
+    ) -> MysqlCreateTableStatement {
+        let pg_table = statement.table;
+        let (db, tb) = (pg_table.schema_name, pg_table.table_name);
+
+        let pk_cols: Vec<String> = statement
+            .constraints
+            .iter()
+            .find(|i| i.constraint_type == ConstraintType::Primary)
+            .map(|i| Self::parse_cols(&i.definition))
+            .unwrap_or_default();
+
+        let mut columns = Vec::new();
+        let mut has_auto_increment = false;
+        let mut pg_columns = pg_table.columns;
+        pg_columns.sort_by_key(|i| i.ordinal_position);
+        for pg_column in pg_columns {
+            let col = pg_column.column_name.clone();
+            let column_type = Self::get_mysql_col_type(&pg_column.column_type);
+
+            // serial / identity columns, mysql allows only one auto_increment column which must be a key
+            let is_serial = matches!(
+                &pg_column.column_default,
+                Some(ColumnDefault::Literal(v)) if v.starts_with("nextval(")
+            );
+            let is_sequence = is_serial || pg_column.generated.is_some();
+            let auto_increment = is_sequence
+                && !has_auto_increment
+                && pk_cols.contains(&col)
+                && Self::is_integer(&column_type);
+            has_auto_increment |= auto_increment;
+
+            let column_default = if is_sequence {
+                None
+            } else {
+                Self::get_mysql_col_default(&db, &tb, &pg_column, &column_type)
+            };
+
+            let column_comment = statement
+                .column_comments
+                .iter()
+                .find(|i| i.column_name == col)
+                .map(|i| i.comment.clone())
+                .unwrap_or_default();
+
+            columns.push(Column {
+                column_key: if pk_cols.contains(&col) {
+                    "PRI".into()
+                } else {
+                    String::new()
+                },
+                extra: if auto_increment {
+                    AUTO_INCREMENT.into()
+                } else {
+                    String::new()
+                },
+                column_name: col,
+                ordinal_position: pg_column.ordinal_position,
+                column_default,
+                is_nullable: pg_column.is_nullable,
+                column_type,
+                column_comment,
+                ..Default::default()
+            });
+        }
+
+        let mut indexes = Vec::new();
+        for index in statement.indexes {
+            // the index of primary key is created with table
+            if statement.constraints.iter().any(|i| {
+                i.constraint_type == ConstraintType::Primary
+                    && i.constraint_name == index.index_name
+            }) {
+                continue;
+            }
+            if let Some(index) = Self::convert_index(&db, &tb, index) {
+                indexes.push(index);
+            }
+        }
+
+        let mut constraints = Vec::new();
+        for constraint in statement.constraints {
+            if let Some(constraint) = Self::convert_constraint(src_schema, &db, &tb, constraint) {
+                constraints.push(constraint);
+            }
+        }
+
+        let table_comment = statement
+            .table_comments
+            .first()
+            .map(|i| i.comment.clone())
+            .unwrap_or_default();
+
+        MysqlCreateTableStatement {
+            table: Table {
+                database_name: db,
+                table_name: tb,
+                table_comment,
+                columns,
+                ..Default::default()
+            },
+            constraints,
+            indexes,
+        }
+    }
+
+    /// Maps Postgres pg_catalog.format_type to MySQL column type.
+    pub fn get_mysql_col_type(pg_col_type: &str) -> String {
+        let col_type = pg_col_type.trim().to_lowercase();
+        if col_type.ends_with("[]") {
+            return "json".into();
+        }
+
+        let base_end = col_type.find('(').unwrap_or(col_type.len());
+        let base = col_type[..base_end].trim();
+        let args = match (col_type.find('('), col_type.find(')')) {
+            (Some(start), Some(end)) if start < end => col_type[start + 1..end].replace(' ', ""),
+            _ => String::new(),
+        };
+        // timestamp(3) without time zone
+        let suffix = col_type[col_type.find(')').map(|i| i + 1).unwrap_or(base_end)..].trim();
+        let base = if suffix.is_empty() {
+            base.to_string()
+        } else {
+            format!("{} {}", base, suffix)
+        };
+        // the default precision of time / timestamp in Postgres is 6
+        let precision = if args.is_empty() {
+            "6".to_string()
+        } else {
+            args.clone()
+        };
+
+        match base.as_str() {
+            "smallint" => "smallint".into(),
+            "integer" => "int".into(),
+            "bigint" => "bigint".into(),
+            "real" => "float".into(),
+            "double precision" => "double".into(),
+            "numeric" if args.is_empty() => "decimal(65,30)".into(),
+            "numeric" => format!("decimal({})", args),
+            "money" => "varchar(64)".into(),
+            "boolean" => "tinyint(1)".into(),
+
+            "character" => match args.parse::<u64>() {
+                Ok(n) if n <= 255 => format!("char({})", n),
+                Ok(n) if n <= MAX_VARCHAR_LENGTH => format!("varchar({})", n),
+                _ => "longtext".into(),
+            },
+            "character varying" => match args.parse::<u64>() {
+                Ok(n) if n <= MAX_VARCHAR_LENGTH => format!("varchar({})", n),
+                _ => "longtext".into(),
+            },
+            "text" | "xml" => "longtext".into(),
+            "bytea" => "longblob".into(),
+
+            "date" => "date".into(),
+            "time without time zone" => format!("time({})", precision),
+            "timestamp without time zone" => format!("datetime({})", precision),
+            // values are converted to UTC
+            "timestamp with time zone" => format!("datetime({})", precision),
+            "time with time zone" => "varchar(32)".into(),
+            "interval" => "varchar(64)".into(),
+
+            "bit" => match args.parse::<u64>() {
+                Ok(n) if n <= 64 => format!("bit({})", n),
+                _ => "varchar(255)".into(),
+            },
+            "bit varying" => match args.parse::<u64>() {
+                Ok(n) if n <= MAX_VARCHAR_LENGTH => format!("varchar({})", n),
+                _ => "longtext".into(),
+            },
+
+            "uuid" => "char(36)".into(),
+            "inet" | "cidr" => "varchar(43)".into(),
+            "macaddr" => "varchar(17)".into(),
+            "macaddr8" => "varchar(23)".into(),
+            "json" | "jsonb" => "json".into(),
+
+            // enums, geometric, range, tsvector, extension types and others
+            _ => "longtext".into(),
+        }
+    }
+
+    pub(crate) fn get_mysql_col_default(
+        db: &str,
+        tb: &str,
+        column: &Column,
+        mysql_col_type: &str,
+    ) -> Option<ColumnDefault> {
+        let Some(ColumnDefault::Literal(v) | ColumnDefault::Expression(v)) = &column.column_default
+        else {
+            return None;
+        };
+
+        // 'abc'::character varying, (-1), NULL::text
+        let mut value = v.trim();
+        while value.starts_with('(') && value.ends_with(')') {
+            value = value[1..value.len() - 1].trim();
+        }
+        let lower = value.to_lowercase();
+        // text / blob / json columns only support expression defaults (8.0.13+)
+        let no_literal = mysql_col_type.ends_with("text")
+            || mysql_col_type.ends_with("blob")
+            || mysql_col_type == "json";
+
+        if lower.starts_with("null") {
+            return None;
+        }
+
+        if lower == "now()"
+            || lower == "current_timestamp"
+            || lower == "localtimestamp"
+            || lower == "transaction_timestamp()"
+            || lower.starts_with("current_timestamp(")
+        {
+            // the precision must be the same as the column
+            let precision = mysql_col_type
+                .strip_prefix("datetime(")
+                .and_then(|i| i.strip_suffix(')'))
+                .unwrap_or("0");
+            let default = if precision == "0" {
+                "CURRENT_TIMESTAMP".to_string()
+            } else {
+                format!("CURRENT_TIMESTAMP({})", precision)
+            };
+            return Some(ColumnDefault::Expression(default));
+        }
+
+        if lower == "current_date" {
+            return Some(ColumnDefault::Expression("(CURRENT_DATE)".into()));
+        }
+
+        if lower == "true" || lower == "false" {
+            let default = if lower == "true" { "1" } else { "0" };
+            return Some(ColumnDefault::Literal(default.into()));
+        }
+
+        let literal = if value.starts_with('\'') {
+            // 'it''s'::text
+            let end = value.rfind('\'').unwrap_or(0);
+            if end == 0 {
+                None
+            } else {
+                Some(value[1..end].replace("''", "'"))
+            }
+        } else if value.trim_start_matches('-').parse::<f64>().is_ok() {
+            Some(value.to_string())
+        } else {
+            None
+        };
+
+        match literal {
+            Some(v) if mysql_col_type.starts_with("bit") => {
+                Some(ColumnDefault::Literal(format!("b'{}'", v)))
+            }
+            Some(v) if no_literal => Some(ColumnDefault::Expression(format!(
+                "('{}')",
+                v.replace('\'', "''")
+            ))),
+            Some(v) => Some(ColumnDefault::Literal(v)),
+            None => {
+                log_warn!(
+                    "default value: {} of column: {}.{}.{} not migrated",
+                    v,
+                    db,
+                    tb,
+                    column.column_name
+                );
+                None
+            }
+        }
+    }
+
+    fn convert_index(db: &str, tb: &str, index: Index) -> Option<Index> {
+        // CREATE UNIQUE INDEX idx_1 ON public.tb_1 USING btree (col_1, col_2 DESC) WHERE (col_1 > 0)
+        let definition = index.definition.as_str();
+        let method = definition
+            .split(" USING ")
+            .nth(1)
+            .and_then(|i| i.split_whitespace().next())
+            .unwrap_or("btree");
+        let unsupported = if method != "btree" && method != "hash" {
+            Some(format!("method: {}", method))
+        } else if definition.contains(" WHERE ") {
+            Some("partial index".to_string())
+        } else if definition.contains(" INCLUDE ") {
+            Some("covering index".to_string())
+        } else {
+            None
+        };
+
+        let cols = Self::parse_cols(definition.split(" USING ").nth(1).unwrap_or(definition));
+        let unsupported = unsupported.or_else(|| {
+            cols.iter()
+                .any(|i| i.is_empty() || i.contains('('))
+                .then(|| "expression index".to_string())
+        });
+        if let Some(reason) = unsupported {
+            log_warn!(
+                "index: {} of table: {}.{} not migrated, {} not supported in MySQL",
+                index.index_name,
+                db,
+                tb,
+                reason
+            );
+            return None;
+        }
+
+        let columns = cols
+            .into_iter()
+            .enumerate()
+            .map(|(i, col)| IndexColumn {
+                column_name: col,
+                seq_in_index: i as u32 + 1,
+            })
+            .collect();
+        Some(Index {
+            database_name: db.to_string(),
+            table_name: tb.to_string(),
+            index_name: index.index_name,
+            index_kind: if index.index_kind == IndexKind::Unique {
+                IndexKind::Unique
+            } else {
+                IndexKind::Unknown
+            },
+            // InnoDB only supports btree
+            index_type: IndexType::Btree,
+            columns,
+            ..Default::default()
+        })
+    }
+
+    fn convert_constraint(
+        src_schema: &str,
+        db: &str,
+        tb: &str,
+        constraint: Constraint,
+    ) -> Option<Constraint> {
+        let definition = match constraint.constraint_type {
+            // primary key is created with table, unique constraints are created by their indexes
+            ConstraintType::Primary | ConstraintType::Unique => return None,
+
+            // CHECK ((age > 0)) -> ((age > 0))
+            ConstraintType::Check if !constraint.definition.contains("::") => constraint
+                .definition
+                .trim_start_matches("CHECK")
+                .trim()
+                .trim_end_matches(" NOT VALID")
+                .replace('"', "`"),
+
+            // FOREIGN KEY (parent_id) REFERENCES parent(id) ON DELETE CASCADE
+            ConstraintType::Foregin => {
+                let definition = constraint
+                    .definition
+                    .trim_start_matches("FOREIGN KEY")
+                    .trim()
+                    .trim_end_matches(" NOT VALID");
+                let (cols, references) = definition.split_once(" REFERENCES ")?;
+                let (ref_tb, ref_rest) = references.split_once('(')?;
+                let ref_tb = match ref_tb.split_once('.') {
+                    Some((schema, ref_tb)) if schema.trim_matches('"') != src_schema => {
+                        format!(
+                            "`{}`.`{}`",
+                            schema.trim_matches('"'),
+                            ref_tb.trim_matches('"')
+                        )
+                    }
+                    Some((_, ref_tb)) => format!("`{}`.`{}`", db, ref_tb.trim_matches('"')),
+                    None => format!("`{}`.`{}`", db, ref_tb.trim_matches('"')),
+                };
+                format!("{} REFERENCES {}({}", cols, ref_tb, ref_rest).replace('"', "`")
+            }
+
+            _ => {
+                log_warn!(
+                    "constraint: {} of table: {}.{} not migrated, definition: {}",
+                    constraint.constraint_name,
+                    db,
+                    tb,
+                    constraint.definition
+                );
+                return None;
+            }
+        };
+
+        Some(Constraint {
+            database_name: db.to_string(),
+            schema_name: String::new(),
+            table_name: tb.to_string(),
+            constraint_name: constraint.constraint_name,
+            constraint_type: constraint.constraint_type,
+            definition,
+        })
+    }
+
+    /// PRIMARY KEY (id, "Name") -> [id, Name]
+    fn parse_cols(definition: &str) -> Vec<String> {
+        let (Some(start), Some(end)) = (definition.find('('), definition.rfind(')')) else {
+            return vec![];
+        };
+
+        let mut cols = Vec::new();
+        let mut col = String::new();
+        let (mut in_quote, mut depth) = (false, 0);
+        for c in definition[start + 1..end].chars() {
+            match c {
+                '"' => in_quote = !in_quote,
+                '(' if !in_quote => {
+                    depth += 1;
+                    col.push(c);
+                }
+                ')' if !in_quote => {
+                    depth -= 1;
+                    col.push(c);
+                }
+                ',' if !in_quote && depth == 0 => cols.push(std::mem::take(&mut col)),
+                _ => col.push(c),
+            }
+        }
+        cols.push(col);
+
+        // id DESC, name COLLATE "C" -> id, name
+        cols.into_iter()
+            .map(|i| {
+                let i = i.trim();
+                match i.find(' ') {
+                    Some(pos) if !i.contains('(') => i[..pos].to_string(),
+                    _ => i.to_string(),
+                }
+            })
+            .collect()
+    }
+
+    fn is_integer(mysql_col_type: &str) -> bool {
+        ["smallint", "int", "bigint"].contains(&mysql_col_type)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_mysql_col_type() {
+        let cases = [
+            ("smallint", "smallint"),
+            ("integer", "int"),
+            ("bigint", "bigint"),
+            ("real", "float"),
+            ("double precision", "double"),
+            ("numeric(10,2)", "decimal(10,2)"),
+            ("numeric", "decimal(65,30)"),
+            ("boolean", "tinyint(1)"),
+            ("character(10)", "char(10)"),
+            ("character varying(100)", "varchar(100)"),
+            ("character varying", "longtext"),
+            ("text", "longtext"),
+            ("bytea", "longblob"),
+            ("timestamp without time zone", "datetime(6)"),
+            ("timestamp(3) without time zone", "datetime(3)"),
+            ("timestamp(0) with time zone", "datetime(0)"),
+            ("time(2) without time zone", "time(2)"),
+            ("interval", "varchar(64)"),
+            ("uuid", "char(36)"),
+            ("inet", "varchar(43)"),
+            ("jsonb", "json"),
+            ("integer[]", "json"),
+            ("character varying(10)[]", "json"),
+            ("bit(8)", "bit(8)"),
+            ("point", "longtext"),
+        ];
+        for (pg_col_type, mysql_col_type) in cases {
+            assert_eq!(
+                PgToMysqlConverter::get_mysql_col_type(pg_col_type),
+                mysql_col_type
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_cols() {
+        assert_eq!(
+            PgToMysqlConverter::parse_cols(r#"PRIMARY KEY (id, "Name")"#),
+            vec!["id", "Name"]
+        );
+        assert_eq!(
+            PgToMysqlConverter::parse_cols("USING btree (id DESC, lower(name))"),
+            vec!["id", "lower(name)"]
+        );
+    }
+
+    #[test]
+    fn test_convert_constraint() {
+        let constraint = Constraint {
+            database_name: String::new(),
+            schema_name: "public".into(),
+            table_name: "child".into(),
+            constraint_name: "fk_1".into(),
+            constraint_type: ConstraintType::Foregin,
+            definition: "FOREIGN KEY (parent_id) REFERENCES parent(id) ON DELETE CASCADE".into(),
+        };
+        let constraint =
+            PgToMysqlConverter::convert_constraint("public", "dst_db", "child", constraint)
+                .unwrap();
+        assert_eq!(
+            constraint.definition,
+            "(parent_id) REFERENCES `dst_db`.`parent`(id) ON DELETE CASCADE"
+        );
+    }
+}
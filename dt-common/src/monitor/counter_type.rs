@@ -29,6 +29,11 @@ pub enum CounterType {
     // no window counter
     #[strum(serialize = "sinked_count")]
     SinkedCount,
+    // bytes of wal retained by the pg replication slot, set by the latest check
+    #[strum(serialize = "slot_restart_lag_bytes")]
+    SlotRestartLagBytes,
+    #[strum(serialize = "slot_confirmed_flush_lag_bytes")]
+    SlotConfirmedFlushLagBytes,
 }
 
 #[derive(EnumString, IntoStaticStr, Display, PartialEq, Eq, Hash, Clone)]
@@ -66,7 +71,9 @@ impl CounterType {
             | Self::BufferSize
             | Self::DataBytes
             | Self::RecordSize => WindowType::TimeWindow,
-            Self::SinkedCount => WindowType::NoWindow,
+            Self::SinkedCount | Self::SlotRestartLagBytes | Self::SlotConfirmedFlushLagBytes => {
+                WindowType::NoWindow
+            }
         }
     }
 
@@ -93,6 +93,13 @@ impl Monitor {
         self.add_counter_internal(counter_type, value, 1)
     }
 
+    /// For no window counters only, overwrites the value instead of accumulating it
+    pub fn set_counter(&mut self, counter_type: CounterType, value: usize) -> &mut Self {
+        self.no_window_counters
+            .insert(counter_type, Counter::new(value, 1));
+        self
+    }
+
     fn add_counter_internal(
         &mut self,
         counter_type: CounterType,
@@ -28,6 +28,17 @@ impl SqlUtil {
         result
     }
 
+    /// Always escapes the token, escape chars inside are doubled, e.g. a"b -> "a""b"
+    pub fn quote_identifier(token: &str, db_type: &DbType) -> String {
+        match Self::get_escape_pairs(db_type).first() {
+            Some((left, right)) => {
+                let doubled = format!("{}{}", right, right);
+                format!("{}{}{}", left, token.replace(*right, &doubled), right)
+            }
+            None => token.to_string(),
+        }
+    }
+
     pub fn unescape(token: &str, escape_pair: &(char, char)) -> String {
         if !Self::is_escaped(token, escape_pair) {
             return token.to_string();
@@ -161,6 +172,18 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_quote_identifier() {
+        assert_eq!(SqlUtil::quote_identifier("tb_1", &DbType::Mysql), "`tb_1`");
+        assert_eq!(SqlUtil::quote_identifier("a`b", &DbType::Mysql), "`a``b`");
+        assert_eq!(SqlUtil::quote_identifier("`a`", &DbType::Mysql), "```a```");
+        assert_eq!(
+            SqlUtil::quote_identifier(r#"a"b"#, &DbType::Pg),
+            r#""a""b""#
+        );
+        assert_eq!(SqlUtil::quote_identifier("a'b", &DbType::Pg), r#""a'b""#);
+    }
+
     #[test]
     fn test_check_valid_token_with_escapes() {
         let db_type = DbType::Mysql;
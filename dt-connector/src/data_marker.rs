@@ -1,13 +1,19 @@
 use std::collections::HashSet;
 
+use anyhow::bail;
 use dt_common::{
-    config::{config_enums::DbType, data_marker_config::DataMarkerConfig},
+    config::{
+        config_enums::{DataMarkerType, DbType},
+        data_marker_config::DataMarkerConfig,
+    },
+    error::Error,
     meta::{dt_data::DtData, redis::redis_entry::RedisEntry},
 };
 
 #[derive(Debug, Clone, Default)]
 pub struct DataMarker {
     pub db_type: DbType,
+    pub marker_type: DataMarkerType,
     pub topo_name: String,
     pub topo_nodes: Vec<String>,
     pub src_node: String,
@@ -26,6 +32,7 @@ pub struct DataMarker {
 }
 
 const DATA_ORIGIN_NODE: &str = "data_origin_node";
+const REPLICATION_ORIGIN_PREFIX: &str = "ape_dts";
 
 impl DataMarker {
     pub fn from_config(config: &DataMarkerConfig, db_type: &DbType) -> anyhow::Result<Self> {
@@ -43,6 +50,7 @@ impl DataMarker {
 
         let mut me = Self {
             db_type: db_type.clone(),
+            marker_type: config.marker_type.clone(),
             topo_name: config.topo_name.clone(),
             topo_nodes,
             src_node: config.src_node.clone(),
@@ -52,8 +60,15 @@ impl DataMarker {
             ..Default::default()
         };
 
-        match *db_type {
-            DbType::Mysql | DbType::Pg | DbType::Mongo => {
+        match (&config.marker_type, db_type) {
+            (DataMarkerType::ReplicationOrigin, DbType::Pg) => {}
+            (DataMarkerType::ReplicationOrigin, _) => {
+                bail! {Error::ConfigError(format!(
+                    "data marker type: replication_origin not supported for db type: {}",
+                    db_type
+                ))}
+            }
+            (_, DbType::Mysql | DbType::Pg | DbType::Mongo) => {
                 let marker_info: Vec<&str> = config.marker.split('.').collect();
                 me.marker_schema = marker_info[0].to_string();
                 me.marker_tb = marker_info[1].to_string();
@@ -125,4 +140,32 @@ impl DataMarker {
     pub fn filter(&self) -> bool {
         self.filter
     }
+
+    pub fn is_replication_origin(&self) -> bool {
+        self.marker_type == DataMarkerType::ReplicationOrigin
+    }
+
+    /// Prefix of the replication origins used by sinker sessions writing data from data_origin_node,
+    /// E.g. "ape_dts:topo1:node1:", the sinker appends a sequence to make it unique per session.
+    pub fn get_replication_origin_prefix(&self) -> String {
+        format!(
+            "{}:{}:{}:",
+            REPLICATION_ORIGIN_PREFIX, self.topo_name, self.data_origin_node
+        )
+    }
+
+    pub fn refresh_by_replication_origin(&mut self, origin_name: &str) {
+        // origins not created by this topology are treated as local changes of src_node
+        let topo_prefix = format!("{}:{}:", REPLICATION_ORIGIN_PREFIX, self.topo_name);
+        if let Some((node, _seq)) = origin_name
+            .strip_prefix(&topo_prefix)
+            .and_then(|i| i.rsplit_once(':'))
+        {
+            self.data_origin_node = node.to_string();
+        }
+
+        self.filter = self.ignore_nodes.contains(&self.data_origin_node)
+            || !self.do_nodes.contains(&self.data_origin_node);
+        self.reseted = false;
+    }
 }
@@ -0,0 +1,247 @@
+use std::{
+    fs::File,
+    io::{BufRead, BufReader, Read},
+};
+
+use anyhow::bail;
+use dt_common::{config::config_enums::FileFormat, error::Error};
+use parquet::{
+    file::reader::{ChunkReader, SerializedFileReader},
+    record::{reader::RowIter, Field, Row},
+};
+use serde_json::Value;
+
+/// Values of a row in file, as text, null values are None
+pub type FileRow = Vec<(String, Option<String>)>;
+
+/// Rows decoded lazily from a file
+pub type FileRowIter = Box<dyn Iterator<Item = anyhow::Result<FileRow>> + Send>;
+
+/// Decodes a file into rows, values are kept as text and typed later by the target table,
+/// binary values are expected to be hex, the same as written by FileSinker
+pub struct FileDecoder {
+    pub file_format: FileFormat,
+    pub csv_delimiter: u8,
+    pub csv_has_header: bool,
+}
+
+impl FileDecoder {
+    /// Rows are decoded while iterating, so the file is never loaded into memory as a whole,
+    /// csv_cols: names of csv columns if the file has no header
+    pub fn decode(&self, file: File, csv_cols: &[String]) -> anyhow::Result<FileRowIter> {
+        match self.file_format {
+            FileFormat::Csv => self.decode_csv(file, csv_cols),
+            FileFormat::Jsonl => Ok(Self::decode_jsonl(file)),
+            FileFormat::Parquet => Self::decode_parquet(file),
+        }
+    }
+
+    fn decode_csv<R: Read + Send + 'static>(
+        &self,
+        reader: R,
+        csv_cols: &[String],
+    ) -> anyhow::Result<FileRowIter> {
+        let mut reader = csv::ReaderBuilder::new()
+            .delimiter(self.csv_delimiter)
+            .has_headers(self.csv_has_header)
+            .from_reader(BufReader::new(reader));
+        let cols: Vec<String> = if self.csv_has_header {
+            reader
+                .headers()?
+                .iter()
+                .map(|col| col.to_string())
+                .collect()
+        } else {
+            csv_cols.to_vec()
+        };
+
+        let rows = reader.into_records().map(move |record| {
+            let record = record?;
+            if record.len() != cols.len() {
+                bail! {Error::Unexpected(format!(
+                    "csv record has {} fields, but {} columns expected, line: {:?}",
+                    record.len(),
+                    cols.len(),
+                    record.position().map(|p| p.line())
+                ))}
+            }
+
+            // null is written as empty field
+            Ok(cols
+                .iter()
+                .zip(record.iter())
+                .map(|(col, v)| (col.clone(), (!v.is_empty()).then(|| v.to_string())))
+                .collect())
+        });
+        Ok(Box::new(rows))
+    }
+
+    fn decode_jsonl<R: Read + Send + 'static>(reader: R) -> FileRowIter {
+        let rows = BufReader::new(reader)
+            .lines()
+            .filter(|line| !line.as_ref().is_ok_and(|line| line.trim().is_empty()))
+            .map(|line| Self::decode_json_line(&line?));
+        Box::new(rows)
+    }
+
+    fn decode_json_line(line: &str) -> anyhow::Result<FileRow> {
+        let doc: serde_json::Map<String, Value> = serde_json::from_str(line)?;
+        Ok(doc
+            .into_iter()
+            .map(|(col, v)| {
+                let v = match v {
+                    Value::Null => None,
+                    Value::String(v) => Some(v),
+                    // numbers, bools, and json objects / arrays for json columns
+                    _ => Some(v.to_string()),
+                };
+                (col, v)
+            })
+            .collect())
+    }
+
+    fn decode_parquet<R: ChunkReader + 'static>(reader: R) -> anyhow::Result<FileRowIter> {
+        let reader = SerializedFileReader::new(reader)?;
+        // row groups are read one by one while iterating
+        let rows = RowIter::from_file_into(Box::new(reader))
+            .map(|record| Ok(Self::decode_parquet_row(record?)));
+        Ok(Box::new(rows))
+    }
+
+    fn decode_parquet_row(record: Row) -> FileRow {
+        record
+            .get_column_iter()
+            .map(|(col, v)| {
+                let v = match v {
+                    Field::Null => None,
+                    Field::Str(v) => Some(v.clone()),
+                    Field::Bytes(v) => Some(hex::encode(v.data())),
+                    _ => Some(v.to_string()),
+                };
+                (col.clone(), v)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use arrow_schema::{DataType, Field, Schema};
+    use bytes::Bytes;
+    use dt_common::meta::col_value::ColValue;
+
+    use super::*;
+    use crate::sinker::file::file_encoder::FileEncoder;
+
+    fn get_decoder(file_format: FileFormat, csv_has_header: bool) -> FileDecoder {
+        FileDecoder {
+            file_format,
+            csv_delimiter: b',',
+            csv_has_header,
+        }
+    }
+
+    fn decode_all(rows: FileRowIter) -> anyhow::Result<Vec<FileRow>> {
+        rows.collect()
+    }
+
+    #[test]
+    fn test_decode_csv() {
+        let data = "id,name\n1,\"a,b\"\n2,\n".as_bytes();
+        let rows = decode_all(
+            get_decoder(FileFormat::Csv, true)
+                .decode_csv(data, &[])
+                .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            rows,
+            vec![
+                vec![
+                    ("id".to_string(), Some("1".to_string())),
+                    ("name".to_string(), Some("a,b".to_string())),
+                ],
+                vec![
+                    ("id".to_string(), Some("2".to_string())),
+                    ("name".to_string(), None)
+                ],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_decode_csv_without_header() {
+        let csv_cols = vec!["id".to_string(), "name".to_string()];
+        let decoder = get_decoder(FileFormat::Csv, false);
+        let rows = decode_all(decoder.decode_csv("1,a\n".as_bytes(), &csv_cols).unwrap()).unwrap();
+        assert_eq!(
+            rows,
+            vec![vec![
+                ("id".to_string(), Some("1".to_string())),
+                ("name".to_string(), Some("a".to_string())),
+            ]]
+        );
+
+        // columns mismatch
+        let rows = decoder
+            .decode_csv("1,a\n1,a,b\n".as_bytes(), &csv_cols)
+            .unwrap();
+        assert!(decode_all(rows).is_err());
+    }
+
+    #[test]
+    fn test_decode_jsonl() {
+        let data = "{\"id\":1,\"name\":null,\"doc\":{\"k\":\"v\"}}\n\n".as_bytes();
+        let mut rows = decode_all(FileDecoder::decode_jsonl(data)).unwrap();
+        // key order depends on serde_json features
+        rows[0].sort();
+        assert_eq!(
+            rows,
+            vec![vec![
+                ("doc".to_string(), Some("{\"k\":\"v\"}".to_string())),
+                ("id".to_string(), Some("1".to_string())),
+                ("name".to_string(), None),
+            ]]
+        );
+    }
+
+    #[test]
+    fn test_decode_parquet() {
+        // files written by FileSinker
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("id", DataType::Int32, true),
+            Field::new("name", DataType::Utf8, true),
+            Field::new("bin", DataType::Binary, true),
+        ]));
+        let col_values = vec![
+            vec![
+                ColValue::Long(1),
+                ColValue::String("a".into()),
+                ColValue::Blob(vec![1, 2]),
+            ],
+            vec![ColValue::Long(2), ColValue::None, ColValue::None],
+        ];
+        let mut encoder = FileEncoder::new(&FileFormat::Parquet, schema.clone(), b',').unwrap();
+        encoder.encode(&schema, &col_values).unwrap();
+        let data = encoder.finish().unwrap();
+
+        let rows = decode_all(FileDecoder::decode_parquet(Bytes::from(data)).unwrap()).unwrap();
+        assert_eq!(
+            rows,
+            vec![
+                vec![
+                    ("id".to_string(), Some("1".to_string())),
+                    ("name".to_string(), Some("a".to_string())),
+                    ("bin".to_string(), Some("0102".to_string())),
+                ],
+                vec![
+                    ("id".to_string(), Some("2".to_string())),
+                    ("name".to_string(), None),
+                    ("bin".to_string(), None),
+                ],
+            ]
+        );
+    }
+}
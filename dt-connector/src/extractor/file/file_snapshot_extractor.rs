@@ -0,0 +1,472 @@
+use std::{collections::HashMap, path::Path};
+
+use anyhow::{bail, Context};
+use async_trait::async_trait;
+use dt_common::{
+    config::s3_config::S3Config,
+    error::Error,
+    log_info,
+    meta::{
+        adaptor::{
+            mysql_col_value_convertor::MysqlColValueConvertor,
+            pg_col_value_convertor::PgColValueConvertor,
+        },
+        col_value::ColValue,
+        mysql::{mysql_col_type::MysqlColType, mysql_tb_meta::MysqlTbMeta},
+        pg::{pg_tb_meta::PgTbMeta, pg_value_type::PgValueType},
+        position::Position,
+        rdb_meta_manager::RdbMetaManager,
+        row_data::RowData,
+        row_type::RowType,
+    },
+};
+use rusoto_s3::{GetObjectRequest, ListObjectsV2Request, S3Client, S3};
+use tokio::io::AsyncWriteExt;
+
+use crate::{
+    extractor::{base_extractor::BaseExtractor, resumer::snapshot_resumer::SnapshotResumer},
+    Extractor,
+};
+
+use super::file_decoder::{FileDecoder, FileRow};
+
+pub struct FileSnapshotExtractor {
+    // root directory, or the key prefix in bucket if s3_client is set
+    pub url: String,
+    pub decoder: FileDecoder,
+    pub s3_client: Option<S3Client>,
+    pub s3_config: S3Config,
+    // to get columns and types of target tables,
+    // None if target is not mysql / pg compatible, and all values are extracted as strings
+    pub sinker_meta_manager: Option<RdbMetaManager>,
+    pub base_extractor: BaseExtractor,
+    pub resumer: SnapshotResumer,
+    pub batch_size: usize,
+    pub schema: String,
+    pub tb: String,
+}
+
+enum TbMeta {
+    Mysql(MysqlTbMeta),
+    Pg(PgTbMeta),
+    None,
+}
+
+#[async_trait]
+impl Extractor for FileSnapshotExtractor {
+    async fn extract(&mut self) -> anyhow::Result<()> {
+        log_info!(
+            "FileSnapshotExtractor starts, schema: `{}`, tb: `{}`, file_format: {}",
+            self.schema,
+            self.tb,
+            self.decoder.file_format
+        );
+        self.extract_internal().await?;
+        self.base_extractor.wait_task_finish().await
+    }
+
+    async fn close(&mut self) -> anyhow::Result<()> {
+        if let Some(meta_manager) = &self.sinker_meta_manager {
+            meta_manager.close().await?;
+        }
+        Ok(())
+    }
+}
+
+impl FileSnapshotExtractor {
+    async fn extract_internal(&mut self) -> anyhow::Result<()> {
+        let (schema, tb) = (self.schema.clone(), self.tb.clone());
+        let tb_meta = self.get_dst_tb_meta().await?;
+        // src col -> dst col
+        let col_map = self
+            .base_extractor
+            .router
+            .get_col_map(&schema, &tb)
+            .cloned();
+        let csv_cols = Self::get_csv_cols(&tb_meta, &col_map);
+
+        let resume_file = self.resumer.get_resume_value(&schema, &tb, "", false);
+        let resume_offset: usize = resume_file
+            .as_ref()
+            .and_then(|file| self.resumer.get_resume_value(&schema, &tb, file, false))
+            .and_then(|offset| offset.parse().ok())
+            .unwrap_or_default();
+        log_info!(
+            "start extracting from file: {:?}, offset: {}",
+            resume_file,
+            resume_offset
+        );
+
+        let dir = format!("{}/{}/{}", self.url.trim_end_matches('/'), schema, tb);
+        let mut extracted_count = 0;
+        for file in self.list_files(&dir).await? {
+            // files are extracted in order of names, files before the resume file are skipped
+            let mut start_offset = 0;
+            if let Some(resume_file) = &resume_file {
+                if file < *resume_file {
+                    continue;
+                }
+                if file == *resume_file {
+                    start_offset = resume_offset;
+                }
+            }
+
+            let (path, is_temp) = self.get_local_file(&dir, &file).await?;
+            let result = self
+                .extract_file(&path, &file, start_offset, &csv_cols, &tb_meta, &col_map)
+                .await;
+            if is_temp {
+                tokio::fs::remove_file(&path).await?;
+            }
+            extracted_count += result?;
+        }
+
+        log_info!(
+            "end extracting data from {}.{}, all count: {}",
+            schema,
+            tb,
+            extracted_count
+        );
+        Ok(())
+    }
+
+    /// Rows are decoded batch_size at a time, and pushed before decoding the next batch
+    async fn extract_file(
+        &mut self,
+        path: &str,
+        file: &str,
+        start_offset: usize,
+        csv_cols: &[String],
+        tb_meta: &TbMeta,
+        col_map: &Option<HashMap<String, String>>,
+    ) -> anyhow::Result<usize> {
+        log_info!("extracting file: {}, offset: {}", file, start_offset);
+        let local_file =
+            std::fs::File::open(path).with_context(|| format!("failed to open file: {}", path))?;
+        let mut rows = self
+            .decoder
+            .decode(local_file, csv_cols)
+            .with_context(|| format!("failed to decode file: {}", file))?;
+
+        let mut offset = 0;
+        let mut extracted_count = 0;
+        loop {
+            let batch = rows
+                .by_ref()
+                .take(self.batch_size.max(1))
+                .collect::<anyhow::Result<Vec<_>>>()
+                .with_context(|| format!("failed to decode file: {}, offset: {}", file, offset))?;
+            if batch.is_empty() {
+                break;
+            }
+
+            for row in batch {
+                offset += 1;
+                if offset <= start_offset {
+                    continue;
+                }
+
+                let after = self
+                    .build_col_values(row, tb_meta, col_map)
+                    .with_context(|| {
+                        format!("invalid row in file: {}, offset: {}", file, offset - 1)
+                    })?;
+                let row_data = RowData::new(
+                    self.schema.clone(),
+                    self.tb.clone(),
+                    RowType::Insert,
+                    None,
+                    Some(after),
+                );
+                let position = Position::File {
+                    schema: self.schema.clone(),
+                    tb: self.tb.clone(),
+                    file: file.to_string(),
+                    offset: offset as u64,
+                };
+                self.base_extractor.push_row(row_data, position).await?;
+                extracted_count += 1;
+            }
+        }
+        Ok(extracted_count)
+    }
+
+    async fn get_dst_tb_meta(&mut self) -> anyhow::Result<TbMeta> {
+        let (dst_schema, dst_tb) = self
+            .base_extractor
+            .router
+            .get_tb_map(&self.schema, &self.tb);
+        let tb_meta = match &mut self.sinker_meta_manager {
+            Some(RdbMetaManager {
+                mysql_meta_manager: Some(meta_manager),
+                ..
+            }) => TbMeta::Mysql(meta_manager.get_tb_meta(dst_schema, dst_tb).await?.clone()),
+
+            Some(RdbMetaManager {
+                pg_meta_manager: Some(meta_manager),
+                ..
+            }) => TbMeta::Pg(meta_manager.get_tb_meta(dst_schema, dst_tb).await?.clone()),
+
+            _ => TbMeta::None,
+        };
+        Ok(tb_meta)
+    }
+
+    fn get_csv_cols(tb_meta: &TbMeta, col_map: &Option<HashMap<String, String>>) -> Vec<String> {
+        // csv files without header have columns in the same order as the target table
+        let dst_cols = match tb_meta {
+            TbMeta::Mysql(tb_meta) => &tb_meta.basic.cols,
+            TbMeta::Pg(tb_meta) => &tb_meta.basic.cols,
+            TbMeta::None => return Vec::new(),
+        };
+
+        dst_cols
+            .iter()
+            .map(|dst_col| {
+                col_map
+                    .as_ref()
+                    .and_then(|col_map| {
+                        col_map
+                            .iter()
+                            .find(|(_, v)| *v == dst_col)
+                            .map(|(src_col, _)| src_col)
+                    })
+                    .unwrap_or(dst_col)
+                    .clone()
+            })
+            .collect()
+    }
+
+    fn build_col_values(
+        &mut self,
+        row: FileRow,
+        tb_meta: &TbMeta,
+        col_map: &Option<HashMap<String, String>>,
+    ) -> anyhow::Result<HashMap<String, ColValue>> {
+        let mut col_values = HashMap::new();
+        for (col, value) in row {
+            let dst_col = col_map
+                .as_ref()
+                .and_then(|col_map| col_map.get(&col))
+                .unwrap_or(&col);
+
+            // columns not in target table are ignored, such as op_col / ts_col written by FileSinker
+            let col_value = match tb_meta {
+                TbMeta::Mysql(tb_meta) => {
+                    if !tb_meta.basic.cols.contains(dst_col) {
+                        continue;
+                    }
+                    match value {
+                        Some(v) => Self::parse_mysql_value(tb_meta.get_col_type(dst_col)?, v)?,
+                        None => ColValue::None,
+                    }
+                }
+
+                TbMeta::Pg(tb_meta) => {
+                    if !tb_meta.basic.cols.contains(dst_col) {
+                        continue;
+                    }
+                    match value {
+                        Some(v) => self.parse_pg_value(tb_meta, dst_col, v)?,
+                        None => ColValue::None,
+                    }
+                }
+
+                TbMeta::None => value.map_or(ColValue::None, ColValue::String),
+            };
+            col_values.insert(col, col_value);
+        }
+        Ok(col_values)
+    }
+
+    fn parse_mysql_value(col_type: &MysqlColType, value: String) -> anyhow::Result<ColValue> {
+        match col_type {
+            MysqlColType::Binary { .. }
+            | MysqlColType::VarBinary { .. }
+            | MysqlColType::TinyBlob
+            | MysqlColType::MediumBlob
+            | MysqlColType::Blob
+            | MysqlColType::LongBlob => Ok(ColValue::Blob(Self::parse_hex(value))),
+            _ => MysqlColValueConvertor::from_str(col_type, &value),
+        }
+    }
+
+    fn parse_pg_value(
+        &mut self,
+        tb_meta: &PgTbMeta,
+        col: &str,
+        value: String,
+    ) -> anyhow::Result<ColValue> {
+        let col_type = tb_meta.get_col_type(col)?;
+        match col_type.value_type {
+            PgValueType::Bytes if !value.starts_with(r#"\x"#) => {
+                Ok(ColValue::Blob(Self::parse_hex(value)))
+            }
+            // values from other databases may be: 1 / true
+            PgValueType::Boolean => match value.to_lowercase().as_str() {
+                "t" | "true" | "1" | "y" | "yes" | "on" => Ok(ColValue::Bool(true)),
+                "f" | "false" | "0" | "n" | "no" | "off" => Ok(ColValue::Bool(false)),
+                _ => bail! {Error::ExtractorError(format!(
+                    "failed to parse value: {}, as bool",
+                    value
+                ))},
+            },
+            _ => {
+                let meta_manager = self
+                    .sinker_meta_manager
+                    .as_mut()
+                    .and_then(|m| m.pg_meta_manager.as_mut())
+                    .unwrap();
+                PgColValueConvertor::from_str(col_type, &value, meta_manager)
+            }
+        }
+    }
+
+    fn parse_hex(value: String) -> Vec<u8> {
+        // binary values are written as hex by FileSinker, otherwise take them as raw bytes
+        hex::decode(&value).unwrap_or_else(|_| value.into_bytes())
+    }
+
+    /// List names of files in dir, sorted
+    async fn list_files(&self, dir: &str) -> anyhow::Result<Vec<String>> {
+        let suffix = format!(".{}", self.decoder.file_format);
+        let mut files = Vec::new();
+        if let Some(s3_client) = &self.s3_client {
+            let prefix = format!("{}/", dir.trim_start_matches('/'));
+            let mut continuation_token = None;
+            loop {
+                let request = ListObjectsV2Request {
+                    bucket: self.s3_config.bucket.clone(),
+                    max_keys: Some(self.batch_size as i64),
+                    prefix: Some(prefix.clone()),
+                    continuation_token: continuation_token.clone(),
+                    ..Default::default()
+                };
+                let output = s3_client
+                    .list_objects_v2(request)
+                    .await
+                    .with_context(|| format!("failed to list s3 objects, prefix: {}", prefix))?;
+                for object in output.contents.unwrap_or_default() {
+                    if let Some(key) = object.key {
+                        let file = &key[prefix.len()..];
+                        // files in sub directories are not included
+                        if !file.contains('/') && file.ends_with(&suffix) {
+                            files.push(file.to_string());
+                        }
+                    }
+                }
+
+                continuation_token = output.next_continuation_token;
+                if continuation_token.is_none() {
+                    break;
+                }
+            }
+        } else {
+            if !Path::new(dir).exists() {
+                return Ok(files);
+            }
+            let mut entries = tokio::fs::read_dir(dir).await?;
+            while let Some(entry) = entries.next_entry().await? {
+                let file = entry.file_name().to_string_lossy().to_string();
+                // temporary files like *.parquet.tmp are excluded by suffix
+                if entry.file_type().await?.is_file() && file.ends_with(&suffix) {
+                    files.push(file);
+                }
+            }
+        }
+
+        files.sort();
+        Ok(files)
+    }
+
+    /// Path of the file to decode, objects in S3 are downloaded to a temporary file first,
+    /// which should be removed after extraction
+    async fn get_local_file(&self, dir: &str, file: &str) -> anyhow::Result<(String, bool)> {
+        let path = format!("{}/{}", dir, file);
+        let Some(s3_client) = &self.s3_client else {
+            return Ok((path, false));
+        };
+
+        let key = path.trim_start_matches('/').to_string();
+        let request = GetObjectRequest {
+            bucket: self.s3_config.bucket.clone(),
+            key: key.clone(),
+            ..Default::default()
+        };
+        let output = s3_client
+            .get_object(request)
+            .await
+            .with_context(|| format!("failed to get s3 object, key: {}", key))?;
+
+        let temp_path = std::env::temp_dir()
+            .join(format!(
+                "ape_dts_{}_{}",
+                std::process::id(),
+                key.replace('/', "_")
+            ))
+            .to_string_lossy()
+            .to_string();
+        let mut temp_file = tokio::fs::File::create(&temp_path).await?;
+        if let Some(body) = output.body {
+            let mut reader = Box::pin(body.into_async_read());
+            let result = tokio::io::copy(&mut reader, &mut temp_file).await;
+            if let Err(error) = result {
+                tokio::fs::remove_file(&temp_path).await?;
+                return Err(error)
+                    .with_context(|| format!("failed to download s3 object: {}", key));
+            }
+        }
+        temp_file.flush().await?;
+        Ok((temp_path, true))
+    }
+
+    /// List sub directories, which are schemas under url, or tables under url/schema
+    pub async fn list_dirs(
+        s3_client: &Option<S3Client>,
+        s3_config: &S3Config,
+        dir: &str,
+    ) -> anyhow::Result<Vec<String>> {
+        let mut dirs = Vec::new();
+        if let Some(s3_client) = s3_client {
+            let prefix = format!("{}/", dir.trim_matches('/'));
+            let prefix = prefix.trim_start_matches('/').to_string();
+            let mut continuation_token = None;
+            loop {
+                let request = ListObjectsV2Request {
+                    bucket: s3_config.bucket.clone(),
+                    prefix: Some(prefix.clone()),
+                    delimiter: Some("/".into()),
+                    continuation_token: continuation_token.clone(),
+                    ..Default::default()
+                };
+                let output = s3_client
+                    .list_objects_v2(request)
+                    .await
+                    .with_context(|| format!("failed to list s3 objects, prefix: {}", prefix))?;
+                for common_prefix in output.common_prefixes.unwrap_or_default() {
+                    if let Some(sub_prefix) = common_prefix.prefix {
+                        dirs.push(sub_prefix[prefix.len()..].trim_end_matches('/').to_string());
+                    }
+                }
+
+                continuation_token = output.next_continuation_token;
+                if continuation_token.is_none() {
+                    break;
+                }
+            }
+        } else {
+            if !Path::new(dir).exists() {
+                return Ok(dirs);
+            }
+            let mut entries = tokio::fs::read_dir(dir).await?;
+            while let Some(entry) = entries.next_entry().await? {
+                if entry.file_type().await?.is_dir() {
+                    dirs.push(entry.file_name().to_string_lossy().to_string());
+                }
+            }
+        }
+
+        dirs.sort();
+        Ok(dirs)
+    }
+}
@@ -0,0 +1,2 @@
+pub mod file_decoder;
+pub mod file_snapshot_extractor;
@@ -1,85 +1,391 @@
 use crate::extractor::resumer::cdc_resumer::CdcResumer;
+use crate::kafka_util::KafkaUtil;
+use crate::schema_registry_client::SchemaRegistryClient;
 use crate::{extractor::base_extractor::BaseExtractor, Extractor};
-use anyhow::Context;
+use anyhow::{bail, Context};
 use async_trait::async_trait;
-use std::sync::{Arc, Mutex};
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
 
+use dt_common::config::{config_enums::MessageFormat, kafka_security_config::KafkaSecurityConfig};
+use dt_common::error::Error;
 use dt_common::log_info;
-use dt_common::meta::{avro::avro_converter::AvroConverter, position::Position, syncer::Syncer};
+use dt_common::meta::{
+    avro::avro_converter::AvroConverter, dt_data::DtData, json::json_decoder::JsonDecoder,
+    position::Position, syncer::Syncer,
+};
 use rdkafka::{
-    consumer::{Consumer, StreamConsumer},
-    ClientConfig, Message, Offset, TopicPartitionList,
+    consumer::{CommitMode, Consumer, ConsumerContext, Rebalance, StreamConsumer},
+    ClientContext, Message, Offset, TopicPartitionList,
 };
 
+const FETCH_METADATA_TIMEOUT_SECS: u64 = 10;
+const RECV_TIMEOUT_SECS: u64 = 1;
+const SEEK_TIMEOUT_SECS: u64 = 10;
+
 pub struct KafkaExtractor {
     pub base_extractor: BaseExtractor,
     pub url: String,
     pub group: String,
-    pub topic: String,
-    pub partition: i32,
+    pub topics: Vec<String>,
+    // empty: all partitions of the topics
+    pub partitions: Vec<i32>,
+    // partitions are assigned by group rebalancing instead of explicitly
+    pub subscribe: bool,
     pub offset: i64,
     pub ack_interval_secs: u64,
+    pub message_format: MessageFormat,
     pub avro_converter: AvroConverter,
+    pub json_decoder: JsonDecoder,
+    pub schema_registry_client: Option<SchemaRegistryClient>,
+    pub security_config: KafkaSecurityConfig,
     pub syncer: Arc<Mutex<Syncer>>,
     pub resumer: CdcResumer,
+    // next offsets to consume, key: {topic}:{partition}
+    pub offsets: BTreeMap<String, i64>,
+}
+
+/// Records partitions revoked by group rebalancing in subscribe mode,
+/// their offsets are dropped from positions since other group members consume them now
+#[derive(Default)]
+pub struct KafkaConsumerContext {
+    // keys: {topic}:{partition}
+    revoked: Mutex<Vec<String>>,
+}
+
+impl ClientContext for KafkaConsumerContext {}
+
+impl ConsumerContext for KafkaConsumerContext {
+    fn pre_rebalance(&self, rebalance: &Rebalance) {
+        if let Rebalance::Revoke(tpl) = rebalance {
+            let keys: Vec<String> = tpl
+                .elements()
+                .iter()
+                .map(|e| KafkaExtractor::offset_key(e.topic(), e.partition()))
+                .collect();
+            log_info!("KafkaCdcExtractor partitions revoked: {:?}", keys);
+            self.revoked.lock().unwrap().extend(keys);
+        }
+    }
 }
 
+type KafkaConsumer = StreamConsumer<KafkaConsumerContext>;
+
 #[async_trait]
 impl Extractor for KafkaExtractor {
     async fn extract(&mut self) -> anyhow::Result<()> {
-        if let Position::Kafka { offset, .. } = &self.resumer.current_position {
-            self.offset = offset.to_owned();
+        if let Position::Kafka {
+            topic,
+            partition,
+            offset,
+            offsets,
+        } = &self.resumer.current_position
+        {
+            self.offsets = if offsets.is_empty() {
+                // position of older versions, only one partition was consumed
+                BTreeMap::from([(Self::offset_key(topic, *partition), *offset)])
+            } else {
+                offsets.clone()
+            };
         };
 
         log_info!(
-            "KafkaCdcExtractor starts, topic: {}, partition: {}, offset: {}",
-            self.topic,
-            self.partition,
-            self.offset
+            "KafkaCdcExtractor starts, topics: {:?}, partitions: {:?}, subscribe: {}, offset: {}, resume offsets: {:?}",
+            self.topics,
+            self.partitions,
+            self.subscribe,
+            self.offset,
+            self.offsets
         );
-        let consumer = self.create_consumer();
-        self.extract_avro(consumer).await
+        let consumer = self.create_consumer()?;
+        self.extract_internal(consumer).await
     }
 }
 
 impl KafkaExtractor {
-    async fn extract_avro(&mut self, consumer: StreamConsumer) -> anyhow::Result<()> {
+    async fn extract_internal(&mut self, consumer: KafkaConsumer) -> anyhow::Result<()> {
+        let mut last_ack_time = Instant::now();
+        let mut last_committed_offsets = BTreeMap::new();
+        // in subscribe mode, partitions are assigned by the group, the resume offset of a partition
+        // is applied when its first message comes, offsets of other members are never committed
+        let mut resume_offsets = if self.subscribe {
+            std::mem::take(&mut self.offsets)
+        } else {
+            BTreeMap::new()
+        };
         loop {
-            let msg = consumer
-                .recv()
-                .await
-                .with_context(|| format!("KafkaCdcExtractor failed, topic: {}", self.topic))?;
+            if last_ack_time.elapsed().as_secs() >= self.ack_interval_secs {
+                self.commit_offsets(&consumer, &mut last_committed_offsets)?;
+                last_ack_time = Instant::now();
+            }
+
+            // wake up periodically to commit offsets even if no message comes
+            let recv_timeout = Duration::from_secs(RECV_TIMEOUT_SECS);
+            let Ok(msg) = tokio::time::timeout(recv_timeout, consumer.recv()).await else {
+                continue;
+            };
+            let msg = msg
+                .with_context(|| format!("KafkaCdcExtractor failed, topics: {:?}", self.topics))?;
+
+            for key in Self::take_revoked_keys(consumer.context()) {
+                self.offsets.remove(&key);
+                resume_offsets.remove(&key);
+            }
+            let key = Self::offset_key(msg.topic(), msg.partition());
+            if let Some(offset) = resume_offsets.remove(&key) {
+                if msg.offset() != offset {
+                    log_info!(
+                        "KafkaCdcExtractor seek {} to resume offset: {}",
+                        key,
+                        offset
+                    );
+                    consumer.seek(
+                        msg.topic(),
+                        msg.partition(),
+                        Offset::Offset(offset),
+                        Duration::from_secs(SEEK_TIMEOUT_SECS),
+                    )?;
+                    continue;
+                }
+            }
+            self.offsets.insert(key, msg.offset() + 1);
+            let position = Position::Kafka {
+                topic: msg.topic().to_string(),
+                partition: msg.partition(),
+                offset: msg.offset(),
+                offsets: self.offsets.clone(),
+            };
+
+            // tombstone
             if let Some(payload) = msg.payload() {
-                let dt_data = self
-                    .avro_converter
-                    .avro_value_to_dt_data(payload.to_vec())?;
-                let position = Position::Kafka {
-                    topic: self.topic.clone(),
-                    partition: self.partition,
-                    offset: msg.offset(),
-                };
-                self.base_extractor.push_dt_data(dt_data, position).await?;
+                match self.message_format {
+                    // transaction metadata is in json for all message formats
+                    MessageFormat::Avro if !Self::is_json(payload) => {
+                        let dt_data = self
+                            .avro_converter
+                            .avro_value_to_dt_data(payload.to_vec())?;
+                        self.base_extractor.push_dt_data(dt_data, position).await?;
+                    }
+                    _ => self.push_cdc_message(payload, position).await?,
+                }
+            }
+        }
+    }
+
+    /// messages produced by other CDC tools, schema / table are taken from the messages
+    async fn push_cdc_message(&mut self, payload: &[u8], position: Position) -> anyhow::Result<()> {
+        let dt_datas =
+            if self.message_format == MessageFormat::DebeziumAvro && !Self::is_json(payload) {
+                self.decode_debezium_avro(payload).await?
+            } else {
+                self.json_decoder.json_value_to_dt_data(payload)?
+            };
+
+        for dt_data in dt_datas {
+            match dt_data {
+                DtData::Dml { row_data } => {
+                    self.base_extractor
+                        .push_row(row_data, position.clone())
+                        .await?
+                }
+
+                DtData::Ddl { ddl_data } => {
+                    let (db_type, schema, query) =
+                        (ddl_data.db_type, ddl_data.default_schema, ddl_data.query);
+                    // ddls not supported by DdlParser are ignored
+                    if let Ok(ddl_data) = self
+                        .base_extractor
+                        .parse_ddl(&db_type, &schema, &query)
+                        .await
+                    {
+                        self.base_extractor
+                            .push_ddl(ddl_data, position.clone())
+                            .await?
+                    }
+                }
+
+                // END of transaction metadata, data before it can be committed to target
+                DtData::Commit { .. } => {
+                    self.base_extractor
+                        .push_dt_data(dt_data, position.clone())
+                        .await?
+                }
+
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    // avro messages never start with '{': the schema registry magic byte is 0,
+    // and the first field of ape-dts avro is a string with a non-negative length
+    fn is_json(payload: &[u8]) -> bool {
+        payload.first() == Some(&b'{')
+    }
+
+    async fn decode_debezium_avro(&mut self, payload: &[u8]) -> anyhow::Result<Vec<DtData>> {
+        let (schema_id, datum) = SchemaRegistryClient::decode_wire_format(payload)?;
+        if !self.json_decoder.contains_avro_schema(schema_id) {
+            let schema = self
+                .schema_registry_client
+                .as_ref()
+                .context("schema registry client not initialized")?
+                .get_schema(schema_id)
+                .await?;
+            log_info!(
+                "KafkaCdcExtractor got new avro schema, id: {}, schema: {}",
+                schema_id,
+                schema
+            );
+            self.json_decoder.add_avro_schema(schema_id, &schema)?;
+        }
+        self.json_decoder.debezium_avro_to_dt_data(schema_id, datum)
+    }
+
+    fn take_revoked_keys(context: &KafkaConsumerContext) -> Vec<String> {
+        std::mem::take(&mut *context.revoked.lock().unwrap())
+    }
+
+    /// commit offsets of the data sinked to target, so the group can resume from them
+    fn commit_offsets(
+        &self,
+        consumer: &KafkaConsumer,
+        last_committed_offsets: &mut BTreeMap<String, i64>,
+    ) -> anyhow::Result<()> {
+        let offsets = match &self.syncer.lock().unwrap().committed_position {
+            Position::Kafka { offsets, .. } => offsets.clone(),
+            _ => return Ok(()),
+        };
+        if offsets == *last_committed_offsets {
+            return Ok(());
+        }
+
+        // partitions revoked by rebalancing are consumed by other group members now
+        let assignment = consumer.assignment()?;
+        let mut tpl = TopicPartitionList::new();
+        for (key, offset) in offsets.iter() {
+            let (topic, partition) = Self::parse_offset_key(key)?;
+            if assignment.find_partition(topic, partition).is_some() {
+                tpl.add_partition_offset(topic, partition, Offset::Offset(*offset))?;
             }
         }
+        if tpl.count() > 0 {
+            consumer.commit(&tpl, CommitMode::Async)?;
+            log_info!("KafkaCdcExtractor committed offsets: {:?}", offsets);
+        }
+        *last_committed_offsets = offsets;
+        Ok(())
     }
 
-    fn create_consumer(&self) -> StreamConsumer {
-        let mut config = ClientConfig::new();
-        config.set("bootstrap.servers", &self.url);
-        config.set("group.id", &self.group);
-        config.set("auto.offset.reset", "latest");
-        config.set("session.timeout.ms", "10000");
+    fn create_consumer(&self) -> anyhow::Result<KafkaConsumer> {
+        let config = KafkaUtil::create_rdkafka_client_config(
+            &self.url,
+            &self.security_config,
+            &[
+                ("group.id", self.group.as_str()),
+                ("auto.offset.reset", "latest"),
+                ("session.timeout.ms", "10000"),
+                // offsets are committed after data sinked to target
+                ("enable.auto.commit", "false"),
+                // messages of aborted transactions are skipped
+                ("isolation.level", "read_committed"),
+            ],
+        );
+        let consumer: KafkaConsumer = config.create_with_context(KafkaConsumerContext::default())?;
+
+        let topics: Vec<&str> = self.topics.iter().map(|v| v.as_str()).collect();
+        if self.subscribe {
+            consumer.subscribe(&topics)?;
+            return Ok(consumer);
+        }
 
-        let consumer: StreamConsumer = config.create().unwrap();
-        // only support extract data from one topic, one partition
         let mut tpl = TopicPartitionList::new();
-        if self.offset >= 0 {
-            tpl.add_partition_offset(&self.topic, self.partition, Offset::Offset(self.offset))
-                .unwrap();
-        } else {
-            tpl.add_partition(&self.topic, self.partition);
+        for topic in topics {
+            let partitions = if self.partitions.is_empty() {
+                Self::fetch_partitions(&consumer, topic)?
+            } else {
+                self.partitions.clone()
+            };
+
+            for partition in partitions {
+                let offset = match self.offsets.get(&Self::offset_key(topic, partition)) {
+                    Some(offset) => Offset::Offset(*offset),
+                    None if self.offset >= 0 => Offset::Offset(self.offset),
+                    // the committed offset of the group, or the latest if not exists
+                    None => Offset::Stored,
+                };
+                tpl.add_partition_offset(topic, partition, offset)?;
+            }
         }
-        consumer.assign(&tpl).unwrap();
-        consumer
+        consumer.assign(&tpl)?;
+        Ok(consumer)
+    }
+
+    fn fetch_partitions(consumer: &KafkaConsumer, topic: &str) -> anyhow::Result<Vec<i32>> {
+        let metadata = consumer.fetch_metadata(
+            Some(topic),
+            Duration::from_secs(FETCH_METADATA_TIMEOUT_SECS),
+        )?;
+        let partitions: Vec<i32> = metadata
+            .topics()
+            .iter()
+            .filter(|t| t.name() == topic)
+            .flat_map(|t| t.partitions().iter().map(|p| p.id()))
+            .collect();
+        if partitions.is_empty() {
+            bail! {Error::ExtractorError(format!("no partitions found for topic: {}", topic))}
+        }
+        Ok(partitions)
+    }
+
+    fn offset_key(topic: &str, partition: i32) -> String {
+        format!("{}:{}", topic, partition)
+    }
+
+    fn parse_offset_key(key: &str) -> anyhow::Result<(&str, i32)> {
+        // topic names can not contain ':'
+        if let Some((topic, partition)) = key.rsplit_once(':') {
+            if let Ok(partition) = partition.parse() {
+                return Ok((topic, partition));
+            }
+        }
+        bail! {Error::Unexpected(format!("invalid kafka offset key: {}", key))}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_offset_key() {
+        let key = KafkaExtractor::offset_key("test.topic-1", 2);
+        assert_eq!(key, "test.topic-1:2");
+        assert_eq!(
+            KafkaExtractor::parse_offset_key(&key).unwrap(),
+            ("test.topic-1", 2)
+        );
+        assert!(KafkaExtractor::parse_offset_key("test").is_err());
+        assert!(KafkaExtractor::parse_offset_key("test:a").is_err());
+    }
+
+    #[test]
+    fn test_take_revoked_keys() {
+        let context = KafkaConsumerContext::default();
+        let mut tpl = TopicPartitionList::new();
+        tpl.add_partition("topic_1", 1);
+        tpl.add_partition("topic_2", 0);
+        context.pre_rebalance(&Rebalance::Revoke(&tpl));
+        // assigned partitions are not revoked
+        context.pre_rebalance(&Rebalance::Assign(&tpl));
+
+        assert_eq!(
+            KafkaExtractor::take_revoked_keys(&context),
+            vec!["topic_1:1".to_string(), "topic_2:0".to_string()]
+        );
+        assert!(KafkaExtractor::take_revoked_keys(&context).is_empty());
     }
 }
@@ -1,6 +1,7 @@
 pub mod base_check_extractor;
 pub mod base_extractor;
 pub mod extractor_monitor;
+pub mod file;
 pub mod foxlake;
 pub mod kafka;
 pub mod mongo;
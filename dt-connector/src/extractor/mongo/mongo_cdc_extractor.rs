@@ -146,23 +146,25 @@ impl MongoCdcExtractor {
                     // https://www.mongodb.com/docs/manual/reference/operator/update/#update-operators-1
                     // in MongoDB 4.4 and earlier, after_doc contains $set with all new document fields,
                     // after that, after_doc contains diff with only changed fields.
-                    let diff_doc = if let Some(doc) = after_doc.get("diff") {
-                        let doc = doc.as_document().unwrap();
-                        if let Some(i_doc) = doc.get("i") {
-                            doc! {MongoConstants::SET: i_doc.as_document().unwrap()}
-                        } else if let Some(u_doc) = doc.get("u") {
-                            doc! {MongoConstants::SET: u_doc.as_document().unwrap()}
-                        } else if let Some(d_doc) = doc.get("d") {
-                            doc! {MongoConstants::UNSET: d_doc.as_document().unwrap()}
-                        } else {
-                            doc! {}
+                    let o2_doc = o2.unwrap().as_document().unwrap();
+                    let diff_doc = if let Ok(doc) = after_doc.get_document("diff") {
+                        match Self::oplog_diff_to_update_doc(doc) {
+                            Some(diff_doc) => diff_doc,
+                            // array truncated or unknown diff
+                            None => match self.get_full_doc(&ns, o2_doc).await? {
+                                Some(full_doc) => Self::full_doc_to_update_doc(doc, full_doc),
+                                // removed in source, the delete will come later
+                                None => continue,
+                            },
                         }
-                    } else if let Some(set_doc) = after_doc.get(MongoConstants::SET) {
-                        doc! {MongoConstants::SET: set_doc.as_document().unwrap()}
-                    } else if let Some(unset_doc) = after_doc.get(MongoConstants::UNSET) {
-                        doc! {MongoConstants::UNSET: unset_doc.as_document().unwrap()}
                     } else {
-                        doc! {}
+                        let mut diff_doc = Document::new();
+                        for key in [MongoConstants::SET, MongoConstants::UNSET] {
+                            if let Ok(doc) = after_doc.get_document(key) {
+                                diff_doc.insert(key, doc.clone());
+                            }
+                        }
+                        diff_doc
                     };
 
                     if diff_doc.is_empty() {
@@ -180,7 +182,7 @@ impl MongoCdcExtractor {
                     );
                     before.insert(
                         MongoConstants::DOC.to_string(),
-                        ColValue::MongoDoc(o2.unwrap().as_document().unwrap().clone()),
+                        ColValue::MongoDoc(o2_doc.clone()),
                     );
                 }
                 "d" => {
@@ -217,6 +219,106 @@ impl MongoCdcExtractor {
         Ok(())
     }
 
+    /// Converts diff of update op_log (MongoDB 5.0+) to update doc with $set / $unset,
+    /// nested diffs ("s" + field) are converted to paths, e.g.
+    /// {"u": {"name": "a"}, "saddr": {"u": {"city": "b"}}}
+    /// -> {"$set": {"name": "a", "addr.city": "b"}}
+    /// None if the diff can not be expressed by $set / $unset, e.g. an array is truncated.
+    fn oplog_diff_to_update_doc(diff: &Document) -> Option<Document> {
+        let mut set_doc = Document::new();
+        let mut unset_doc = Document::new();
+        if !Self::flatten_oplog_diff(diff, "", &mut set_doc, &mut unset_doc) {
+            return None;
+        }
+
+        let mut update_doc = Document::new();
+        if !set_doc.is_empty() {
+            update_doc.insert(MongoConstants::SET, set_doc);
+        }
+        if !unset_doc.is_empty() {
+            update_doc.insert(MongoConstants::UNSET, unset_doc);
+        }
+        Some(update_doc)
+    }
+
+    fn flatten_oplog_diff(
+        diff: &Document,
+        prefix: &str,
+        set_doc: &mut Document,
+        unset_doc: &mut Document,
+    ) -> bool {
+        // array diff: {"a": true, "u0": value, "s1": {...}, "l": new_len}
+        let is_array = diff.contains_key("a");
+        for (key, value) in diff.iter() {
+            match (key.as_str(), value) {
+                ("a", _) if is_array => {}
+                ("i" | "u", Bson::Document(doc)) => {
+                    for (k, v) in doc.iter() {
+                        set_doc.insert(format!("{}{}", prefix, k), v.clone());
+                    }
+                }
+                ("d", Bson::Document(doc)) => {
+                    for (k, v) in doc.iter() {
+                        unset_doc.insert(format!("{}{}", prefix, k), v.clone());
+                    }
+                }
+                (_, Bson::Document(sub_diff)) if key.len() > 1 && key.starts_with('s') => {
+                    let prefix = format!("{}{}.", prefix, &key[1..]);
+                    if !Self::flatten_oplog_diff(sub_diff, &prefix, set_doc, unset_doc) {
+                        return false;
+                    }
+                }
+                _ if is_array && key.len() > 1 && key.starts_with('u') => {
+                    set_doc.insert(format!("{}{}", prefix, &key[1..]), value.clone());
+                }
+                // "l": array truncated, which can not be expressed by $set / $unset,
+                // and unknown diffs
+                _ => return false,
+            }
+        }
+        true
+    }
+
+    /// $set all fields of the current document, and $unset the top level fields removed by the
+    /// diff if they are not in the current document.
+    fn full_doc_to_update_doc(diff: &Document, mut full_doc: Document) -> Document {
+        full_doc.remove(MongoConstants::ID);
+        let mut unset_doc = Document::new();
+        if let Ok(doc) = diff.get_document("d") {
+            for (k, v) in doc.iter() {
+                if !full_doc.contains_key(k) {
+                    unset_doc.insert(k, v.clone());
+                }
+            }
+        }
+
+        let mut update_doc = doc! {MongoConstants::SET: full_doc};
+        if !unset_doc.is_empty() {
+            update_doc.insert(MongoConstants::UNSET, unset_doc);
+        }
+        update_doc
+    }
+
+    /// Looks up the current document in source by _id of an update op_log,
+    /// the document may be newer than the op_log, the later changes will be applied again.
+    async fn get_full_doc(
+        &self,
+        ns: &Option<&Bson>,
+        o2: &Document,
+    ) -> anyhow::Result<Option<Document>> {
+        let ns = ns.unwrap().as_str().unwrap();
+        let Some((db, tb)) = ns.split_once('.') else {
+            return Ok(None);
+        };
+        let Some(id) = o2.get(MongoConstants::ID) else {
+            return Ok(None);
+        };
+        let collection = self.mongo_client.database(db).collection::<Document>(tb);
+        Ok(collection
+            .find_one(doc! {MongoConstants::ID: id.clone()}, None)
+            .await?)
+    }
+
     fn get_op(doc: &Document) -> String {
         if doc.get("op").is_none() || doc.get("op").unwrap().as_str().is_none() {
             return String::new();
@@ -542,3 +644,56 @@ impl MongoCdcExtractor {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_oplog_diff_to_update_doc() {
+        let diff = doc! {
+            "u": {"name": "a"},
+            "d": {"age": false},
+            "saddr": {"u": {"city": "b"}, "sgeo": {"i": {"lat": 1}}},
+            "stags": {"a": true, "u0": "x", "s1": {"u": {"k": "v"}}},
+        };
+        assert_eq!(
+            MongoCdcExtractor::oplog_diff_to_update_doc(&diff).unwrap(),
+            doc! {
+                "$set": {
+                    "name": "a",
+                    "addr.city": "b",
+                    "addr.geo.lat": 1,
+                    "tags.0": "x",
+                    "tags.1.k": "v",
+                },
+                "$unset": {"age": false},
+            }
+        );
+
+        // array truncated by $pop / $pull
+        assert!(
+            MongoCdcExtractor::oplog_diff_to_update_doc(&doc! {"stags": {"a": true, "l": 1}})
+                .is_none()
+        );
+        assert!(MongoCdcExtractor::oplog_diff_to_update_doc(
+            &doc! {"u": {"name": "a"}, "stags": {"a": true, "u0": "x", "l": 1}}
+        )
+        .is_none());
+        // unknown diff
+        assert!(MongoCdcExtractor::oplog_diff_to_update_doc(&doc! {"x": {"name": "a"}}).is_none());
+    }
+
+    #[test]
+    fn test_full_doc_to_update_doc() {
+        let diff = doc! {"d": {"age": false, "salary": false}, "stags": {"a": true, "l": 1}};
+        let full_doc = doc! {"_id": 1, "name": "a", "salary": 100, "tags": ["x"]};
+        assert_eq!(
+            MongoCdcExtractor::full_doc_to_update_doc(&diff, full_doc),
+            doc! {
+                "$set": {"name": "a", "salary": 100, "tags": ["x"]},
+                "$unset": {"age": false},
+            }
+        );
+    }
+}
@@ -1,5 +1,7 @@
 pub mod pg_cdc_client;
 pub mod pg_cdc_extractor;
+pub mod pg_cdc_stream_buffer;
 pub mod pg_check_extractor;
+pub mod pg_slot_monitor;
 pub mod pg_snapshot_extractor;
 pub mod pg_struct_extractor;
@@ -4,7 +4,7 @@ use dt_common::{log_info, log_warn};
 use postgres_types::PgLsn;
 use tokio_postgres::NoTls;
 use tokio_postgres::SimpleQueryMessage::Row;
-use tokio_postgres::{replication::LogicalReplicationStream, Client};
+use tokio_postgres::{replication::ReplicationStream, Client};
 use url::Url;
 
 pub struct PgCdcClient {
@@ -13,10 +13,11 @@ pub struct PgCdcClient {
     pub pub_name: String,
     pub start_lsn: String,
     pub recreate_slot_if_exists: bool,
+    pub streaming: bool,
 }
 
 impl PgCdcClient {
-    pub async fn connect(&mut self) -> anyhow::Result<(LogicalReplicationStream, String)> {
+    pub async fn connect(&mut self) -> anyhow::Result<(ReplicationStream, String)> {
         let url_info = Url::parse(&self.url)?;
         let host = url_info.host_str().unwrap().to_string();
         let port = format!("{}", url_info.port().unwrap());
@@ -38,12 +39,16 @@ impl PgCdcClient {
         self.start_replication(&client).await
     }
 
+    pub fn get_default_pub_name(slot_name: &str) -> String {
+        format!("{}_publication_for_all_tables", slot_name)
+    }
+
     async fn prepare_slot(&self, client: &Client) -> anyhow::Result<(String, String)> {
         let mut start_lsn = self.start_lsn.clone();
 
         // create publication for all tables if not exists
         let pub_name = if self.pub_name.is_empty() {
-            format!("{}_publication_for_all_tables", self.slot_name)
+            Self::get_default_pub_name(&self.slot_name)
         } else {
             self.pub_name.clone()
         };
@@ -145,18 +150,26 @@ impl PgCdcClient {
     async fn start_replication(
         &mut self,
         client: &Client,
-    ) -> anyhow::Result<(LogicalReplicationStream, String)> {
+    ) -> anyhow::Result<(ReplicationStream, String)> {
         let (pub_name, start_lsn) = self.prepare_slot(client).await?;
 
         // set extra_float_digits to max so no precision will lose
         client.simple_query("SET extra_float_digits=3").await?;
         client.simple_query("SET TIME ZONE 'UTC'").await?;
 
-        // start replication slot
-        let options = format!(
-            r#"("proto_version" '{}', "publication_names" '{}')"#,
-            "1", pub_name
-        );
+        // start replication slot,
+        // in-progress transactions are streamed since proto_version 2 (postgres 14+)
+        let options = if self.streaming {
+            format!(
+                r#"("proto_version" '{}', "streaming" 'on', "publication_names" '{}')"#,
+                "2", pub_name
+            )
+        } else {
+            format!(
+                r#"("proto_version" '{}', "publication_names" '{}')"#,
+                "1", pub_name
+            )
+        };
         let query = format!(
             "START_REPLICATION SLOT {} LOGICAL {} {}",
             self.slot_name, start_lsn, options
@@ -164,7 +177,8 @@ impl PgCdcClient {
         log_info!("execute: {}", query);
 
         let copy_stream = client.copy_both_simple::<bytes::Bytes>(&query).await?;
-        let stream = LogicalReplicationStream::new(copy_stream);
+        // pgoutput messages are parsed by the extractor since streamed ones carry extra xids
+        let stream = ReplicationStream::new(copy_stream);
         Ok((stream, start_lsn))
     }
 }
@@ -13,7 +13,7 @@ use async_trait::async_trait;
 use futures::StreamExt;
 
 use postgres_protocol::message::backend::{
-    DeleteBody, InsertBody,
+    DeleteBody, InsertBody, LogicalReplicationMessage,
     LogicalReplicationMessage::{
         Begin, Commit, Delete, Insert, Origin, Relation, Truncate, Type, Update,
     },
@@ -24,10 +24,13 @@ use postgres_protocol::message::backend::{
 
 use postgres_types::PgLsn;
 use sqlx::{postgres::PgArguments, query::Query, Pool, Postgres};
-use tokio_postgres::replication::LogicalReplicationStream;
+use tokio_postgres::replication::ReplicationStream;
 
 use dt_common::{
-    config::{config_enums::DbType, config_token_parser::ConfigTokenParser},
+    config::{
+        config_enums::{DbType, PgSlotLimitPolicy},
+        config_token_parser::ConfigTokenParser,
+    },
     error::Error,
     log_error, log_info,
     rdb_filter::RdbFilter,
@@ -37,7 +40,12 @@ use dt_common::{
 use crate::{
     close_conn_pool,
     extractor::{
-        base_extractor::BaseExtractor, pg::pg_cdc_client::PgCdcClient,
+        base_extractor::BaseExtractor,
+        pg::{
+            pg_cdc_client::PgCdcClient,
+            pg_cdc_stream_buffer::{PgCdcStreamBuffer, PgStreamMessage},
+            pg_slot_monitor::PgSlotMonitor,
+        },
         resumer::cdc_resumer::CdcResumer,
     },
     Extractor,
@@ -68,6 +76,14 @@ pub struct PgCdcExtractor {
     pub heartbeat_interval_secs: u64,
     pub heartbeat_tb: String,
     pub ddl_meta_tb: String,
+    pub streaming: bool,
+    pub slot_check_interval_secs: u64,
+    pub slot_lag_warn_mb: u64,
+    pub slot_lag_limit_mb: u64,
+    pub slot_limit_policy: PgSlotLimitPolicy,
+    pub drop_slot_on_finish: bool,
+    // messages of in-progress transactions, emitted on Stream Commit or discarded on Stream Abort
+    pub stream_buffer: PgCdcStreamBuffer,
     pub syncer: Arc<Mutex<Syncer>>,
     pub resumer: CdcResumer,
 }
@@ -89,25 +105,53 @@ impl Extractor for PgCdcExtractor {
         };
 
         log_info!(
-            "PgCdcExtractor starts, slot_name: {}, start_lsn: {}, keepalive_interval_secs: {}, heartbeat_interval_secs: {}, heartbeat_tb: {}, ddl_meta_tb: {}",
+            "PgCdcExtractor starts, slot_name: {}, start_lsn: {}, keepalive_interval_secs: {}, heartbeat_interval_secs: {}, heartbeat_tb: {}, ddl_meta_tb: {}, streaming: {}",
             self.slot_name,
             self.start_lsn,
             self.keepalive_interval_secs,
             self.heartbeat_interval_secs,
             self.heartbeat_tb,
             self.ddl_meta_tb,
+            self.streaming,
         );
-        self.extract_internal().await?;
+        if let Err(error) = self.extract_internal().await {
+            // close() is not called if extract fails, only remove the spill dir
+            if let Err(close_error) = self.stream_buffer.close() {
+                log_error!(
+                    "failed to remove spill dir: {}, error: {}",
+                    self.stream_buffer.spill_dir.display(),
+                    close_error
+                );
+            }
+            // keep the slot so the task can resume from the checkpoint after restart
+            return Err(error);
+        }
         self.base_extractor.wait_task_finish().await
     }
 
     async fn close(&mut self) -> anyhow::Result<()> {
+        self.stream_buffer.close()?;
+        self.drop_slot_if_needed().await?;
         self.meta_manager.close().await?;
         close_conn_pool!(self)
     }
 }
 
 impl PgCdcExtractor {
+    async fn drop_slot_if_needed(&self) -> anyhow::Result<()> {
+        if !self.drop_slot_on_finish {
+            return Ok(());
+        }
+
+        PgSlotMonitor::drop_slot(&self.conn_pool, &self.slot_name).await?;
+        // only drop the publication created by ourselves
+        if self.pub_name.is_empty() {
+            let pub_name = PgCdcClient::get_default_pub_name(&self.slot_name);
+            PgSlotMonitor::drop_publication(&self.conn_pool, &pub_name).await?;
+        }
+        Ok(())
+    }
+
     async fn extract_internal(&mut self) -> anyhow::Result<()> {
         let mut cdc_client = PgCdcClient {
             url: self.url.clone(),
@@ -115,6 +159,7 @@ impl PgCdcExtractor {
             slot_name: self.slot_name.clone(),
             start_lsn: self.start_lsn.clone(),
             recreate_slot_if_exists: self.recreate_slot_if_exists,
+            streaming: self.streaming,
         };
         let (stream, actual_start_lsn) = cdc_client.connect().await?;
         tokio::pin!(stream);
@@ -128,6 +173,20 @@ impl PgCdcExtractor {
         // start heartbeat
         self.start_heartbeat(self.base_extractor.shut_down.clone())?;
 
+        // start slot monitor
+        let slot_limit_reached = Arc::new(AtomicBool::new(false));
+        let slot_monitor = PgSlotMonitor {
+            conn_pool: self.conn_pool.clone(),
+            slot_name: self.slot_name.clone(),
+            check_interval_secs: self.slot_check_interval_secs,
+            lag_warn_mb: self.slot_lag_warn_mb,
+            lag_limit_mb: self.slot_lag_limit_mb,
+            limit_policy: self.slot_limit_policy.clone(),
+            monitor: self.base_extractor.monitor.monitor.clone(),
+            limit_reached: slot_limit_reached.clone(),
+        };
+        slot_monitor.start(self.base_extractor.shut_down.clone());
+
         let mut last_tx_end_lsn = actual_start_lsn.clone();
         let mut xid = String::new();
         let mut start_time = Instant::now();
@@ -142,11 +201,20 @@ impl PgCdcExtractor {
         };
         let mut position: Position = get_position("", 0);
 
+        let mut stream_xid: Option<u32> = None;
+
         // refer: https://www.postgresql.org/docs/10/protocol-replication.html to get WAL data details
         loop {
             if self.base_extractor.time_filter.ended {
                 // cdc stream will be dropped automaticaly if postgres receives no keepalive ack
-                return Ok(());
+                return self.stream_buffer.close();
+            }
+
+            if slot_limit_reached.load(Ordering::Acquire) {
+                bail! {Error::ExtractorError(format!(
+                    "replication slot: {} reaches slot_lag_limit_mb or its wal is lost, policy: {}",
+                    self.slot_name, self.slot_limit_policy
+                ))}
             }
 
             if start_time.elapsed().as_secs() >= self.keepalive_interval_secs {
@@ -157,22 +225,61 @@ impl PgCdcExtractor {
             match stream.next().await {
                 Some(Ok(XLogData(body))) => {
                     let data = body.into_data();
-                    match data {
-                        Relation(relation) => {
-                            self.decode_relation(&relation).await?;
+
+                    if self.streaming {
+                        if let Some(message) = PgStreamMessage::parse(&data)? {
+                            match message {
+                                PgStreamMessage::Start { xid, .. } => stream_xid = Some(xid),
+
+                                PgStreamMessage::Stop => stream_xid = None,
+
+                                PgStreamMessage::Abort { xid, subxid } => {
+                                    self.stream_buffer.abort(xid, subxid)?;
+                                }
+
+                                // replay the buffered transaction as if it was not streamed
+                                PgStreamMessage::Commit {
+                                    xid,
+                                    end_lsn,
+                                    timestamp,
+                                    ..
+                                } => {
+                                    position = get_position(&last_tx_end_lsn, timestamp);
+                                    self.begin_tx(timestamp, &position);
+
+                                    let mut reader = self.stream_buffer.take(xid)?;
+                                    while let Some(data) = reader.next()? {
+                                        let message = LogicalReplicationMessage::parse(&data)?;
+                                        self.decode_message(message, &position, &ddl_meta).await?;
+                                    }
+
+                                    last_tx_end_lsn = PgLsn::from(end_lsn).to_string();
+                                    position = get_position(&last_tx_end_lsn, timestamp);
+                                    let commit = DtData::Commit {
+                                        xid: xid.to_string(),
+                                    };
+                                    self.base_extractor
+                                        .push_dt_data(commit, position.clone())
+                                        .await?;
+                                }
+                            }
+                            continue;
+                        }
+
+                        // inside a stream block, buffer the message by the top-level xid
+                        if let Some(xid) = stream_xid {
+                            let (subxid, data) = PgStreamMessage::strip_xid(&data)?;
+                            self.stream_buffer.push(xid, subxid.unwrap_or(xid), data)?;
+                            continue;
                         }
+                    }
 
+                    match LogicalReplicationMessage::parse(&data)? {
                         // do not push Begin into buffer to accelerate sinking
                         Begin(begin) => {
                             position = get_position(&last_tx_end_lsn, begin.timestamp());
                             xid = begin.xid().to_string();
-
-                            let timestamp = begin.timestamp() / 1_000_000 + SECS_FROM_1970_TO_2000;
-                            BaseExtractor::update_time_filter(
-                                &mut self.base_extractor.time_filter,
-                                timestamp as u32,
-                                &position,
-                            );
+                            self.begin_tx(begin.timestamp(), &position);
                         }
 
                         Commit(commit) => {
@@ -184,31 +291,7 @@ impl PgCdcExtractor {
                                 .await?;
                         }
 
-                        Origin(_origin) => {}
-
-                        Truncate(_truncate) => {}
-
-                        Type(_typee) => {}
-
-                        Insert(insert) => {
-                            if self.base_extractor.time_filter.started {
-                                self.decode_insert(&insert, &position, &ddl_meta).await?;
-                            }
-                        }
-
-                        Update(update) => {
-                            if self.base_extractor.time_filter.started {
-                                self.decode_update(&update, &position).await?;
-                            }
-                        }
-
-                        Delete(delete) => {
-                            if self.base_extractor.time_filter.started {
-                                self.decode_delete(&delete, &position).await?;
-                            }
-                        }
-
-                        _ => {}
+                        message => self.decode_message(message, &position, &ddl_meta).await?,
                     }
                 }
 
@@ -231,9 +314,71 @@ impl PgCdcExtractor {
         }
     }
 
+    fn begin_tx(&mut self, timestamp: i64, position: &Position) {
+        let timestamp = timestamp / 1_000_000 + SECS_FROM_1970_TO_2000;
+        BaseExtractor::update_time_filter(
+            &mut self.base_extractor.time_filter,
+            timestamp as u32,
+            position,
+        );
+
+        // Begin is not pushed, reset data marker here since Origin may follow
+        if let Some(data_marker) = &mut self.base_extractor.data_marker {
+            data_marker.reset();
+        }
+    }
+
+    async fn decode_message(
+        &mut self,
+        message: LogicalReplicationMessage,
+        position: &Position,
+        ddl_meta: &[String],
+    ) -> anyhow::Result<()> {
+        match message {
+            Relation(relation) => {
+                self.decode_relation(&relation).await?;
+            }
+
+            // sent for transactions written by sessions with a replication origin,
+            // E.g. our own sinker when data_marker uses replication_origin
+            Origin(origin) => {
+                if let Some(data_marker) = &mut self.base_extractor.data_marker {
+                    if data_marker.is_replication_origin() {
+                        data_marker.refresh_by_replication_origin(origin.name()?);
+                    }
+                }
+            }
+
+            Truncate(_truncate) => {}
+
+            Type(_typee) => {}
+
+            Insert(insert) => {
+                if self.base_extractor.time_filter.started {
+                    self.decode_insert(&insert, position, ddl_meta).await?;
+                }
+            }
+
+            Update(update) => {
+                if self.base_extractor.time_filter.started {
+                    self.decode_update(&update, position).await?;
+                }
+            }
+
+            Delete(delete) => {
+                if self.base_extractor.time_filter.started {
+                    self.decode_delete(&delete, position).await?;
+                }
+            }
+
+            _ => {}
+        }
+        Ok(())
+    }
+
     async fn keep_alive_ack(
         &mut self,
-        stream: &mut Pin<&mut LogicalReplicationStream>,
+        stream: &mut Pin<&mut ReplicationStream>,
         start_lsn: &str,
     ) -> anyhow::Result<()> {
         let lsn: PgLsn =
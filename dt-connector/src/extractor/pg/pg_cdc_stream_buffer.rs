@@ -0,0 +1,335 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fs::{self, File, OpenOptions},
+    io::{BufReader, BufWriter, Read, Write},
+    path::PathBuf,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+use anyhow::bail;
+use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
+use bytes::{Buf, Bytes, BytesMut};
+use dt_common::{error::Error, log_info};
+
+// in-progress transaction messages of pgoutput proto_version >= 2,
+// refer: https://www.postgresql.org/docs/current/protocol-logicalrep-message-formats.html
+const STREAM_START_TAG: u8 = b'S';
+const STREAM_STOP_TAG: u8 = b'E';
+const STREAM_COMMIT_TAG: u8 = b'c';
+const STREAM_ABORT_TAG: u8 = b'A';
+// messages prefixed with the xid of the (sub)transaction when sent inside a stream block
+const XID_PREFIXED_TAGS: &[u8] = b"RYIUDTM";
+
+// buffered bytes of a streamed transaction before it is spilled to disk
+const MAX_MEM_SIZE: usize = 64 * 1024 * 1024;
+
+static SPILL_DIR_SEQ: AtomicU64 = AtomicU64::new(0);
+
+#[derive(Debug, PartialEq)]
+pub enum PgStreamMessage {
+    Start {
+        xid: u32,
+        first_segment: bool,
+    },
+    Stop,
+    Commit {
+        xid: u32,
+        commit_lsn: u64,
+        end_lsn: u64,
+        timestamp: i64,
+    },
+    Abort {
+        xid: u32,
+        subxid: u32,
+    },
+}
+
+impl PgStreamMessage {
+    /// Returns None if buf is not a stream control message
+    pub fn parse(buf: &Bytes) -> anyhow::Result<Option<Self>> {
+        if buf.is_empty() {
+            return Ok(None);
+        }
+
+        let mut reader = &buf[1..];
+        let message = match buf[0] {
+            STREAM_START_TAG => Self::Start {
+                xid: reader.read_u32::<BigEndian>()?,
+                first_segment: reader.read_u8()? == 1,
+            },
+            STREAM_STOP_TAG => Self::Stop,
+            STREAM_COMMIT_TAG => {
+                let xid = reader.read_u32::<BigEndian>()?;
+                // flags, currently unused
+                reader.read_u8()?;
+                Self::Commit {
+                    xid,
+                    commit_lsn: reader.read_u64::<BigEndian>()?,
+                    end_lsn: reader.read_u64::<BigEndian>()?,
+                    timestamp: reader.read_i64::<BigEndian>()?,
+                }
+            }
+            STREAM_ABORT_TAG => Self::Abort {
+                xid: reader.read_u32::<BigEndian>()?,
+                subxid: reader.read_u32::<BigEndian>()?,
+            },
+            _ => return Ok(None),
+        };
+        Ok(Some(message))
+    }
+
+    /// Removes the (sub)transaction xid from a message sent inside a stream block,
+    /// so it can be parsed the same way as messages of non-streamed transactions
+    pub fn strip_xid(buf: &Bytes) -> anyhow::Result<(Option<u32>, Bytes)> {
+        if buf.is_empty() || !XID_PREFIXED_TAGS.contains(&buf[0]) {
+            return Ok((None, buf.clone()));
+        }
+
+        if buf.len() < 5 {
+            bail! {Error::ExtractorError(format!(
+                "invalid streamed message, tag: {}, length: {}",
+                buf[0] as char,
+                buf.len()
+            ))}
+        }
+
+        let xid = (&buf[1..5]).get_u32();
+        let mut stripped = BytesMut::with_capacity(buf.len() - 4);
+        stripped.extend_from_slice(&buf[..1]);
+        stripped.extend_from_slice(&buf[5..]);
+        Ok((Some(xid), stripped.freeze()))
+    }
+}
+
+/// Buffers messages of in-progress transactions by top-level xid until Stream Commit / Abort.
+pub struct PgCdcStreamBuffer {
+    pub spill_dir: PathBuf,
+    txs: HashMap<u32, StreamedTx>,
+}
+
+#[derive(Default)]
+struct StreamedTx {
+    messages: Vec<(u32, Bytes)>,
+    mem_size: usize,
+    spill_file: Option<(PathBuf, BufWriter<File>)>,
+    aborted_subxids: HashSet<u32>,
+}
+
+pub struct StreamedTxReader {
+    messages: std::vec::IntoIter<(u32, Bytes)>,
+    spill_file: Option<(PathBuf, BufReader<File>)>,
+    aborted_subxids: HashSet<u32>,
+}
+
+impl PgCdcStreamBuffer {
+    /// The spill dir is unique to the buffer, since tasks of different clusters
+    /// may use the same slot name on one host
+    pub fn new(slot_name: &str) -> Self {
+        let spill_dir = std::env::temp_dir().join(format!(
+            "ape_dts_{}_{}_{}",
+            slot_name,
+            std::process::id(),
+            SPILL_DIR_SEQ.fetch_add(1, Ordering::Relaxed)
+        ));
+        Self {
+            spill_dir,
+            txs: HashMap::new(),
+        }
+    }
+
+    pub fn push(&mut self, xid: u32, subxid: u32, data: Bytes) -> anyhow::Result<()> {
+        let tx = self.txs.entry(xid).or_default();
+        tx.mem_size += data.len();
+        tx.messages.push((subxid, data));
+
+        if tx.spill_file.is_none() && tx.mem_size >= MAX_MEM_SIZE {
+            fs::create_dir_all(&self.spill_dir)?;
+            let path = self.spill_dir.join(format!("{}.spill", xid));
+            log_info!(
+                "streamed transaction: {} exceeds {} bytes, spill to: {}",
+                xid,
+                MAX_MEM_SIZE,
+                path.display()
+            );
+            let file = OpenOptions::new()
+                .create(true)
+                .write(true)
+                .truncate(true)
+                .open(&path)?;
+            tx.spill_file = Some((path, BufWriter::new(file)));
+        }
+
+        // once spilled, all following messages of the transaction go to disk to keep the order
+        if let Some((_, writer)) = &mut tx.spill_file {
+            for (subxid, data) in tx.messages.drain(..) {
+                writer.write_u32::<BigEndian>(subxid)?;
+                writer.write_u32::<BigEndian>(data.len() as u32)?;
+                writer.write_all(&data)?;
+            }
+            tx.mem_size = 0;
+        }
+        Ok(())
+    }
+
+    pub fn abort(&mut self, xid: u32, subxid: u32) -> anyhow::Result<()> {
+        if xid == subxid {
+            if let Some(tx) = self.txs.remove(&xid) {
+                Self::remove_spill_file(tx.spill_file.map(|i| i.0))?;
+            }
+        } else if let Some(tx) = self.txs.get_mut(&xid) {
+            // messages of the subtransaction are skipped when the transaction is read
+            tx.aborted_subxids.insert(subxid);
+        }
+        Ok(())
+    }
+
+    /// Takes all messages of a committed transaction, in the order they were received
+    pub fn take(&mut self, xid: u32) -> anyhow::Result<StreamedTxReader> {
+        let tx = self.txs.remove(&xid).unwrap_or_default();
+        let spill_file = if let Some((path, mut writer)) = tx.spill_file {
+            writer.flush()?;
+            let file = File::open(&path)?;
+            Some((path, BufReader::new(file)))
+        } else {
+            None
+        };
+
+        Ok(StreamedTxReader {
+            messages: tx.messages.into_iter(),
+            spill_file,
+            aborted_subxids: tx.aborted_subxids,
+        })
+    }
+
+    /// Discards the buffered transactions and removes the spill dir
+    pub fn close(&mut self) -> anyhow::Result<()> {
+        self.txs.clear();
+        if self.spill_dir.exists() {
+            fs::remove_dir_all(&self.spill_dir)?;
+        }
+        Ok(())
+    }
+
+    fn remove_spill_file(path: Option<PathBuf>) -> anyhow::Result<()> {
+        if let Some(path) = path {
+            fs::remove_file(path)?;
+        }
+        Ok(())
+    }
+}
+
+impl StreamedTxReader {
+    pub fn next(&mut self) -> anyhow::Result<Option<Bytes>> {
+        loop {
+            let (subxid, data) = if let Some(next) = self.next_spilled()? {
+                next
+            } else if let Some(next) = self.messages.next() {
+                next
+            } else {
+                return Ok(None);
+            };
+
+            if !self.aborted_subxids.contains(&subxid) {
+                return Ok(Some(data));
+            }
+        }
+    }
+
+    fn next_spilled(&mut self) -> anyhow::Result<Option<(u32, Bytes)>> {
+        let Some((path, reader)) = &mut self.spill_file else {
+            return Ok(None);
+        };
+
+        let subxid = match reader.read_u32::<BigEndian>() {
+            Ok(subxid) => subxid,
+            Err(error) if error.kind() == std::io::ErrorKind::UnexpectedEof => {
+                fs::remove_file(path)?;
+                self.spill_file = None;
+                return Ok(None);
+            }
+            Err(error) => return Err(error.into()),
+        };
+        let len = reader.read_u32::<BigEndian>()? as usize;
+        let mut data = vec![0u8; len];
+        reader.read_exact(&mut data)?;
+        Ok(Some((subxid, Bytes::from(data))))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_stream_message() {
+        let buf = Bytes::from(vec![b'S', 0, 0, 0, 10, 1]);
+        assert_eq!(
+            PgStreamMessage::parse(&buf).unwrap(),
+            Some(PgStreamMessage::Start {
+                xid: 10,
+                first_segment: true
+            })
+        );
+
+        let mut buf = vec![b'c', 0, 0, 0, 10, 0];
+        buf.extend_from_slice(&100u64.to_be_bytes());
+        buf.extend_from_slice(&200u64.to_be_bytes());
+        buf.extend_from_slice(&300i64.to_be_bytes());
+        assert_eq!(
+            PgStreamMessage::parse(&Bytes::from(buf)).unwrap(),
+            Some(PgStreamMessage::Commit {
+                xid: 10,
+                commit_lsn: 100,
+                end_lsn: 200,
+                timestamp: 300
+            })
+        );
+
+        let buf = Bytes::from(vec![b'I', 0, 0, 0, 11, 0, 0, 0, 5]);
+        assert_eq!(PgStreamMessage::parse(&buf).unwrap(), None);
+        let (xid, stripped) = PgStreamMessage::strip_xid(&buf).unwrap();
+        assert_eq!(xid, Some(11));
+        assert_eq!(stripped.as_ref(), &[b'I', 0, 0, 0, 5]);
+    }
+
+    #[test]
+    fn test_stream_buffer() {
+        let mut buffer = PgCdcStreamBuffer::new("test");
+        assert_ne!(buffer.spill_dir, PgCdcStreamBuffer::new("test").spill_dir);
+
+        buffer.push(1, 1, Bytes::from("a")).unwrap();
+        buffer.push(2, 2, Bytes::from("b")).unwrap();
+        buffer.push(1, 3, Bytes::from("c")).unwrap();
+        buffer.push(1, 1, Bytes::from("d")).unwrap();
+        // spill the rest of tx 1
+        buffer
+            .push(1, 1, Bytes::from(vec![b'e'; MAX_MEM_SIZE]))
+            .unwrap();
+        buffer.push(1, 1, Bytes::from("f")).unwrap();
+
+        // subtransaction 3 aborted, tx 2 aborted
+        buffer.abort(1, 3).unwrap();
+        buffer.abort(2, 2).unwrap();
+
+        let mut reader = buffer.take(1).unwrap();
+        let mut messages = vec![];
+        while let Some(data) = reader.next().unwrap() {
+            messages.push(data);
+        }
+        assert_eq!(messages.len(), 4);
+        assert_eq!(messages[0].as_ref(), b"a");
+        assert_eq!(messages[1].as_ref(), b"d");
+        assert_eq!(messages[2].len(), MAX_MEM_SIZE);
+        assert_eq!(messages[3].as_ref(), b"f");
+
+        let mut reader = buffer.take(2).unwrap();
+        assert!(reader.next().unwrap().is_none());
+
+        buffer
+            .push(3, 3, Bytes::from(vec![b'g'; MAX_MEM_SIZE]))
+            .unwrap();
+        assert!(buffer.spill_dir.exists());
+        buffer.close().unwrap();
+        assert!(!buffer.spill_dir.exists());
+    }
+}
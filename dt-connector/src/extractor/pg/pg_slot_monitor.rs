@@ -0,0 +1,284 @@
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc, Mutex,
+};
+
+use dt_common::{
+    config::config_enums::PgSlotLimitPolicy,
+    log_error, log_info, log_warn,
+    monitor::{counter_type::CounterType, monitor::Monitor},
+    utils::time_util::TimeUtil,
+};
+use sqlx::{Pool, Postgres, Row};
+
+const MB: u64 = 1024 * 1024;
+const DROP_SLOT_RETRIES: usize = 10;
+
+/// Tracks the wal retained by the replication slot, since a stalled task may fill the source disk.
+#[derive(Clone)]
+pub struct PgSlotMonitor {
+    pub conn_pool: Pool<Postgres>,
+    pub slot_name: String,
+    pub check_interval_secs: u64,
+    pub lag_warn_mb: u64,
+    pub lag_limit_mb: u64,
+    pub limit_policy: PgSlotLimitPolicy,
+    pub monitor: Arc<Mutex<Monitor>>,
+    // set if the hard limit is reached and the task should stop
+    pub limit_reached: Arc<AtomicBool>,
+}
+
+#[derive(Default)]
+struct SlotStatus {
+    wal_status: String,
+    restart_lag: u64,
+    confirmed_flush_lag: u64,
+}
+
+#[derive(Debug, PartialEq)]
+enum SlotAction {
+    None,
+    Warn,
+    // the limit is reached, only log errors
+    LogLimit,
+    Fail,
+    DropSlot,
+}
+
+impl PgSlotMonitor {
+    pub fn start(&self, shut_down: Arc<AtomicBool>) {
+        if self.check_interval_secs == 0 {
+            log_warn!("slot monitor disabled, slot_check_interval_secs: 0");
+            return;
+        }
+
+        let me = self.clone();
+        tokio::spawn(async move {
+            while !shut_down.load(Ordering::Acquire) && !me.limit_reached.load(Ordering::Acquire) {
+                if let Err(error) = me.check().await {
+                    log_error!("slot: {} check failed, error: {}", me.slot_name, error);
+                }
+                TimeUtil::sleep_millis(1000 * me.check_interval_secs).await;
+            }
+        });
+        log_info!("slot monitor started, slot_name: {}", self.slot_name);
+    }
+
+    pub async fn check(&self) -> anyhow::Result<()> {
+        let status = match self.fetch_status().await? {
+            Some(status) => status,
+            None => {
+                log_error!("slot: {} does not exist", self.slot_name);
+                return Ok(());
+            }
+        };
+
+        self.monitor
+            .lock()
+            .unwrap()
+            .set_counter(
+                CounterType::SlotRestartLagBytes,
+                status.restart_lag as usize,
+            )
+            .set_counter(
+                CounterType::SlotConfirmedFlushLagBytes,
+                status.confirmed_flush_lag as usize,
+            );
+
+        let action = Self::decide(
+            &status,
+            self.lag_warn_mb,
+            self.lag_limit_mb,
+            &self.limit_policy,
+        );
+        match action {
+            SlotAction::None => return Ok(()),
+
+            SlotAction::Warn => {
+                log_warn!(
+                    "slot: {} retains too much wal, restart_lag_bytes: {}, confirmed_flush_lag_bytes: {}, wal_status: {}",
+                    self.slot_name,
+                    status.restart_lag,
+                    status.confirmed_flush_lag,
+                    status.wal_status
+                );
+                return Ok(());
+            }
+
+            _ => log_error!(
+                "slot: {} reaches the limit, restart_lag_bytes: {}, slot_lag_limit_mb: {}, wal_status: {}, policy: {}",
+                self.slot_name,
+                status.restart_lag,
+                self.lag_limit_mb,
+                status.wal_status,
+                self.limit_policy
+            ),
+        }
+
+        match action {
+            SlotAction::Fail => self.limit_reached.store(true, Ordering::Release),
+            SlotAction::DropSlot => {
+                self.limit_reached.store(true, Ordering::Release);
+                Self::drop_slot(&self.conn_pool, &self.slot_name).await?;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn decide(
+        status: &SlotStatus,
+        lag_warn_mb: u64,
+        lag_limit_mb: u64,
+        limit_policy: &PgSlotLimitPolicy,
+    ) -> SlotAction {
+        // wal_status: reserved / extended / unreachable / lost, postgres 13+
+        let lost = status.wal_status == "lost";
+        if lost || lag_limit_mb > 0 && status.restart_lag >= lag_limit_mb * MB {
+            return match limit_policy {
+                PgSlotLimitPolicy::None => SlotAction::LogLimit,
+                PgSlotLimitPolicy::Fail => SlotAction::Fail,
+                PgSlotLimitPolicy::DropSlot => SlotAction::DropSlot,
+            };
+        }
+
+        if lag_warn_mb > 0 && status.restart_lag >= lag_warn_mb * MB
+            || status.wal_status == "unreachable"
+        {
+            return SlotAction::Warn;
+        }
+        SlotAction::None
+    }
+
+    async fn fetch_status(&self) -> anyhow::Result<Option<SlotStatus>> {
+        // use to_jsonb to be compatible with versions without wal_status
+        let sql = format!(
+            "SELECT COALESCE(to_jsonb(s) ->> 'wal_status', '') AS wal_status,
+                COALESCE(pg_wal_lsn_diff(pg_current_wal_lsn(), restart_lsn), 0)::bigint AS restart_lag,
+                COALESCE(pg_wal_lsn_diff(pg_current_wal_lsn(), confirmed_flush_lsn), 0)::bigint AS confirmed_flush_lag
+            FROM pg_catalog.pg_replication_slots s WHERE slot_name = '{}'",
+            self.slot_name
+        );
+
+        let row = sqlx::query(&sql).fetch_optional(&self.conn_pool).await?;
+        Ok(row.map(|row| {
+            let restart_lag: i64 = row.get("restart_lag");
+            let confirmed_flush_lag: i64 = row.get("confirmed_flush_lag");
+            SlotStatus {
+                wal_status: row.get("wal_status"),
+                restart_lag: restart_lag.max(0) as u64,
+                confirmed_flush_lag: confirmed_flush_lag.max(0) as u64,
+            }
+        }))
+    }
+
+    pub async fn drop_slot(conn_pool: &Pool<Postgres>, slot_name: &str) -> anyhow::Result<()> {
+        // an active slot can not be dropped, terminate the walsender first
+        let terminate_sql = format!(
+            "SELECT pg_terminate_backend(active_pid) FROM pg_catalog.pg_replication_slots
+            WHERE slot_name = '{}' AND active_pid IS NOT NULL",
+            slot_name
+        );
+        let drop_sql = format!(
+            "SELECT pg_drop_replication_slot(slot_name) FROM pg_catalog.pg_replication_slots
+            WHERE slot_name = '{}' AND NOT active",
+            slot_name
+        );
+        let exists_sql = format!(
+            "SELECT 1 FROM pg_catalog.pg_replication_slots WHERE slot_name = '{}'",
+            slot_name
+        );
+
+        for _ in 0..DROP_SLOT_RETRIES {
+            sqlx::query(&terminate_sql).execute(conn_pool).await?;
+            sqlx::query(&drop_sql).execute(conn_pool).await?;
+            if sqlx::query(&exists_sql)
+                .fetch_optional(conn_pool)
+                .await?
+                .is_none()
+            {
+                log_info!("slot: {} dropped", slot_name);
+                return Ok(());
+            }
+            TimeUtil::sleep_millis(1000).await;
+        }
+
+        log_error!("failed to drop slot: {}, it is still active", slot_name);
+        Ok(())
+    }
+
+    pub async fn drop_publication(
+        conn_pool: &Pool<Postgres>,
+        pub_name: &str,
+    ) -> anyhow::Result<()> {
+        // not quoted, the same as it was created
+        let sql = format!("DROP PUBLICATION IF EXISTS {}", pub_name);
+        sqlx::query(&sql).execute(conn_pool).await?;
+        log_info!("publication: {} dropped", pub_name);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mock_status(wal_status: &str, restart_lag_mb: u64) -> SlotStatus {
+        SlotStatus {
+            wal_status: wal_status.into(),
+            restart_lag: restart_lag_mb * MB,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_decide_thresholds() {
+        let policy = PgSlotLimitPolicy::Fail;
+        let decide = |status: &SlotStatus, warn_mb, limit_mb| {
+            PgSlotMonitor::decide(status, warn_mb, limit_mb, &policy)
+        };
+
+        assert_eq!(
+            decide(&mock_status("reserved", 9), 10, 100),
+            SlotAction::None
+        );
+        assert_eq!(
+            decide(&mock_status("reserved", 10), 10, 100),
+            SlotAction::Warn
+        );
+        assert_eq!(
+            decide(&mock_status("extended", 99), 10, 100),
+            SlotAction::Warn
+        );
+        assert_eq!(
+            decide(&mock_status("reserved", 100), 10, 100),
+            SlotAction::Fail
+        );
+        // 0 to disable
+        assert_eq!(
+            decide(&mock_status("reserved", 100), 0, 0),
+            SlotAction::None
+        );
+        // versions without wal_status
+        assert_eq!(decide(&mock_status("", 100), 10, 0), SlotAction::Warn);
+        assert_eq!(
+            decide(&mock_status("unreachable", 0), 0, 0),
+            SlotAction::Warn
+        );
+        // lost wal reaches the limit regardless of the lag
+        assert_eq!(decide(&mock_status("lost", 0), 0, 0), SlotAction::Fail);
+    }
+
+    #[test]
+    fn test_decide_policies() {
+        let status = mock_status("reserved", 100);
+        let cases = [
+            (PgSlotLimitPolicy::None, SlotAction::LogLimit),
+            (PgSlotLimitPolicy::Fail, SlotAction::Fail),
+            (PgSlotLimitPolicy::DropSlot, SlotAction::DropSlot),
+        ];
+        for (policy, action) in cases {
+            assert_eq!(PgSlotMonitor::decide(&status, 10, 50, &policy), action);
+        }
+    }
+}
@@ -189,7 +189,11 @@ impl PgSnapshotExtractor {
         if let Some(order_col) = &tb_meta.basic.order_col {
             if has_start_value {
                 let order_col_type = tb_meta.get_col_type(order_col)?;
-                let condition = format!(r#""{}" > $1::{}"#, order_col, order_col_type.alias);
+                let condition = format!(
+                    r#""{}" > $1::{}"#,
+                    order_col,
+                    order_col_type.get_cast_name()
+                );
                 let where_sql =
                     BaseExtractor::get_where_sql(&self.filter, &self.schema, &self.tb, &condition);
                 Ok(format!(
@@ -6,13 +6,15 @@ use std::{
 
 use anyhow::Context;
 use dt_common::{
-    config::{config_enums::ExtractType, task_config::TaskConfig},
-    log_warn,
+    config::{config_enums::ExtractType, sinker_config::SinkerConfig, task_config::TaskConfig},
+    log_info, log_warn,
     meta::position::Position,
     utils::file_util::FileUtil,
 };
 use serde_json::json;
 
+use crate::kafka_util::KafkaUtil;
+
 use super::{CURRENT_POSITION_LOG_FLAG, TAIL_POSITION_COUNT};
 
 #[derive(Clone, Default)]
@@ -69,6 +71,33 @@ impl CdcResumer {
                 );
             }
         }
+
+        // positions committed with kafka transactions take precedence
+        if let SinkerConfig::Kafka {
+            url,
+            security_config,
+            transactional_id,
+            checkpoint_topic,
+            ..
+        } = &task_config.sinker
+        {
+            if !transactional_id.is_empty() {
+                if let Some(position) = KafkaUtil::fetch_checkpoint(
+                    url,
+                    security_config,
+                    checkpoint_topic,
+                    transactional_id,
+                )? {
+                    log_info!(
+                        "resume from kafka checkpoint topic: {}, position: {}",
+                        checkpoint_topic,
+                        position
+                    );
+                    me.checkpoint_position = position.clone();
+                    me.current_position = position;
+                }
+            }
+        }
         Ok(me)
     }
 
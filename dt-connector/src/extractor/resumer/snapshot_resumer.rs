@@ -165,6 +165,17 @@ impl SnapshotResumer {
                 tb_positions.insert((schema, tb, String::new()), s3_meta_file);
             }
 
+            Position::File {
+                schema,
+                tb,
+                file,
+                offset,
+            } => {
+                // the last extracted file of the table, and the row offset in it
+                tb_positions.insert((schema.clone(), tb.clone(), String::new()), file.clone());
+                tb_positions.insert((schema, tb, file), offset.to_string());
+            }
+
             Position::RdbSnapshotFinished { schema, tb, .. } => {
                 self.finished_tbs.insert((schema, tb));
             }
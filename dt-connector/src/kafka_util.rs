@@ -0,0 +1,283 @@
+use std::{str::FromStr, time::Duration};
+
+use anyhow::{bail, Context};
+use dt_common::{
+    config::kafka_security_config::KafkaSecurityConfig, error::Error, meta::position::Position,
+};
+use kafka::client::SecurityConfig;
+use openssl::{
+    pkey::PKey,
+    ssl::{SslConnector, SslFiletype, SslMethod, SslVerifyMode},
+};
+use rdkafka::{
+    admin::{AdminClient, AdminOptions, NewTopic, ResourceSpecifier, TopicReplication},
+    client::DefaultClientContext,
+    consumer::{BaseConsumer, Consumer},
+    error::KafkaError,
+    types::RDKafkaErrorCode,
+    ClientConfig, Message, Offset, TopicPartitionList,
+};
+
+const FETCH_TIMEOUT_SECS: u64 = 10;
+
+pub struct KafkaUtil {}
+
+impl KafkaUtil {
+    /// Client config of librdkafka, the passthrough properties in security config override defaults
+    pub fn create_rdkafka_client_config(
+        url: &str,
+        security_config: &KafkaSecurityConfig,
+        defaults: &[(&str, &str)],
+    ) -> ClientConfig {
+        let mut config = ClientConfig::new();
+        config.set("bootstrap.servers", url);
+        for (key, value) in defaults {
+            config.set(*key, *value);
+        }
+        for (key, value) in security_config.to_rdkafka_properties() {
+            config.set(key, value);
+        }
+        config
+    }
+
+    /// Whether the producer of kafka crate can connect with the security config,
+    /// it supports plaintext and ssl (including client certificates) only
+    pub fn is_supported_by_kafka_crate(security_config: &KafkaSecurityConfig) -> bool {
+        !security_config.is_sasl() && security_config.properties.is_empty()
+    }
+
+    /// Ssl config for the producer of kafka crate, None if ssl is not enabled
+    pub fn create_kafka_ssl_config(
+        security_config: &KafkaSecurityConfig,
+    ) -> anyhow::Result<Option<SecurityConfig>> {
+        if !security_config.is_ssl() {
+            return Ok(None);
+        }
+        if security_config.is_sasl() {
+            bail! {Error::ConfigError(
+                "sasl is not supported by the producer of kafka crate".into()
+            )}
+        }
+
+        let mut builder = SslConnector::builder(SslMethod::tls())?;
+        if !security_config.ssl_ca_location.is_empty() {
+            builder
+                .set_ca_file(&security_config.ssl_ca_location)
+                .with_context(|| {
+                    format!(
+                        "failed to load ssl_ca_location: {}",
+                        security_config.ssl_ca_location
+                    )
+                })?;
+        }
+        if !security_config.ssl_certificate_location.is_empty() {
+            builder
+                .set_certificate_file(&security_config.ssl_certificate_location, SslFiletype::PEM)
+                .with_context(|| {
+                    format!(
+                        "failed to load ssl_certificate_location: {}",
+                        security_config.ssl_certificate_location
+                    )
+                })?;
+        }
+        if !security_config.ssl_key_location.is_empty() {
+            let key = std::fs::read(&security_config.ssl_key_location).with_context(|| {
+                format!(
+                    "failed to read ssl_key_location: {}",
+                    security_config.ssl_key_location
+                )
+            })?;
+            let key = if security_config.ssl_key_password.is_empty() {
+                PKey::private_key_from_pem(&key)?
+            } else {
+                PKey::private_key_from_pem_passphrase(
+                    &key,
+                    security_config.ssl_key_password.as_bytes(),
+                )?
+            };
+            builder.set_private_key(&key)?;
+            builder.check_private_key()?;
+        }
+        builder.set_verify(SslVerifyMode::PEER);
+
+        Ok(Some(
+            SecurityConfig::new(builder.build())
+                .with_hostname_verification(security_config.ssl_verify_hostname),
+        ))
+    }
+
+    /// murmur2 hash, the same as the default partitioner of kafka java client
+    pub fn murmur2(data: &[u8]) -> i32 {
+        const SEED: u32 = 0x9747b28c;
+        const M: u32 = 0x5bd1e995;
+        const R: u32 = 24;
+
+        let length = data.len();
+        let mut h = SEED ^ length as u32;
+        let mut chunks = data.chunks_exact(4);
+        for chunk in chunks.by_ref() {
+            let mut k = u32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+            k = k.wrapping_mul(M);
+            k ^= k >> R;
+            k = k.wrapping_mul(M);
+            h = h.wrapping_mul(M);
+            h ^= k;
+        }
+
+        let tail = chunks.remainder();
+        if tail.len() >= 3 {
+            h ^= (tail[2] as u32) << 16;
+        }
+        if tail.len() >= 2 {
+            h ^= (tail[1] as u32) << 8;
+        }
+        if !tail.is_empty() {
+            h ^= tail[0] as u32;
+            h = h.wrapping_mul(M);
+        }
+
+        h ^= h >> 13;
+        h = h.wrapping_mul(M);
+        h ^= h >> 15;
+        h as i32
+    }
+
+    /// The checkpoint topic is read from the beginning when the task starts, it is created
+    /// with cleanup.policy=compact if not exists, and an existing one must be compacted
+    pub async fn ensure_checkpoint_topic(
+        url: &str,
+        security_config: &KafkaSecurityConfig,
+        checkpoint_topic: &str,
+    ) -> anyhow::Result<()> {
+        let config = Self::create_rdkafka_client_config(url, security_config, &[]);
+        let admin: AdminClient<DefaultClientContext> = config.create()?;
+        let options = AdminOptions::new();
+
+        let results = admin
+            .describe_configs(&[ResourceSpecifier::Topic(checkpoint_topic)], &options)
+            .await?;
+        match results.into_iter().next() {
+            Some(Ok(resource)) => {
+                let policy = resource
+                    .get("cleanup.policy")
+                    .and_then(|entry| entry.value.clone())
+                    .unwrap_or_default();
+                if !policy.split(',').any(|v| v.trim() == "compact") {
+                    bail! {Error::ConfigError(format!(
+                        "cleanup.policy of checkpoint topic: {} should be compact, current: {}",
+                        checkpoint_topic, policy
+                    ))}
+                }
+            }
+
+            Some(Err(RDKafkaErrorCode::UnknownTopicOrPartition)) => {
+                let topic = NewTopic::new(checkpoint_topic, 1, TopicReplication::Fixed(-1))
+                    .set("cleanup.policy", "compact");
+                for result in admin.create_topics(&[topic], &options).await? {
+                    match result {
+                        Ok(_) | Err((_, RDKafkaErrorCode::TopicAlreadyExists)) => {}
+                        Err((topic, code)) => bail! {Error::Unexpected(format!(
+                            "failed to create checkpoint topic: {}, error: {:?}",
+                            topic, code
+                        ))},
+                    }
+                }
+            }
+
+            result => bail! {Error::Unexpected(format!(
+                "failed to describe checkpoint topic: {}, result: {:?}",
+                checkpoint_topic, result
+            ))},
+        }
+        Ok(())
+    }
+
+    /// The last position committed in transactions of the transactional producer, only
+    /// committed messages are read. None if the checkpoint topic or the position does not exist
+    pub fn fetch_checkpoint(
+        url: &str,
+        security_config: &KafkaSecurityConfig,
+        checkpoint_topic: &str,
+        transactional_id: &str,
+    ) -> anyhow::Result<Option<Position>> {
+        let config = Self::create_rdkafka_client_config(
+            url,
+            security_config,
+            &[
+                ("group.id", transactional_id),
+                ("enable.auto.commit", "false"),
+                ("enable.partition.eof", "true"),
+                ("isolation.level", "read_committed"),
+            ],
+        );
+        let consumer: BaseConsumer = config.create()?;
+
+        let timeout = Duration::from_secs(FETCH_TIMEOUT_SECS);
+        let metadata = consumer.fetch_metadata(Some(checkpoint_topic), timeout)?;
+        let mut tpl = TopicPartitionList::new();
+        for topic in metadata
+            .topics()
+            .iter()
+            .filter(|t| t.name() == checkpoint_topic)
+        {
+            for partition in topic.partitions() {
+                let (low, high) =
+                    consumer.fetch_watermarks(checkpoint_topic, partition.id(), timeout)?;
+                if high > low {
+                    tpl.add_partition_offset(checkpoint_topic, partition.id(), Offset::Beginning)?;
+                }
+            }
+        }
+        if tpl.count() == 0 {
+            return Ok(None);
+        }
+
+        // positions of the same transactional_id are in the same partition, the last one wins
+        consumer.assign(&tpl)?;
+        let mut remaining_partitions = tpl.count();
+        let mut checkpoint = None;
+        while remaining_partitions > 0 {
+            match consumer.poll(timeout) {
+                Some(Ok(msg)) => {
+                    if msg.key() != Some(transactional_id.as_bytes()) {
+                        continue;
+                    }
+                    if let Some(payload) = msg.payload() {
+                        checkpoint = Some(Position::from_str(&String::from_utf8_lossy(payload))?);
+                    }
+                }
+                Some(Err(KafkaError::PartitionEOF(_))) => remaining_partitions -= 1,
+                Some(Err(err)) => return Err(err.into()),
+                None => bail! {Error::Unexpected(format!(
+                    "timeout while reading checkpoint topic: {}",
+                    checkpoint_topic
+                ))},
+            }
+        }
+        Ok(checkpoint)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_murmur2() {
+        // values of org.apache.kafka.common.utils.Utils.murmur2
+        let cases = [
+            ("21", -973932308),
+            ("foobar", -790332482),
+            ("a-little-bit-long-string", -985981536),
+            ("a-little-bit-longer-string", -1486304829),
+            (
+                "lkjh234lh9fiuh90y23oiuhsafujhadof229phr9h19h89h8",
+                -58897971,
+            ),
+            ("abc", 479470107),
+        ];
+        for (data, hash) in cases {
+            assert_eq!(KafkaUtil::murmur2(data.as_bytes()), hash);
+        }
+    }
+}
@@ -6,9 +6,12 @@ pub mod check_log;
 pub mod conn_util;
 pub mod data_marker;
 pub mod extractor;
+pub mod kafka_util;
 pub mod meta_fetcher;
 pub mod rdb_query_builder;
 pub mod rdb_router;
+pub mod schema_registry_client;
+pub mod sequence_syncer;
 pub mod sinker;
 
 use async_trait::async_trait;
@@ -48,6 +51,12 @@ pub trait Sinker {
         Ok(())
     }
 
+    /// Called before the checkpoint position is recorded,
+    /// sinkers buffering data should persist it here.
+    async fn flush(&mut self) -> anyhow::Result<()> {
+        Ok(())
+    }
+
     fn get_id(&self) -> String {
         String::new()
     }
@@ -264,6 +264,7 @@ impl PgStructCheckFetcher {
             element_oid: 0,
             category: String::new(),
             enum_values: None,
+            schema: String::new(),
         };
 
         if !alias.is_empty() {
@@ -131,7 +131,7 @@ impl RdbQueryBuilder<'_> {
         for _ in 0..batch_size {
             let mut placeholders = Vec::new();
             for col in self.rdb_tb_meta.id_cols.iter() {
-                placeholders.push(self.get_placeholder(placeholder_index, col)?);
+                placeholders.push(self.get_where_placeholder(placeholder_index, col)?);
                 placeholder_index += 1;
             }
             all_placeholders.push(format!("({})", placeholders.join(",")));
@@ -141,7 +141,7 @@ impl RdbQueryBuilder<'_> {
             "DELETE FROM {}.{} WHERE ({}) IN ({})",
             self.escape(&self.rdb_tb_meta.schema),
             self.escape(&self.rdb_tb_meta.tb),
-            self.escape_cols(&self.rdb_tb_meta.id_cols).join(","),
+            self.get_where_cols(&self.rdb_tb_meta.id_cols)?.join(","),
             all_placeholders.join(",")
         );
 
@@ -451,9 +451,13 @@ impl RdbQueryBuilder<'_> {
                 if *value == ColValue::None {
                     where_sql = format!("{} {} IS NULL", where_sql, escaped_col);
                 } else {
-                    let sql_value =
-                        self.get_sql_value(index, col, &col_value_map.get(col), placeholder)?;
-                    where_sql = format!("{} {} = {}", where_sql, escaped_col, sql_value);
+                    let sql_value = if placeholder {
+                        self.get_where_placeholder(index, col)?
+                    } else {
+                        self.get_sql_value(index, col, &col_value_map.get(col), placeholder)?
+                    };
+                    where_sql =
+                        format!("{} {} = {}", where_sql, self.get_where_col(col)?, sql_value);
                     not_null_cols.push(col.clone());
                 }
             } else {
@@ -471,7 +475,7 @@ impl RdbQueryBuilder<'_> {
         for _ in 0..batch_size {
             let mut placeholders = Vec::new();
             for col in self.rdb_tb_meta.id_cols.iter() {
-                placeholders.push(self.get_placeholder(placeholder_index, col)?);
+                placeholders.push(self.get_where_placeholder(placeholder_index, col)?);
                 placeholder_index += 1;
             }
             all_placeholders.push(format!("({})", placeholders.join(",")));
@@ -479,7 +483,7 @@ impl RdbQueryBuilder<'_> {
 
         Ok(format!(
             "({}) IN ({})",
-            self.escape_cols(&self.rdb_tb_meta.id_cols).join(","),
+            self.get_where_cols(&self.rdb_tb_meta.id_cols)?.join(","),
             all_placeholders.join(",")
         ))
     }
@@ -528,6 +532,9 @@ impl RdbQueryBuilder<'_> {
             // tinyblob, mediumblob, longblob, blob, varbinary, binary
             ColValue::Blob(v) => (hex::encode(v), true),
 
+            // boolean from postgres
+            ColValue::Bool(v) => return Ok((*v as u8).to_string()),
+
             _ => {
                 if let Some(v) = col_value.to_option_string() {
                     (v, false)
@@ -566,18 +573,42 @@ impl RdbQueryBuilder<'_> {
     fn get_placeholder(&self, index: usize, col: &str) -> anyhow::Result<String> {
         if let Some(tb_meta) = self.pg_tb_meta {
             let col_type = tb_meta.get_col_type(col)?;
-            // TODO: workaround for types like bit(3)
-            let col_type_name = if col_type.alias == "bit" {
-                "varbit"
-            } else {
-                &col_type.alias
-            };
-            return Ok(format!("${}::{}", index, col_type_name));
+            return Ok(format!("${}::{}", index, col_type.get_cast_name()));
         }
 
         Ok("?".to_string())
     }
 
+    fn get_where_placeholder(&self, index: usize, col: &str) -> anyhow::Result<String> {
+        if self.compare_by_text(col)? {
+            return Ok(format!("${}::text", index));
+        }
+        self.get_placeholder(index, col)
+    }
+
+    fn get_where_col(&self, col: &str) -> anyhow::Result<String> {
+        if self.compare_by_text(col)? {
+            return Ok(format!("{}::text", self.escape(col)));
+        }
+        Ok(self.escape(col))
+    }
+
+    fn get_where_cols(&self, cols: &[String]) -> anyhow::Result<Vec<String>> {
+        let mut where_cols = Vec::new();
+        for col in cols.iter() {
+            where_cols.push(self.get_where_col(col)?);
+        }
+        Ok(where_cols)
+    }
+
+    fn compare_by_text(&self, col: &str) -> anyhow::Result<bool> {
+        if let Some(tb_meta) = self.pg_tb_meta {
+            let col_type = tb_meta.get_col_type(col)?;
+            return Ok(!col_type.value_type.has_eq_operator());
+        }
+        Ok(false)
+    }
+
     fn escape(&self, origin: &str) -> String {
         SqlUtil::escape_by_db_type(origin, &self.db_type)
     }
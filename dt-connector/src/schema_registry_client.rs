@@ -0,0 +1,139 @@
+use anyhow::bail;
+use dt_common::error::Error;
+use reqwest::{Client, StatusCode};
+use serde_json::{json, Value};
+
+// confluent wire format: [magic byte 0][schema id: 4 bytes big-endian][avro datum]
+const MAGIC_BYTE: u8 = 0;
+const HEADER_LEN: usize = 5;
+
+/// Client of Confluent Schema Registry compatible services
+#[derive(Clone)]
+pub struct SchemaRegistryClient {
+    pub http_client: Client,
+    pub url: String,
+    // compatibility level set to subjects before registering, empty: not set
+    pub compatibility: String,
+}
+
+impl SchemaRegistryClient {
+    pub fn new(url: &str) -> anyhow::Result<Self> {
+        Ok(Self {
+            http_client: Client::builder().build()?,
+            url: url.trim_end_matches('/').to_string(),
+            compatibility: String::new(),
+        })
+    }
+
+    pub async fn get_schema(&self, schema_id: u32) -> anyhow::Result<String> {
+        let url = format!("{}/schemas/ids/{}", self.url, schema_id);
+        let response = self.http_client.get(&url).send().await?;
+        let status_code = response.status();
+        let response_text = response.text().await?;
+        if status_code != StatusCode::OK {
+            bail! {Error::HttpError(format!(
+                "get schema failed, url: {}, status_code: {}, response_text: {:?}",
+                url, status_code, response_text
+            ))}
+        }
+
+        // {"schema": "{\"type\": \"record\", ...}"}
+        let json: Value = serde_json::from_str(&response_text)?;
+        match json["schema"].as_str() {
+            Some(schema) => Ok(schema.to_string()),
+            None => bail! {Error::HttpError(format!(
+                "invalid schema response, url: {}, response_text: {:?}",
+                url, response_text
+            ))},
+        }
+    }
+
+    /// Registers the schema under subject and returns its id, the id of an identical schema
+    /// registered before is returned. The schema registry rejects the schema if it is not
+    /// compatible with the previous versions under the compatibility level of the subject
+    pub async fn register_schema(&self, subject: &str, schema: &str) -> anyhow::Result<u32> {
+        if !self.compatibility.is_empty() {
+            let url = format!("{}/config/{}", self.url, subject);
+            let body = json!({ "compatibility": self.compatibility });
+            self.send_request(self.http_client.put(&url).json(&body), &url)
+                .await?;
+        }
+
+        let url = format!("{}/subjects/{}/versions", self.url, subject);
+        let body = json!({ "schema": schema });
+        let response_text = self
+            .send_request(self.http_client.post(&url).json(&body), &url)
+            .await?;
+
+        // {"id": 1}
+        let json: Value = serde_json::from_str(&response_text)?;
+        match json["id"].as_u64() {
+            Some(id) => Ok(id as u32),
+            None => bail! {Error::HttpError(format!(
+                "invalid register schema response, url: {}, response_text: {:?}",
+                url, response_text
+            ))},
+        }
+    }
+
+    async fn send_request(
+        &self,
+        request: reqwest::RequestBuilder,
+        url: &str,
+    ) -> anyhow::Result<String> {
+        let response = request.send().await?;
+        let status_code = response.status();
+        let response_text = response.text().await?;
+        if status_code == StatusCode::CONFLICT {
+            bail! {Error::HttpError(format!(
+                "schema is incompatible with the previous versions, url: {}, response_text: {:?}",
+                url, response_text
+            ))}
+        }
+        if status_code != StatusCode::OK {
+            bail! {Error::HttpError(format!(
+                "schema registry request failed, url: {}, status_code: {}, response_text: {:?}",
+                url, status_code, response_text
+            ))}
+        }
+        Ok(response_text)
+    }
+
+    pub fn encode_wire_format(schema_id: u32, datum: &[u8]) -> Vec<u8> {
+        let mut payload = Vec::with_capacity(HEADER_LEN + datum.len());
+        payload.push(MAGIC_BYTE);
+        payload.extend_from_slice(&schema_id.to_be_bytes());
+        payload.extend_from_slice(datum);
+        payload
+    }
+
+    /// Returns the schema id and the avro datum
+    pub fn decode_wire_format(payload: &[u8]) -> anyhow::Result<(u32, &[u8])> {
+        if payload.len() < HEADER_LEN || payload[0] != MAGIC_BYTE {
+            bail! {Error::Unexpected(
+                "message is not in schema registry wire format".into()
+            )}
+        }
+        let schema_id = u32::from_be_bytes([payload[1], payload[2], payload[3], payload[4]]);
+        Ok((schema_id, &payload[HEADER_LEN..]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_wire_format() {
+        let payload = [0, 0, 0, 1, 2, 10, 11];
+        let (schema_id, datum) = SchemaRegistryClient::decode_wire_format(&payload).unwrap();
+        assert_eq!(schema_id, 258);
+        assert_eq!(datum, &[10, 11]);
+
+        assert!(SchemaRegistryClient::decode_wire_format(&[1, 0, 0, 0, 1]).is_err());
+        assert!(SchemaRegistryClient::decode_wire_format(&[0, 0]).is_err());
+
+        let payload = SchemaRegistryClient::encode_wire_format(258, &[10, 11]);
+        assert_eq!(payload, vec![0, 0, 0, 1, 2, 10, 11]);
+    }
+}
@@ -0,0 +1,217 @@
+use anyhow::bail;
+use dt_common::{
+    config::config_enums::DbType, error::Error, log_debug, log_error, log_info,
+    rdb_filter::RdbFilter, utils::sql_util::SqlUtil,
+};
+use futures::TryStreamExt;
+use sqlx::{MySql, Pool, Postgres, Row};
+
+use crate::rdb_router::RdbRouter;
+
+/// Copies current sequence values (Postgres) / AUTO_INCREMENT counters (MySQL) from source to target,
+/// so inserts on the target won't collide with migrated rows after cutover.
+pub struct SequenceSyncer {
+    pub src_mysql_conn_pool: Option<Pool<MySql>>,
+    pub dst_mysql_conn_pool: Option<Pool<MySql>>,
+    pub src_pg_conn_pool: Option<Pool<Postgres>>,
+    pub dst_pg_conn_pool: Option<Pool<Postgres>>,
+    pub filter: RdbFilter,
+    pub router: RdbRouter,
+}
+
+impl SequenceSyncer {
+    pub async fn sync(&mut self) -> anyhow::Result<()> {
+        if let (Some(src_pool), Some(dst_pool)) = (&self.src_pg_conn_pool, &self.dst_pg_conn_pool) {
+            let (src_pool, dst_pool) = (src_pool.clone(), dst_pool.clone());
+            return self.sync_pg(&src_pool, &dst_pool).await;
+        }
+
+        if let (Some(src_pool), Some(dst_pool)) =
+            (&self.src_mysql_conn_pool, &self.dst_mysql_conn_pool)
+        {
+            let (src_pool, dst_pool) = (src_pool.clone(), dst_pool.clone());
+            return self.sync_mysql(&src_pool, &dst_pool).await;
+        }
+        Ok(())
+    }
+
+    async fn sync_pg(
+        &mut self,
+        src_pool: &Pool<Postgres>,
+        dst_pool: &Pool<Postgres>,
+    ) -> anyhow::Result<()> {
+        // table_name is the owner table of sequences created by serial / identity columns
+        let sql = "SELECT s.schemaname, s.sequencename, s.last_value, tab.relname AS table_name
+            FROM pg_catalog.pg_sequences s
+            JOIN pg_catalog.pg_namespace ns ON ns.nspname = s.schemaname
+            JOIN pg_catalog.pg_class seq ON seq.relname = s.sequencename AND seq.relnamespace = ns.oid
+            LEFT JOIN pg_catalog.pg_depend dep ON dep.objid = seq.oid
+                AND dep.classid = 'pg_catalog.pg_class'::regclass
+                AND dep.refclassid = 'pg_catalog.pg_class'::regclass
+                AND dep.deptype IN ('a', 'i')
+            LEFT JOIN pg_catalog.pg_class tab ON tab.oid = dep.refobjid
+            WHERE s.schemaname NOT IN ('pg_catalog', 'information_schema')";
+
+        let mut sequences = Vec::new();
+        let mut rows = sqlx::query(sql).fetch(src_pool);
+        while let Some(row) = rows.try_next().await? {
+            let schema: String = row.try_get("schemaname")?;
+            let sequence: String = row.try_get("sequencename")?;
+            let table: Option<String> = row.try_get("table_name")?;
+            // null if nextval has never been called
+            let last_value: Option<i64> = row.try_get("last_value")?;
+            sequences.push((schema, sequence, table, last_value));
+        }
+
+        let (mut synced_count, mut failed_count) = (0, 0);
+        for (schema, sequence, table, last_value) in sequences {
+            let Some(last_value) = last_value else {
+                continue;
+            };
+
+            // owned sequences follow the owner table, others follow the schema
+            let dst_schema = if let Some(table) = &table {
+                if self.filter.filter_tb(&schema, table) {
+                    continue;
+                }
+                self.router.get_tb_map(&schema, table).0
+            } else {
+                if self.filter.filter_schema(&schema) {
+                    continue;
+                }
+                self.router.get_schema_map(&schema)
+            };
+
+            let dst_sequence = format!(
+                "{}.{}",
+                SqlUtil::quote_identifier(&dst_schema, &DbType::Pg),
+                SqlUtil::quote_identifier(&sequence, &DbType::Pg)
+            );
+            let result = sqlx::query("SELECT setval($1::regclass, $2, true)")
+                .bind(&dst_sequence)
+                .bind(last_value)
+                .execute(dst_pool)
+                .await;
+            match result {
+                Ok(_) => {
+                    synced_count += 1;
+                    log_debug!(
+                        "sequence: {}.{} synced to: {}, value: {}",
+                        schema,
+                        sequence,
+                        dst_sequence,
+                        last_value
+                    )
+                }
+                Err(error) => {
+                    failed_count += 1;
+                    log_error!(
+                        "failed to sync sequence: {}, error: {}",
+                        dst_sequence,
+                        error
+                    )
+                }
+            }
+        }
+        log_info!("{} sequences synced", synced_count);
+        Self::check_failed_count(failed_count, "sequences")
+    }
+
+    async fn sync_mysql(
+        &mut self,
+        src_pool: &Pool<MySql>,
+        dst_pool: &Pool<MySql>,
+    ) -> anyhow::Result<()> {
+        let mut conn = src_pool.acquire().await?;
+        // mysql 8.0 caches table statistics in information_schema by default,
+        // the variable does not exist in 5.7 which always reads the latest values
+        if let Err(error) = sqlx::query("SET SESSION information_schema_stats_expiry = 0")
+            .execute(&mut *conn)
+            .await
+        {
+            log_debug!("information_schema_stats_expiry not set, error: {}", error);
+        }
+
+        let sql = "SELECT TABLE_SCHEMA, TABLE_NAME, AUTO_INCREMENT
+            FROM information_schema.TABLES
+            WHERE AUTO_INCREMENT IS NOT NULL
+            AND TABLE_SCHEMA NOT IN ('information_schema', 'mysql', 'performance_schema', 'sys')";
+
+        let mut counters = Vec::new();
+        let mut rows = sqlx::query(sql).fetch(&mut *conn);
+        while let Some(row) = rows.try_next().await? {
+            let schema: String = row.try_get("TABLE_SCHEMA")?;
+            let tb: String = row.try_get("TABLE_NAME")?;
+            let auto_increment: u64 = row.try_get("AUTO_INCREMENT")?;
+            counters.push((schema, tb, auto_increment));
+        }
+        drop(rows);
+
+        let (mut synced_count, mut failed_count) = (0, 0);
+        for (schema, tb, auto_increment) in counters {
+            if self.filter.filter_tb(&schema, &tb) {
+                continue;
+            }
+
+            let (dst_schema, dst_tb) = self.router.get_tb_map(&schema, &tb);
+            // mysql never sets it below max(auto increment column) + 1
+            let sql = format!(
+                "ALTER TABLE {}.{} AUTO_INCREMENT = {}",
+                SqlUtil::quote_identifier(&dst_schema, &DbType::Mysql),
+                SqlUtil::quote_identifier(&dst_tb, &DbType::Mysql),
+                auto_increment
+            );
+            match sqlx::query(&sql).execute(dst_pool).await {
+                Ok(_) => {
+                    synced_count += 1;
+                    log_debug!(
+                        "auto_increment of: {}.{} synced to: {}.{}, value: {}",
+                        schema,
+                        tb,
+                        dst_schema,
+                        dst_tb,
+                        auto_increment
+                    )
+                }
+                Err(error) => {
+                    failed_count += 1;
+                    log_error!(
+                        "failed to sync auto_increment of: {}.{}, error: {}",
+                        dst_schema,
+                        dst_tb,
+                        error
+                    )
+                }
+            }
+        }
+        log_info!("auto_increment of {} tables synced", synced_count);
+        Self::check_failed_count(failed_count, "auto_increment counters")
+    }
+
+    // all sequences are tried before failing, so the errors of them are logged together
+    fn check_failed_count(failed_count: usize, target: &str) -> anyhow::Result<()> {
+        if failed_count > 0 {
+            bail! {Error::SinkerError(format!(
+                "failed to sync {} {}, refer to error logs",
+                failed_count, target
+            ))}
+        }
+        Ok(())
+    }
+
+    pub async fn close(&self) -> anyhow::Result<()> {
+        for pool in [&self.src_mysql_conn_pool, &self.dst_mysql_conn_pool]
+            .into_iter()
+            .flatten()
+        {
+            pool.close().await;
+        }
+        for pool in [&self.src_pg_conn_pool, &self.dst_pg_conn_pool]
+            .into_iter()
+            .flatten()
+        {
+            pool.close().await;
+        }
+        Ok(())
+    }
+}
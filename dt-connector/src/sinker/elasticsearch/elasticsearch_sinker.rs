@@ -0,0 +1,316 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::Instant,
+};
+
+use anyhow::bail;
+use async_trait::async_trait;
+use dt_common::{
+    error::Error,
+    meta::{
+        col_value::ColValue, ddl_meta::ddl_data::DdlData, rdb_meta_manager::RdbMetaManager,
+        row_data::RowData, row_type::RowType,
+    },
+    monitor::monitor::Monitor,
+};
+use reqwest::{header, Client, Method, Response, StatusCode};
+use serde_json::{json, Map, Value};
+
+use crate::{call_batch_fn, rdb_router::RdbRouter, sinker::base_sinker::BaseSinker, Sinker};
+
+#[derive(Clone)]
+pub struct ElasticsearchSinker {
+    pub http_client: Client,
+    // http://host:port, without credentials
+    pub url: String,
+    pub username: String,
+    pub password: String,
+    pub batch_size: usize,
+    // to get primary keys of source tables for document ids
+    pub extractor_meta_manager: RdbMetaManager,
+    pub router: RdbRouter,
+    pub reverse_router: RdbRouter,
+    pub monitor: Arc<Mutex<Monitor>>,
+}
+
+#[async_trait]
+impl Sinker for ElasticsearchSinker {
+    async fn sink_dml(&mut self, mut data: Vec<RowData>, _batch: bool) -> anyhow::Result<()> {
+        if data.is_empty() {
+            return Ok(());
+        }
+
+        // actions in a _bulk request are executed in order, rows of different indexes
+        // and row types can be sent in the same request
+        call_batch_fn!(self, data, Self::batch_sink);
+        Ok(())
+    }
+
+    async fn close(&mut self) -> anyhow::Result<()> {
+        self.extractor_meta_manager.close().await
+    }
+
+    async fn refresh_meta(&mut self, data: Vec<DdlData>) -> anyhow::Result<()> {
+        for ddl_data in data.iter() {
+            self.extractor_meta_manager
+                .invalidate_cache_by_ddl_data(ddl_data);
+        }
+        Ok(())
+    }
+}
+
+impl ElasticsearchSinker {
+    async fn batch_sink(
+        &mut self,
+        data: &mut [RowData],
+        start_index: usize,
+        batch_size: usize,
+    ) -> anyhow::Result<()> {
+        let start_time = Instant::now();
+
+        let mut data_size = 0;
+        let mut body = String::new();
+        for row_data in data.iter().skip(start_index).take(batch_size) {
+            data_size += row_data.data_size;
+            self.append_actions(row_data, &mut body).await?;
+        }
+
+        // curl -X POST 'http://localhost:9200/_bulk' -H 'Content-Type: application/x-ndjson' --data-binary @data.json
+        let url = format!("{}/_bulk", self.url);
+        let request = self.build_request(&url, body)?;
+        let response = self.http_client.execute(request).await?;
+        Self::check_response(response).await?;
+
+        BaseSinker::update_batch_monitor(&mut self.monitor, batch_size, data_size, start_time)
+    }
+
+    async fn append_actions(
+        &mut self,
+        row_data: &RowData,
+        body: &mut String,
+    ) -> anyhow::Result<()> {
+        let index = Self::get_index(&row_data.schema, &row_data.tb);
+        let (src_schema, src_tb) = self
+            .reverse_router
+            .get_tb_map(&row_data.schema, &row_data.tb);
+        let col_map = self.router.get_col_map(src_schema, src_tb);
+        let id_cols = Self::route_id_cols(
+            &self
+                .extractor_meta_manager
+                .get_tb_meta(src_schema, src_tb)
+                .await?
+                .id_cols,
+            col_map,
+        );
+
+        let mut push_line = |line: Value| {
+            body.push_str(&line.to_string());
+            body.push('\n');
+        };
+
+        match row_data.row_type {
+            RowType::Insert => {
+                let after = row_data.after.as_ref().unwrap();
+                let id = Self::get_doc_id(after, &id_cols);
+                push_line(json!({"index": {"_index": index, "_id": id}}));
+                push_line(Self::to_doc(after));
+            }
+
+            RowType::Update => {
+                let before = row_data.before.as_ref().unwrap();
+                let after = row_data.after.as_ref().unwrap();
+                let before_id = Self::get_doc_id(before, &id_cols);
+                let after_id = Self::get_doc_id(after, &id_cols);
+                // key changed, remove the document with the old id
+                if before_id != after_id {
+                    push_line(json!({"delete": {"_index": index, "_id": before_id}}));
+                }
+                push_line(json!({"index": {"_index": index, "_id": after_id}}));
+                push_line(Self::to_doc(after));
+            }
+
+            RowType::Delete => {
+                let before = row_data.before.as_ref().unwrap();
+                let id = Self::get_doc_id(before, &id_cols);
+                push_line(json!({"delete": {"_index": index, "_id": id}}));
+            }
+        }
+        Ok(())
+    }
+
+    /// Index names must be lowercase, the routed schema and table are joined by '.'
+    pub fn get_index(schema: &str, tb: &str) -> String {
+        format!("{}.{}", schema, tb).to_lowercase()
+    }
+
+    /// id_cols of source table -> col names in routed rows
+    pub fn route_id_cols(
+        id_cols: &[String],
+        col_map: Option<&HashMap<String, String>>,
+    ) -> Vec<String> {
+        id_cols
+            .iter()
+            .map(|col| {
+                col_map
+                    .and_then(|col_map| col_map.get(col))
+                    .unwrap_or(col)
+                    .clone()
+            })
+            .collect()
+    }
+
+    /// Single key: the value as string, composite keys: json array of values, e.g. ["1","a"]
+    pub fn get_doc_id(col_values: &HashMap<String, ColValue>, id_cols: &[String]) -> String {
+        let ids: Vec<Option<String>> = id_cols
+            .iter()
+            .map(|col| col_values.get(col).and_then(|v| v.to_option_string()))
+            .collect();
+        if ids.len() == 1 {
+            if let Some(id) = &ids[0] {
+                return id.clone();
+            }
+        }
+        json!(ids).to_string()
+    }
+
+    pub fn to_doc(col_values: &HashMap<String, ColValue>) -> Value {
+        let mut doc = Map::new();
+        for (col, col_value) in col_values.iter() {
+            doc.insert(col.to_owned(), Self::to_json_value(col_value));
+        }
+        Value::Object(doc)
+    }
+
+    fn to_json_value(col_value: &ColValue) -> Value {
+        match col_value {
+            // date type of elasticsearch: strict_date_optional_time
+            ColValue::DateTime(v) | ColValue::Timestamp(v) => {
+                Value::String(Self::to_iso_datetime(v))
+            }
+            _ => col_value.to_json_value(),
+        }
+    }
+
+    fn to_iso_datetime(v: &str) -> String {
+        // mysql: 2022-01-02 03:04:05.123456 -> 2022-01-02T03:04:05.123456
+        // postgres: 2019-02-10 11:35:00+08 -> 2019-02-10T11:35:00+08:00
+        let mut datetime = v.replacen(' ', "T", 1);
+        if let Some(time_pos) = datetime.find('T') {
+            let time = &datetime[time_pos..];
+            if let Some(sign_pos) = time.rfind(|c| c == '+' || c == '-') {
+                if !time[sign_pos..].contains(':') {
+                    datetime.push_str(":00");
+                }
+            }
+        }
+        datetime
+    }
+
+    fn build_request(&self, url: &str, body: String) -> anyhow::Result<reqwest::Request> {
+        let password = if self.password.is_empty() {
+            None
+        } else {
+            Some(self.password.clone())
+        };
+
+        let mut post = self
+            .http_client
+            .request(Method::POST, url)
+            .header(header::CONTENT_TYPE, "application/x-ndjson")
+            .body(body);
+        if !self.username.is_empty() {
+            post = post.basic_auth(&self.username, password);
+        }
+        Ok(post.build()?)
+    }
+
+    async fn check_response(response: Response) -> anyhow::Result<()> {
+        let status_code = response.status();
+        let response_text = response.text().await?;
+        if status_code != StatusCode::OK {
+            bail! {Error::HttpError(format!(
+                "bulk request failed, status_code: {}, response_text: {:?}",
+                status_code, response_text
+            ))}
+        }
+
+        // the _bulk api returns 200 even if some actions failed
+        let json: Value = serde_json::from_str(&response_text)?;
+        if json["errors"].as_bool().unwrap_or(false) {
+            let failed_item = json["items"].as_array().and_then(|items| {
+                items.iter().find(|item| {
+                    item.as_object()
+                        .and_then(|v| v.values().next())
+                        .is_some_and(|v| v.get("error").is_some())
+                })
+            });
+            bail! {Error::HttpError(format!(
+                "bulk request failed, failed item: {}",
+                failed_item.unwrap_or(&Value::Null)
+            ))}
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_doc_id() {
+        let col_values = HashMap::from([
+            ("id".to_string(), ColValue::Long(1)),
+            ("name".to_string(), ColValue::String("a".into())),
+            ("value".to_string(), ColValue::None),
+        ]);
+        assert_eq!(
+            ElasticsearchSinker::get_doc_id(&col_values, &["id".into()]),
+            "1"
+        );
+        assert_eq!(
+            ElasticsearchSinker::get_doc_id(&col_values, &["id".into(), "name".into()]),
+            r#"["1","a"]"#
+        );
+        assert_eq!(
+            ElasticsearchSinker::get_doc_id(&col_values, &["value".into()]),
+            "[null]"
+        );
+    }
+
+    #[test]
+    fn test_route_id_cols() {
+        let id_cols = vec!["pk_1".to_string(), "pk_2".to_string()];
+        assert_eq!(ElasticsearchSinker::route_id_cols(&id_cols, None), id_cols);
+
+        let col_map = HashMap::from([
+            ("pk_1".to_string(), "dst_pk_1".to_string()),
+            ("value".to_string(), "dst_value".to_string()),
+        ]);
+        assert_eq!(
+            ElasticsearchSinker::route_id_cols(&id_cols, Some(&col_map)),
+            vec!["dst_pk_1".to_string(), "pk_2".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_to_iso_datetime() {
+        assert_eq!(
+            ElasticsearchSinker::to_iso_datetime("2022-01-02 03:04:05.123456"),
+            "2022-01-02T03:04:05.123456"
+        );
+        assert_eq!(
+            ElasticsearchSinker::to_iso_datetime("2019-02-10 11:35:00+08"),
+            "2019-02-10T11:35:00+08:00"
+        );
+        assert_eq!(
+            ElasticsearchSinker::to_iso_datetime("2019-02-10 11:35:00-03:30"),
+            "2019-02-10T11:35:00-03:30"
+        );
+        assert_eq!(
+            ElasticsearchSinker::to_iso_datetime("2022-01-02"),
+            "2022-01-02"
+        );
+    }
+}
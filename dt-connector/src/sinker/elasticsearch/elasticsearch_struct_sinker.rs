@@ -0,0 +1,223 @@
+use crate::{rdb_router::RdbRouter, Sinker};
+
+use anyhow::bail;
+use dt_common::{
+    config::config_enums::ConflictPolicyEnum,
+    error::Error,
+    log_error, log_info,
+    meta::{
+        mysql::{mysql_col_type::MysqlColType, mysql_tb_meta::MysqlTbMeta},
+        pg::{pg_tb_meta::PgTbMeta, pg_value_type::PgValueType},
+        rdb_meta_manager::RdbMetaManager,
+        struct_meta::{statement::struct_statement::StructStatement, struct_data::StructData},
+    },
+};
+use reqwest::{Client, Method, StatusCode};
+use serde_json::{json, Map, Value};
+
+use async_trait::async_trait;
+
+use super::elasticsearch_sinker::ElasticsearchSinker;
+
+#[derive(Clone)]
+pub struct ElasticsearchStructSinker {
+    pub http_client: Client,
+    // http://host:port, without credentials
+    pub url: String,
+    pub username: String,
+    pub password: String,
+    pub conflict_policy: ConflictPolicyEnum,
+    pub router: RdbRouter,
+    pub extractor_meta_manager: RdbMetaManager,
+}
+
+#[async_trait]
+impl Sinker for ElasticsearchStructSinker {
+    async fn sink_struct(&mut self, data: Vec<StructData>) -> anyhow::Result<()> {
+        let reverse_router = self.router.reverse();
+        for i in data {
+            match i.statement {
+                // no databases in elasticsearch, tables are created as indexes
+                StructStatement::MysqlCreateTable(statement) => {
+                    let (schema, tb) = reverse_router
+                        .get_tb_map(&statement.table.database_name, &statement.table.table_name);
+                    if let Some(meta_manager) =
+                        self.extractor_meta_manager.mysql_meta_manager.as_mut()
+                    {
+                        let tb_meta = meta_manager.get_tb_meta(schema, tb).await?.to_owned();
+                        let index = ElasticsearchSinker::get_index(
+                            &statement.table.database_name,
+                            &statement.table.table_name,
+                        );
+                        let mappings = Self::get_mappings(Some(&tb_meta), None)?;
+                        self.create_index(&index, mappings).await?;
+                    }
+                }
+
+                StructStatement::PgCreateTable(statement) => {
+                    let (schema, tb) = reverse_router
+                        .get_tb_map(&statement.table.schema_name, &statement.table.table_name);
+                    if let Some(meta_manager) = self.extractor_meta_manager.pg_meta_manager.as_mut()
+                    {
+                        let tb_meta = meta_manager.get_tb_meta(schema, tb).await?.to_owned();
+                        let index = ElasticsearchSinker::get_index(
+                            &statement.table.schema_name,
+                            &statement.table.table_name,
+                        );
+                        let mappings = Self::get_mappings(None, Some(&tb_meta))?;
+                        self.create_index(&index, mappings).await?;
+                    }
+                }
+
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn close(&mut self) -> anyhow::Result<()> {
+        self.extractor_meta_manager.close().await
+    }
+}
+
+impl ElasticsearchStructSinker {
+    pub fn get_mappings(
+        mysql_tb_meta: Option<&MysqlTbMeta>,
+        pg_tb_meta: Option<&PgTbMeta>,
+    ) -> anyhow::Result<Value> {
+        let cols = if let Some(tb_meta) = pg_tb_meta {
+            &tb_meta.basic.cols
+        } else {
+            &mysql_tb_meta.as_ref().unwrap().basic.cols
+        };
+
+        let mut properties = Map::new();
+        for col in cols.iter() {
+            let field_type = if let Some(tb_meta) = mysql_tb_meta {
+                Self::get_field_type_from_mysql(tb_meta.get_col_type(col)?)
+            } else {
+                Self::get_field_type_from_pg(&pg_tb_meta.unwrap().get_col_type(col)?.value_type)
+            };
+            // fields not in mappings, such as json, are mapped dynamically
+            if let Some(field_type) = field_type {
+                properties.insert(col.to_owned(), field_type);
+            }
+        }
+        Ok(json!({ "properties": properties }))
+    }
+
+    fn get_field_type_from_mysql(col_type: &MysqlColType) -> Option<Value> {
+        let field_type = match col_type {
+            MysqlColType::TinyInt { unsigned: false } => "byte",
+            MysqlColType::TinyInt { unsigned: true }
+            | MysqlColType::SmallInt { unsigned: false }
+            | MysqlColType::Year => "short",
+            MysqlColType::SmallInt { unsigned: true }
+            | MysqlColType::MediumInt { .. }
+            | MysqlColType::Int { unsigned: false } => "integer",
+            MysqlColType::Int { unsigned: true } | MysqlColType::BigInt { unsigned: false } => {
+                "long"
+            }
+            MysqlColType::BigInt { unsigned: true } | MysqlColType::Bit => "unsigned_long",
+
+            MysqlColType::Float => "float",
+            MysqlColType::Double | MysqlColType::Decimal { .. } => "double",
+
+            MysqlColType::Date { .. }
+            | MysqlColType::DateTime { .. }
+            | MysqlColType::Timestamp { .. } => "date",
+            MysqlColType::Time { .. } => "keyword",
+
+            MysqlColType::Char { .. }
+            | MysqlColType::Varchar { .. }
+            | MysqlColType::TinyText { .. }
+            | MysqlColType::MediumText { .. }
+            | MysqlColType::Text { .. }
+            | MysqlColType::LongText { .. } => return Some(Self::text_field_type()),
+
+            MysqlColType::Set { .. } | MysqlColType::Enum { .. } => "keyword",
+
+            MysqlColType::Binary { .. }
+            | MysqlColType::VarBinary { .. }
+            | MysqlColType::TinyBlob
+            | MysqlColType::MediumBlob
+            | MysqlColType::Blob
+            | MysqlColType::LongBlob => return Some(Self::binary_field_type()),
+
+            MysqlColType::Json | MysqlColType::Unknown => return None,
+        };
+        Some(json!({ "type": field_type }))
+    }
+
+    fn get_field_type_from_pg(value_type: &PgValueType) -> Option<Value> {
+        let field_type = match value_type {
+            PgValueType::Boolean => "boolean",
+            PgValueType::Int16 => "short",
+            PgValueType::Int32 => "integer",
+            PgValueType::Int64 => "long",
+            PgValueType::Float32 => "float",
+            PgValueType::Float64 | PgValueType::Numeric => "double",
+            PgValueType::Timestamp | PgValueType::TimestampTZ | PgValueType::Date => "date",
+            PgValueType::Char | PgValueType::String => return Some(Self::text_field_type()),
+            PgValueType::UUID => "keyword",
+            PgValueType::Bytes => return Some(Self::binary_field_type()),
+            _ => return None,
+        };
+        Some(json!({ "type": field_type }))
+    }
+
+    fn text_field_type() -> Value {
+        // the same as dynamic mapping of strings
+        json!({
+            "type": "text",
+            "fields": { "keyword": { "type": "keyword", "ignore_above": 256 } }
+        })
+    }
+
+    fn binary_field_type() -> Value {
+        // binary values are written as hex strings, only kept in _source
+        json!({ "type": "keyword", "index": false, "doc_values": false })
+    }
+
+    async fn create_index(&self, index: &str, mappings: Value) -> anyhow::Result<()> {
+        let url = format!("{}/{}", self.url, index);
+        let body = json!({ "mappings": mappings }).to_string();
+        log_info!("create index begin: {}, body: {}", index, body);
+
+        let password = if self.password.is_empty() {
+            None
+        } else {
+            Some(self.password.clone())
+        };
+        let mut put = self
+            .http_client
+            .request(Method::PUT, &url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(body);
+        if !self.username.is_empty() {
+            put = put.basic_auth(&self.username, password);
+        }
+
+        let response = self.http_client.execute(put.build()?).await?;
+        let status_code = response.status();
+        let response_text = response.text().await?;
+        if status_code == StatusCode::OK {
+            log_info!("create index succeed");
+            return Ok(());
+        }
+
+        log_error!(
+            "create index failed, status_code: {}, response_text: {}",
+            status_code,
+            response_text
+        );
+        match self.conflict_policy {
+            ConflictPolicyEnum::Interrupt => bail! {Error::HttpError(format!(
+                "create index failed, index: {}, status_code: {}, response_text: {:?}",
+                index, status_code, response_text
+            ))},
+            ConflictPolicyEnum::Ignore => Ok(()),
+        }
+    }
+}
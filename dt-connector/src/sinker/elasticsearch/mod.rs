@@ -0,0 +1,2 @@
+pub mod elasticsearch_sinker;
+pub mod elasticsearch_struct_sinker;
@@ -0,0 +1,259 @@
+use std::{str::FromStr, sync::Arc};
+
+use anyhow::bail;
+use arrow_array::{
+    ArrayRef, BinaryArray, BooleanArray, Float32Array, Float64Array, Int16Array, Int32Array,
+    Int64Array, Int8Array, RecordBatch, StringArray, UInt16Array, UInt32Array, UInt64Array,
+    UInt8Array,
+};
+use arrow_schema::{DataType, SchemaRef};
+use dt_common::{config::config_enums::FileFormat, error::Error, meta::col_value::ColValue};
+use parquet::arrow::ArrowWriter;
+use serde_json::Map;
+
+/// Encodes rows of a table into an in-memory file,
+/// values of each row are in the same order as fields of the file schema
+pub enum FileEncoder {
+    Csv(csv::Writer<Vec<u8>>),
+    Jsonl(Vec<u8>),
+    Parquet(ArrowWriter<Vec<u8>>),
+}
+
+impl FileEncoder {
+    pub fn new(
+        file_format: &FileFormat,
+        schema: SchemaRef,
+        csv_delimiter: u8,
+    ) -> anyhow::Result<Self> {
+        let encoder = match file_format {
+            FileFormat::Csv => {
+                let mut writer = csv::WriterBuilder::new()
+                    .delimiter(csv_delimiter)
+                    .from_writer(Vec::new());
+                // header
+                writer.write_record(schema.fields().iter().map(|field| field.name()))?;
+                Self::Csv(writer)
+            }
+            FileFormat::Jsonl => Self::Jsonl(Vec::new()),
+            FileFormat::Parquet => Self::Parquet(ArrowWriter::try_new(Vec::new(), schema, None)?),
+        };
+        Ok(encoder)
+    }
+
+    pub fn encode(&mut self, schema: &SchemaRef, rows: &[Vec<ColValue>]) -> anyhow::Result<()> {
+        match self {
+            Self::Csv(writer) => {
+                for row in rows.iter() {
+                    // null is written as empty field
+                    writer.write_record(
+                        row.iter()
+                            .map(|v| Self::to_string_value(v).unwrap_or_default()),
+                    )?;
+                }
+                // flush to the inner buffer so the file size can be counted
+                writer.flush()?;
+            }
+
+            Self::Jsonl(buf) => {
+                for row in rows.iter() {
+                    let mut doc = Map::new();
+                    for (field, col_value) in schema.fields().iter().zip(row.iter()) {
+                        doc.insert(field.name().to_owned(), col_value.to_json_value());
+                    }
+                    serde_json::to_writer(&mut *buf, &doc)?;
+                    buf.push(b'\n');
+                }
+            }
+
+            Self::Parquet(writer) => {
+                let mut columns = Vec::with_capacity(schema.fields().len());
+                for (i, field) in schema.fields().iter().enumerate() {
+                    let col_values = rows.iter().map(|row| &row[i]);
+                    columns.push(Self::build_array(field.data_type(), col_values)?);
+                }
+                writer.write(&RecordBatch::try_new(schema.clone(), columns)?)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Estimated size of the file if finished now
+    pub fn size(&self) -> usize {
+        match self {
+            Self::Csv(writer) => writer.get_ref().len(),
+            Self::Jsonl(buf) => buf.len(),
+            Self::Parquet(writer) => writer.bytes_written() + writer.in_progress_size(),
+        }
+    }
+
+    pub fn finish(self) -> anyhow::Result<Vec<u8>> {
+        let data = match self {
+            Self::Csv(writer) => writer.into_inner().map_err(|e| e.into_error())?,
+            Self::Jsonl(buf) => buf,
+            // writes the parquet footer
+            Self::Parquet(writer) => writer.into_inner()?,
+        };
+        Ok(data)
+    }
+
+    fn build_array<'a>(
+        data_type: &DataType,
+        col_values: impl Iterator<Item = &'a ColValue>,
+    ) -> anyhow::Result<ArrayRef> {
+        let array: ArrayRef = match data_type {
+            DataType::Boolean => Arc::new(BooleanArray::from(
+                col_values
+                    .map(Self::to_bool)
+                    .collect::<anyhow::Result<Vec<_>>>()?,
+            )),
+            DataType::Int8 => Arc::new(Int8Array::from(Self::parse_all(col_values)?)),
+            DataType::Int16 => Arc::new(Int16Array::from(Self::parse_all(col_values)?)),
+            DataType::Int32 => Arc::new(Int32Array::from(Self::parse_all(col_values)?)),
+            DataType::Int64 => Arc::new(Int64Array::from(Self::parse_all(col_values)?)),
+            DataType::UInt8 => Arc::new(UInt8Array::from(Self::parse_all(col_values)?)),
+            DataType::UInt16 => Arc::new(UInt16Array::from(Self::parse_all(col_values)?)),
+            DataType::UInt32 => Arc::new(UInt32Array::from(Self::parse_all(col_values)?)),
+            DataType::UInt64 => Arc::new(UInt64Array::from(Self::parse_all(col_values)?)),
+            DataType::Float32 => Arc::new(Float32Array::from(Self::parse_all(col_values)?)),
+            DataType::Float64 => Arc::new(Float64Array::from(Self::parse_all(col_values)?)),
+            DataType::Binary => Arc::new(col_values.map(Self::to_bytes).collect::<BinaryArray>()),
+            _ => Arc::new(
+                col_values
+                    .map(Self::to_string_value)
+                    .collect::<StringArray>(),
+            ),
+        };
+        Ok(array)
+    }
+
+    /// Values which can not be parsed as the field type are errors instead of nulls
+    fn parse_all<'a, T: FromStr>(
+        col_values: impl Iterator<Item = &'a ColValue>,
+    ) -> anyhow::Result<Vec<Option<T>>> {
+        col_values.map(Self::parse::<T>).collect()
+    }
+
+    fn parse<T: FromStr>(col_value: &ColValue) -> anyhow::Result<Option<T>> {
+        let Some(v) = Self::to_string_value(col_value) else {
+            return Ok(None);
+        };
+        match v.parse() {
+            Ok(v) => Ok(Some(v)),
+            Err(_) => bail! {Error::SinkerError(format!(
+                "failed to parse value: {}, as {}",
+                v,
+                std::any::type_name::<T>()
+            ))},
+        }
+    }
+
+    fn to_bool(col_value: &ColValue) -> anyhow::Result<Option<bool>> {
+        let v = match col_value {
+            ColValue::Bool(v) => *v,
+            _ => match Self::to_string_value(col_value) {
+                None => return Ok(None),
+                Some(v) => match v.as_str() {
+                    "1" | "t" | "true" => true,
+                    "0" | "f" | "false" => false,
+                    _ => bail! {Error::SinkerError(format!(
+                        "failed to parse value: {}, as bool",
+                        v
+                    ))},
+                },
+            },
+        };
+        Ok(Some(v))
+    }
+
+    fn to_bytes(col_value: &ColValue) -> Option<Vec<u8>> {
+        match col_value {
+            ColValue::Blob(v) | ColValue::RawString(v) | ColValue::Json(v) => Some(v.clone()),
+            _ => Self::to_string_value(col_value).map(|v| v.into_bytes()),
+        }
+    }
+
+    pub fn to_string_value(col_value: &ColValue) -> Option<String> {
+        match col_value {
+            // binary values are written as hex in text formats
+            ColValue::Blob(v) => Some(hex::encode(v)),
+            ColValue::Json(v) => Some(String::from_utf8_lossy(v).to_string()),
+            _ => col_value.to_option_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use arrow_schema::{Field, Schema};
+    use serde_json::Value;
+
+    use super::*;
+
+    fn get_schema() -> SchemaRef {
+        Arc::new(Schema::new(vec![
+            Field::new("id", DataType::Int32, true),
+            Field::new("name", DataType::Utf8, true),
+            Field::new("_op", DataType::Utf8, true),
+        ]))
+    }
+
+    fn get_rows() -> Vec<Vec<ColValue>> {
+        vec![
+            vec![
+                ColValue::Long(1),
+                ColValue::String("a,b".into()),
+                ColValue::String("I".into()),
+            ],
+            vec![
+                ColValue::Long(2),
+                ColValue::None,
+                ColValue::String("D".into()),
+            ],
+        ]
+    }
+
+    #[test]
+    fn test_encode_csv() {
+        let schema = get_schema();
+        let mut encoder = FileEncoder::new(&FileFormat::Csv, schema.clone(), b',').unwrap();
+        encoder.encode(&schema, &get_rows()).unwrap();
+        let data = String::from_utf8(encoder.finish().unwrap()).unwrap();
+        assert_eq!(data, "id,name,_op\n1,\"a,b\",I\n2,,D\n");
+    }
+
+    #[test]
+    fn test_encode_jsonl() {
+        let schema = get_schema();
+        let mut encoder = FileEncoder::new(&FileFormat::Jsonl, schema.clone(), b',').unwrap();
+        encoder.encode(&schema, &get_rows()).unwrap();
+        let data = String::from_utf8(encoder.finish().unwrap()).unwrap();
+        let lines: Vec<Value> = data
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(
+            lines,
+            vec![
+                serde_json::json!({"id": 1, "name": "a,b", "_op": "I"}),
+                serde_json::json!({"id": 2, "name": null, "_op": "D"}),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_encode_parquet() {
+        let schema = get_schema();
+        let mut encoder = FileEncoder::new(&FileFormat::Parquet, schema.clone(), b',').unwrap();
+        encoder.encode(&schema, &get_rows()).unwrap();
+        assert!(!encoder.finish().unwrap().is_empty());
+
+        // values not matching the field type are not written as nulls
+        let mut encoder = FileEncoder::new(&FileFormat::Parquet, schema.clone(), b',').unwrap();
+        let rows = vec![vec![
+            ColValue::String("a".into()),
+            ColValue::None,
+            ColValue::String("I".into()),
+        ]];
+        assert!(encoder.encode(&schema, &rows).is_err());
+    }
+}
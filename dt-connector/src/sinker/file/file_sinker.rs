@@ -0,0 +1,338 @@
+use std::{
+    collections::HashMap,
+    path::Path,
+    sync::{Arc, Mutex},
+    time::Instant,
+};
+
+use anyhow::Context;
+use arrow_schema::{DataType, Field, Schema, SchemaRef};
+use async_trait::async_trait;
+use chrono::Utc;
+use dt_common::{
+    config::{config_enums::FileFormat, s3_config::S3Config},
+    log_info,
+    meta::{
+        col_value::ColValue, ddl_meta::ddl_data::DdlData, mysql::mysql_col_type::MysqlColType,
+        pg::pg_value_type::PgValueType, rdb_meta_manager::RdbMetaManager, row_data::RowData,
+        row_type::RowType,
+    },
+    monitor::monitor::Monitor,
+};
+use rusoto_core::ByteStream;
+use rusoto_s3::{PutObjectRequest, S3Client, S3};
+use uuid::Uuid;
+
+use crate::{rdb_router::RdbRouter, sinker::base_sinker::BaseSinker, Sinker};
+
+use super::file_encoder::FileEncoder;
+
+pub struct FileSinker {
+    // root directory, or the key prefix in bucket if s3_client is set
+    pub url: String,
+    pub file_format: FileFormat,
+    pub csv_delimiter: u8,
+    pub rolling_size_bytes: usize,
+    pub rolling_interval_secs: u64,
+    pub op_col: String,
+    pub ts_col: String,
+    pub s3_client: Option<S3Client>,
+    pub s3_config: S3Config,
+    // to get columns and types of source tables
+    pub extractor_meta_manager: RdbMetaManager,
+    pub router: RdbRouter,
+    pub reverse_router: RdbRouter,
+    pub monitor: Arc<Mutex<Monitor>>,
+    // opened files by routed schema and tb
+    pub tb_files: HashMap<(String, String), TbFile>,
+}
+
+pub struct TbFile {
+    schema: SchemaRef,
+    // routed names of key cols
+    id_cols: Vec<String>,
+    encoder: FileEncoder,
+    create_time: Instant,
+}
+
+#[async_trait]
+impl Sinker for FileSinker {
+    async fn sink_dml(&mut self, data: Vec<RowData>, _batch: bool) -> anyhow::Result<()> {
+        if data.is_empty() {
+            return Ok(());
+        }
+
+        let start_time = Instant::now();
+        let mut data_size = 0;
+
+        // rows of the same table keep their order
+        let mut tb_rows: HashMap<(String, String), Vec<&RowData>> = HashMap::new();
+        for row_data in data.iter() {
+            data_size += row_data.data_size;
+            tb_rows
+                .entry((row_data.schema.clone(), row_data.tb.clone()))
+                .or_default()
+                .push(row_data);
+        }
+
+        let ts = Utc::now().timestamp_millis();
+        for (key, rows) in tb_rows {
+            if !self.tb_files.contains_key(&key) {
+                let tb_file = self.open_tb_file(&key.0, &key.1).await?;
+                self.tb_files.insert(key.clone(), tb_file);
+            }
+            let tb_file = self.tb_files.get_mut(&key).unwrap();
+            let mut file_rows = Vec::with_capacity(rows.len());
+            for row_data in rows {
+                // key changed, write a delete row of the old key first
+                if !self.op_col.is_empty()
+                    && row_data.row_type == RowType::Update
+                    && Self::is_key_changed(row_data, &tb_file.id_cols)
+                {
+                    let (delete_row_data, _) = row_data.clone().split_update_row_data();
+                    file_rows.push(Self::to_row(
+                        &delete_row_data,
+                        &tb_file.schema,
+                        &self.op_col,
+                        &self.ts_col,
+                        ts,
+                    ));
+                }
+                file_rows.push(Self::to_row(
+                    row_data,
+                    &tb_file.schema,
+                    &self.op_col,
+                    &self.ts_col,
+                    ts,
+                ));
+            }
+            tb_file.encoder.encode(&tb_file.schema, &file_rows)?;
+        }
+
+        self.roll_files(false).await?;
+        BaseSinker::update_batch_monitor(&mut self.monitor, data.len(), data_size, start_time)
+    }
+
+    async fn close(&mut self) -> anyhow::Result<()> {
+        self.roll_files(true).await?;
+        self.extractor_meta_manager.close().await
+    }
+
+    async fn flush(&mut self) -> anyhow::Result<()> {
+        // rows written to opened files are lost on restart if the checkpoint is recorded
+        self.roll_files(true).await
+    }
+
+    async fn refresh_meta(&mut self, data: Vec<DdlData>) -> anyhow::Result<()> {
+        // columns may change, finish opened files and create new ones with new schemas
+        self.roll_files(true).await?;
+        for ddl_data in data.iter() {
+            self.extractor_meta_manager
+                .invalidate_cache_by_ddl_data(ddl_data);
+        }
+        Ok(())
+    }
+}
+
+impl FileSinker {
+    async fn open_tb_file(&mut self, schema: &str, tb: &str) -> anyhow::Result<TbFile> {
+        let (src_schema, src_tb) = self.reverse_router.get_tb_map(schema, tb);
+        let col_map = self.router.get_col_map(src_schema, src_tb);
+        let id_cols = self
+            .extractor_meta_manager
+            .get_tb_meta(src_schema, src_tb)
+            .await?
+            .id_cols
+            .iter()
+            .map(|col| {
+                col_map
+                    .and_then(|col_map| col_map.get(col))
+                    .unwrap_or(col)
+                    .clone()
+            })
+            .collect();
+
+        // routed col name -> col type
+        let mut fields = Vec::new();
+        let mut push_field = |col: &str, data_type: DataType| {
+            if let Some(col_map) = col_map {
+                // cols not in col_map are filtered by router
+                if let Some(dst_col) = col_map.get(col) {
+                    fields.push(Field::new(dst_col, data_type, true));
+                }
+            } else {
+                fields.push(Field::new(col, data_type, true));
+            }
+        };
+
+        if let Some(meta_manager) = self.extractor_meta_manager.mysql_meta_manager.as_mut() {
+            let tb_meta = meta_manager.get_tb_meta(src_schema, src_tb).await?;
+            for col in tb_meta.basic.cols.iter() {
+                push_field(
+                    col,
+                    Self::get_data_type_from_mysql(tb_meta.get_col_type(col)?),
+                );
+            }
+        } else if let Some(meta_manager) = self.extractor_meta_manager.pg_meta_manager.as_mut() {
+            let tb_meta = meta_manager.get_tb_meta(src_schema, src_tb).await?;
+            for col in tb_meta.basic.cols.iter() {
+                let value_type = &tb_meta.get_col_type(col)?.value_type;
+                push_field(col, Self::get_data_type_from_pg(value_type));
+            }
+        }
+
+        if !self.op_col.is_empty() {
+            fields.push(Field::new(&self.op_col, DataType::Utf8, true));
+        }
+        if !self.ts_col.is_empty() {
+            fields.push(Field::new(&self.ts_col, DataType::Int64, true));
+        }
+
+        let file_schema = Arc::new(Schema::new(fields));
+        Ok(TbFile {
+            schema: file_schema.clone(),
+            id_cols,
+            encoder: FileEncoder::new(&self.file_format, file_schema, self.csv_delimiter)?,
+            create_time: Instant::now(),
+        })
+    }
+
+    fn is_key_changed(row_data: &RowData, id_cols: &[String]) -> bool {
+        let before = row_data.before.as_ref().unwrap();
+        let after = row_data.after.as_ref().unwrap();
+        id_cols.iter().any(|col| before.get(col) != after.get(col))
+    }
+
+    fn to_row(
+        row_data: &RowData,
+        schema: &SchemaRef,
+        op_col: &str,
+        ts_col: &str,
+        ts: i64,
+    ) -> Vec<ColValue> {
+        // I: insert, U: update, D: delete, deletes are written with before values
+        let (col_values, op) = match row_data.row_type {
+            RowType::Insert => (row_data.after.as_ref().unwrap(), "I"),
+            RowType::Update => (row_data.after.as_ref().unwrap(), "U"),
+            RowType::Delete => (row_data.before.as_ref().unwrap(), "D"),
+        };
+
+        schema
+            .fields()
+            .iter()
+            .map(|field| {
+                let col = field.name();
+                if !op_col.is_empty() && col == op_col {
+                    ColValue::String(op.into())
+                } else if !ts_col.is_empty() && col == ts_col {
+                    ColValue::LongLong(ts)
+                } else {
+                    col_values.get(col).cloned().unwrap_or(ColValue::None)
+                }
+            })
+            .collect()
+    }
+
+    async fn roll_files(&mut self, force: bool) -> anyhow::Result<()> {
+        let keys: Vec<(String, String)> = self
+            .tb_files
+            .iter()
+            .filter(|(_, tb_file)| {
+                force
+                    || tb_file.encoder.size() >= self.rolling_size_bytes
+                    || tb_file.create_time.elapsed().as_secs() >= self.rolling_interval_secs
+            })
+            .map(|(key, _)| key.clone())
+            .collect();
+
+        for key in keys {
+            let tb_file = self.tb_files.remove(&key).unwrap();
+            let data = tb_file.encoder.finish()?;
+            self.write_file(&key.0, &key.1, data).await?;
+        }
+        Ok(())
+    }
+
+    async fn write_file(&self, schema: &str, tb: &str, data: Vec<u8>) -> anyhow::Result<()> {
+        // {url}/{schema}/{tb}/20240101120000_{uuid}.parquet
+        let file_name = format!(
+            "{}_{}.{}",
+            Utc::now().format("%Y%m%d%H%M%S"),
+            Uuid::new_v4().simple(),
+            self.file_format
+        );
+        let path = format!(
+            "{}/{}/{}/{}",
+            self.url.trim_end_matches('/'),
+            schema,
+            tb,
+            file_name
+        );
+
+        if let Some(s3_client) = &self.s3_client {
+            let request = PutObjectRequest {
+                bucket: self.s3_config.bucket.clone(),
+                key: path.trim_start_matches('/').to_string(),
+                body: Some(ByteStream::from(data)),
+                ..Default::default()
+            };
+            s3_client
+                .put_object(request)
+                .await
+                .with_context(|| format!("failed to push: {}", path))?;
+        } else {
+            if let Some(dir) = Path::new(&path).parent() {
+                tokio::fs::create_dir_all(dir).await?;
+            }
+            // rename after written so readers never see partial files
+            let tmp_path = format!("{}.tmp", path);
+            tokio::fs::write(&tmp_path, data)
+                .await
+                .with_context(|| format!("failed to write: {}", tmp_path))?;
+            tokio::fs::rename(&tmp_path, &path).await?;
+        }
+
+        log_info!("file written: {}", path);
+        Ok(())
+    }
+
+    pub fn get_data_type_from_mysql(col_type: &MysqlColType) -> DataType {
+        match col_type {
+            MysqlColType::TinyInt { unsigned: false } => DataType::Int8,
+            MysqlColType::TinyInt { unsigned: true } => DataType::UInt8,
+            MysqlColType::SmallInt { unsigned: false } => DataType::Int16,
+            MysqlColType::SmallInt { unsigned: true } | MysqlColType::Year => DataType::UInt16,
+            MysqlColType::MediumInt { unsigned: false } | MysqlColType::Int { unsigned: false } => {
+                DataType::Int32
+            }
+            MysqlColType::MediumInt { unsigned: true } | MysqlColType::Int { unsigned: true } => {
+                DataType::UInt32
+            }
+            MysqlColType::BigInt { unsigned: false } => DataType::Int64,
+            MysqlColType::BigInt { unsigned: true } | MysqlColType::Bit => DataType::UInt64,
+            MysqlColType::Float => DataType::Float32,
+            MysqlColType::Double => DataType::Float64,
+            MysqlColType::Binary { .. }
+            | MysqlColType::VarBinary { .. }
+            | MysqlColType::TinyBlob
+            | MysqlColType::MediumBlob
+            | MysqlColType::Blob
+            | MysqlColType::LongBlob => DataType::Binary,
+            // decimal, date, time, json, etc. are kept as strings without losing precision
+            _ => DataType::Utf8,
+        }
+    }
+
+    pub fn get_data_type_from_pg(value_type: &PgValueType) -> DataType {
+        match value_type {
+            PgValueType::Boolean => DataType::Boolean,
+            PgValueType::Int16 => DataType::Int16,
+            PgValueType::Int32 => DataType::Int32,
+            PgValueType::Int64 => DataType::Int64,
+            PgValueType::Float32 => DataType::Float32,
+            PgValueType::Float64 => DataType::Float64,
+            PgValueType::Bytes => DataType::Binary,
+            _ => DataType::Utf8,
+        }
+    }
+}
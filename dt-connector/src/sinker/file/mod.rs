@@ -0,0 +1,2 @@
+pub mod file_encoder;
+pub mod file_sinker;
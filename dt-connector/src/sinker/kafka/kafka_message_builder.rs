@@ -0,0 +1,534 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::Context;
+use serde_json::{json, Value};
+
+use crate::{
+    kafka_util::KafkaUtil, rdb_router::RdbRouter, schema_registry_client::SchemaRegistryClient,
+};
+
+use dt_common::{
+    config::config_enums::{
+        KafkaHeader, KafkaKeyFormat, KafkaPartitionStrategy, MessageFormat, SchemaSubjectStrategy,
+    },
+    log_info,
+    meta::{
+        avro::{
+            avro_converter::AvroConverter,
+            debezium_avro_converter::{AvroDatum, DebeziumAvroConverter},
+        },
+        col_value::ColValue,
+        ddl_meta::ddl_data::DdlData,
+        dt_data::{DtData, DtItem},
+        json::json_converter::JsonConverter,
+        position::Position,
+        row_data::RowData,
+        row_type::RowType,
+    },
+};
+
+/// Message to be sent to kafka, an empty key / payload is sent as null
+pub struct KafkaMessage {
+    pub topic: String,
+    pub key: Vec<u8>,
+    pub payload: Vec<u8>,
+    pub partition: KafkaPartition,
+    pub headers: Vec<(String, String)>,
+}
+
+impl KafkaMessage {
+    pub fn new(topic: String, key: Vec<u8>, payload: Vec<u8>) -> Self {
+        Self {
+            topic,
+            key,
+            payload,
+            partition: KafkaPartition::Default,
+            headers: Vec::new(),
+        }
+    }
+}
+
+/// Partition of a message, resolved by the sinker with the partition count of the topic
+#[derive(Clone, Debug, PartialEq)]
+pub enum KafkaPartition {
+    // chosen by the producer
+    Default,
+    // murmur2 hash
+    Hash(i32),
+    Fixed(i32),
+}
+
+impl KafkaPartition {
+    /// None: chosen by the producer, also if the partition count is unknown
+    pub fn resolve(&self, partition_count: Option<i32>) -> Option<i32> {
+        match self {
+            Self::Default => None,
+            Self::Hash(hash) => partition_count
+                .filter(|count| *count > 0)
+                .map(|count| (hash & 0x7fffffff) % count),
+            Self::Fixed(partition) => Some(*partition),
+        }
+    }
+}
+
+/// Converts data to kafka messages, shared by KafkaSinker and RdkafkaSinker
+#[derive(Clone)]
+pub struct KafkaMessageBuilder {
+    pub router: RdbRouter,
+    pub avro_converter: AvroConverter,
+    pub json_converter: JsonConverter,
+    pub debezium_avro_converter: DebeziumAvroConverter,
+    pub with_tombstone: bool,
+    // debezium_avro only
+    pub schema_registry_client: Option<SchemaRegistryClient>,
+    pub schema_subject_strategy: SchemaSubjectStrategy,
+    // key: (subject, schema fingerprint), value: schema id
+    pub schema_ids: HashMap<(String, u64), u32>,
+    pub key_format: KafkaKeyFormat,
+    pub partition_strategy: KafkaPartitionStrategy,
+    // key: (schema, tb), value: col
+    pub partition_cols: HashMap<(String, String), String>,
+    pub partition: i32,
+    pub headers: Vec<KafkaHeader>,
+    pub transaction_topic: String,
+    pub ddl_topic: String,
+    // the source transaction being sent, refer to transaction_topic
+    pub transaction: Option<KafkaTransaction>,
+}
+
+/// Source transaction of BEGIN / END events, started by its first row
+#[derive(Clone)]
+pub struct KafkaTransaction {
+    pub id: String,
+    pub event_count: u64,
+    // key: schema.tb, value: event count
+    pub data_collections: BTreeMap<String, u64>,
+}
+
+impl KafkaTransaction {
+    fn new(position: &Position) -> Self {
+        // unique for each transaction, also across restarts
+        let id = match position {
+            Position::MysqlCdc {
+                binlog_filename,
+                next_event_position,
+                ..
+            } => format!("{}:{}", binlog_filename, next_event_position),
+            Position::PgCdc { lsn, .. } => lsn.clone(),
+            _ => position.to_string(),
+        };
+        Self {
+            id,
+            event_count: 0,
+            data_collections: BTreeMap::new(),
+        }
+    }
+
+    fn add_event(&mut self, row_data: &RowData) {
+        self.event_count += 1;
+        *self
+            .data_collections
+            .entry(format!("{}.{}", row_data.schema, row_data.tb))
+            .or_default() += 1;
+    }
+}
+
+impl KafkaMessageBuilder {
+    pub fn message_format(&self) -> &MessageFormat {
+        &self.json_converter.message_format
+    }
+
+    pub async fn build_avro_messages(
+        &mut self,
+        data: &mut [RowData],
+    ) -> anyhow::Result<Vec<KafkaMessage>> {
+        let mut messages = Vec::new();
+        for row_data in data.iter_mut() {
+            row_data.convert_raw_string();
+            let topic = self.router.get_topic(&row_data.schema, &row_data.tb);
+            let key = self.avro_converter.row_data_to_avro_key(row_data).await?;
+            let payload = self
+                .avro_converter
+                .row_data_to_avro_value(row_data.clone())
+                .await?;
+            let mut message = KafkaMessage::new(topic.into(), key.into_bytes(), payload);
+            self.decorate_row_message(&mut message, row_data, &Position::None)
+                .await?;
+            messages.push(message);
+        }
+        Ok(messages)
+    }
+
+    /// Rows, and commits if transaction_topic is set
+    pub fn filter_raw_items(&self, data: Vec<DtItem>) -> Vec<DtItem> {
+        let with_commits = !self.transaction_topic.is_empty();
+        data.into_iter()
+            .filter(|i| match i.dt_data {
+                DtData::Dml { .. } => true,
+                DtData::Commit { .. } => with_commits,
+                _ => false,
+            })
+            .collect()
+    }
+
+    /// Messages of rows with positions, and BEGIN / END events of source transactions
+    /// if transaction_topic is set
+    pub async fn build_raw_messages(
+        &mut self,
+        data: &mut [DtItem],
+    ) -> anyhow::Result<Vec<KafkaMessage>> {
+        let mut messages = Vec::new();
+        for item in data.iter_mut() {
+            match &mut item.dt_data {
+                DtData::Dml { row_data } => {
+                    if !self.transaction_topic.is_empty() {
+                        if self.transaction.is_none() {
+                            let transaction = KafkaTransaction::new(&item.position);
+                            messages.push(self.build_transaction_message(&transaction, None));
+                            self.transaction = Some(transaction);
+                        }
+                        if let Some(transaction) = self.transaction.as_mut() {
+                            transaction.add_event(row_data);
+                        }
+                    }
+                    self.build_row_messages(row_data, &item.position, &mut messages)
+                        .await?;
+                }
+
+                DtData::Commit { xid } => {
+                    if let Some(transaction) = self.transaction.take() {
+                        let message = self.build_transaction_message(&transaction, Some(xid));
+                        messages.push(message);
+                    }
+                }
+
+                _ => {}
+            }
+        }
+        Ok(messages)
+    }
+
+    async fn build_row_messages(
+        &mut self,
+        row_data: &mut RowData,
+        position: &Position,
+        messages: &mut Vec<KafkaMessage>,
+    ) -> anyhow::Result<()> {
+        row_data.convert_raw_string();
+        let topic = self
+            .router
+            .get_topic(&row_data.schema, &row_data.tb)
+            .to_string();
+        let (key, payload) = match self.json_converter.message_format {
+            // transactional mode, rows are sinked with positions in all formats
+            MessageFormat::Avro => {
+                let key = self.avro_converter.row_data_to_avro_key(row_data).await?;
+                let payload = self
+                    .avro_converter
+                    .row_data_to_avro_value(row_data.clone())
+                    .await?;
+                (key.into_bytes(), payload)
+            }
+            MessageFormat::DebeziumAvro => {
+                let (key, value) = self
+                    .debezium_avro_converter
+                    .row_data_to_avro(row_data, position)
+                    .await?;
+                let key = match key {
+                    Some(key) => self.encode_avro(&topic, &key, true).await?,
+                    None => Vec::new(),
+                };
+                (key, self.encode_avro(&topic, &value, false).await?)
+            }
+            _ => {
+                let key = self.json_converter.row_data_to_json_key(row_data).await?;
+                let payload = self
+                    .json_converter
+                    .row_data_to_json_value(row_data, position)
+                    .await?;
+                (key.into_bytes(), payload)
+            }
+        };
+        let mut message = KafkaMessage::new(topic, key, payload);
+        self.decorate_row_message(&mut message, row_data, position)
+            .await?;
+
+        // tombstone for log compaction, in the same partition
+        let tombstone = if self.with_tombstone
+            && row_data.row_type == RowType::Delete
+            && matches!(
+                self.message_format(),
+                MessageFormat::DebeziumJson | MessageFormat::DebeziumAvro
+            ) {
+            Some(KafkaMessage {
+                topic: message.topic.clone(),
+                key: message.key.clone(),
+                payload: Vec::new(),
+                partition: message.partition.clone(),
+                headers: message.headers.clone(),
+            })
+        } else {
+            None
+        };
+        messages.push(message);
+        messages.extend(tombstone);
+        Ok(())
+    }
+
+    /// BEGIN if xid is None, otherwise END, in json for all message formats, refer to
+    /// debezium transaction metadata
+    fn build_transaction_message(
+        &self,
+        transaction: &KafkaTransaction,
+        xid: Option<&str>,
+    ) -> KafkaMessage {
+        let ts_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or_default();
+        let payload = match xid {
+            None => json!({
+                "status": "BEGIN",
+                "id": transaction.id,
+                "event_count": null,
+                "data_collections": null,
+                "ts_ms": ts_ms,
+            }),
+            Some(xid) => {
+                let data_collections: Vec<Value> = transaction
+                    .data_collections
+                    .iter()
+                    .map(|(tb, count)| json!({"data_collection": tb, "event_count": count}))
+                    .collect();
+                json!({
+                    "status": "END",
+                    "id": transaction.id,
+                    "xid": xid,
+                    "event_count": transaction.event_count,
+                    "data_collections": data_collections,
+                    "ts_ms": ts_ms,
+                })
+            }
+        };
+        let key = json!({ "id": transaction.id });
+
+        let mut message = KafkaMessage::new(
+            self.transaction_topic.clone(),
+            key.to_string().into_bytes(),
+            payload.to_string().into_bytes(),
+        );
+        // BEGIN and END of a transaction are in the same partition
+        message.partition = KafkaPartition::Hash(KafkaUtil::murmur2(transaction.id.as_bytes()));
+        message
+    }
+
+    pub async fn build_ddl_messages(
+        &mut self,
+        data: Vec<DdlData>,
+    ) -> anyhow::Result<Vec<KafkaMessage>> {
+        let mut messages = Vec::new();
+        for ddl_data in data {
+            let topic = if self.ddl_topic.is_empty() {
+                self.router
+                    .get_topic(&ddl_data.default_schema, "")
+                    .to_string()
+            } else {
+                self.ddl_topic.clone()
+            };
+            // refer to debezium SchemaChangeKey
+            let key = match self.json_converter.message_format {
+                MessageFormat::DebeziumJson => json!({ "databaseName": ddl_data.default_schema })
+                    .to_string()
+                    .into_bytes(),
+                MessageFormat::DebeziumAvro => {
+                    let key = self
+                        .debezium_avro_converter
+                        .ddl_key_to_avro(&ddl_data.default_schema)?;
+                    self.encode_avro(&topic, &key, true).await?
+                }
+                _ => ddl_data.default_schema.clone().into_bytes(),
+            };
+            let payload = match self.json_converter.message_format {
+                MessageFormat::Avro => self.avro_converter.ddl_data_to_avro_value(ddl_data).await?,
+                MessageFormat::DebeziumAvro => {
+                    let value = self
+                        .debezium_avro_converter
+                        .ddl_data_to_avro(&ddl_data, &Position::None)?;
+                    self.encode_avro(&topic, &value, false).await?
+                }
+                _ => self
+                    .json_converter
+                    .ddl_data_to_json_value(&ddl_data, &Position::None)?,
+            };
+            let mut message = KafkaMessage::new(topic, key, payload);
+            if self.partition_strategy == KafkaPartitionStrategy::Fixed {
+                message.partition = KafkaPartition::Fixed(self.partition);
+            }
+            for header in self.headers.iter() {
+                let value = match header {
+                    KafkaHeader::Schema => ddl_data.default_schema.clone(),
+                    KafkaHeader::Op => "ddl".to_string(),
+                    KafkaHeader::Table | KafkaHeader::Position | KafkaHeader::Transaction => {
+                        continue
+                    }
+                };
+                message.headers.push((header.to_string(), value));
+            }
+            messages.push(message);
+        }
+        Ok(messages)
+    }
+
+    pub fn refresh_meta(&mut self, data: &[DdlData]) {
+        self.avro_converter.refresh_meta(data);
+        self.json_converter.refresh_meta(data);
+        self.debezium_avro_converter.refresh_meta(data);
+    }
+
+    /// Applies key_format, partition_strategy and headers to the message of a row
+    async fn decorate_row_message(
+        &mut self,
+        message: &mut KafkaMessage,
+        row_data: &RowData,
+        position: &Position,
+    ) -> anyhow::Result<()> {
+        let id_col_values = if matches!(
+            self.key_format,
+            KafkaKeyFormat::PkString | KafkaKeyFormat::Json
+        ) || matches!(
+            self.partition_strategy,
+            KafkaPartitionStrategy::PkHash | KafkaPartitionStrategy::Column
+        ) {
+            self.json_converter.get_id_col_values(row_data).await?
+        } else {
+            Vec::new()
+        };
+
+        match self.key_format {
+            KafkaKeyFormat::PkString => {
+                message.key = Self::to_pk_string(&id_col_values).into_bytes()
+            }
+            KafkaKeyFormat::Json => {
+                message.key = self
+                    .json_converter
+                    .id_col_values_to_json(&id_col_values)
+                    .into_bytes()
+            }
+            // the key of debezium_avro is already in avro
+            KafkaKeyFormat::Avro if *self.message_format() != MessageFormat::DebeziumAvro => {
+                let (key, _) = self
+                    .debezium_avro_converter
+                    .row_data_to_avro(row_data, position)
+                    .await?;
+                message.key = match key {
+                    Some(key) => self.encode_avro(&message.topic, &key, true).await?,
+                    None => Vec::new(),
+                };
+            }
+            _ => {}
+        }
+
+        let pk_hash = || {
+            KafkaPartition::Hash(KafkaUtil::murmur2(
+                Self::to_pk_string(&id_col_values).as_bytes(),
+            ))
+        };
+        message.partition = match self.partition_strategy {
+            KafkaPartitionStrategy::Default => KafkaPartition::Default,
+            KafkaPartitionStrategy::PkHash => pk_hash(),
+            KafkaPartitionStrategy::Table => {
+                let tb = format!("{}.{}", row_data.schema, row_data.tb);
+                KafkaPartition::Hash(KafkaUtil::murmur2(tb.as_bytes()))
+            }
+            KafkaPartitionStrategy::Column => {
+                let key = (row_data.schema.clone(), row_data.tb.clone());
+                match self.partition_cols.get(&key) {
+                    Some(col) => {
+                        let col_values = match row_data.row_type {
+                            RowType::Insert => &row_data.after,
+                            RowType::Update | RowType::Delete => &row_data.before,
+                        };
+                        let value = col_values
+                            .as_ref()
+                            .and_then(|v| v.get(col))
+                            .and_then(|v| v.to_option_string())
+                            .unwrap_or_default();
+                        KafkaPartition::Hash(KafkaUtil::murmur2(value.as_bytes()))
+                    }
+                    // tables without partition col
+                    None => pk_hash(),
+                }
+            }
+            KafkaPartitionStrategy::Fixed => KafkaPartition::Fixed(self.partition),
+        };
+
+        for header in self.headers.iter() {
+            let value = match header {
+                KafkaHeader::Schema => row_data.schema.clone(),
+                KafkaHeader::Table => row_data.tb.clone(),
+                KafkaHeader::Op => row_data.row_type.to_string(),
+                KafkaHeader::Position => position.to_string(),
+                KafkaHeader::Transaction => match &self.transaction {
+                    Some(transaction) => transaction.id.clone(),
+                    None => continue,
+                },
+            };
+            message.headers.push((header.to_string(), value));
+        }
+        Ok(())
+    }
+
+    // 1,a
+    fn to_pk_string(id_col_values: &[(String, ColValue)]) -> String {
+        id_col_values
+            .iter()
+            .map(|(_, v)| v.to_option_string().unwrap_or_default())
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+
+    /// schema registry wire format, the schema is registered when first used
+    async fn encode_avro(
+        &mut self,
+        topic: &str,
+        avro_datum: &AvroDatum,
+        is_key: bool,
+    ) -> anyhow::Result<Vec<u8>> {
+        let subject = match self.schema_subject_strategy {
+            SchemaSubjectStrategy::TopicName if is_key => format!("{}-key", topic),
+            SchemaSubjectStrategy::TopicName => format!("{}-value", topic),
+            SchemaSubjectStrategy::RecordName => avro_datum.schema.name.clone(),
+            SchemaSubjectStrategy::TopicRecordName => {
+                format!("{}-{}", topic, avro_datum.schema.name)
+            }
+        };
+
+        let cache_key = (subject, avro_datum.schema.fingerprint);
+        let schema_id = match self.schema_ids.get(&cache_key) {
+            Some(schema_id) => *schema_id,
+            None => {
+                let schema_id = self
+                    .schema_registry_client
+                    .as_ref()
+                    .context("schema registry client not initialized")?
+                    .register_schema(&cache_key.0, &avro_datum.schema.json)
+                    .await?;
+                log_info!(
+                    "registered avro schema, subject: {}, id: {}, schema: {}",
+                    cache_key.0,
+                    schema_id,
+                    avro_datum.schema.json
+                );
+                self.schema_ids.insert(cache_key, schema_id);
+                schema_id
+            }
+        };
+        Ok(SchemaRegistryClient::encode_wire_format(
+            schema_id,
+            &avro_datum.datum,
+        ))
+    }
+}
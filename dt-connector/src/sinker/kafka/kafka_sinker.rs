@@ -5,19 +5,23 @@ use std::{
 
 use async_trait::async_trait;
 
-use crate::{call_batch_fn, rdb_router::RdbRouter, sinker::base_sinker::BaseSinker, Sinker};
+use crate::{call_batch_fn, sinker::base_sinker::BaseSinker, Sinker};
 
-use dt_common::{meta::ddl_meta::ddl_data::DdlData, monitor::monitor::Monitor};
+use dt_common::{
+    meta::{ddl_meta::ddl_data::DdlData, dt_data::DtItem},
+    monitor::monitor::Monitor,
+};
 
-use dt_common::meta::{avro::avro_converter::AvroConverter, row_data::RowData};
+use dt_common::meta::row_data::RowData;
 
 use kafka::producer::{Producer, Record};
 
+use super::kafka_message_builder::{KafkaMessage, KafkaMessageBuilder};
+
 pub struct KafkaSinker {
     pub batch_size: usize,
-    pub router: RdbRouter,
     pub producer: Producer,
-    pub avro_converter: AvroConverter,
+    pub message_builder: KafkaMessageBuilder,
     pub monitor: Arc<Mutex<Monitor>>,
 }
 
@@ -32,24 +36,24 @@ impl Sinker for KafkaSinker {
         Ok(())
     }
 
-    async fn sink_ddl(&mut self, data: Vec<DdlData>, _batch: bool) -> anyhow::Result<()> {
-        let mut messages = Vec::new();
-        for ddl_data in data {
-            let topic = self.router.get_topic(&ddl_data.default_schema, "");
-            let payload = self.avro_converter.ddl_data_to_avro_value(ddl_data).await?;
-            messages.push(Record {
-                key: String::new(),
-                value: payload,
-                topic,
-                partition: -1,
-            });
+    // json messages need the positions of rows, which are not in RowData
+    async fn sink_raw(&mut self, data: Vec<DtItem>, _batch: bool) -> anyhow::Result<()> {
+        let mut data = self.message_builder.filter_raw_items(data);
+        if data.is_empty() {
+            return Ok(());
         }
-        self.producer.send_all(&messages)?;
+
+        call_batch_fn!(self, data, Self::send_raw);
         Ok(())
     }
 
+    async fn sink_ddl(&mut self, data: Vec<DdlData>, _batch: bool) -> anyhow::Result<()> {
+        let messages = self.message_builder.build_ddl_messages(data).await?;
+        self.send(&messages)
+    }
+
     async fn refresh_meta(&mut self, data: Vec<DdlData>) -> anyhow::Result<()> {
-        self.avro_converter.refresh_meta(&data);
+        self.message_builder.refresh_meta(&data);
         Ok(())
     }
 }
@@ -62,29 +66,47 @@ impl KafkaSinker {
         batch_size: usize,
     ) -> anyhow::Result<()> {
         let start_time = Instant::now();
-        let mut data_size = 0;
-
-        let mut messages = Vec::new();
-        for row_data in data.iter_mut().skip(sinked_count).take(batch_size) {
-            data_size += row_data.data_size;
-
-            row_data.convert_raw_string();
-            let topic = self.router.get_topic(&row_data.schema, &row_data.tb);
-            let key = self.avro_converter.row_data_to_avro_key(row_data).await?;
-            let payload = self
-                .avro_converter
-                .row_data_to_avro_value(row_data.clone())
-                .await?;
-            messages.push(Record {
-                key,
-                value: payload,
-                topic,
-                partition: -1,
-            });
-        }
+        let data = &mut data[sinked_count..sinked_count + batch_size];
+        let data_size: usize = data.iter().map(|i| i.data_size).sum();
 
-        self.producer.send_all(&messages)?;
+        let messages = self.message_builder.build_avro_messages(data).await?;
+        self.send(&messages)?;
 
         BaseSinker::update_batch_monitor(&mut self.monitor, batch_size, data_size, start_time)
     }
+
+    async fn send_raw(
+        &mut self,
+        data: &mut [DtItem],
+        sinked_count: usize,
+        batch_size: usize,
+    ) -> anyhow::Result<()> {
+        let start_time = Instant::now();
+        let data = &mut data[sinked_count..sinked_count + batch_size];
+        let data_size: usize = data.iter().map(|i| i.dt_data.get_data_size()).sum();
+
+        let messages = self.message_builder.build_raw_messages(data).await?;
+        self.send(&messages)?;
+
+        BaseSinker::update_batch_monitor(&mut self.monitor, batch_size, data_size, start_time)
+    }
+
+    fn send(&mut self, messages: &[KafkaMessage]) -> anyhow::Result<()> {
+        // headers are not supported by kafka crate
+        let topics = self.producer.client().topics();
+        let records: Vec<Record<&[u8], &[u8]>> = messages
+            .iter()
+            .map(|m| {
+                let record =
+                    Record::from_key_value(&m.topic, m.key.as_slice(), m.payload.as_slice());
+                let partition_count = topics.partitions(&m.topic).map(|p| p.len() as i32);
+                match m.partition.resolve(partition_count) {
+                    Some(partition) => record.with_partition(partition),
+                    None => record,
+                }
+            })
+            .collect();
+        self.producer.send_all(&records)?;
+        Ok(())
+    }
 }
@@ -1,2 +1,3 @@
+pub mod kafka_message_builder;
 pub mod kafka_sinker;
 pub mod rdkafka_sinker;
@@ -1,77 +1,286 @@
 use std::{
+    collections::HashMap,
     sync::{Arc, Mutex},
     time::{Duration, Instant},
 };
 
 use anyhow::bail;
 use async_trait::async_trait;
-use rdkafka::producer::{FutureProducer, FutureRecord};
+use futures::future::join_all;
+use rdkafka::{
+    error::KafkaResult,
+    message::{Header, OwnedHeaders},
+    producer::{FutureProducer, FutureRecord, Producer},
+};
+
+use crate::{call_batch_fn, sinker::base_sinker::BaseSinker, Sinker};
 
-use crate::{rdb_router::RdbRouter, sinker::base_sinker::BaseSinker, Sinker};
+use dt_common::{
+    error::Error,
+    meta::{
+        ddl_meta::ddl_data::DdlData,
+        dt_data::{DtData, DtItem},
+        position::Position,
+    },
+    monitor::monitor::Monitor,
+};
 
-use dt_common::monitor::monitor::Monitor;
+use dt_common::meta::row_data::RowData;
 
-use dt_common::meta::{avro::avro_converter::AvroConverter, row_data::RowData};
+use super::kafka_message_builder::{KafkaMessage, KafkaMessageBuilder, KafkaPartition};
 
 pub struct RdkafkaSinker {
     pub batch_size: usize,
-    pub router: RdbRouter,
     pub producer: FutureProducer,
-    pub avro_converter: AvroConverter,
+    pub message_builder: KafkaMessageBuilder,
     pub monitor: Arc<Mutex<Monitor>>,
     pub queue_timeout_secs: u64,
+    // exactly-once mode if not empty, each batch is sent in a kafka transaction
+    pub transactional_id: String,
+    pub checkpoint_topic: String,
+    // rows after the last commit position, sent after their source transaction is committed
+    pub pending_rows: Vec<DtItem>,
+    // key: topic
+    pub partition_counts: HashMap<String, i32>,
 }
 
 #[async_trait]
 impl Sinker for RdkafkaSinker {
-    async fn sink_dml(&mut self, data: Vec<RowData>, _batch: bool) -> anyhow::Result<()> {
+    async fn sink_dml(&mut self, mut data: Vec<RowData>, _batch: bool) -> anyhow::Result<()> {
+        if data.is_empty() {
+            return Ok(());
+        }
+
+        call_batch_fn!(self, data, Self::send_avro);
+        Ok(())
+    }
+
+    // json messages need the positions of rows, which are not in RowData
+    async fn sink_raw(&mut self, data: Vec<DtItem>, _batch: bool) -> anyhow::Result<()> {
+        if !self.transactional_id.is_empty() {
+            return self.sink_raw_in_transaction(data).await;
+        }
+
+        let mut data = self.message_builder.filter_raw_items(data);
         if data.is_empty() {
             return Ok(());
         }
 
-        self.send_avro(data).await
+        call_batch_fn!(self, data, Self::send_raw);
+        Ok(())
+    }
+
+    async fn sink_ddl(&mut self, data: Vec<DdlData>, _batch: bool) -> anyhow::Result<()> {
+        let messages = self.message_builder.build_ddl_messages(data).await?;
+        if self.transactional_id.is_empty() || messages.is_empty() {
+            return self.send(&messages).await;
+        }
+
+        // a transactional producer can only send inside a transaction, ddls get their own
+        self.begin_transaction().await?;
+        let result = self.send(&messages).await;
+        self.end_transaction(result).await
+    }
+
+    async fn refresh_meta(&mut self, data: Vec<DdlData>) -> anyhow::Result<()> {
+        self.message_builder.refresh_meta(&data);
+        Ok(())
     }
 }
 
 impl RdkafkaSinker {
-    async fn send_avro(&mut self, data: Vec<RowData>) -> anyhow::Result<()> {
-        let start_time = Instant::now();
-        let batch_size = data.len();
-        let mut data_size = 0;
+    /// Rows and the checkpoint position are sent in one kafka transaction, so consumers with
+    /// read_committed see them exactly once after the task resumes from the checkpoint.
+    /// Rows of uncommitted source transactions are held, the checkpoint is always a commit position
+    async fn sink_raw_in_transaction(&mut self, data: Vec<DtItem>) -> anyhow::Result<()> {
+        let mut rows = Vec::new();
+        let mut checkpoint = None;
+        for item in data {
+            match item.dt_data {
+                DtData::Dml { .. } => self.pending_rows.push(item),
+                DtData::Commit { .. } | DtData::Heartbeat {} => {
+                    checkpoint = Some(item.position.clone());
+                    rows.append(&mut self.pending_rows);
+                    // for END events of transaction_topic
+                    if item.dt_data.is_commit() {
+                        rows.push(item);
+                    }
+                }
+                _ => {}
+            }
+        }
+        let Some(checkpoint) = checkpoint else {
+            return Ok(());
+        };
+        // commits without rows, unless they end a transaction whose rows were sent before
+        let has_rows = rows.iter().any(|i| matches!(i.dt_data, DtData::Dml { .. }));
+        if !has_rows && self.message_builder.transaction.is_none() {
+            return Ok(());
+        }
 
-        let producer = &self.producer.clone();
-        let queue_timeout = Duration::from_secs(self.queue_timeout_secs);
-        let mut futures = Vec::new();
-
-        // This loop is non blocking: all messages will be sent one after the other, without waiting
-        // for the results.
-        for mut row_data in data {
-            data_size += row_data.data_size;
-            row_data.convert_raw_string();
-            let topic = self.router.get_topic(&row_data.schema, &row_data.tb);
-            let key = self.avro_converter.row_data_to_avro_key(&row_data).await?;
-            let payload = self.avro_converter.row_data_to_avro_value(row_data).await?;
-
-            // The send operation on the topic returns a future, which will be
-            // completed once the result or failure from Kafka is received.
-            let delivery_status = async move {
-                producer
-                    .send(
-                        FutureRecord::to(topic).payload(&payload).key(&key),
-                        queue_timeout,
-                    )
+        self.begin_transaction().await?;
+        let result = self.send_rows_with_checkpoint(rows, &checkpoint).await;
+        self.end_transaction(result).await
+    }
+
+    async fn begin_transaction(&self) -> anyhow::Result<()> {
+        self.run_blocking(|producer| producer.begin_transaction())
+            .await
+    }
+
+    /// Commits the current transaction if sending succeeded, aborts it otherwise
+    async fn end_transaction(&self, send_result: anyhow::Result<()>) -> anyhow::Result<()> {
+        let timeout = Duration::from_secs(self.queue_timeout_secs);
+        let result = match send_result {
+            Ok(()) => {
+                self.run_blocking(move |producer| producer.commit_transaction(timeout))
                     .await
+            }
+            Err(err) => Err(err),
+        };
+
+        if let Err(err) = result {
+            // the task fails and resumes from the last committed checkpoint
+            let abort_result = self
+                .run_blocking(move |producer| producer.abort_transaction(timeout))
+                .await;
+            bail! {Error::SinkerError(format!(
+                "kafka transaction aborted, error: {:?}, abort result: {:?}",
+                err, abort_result
+            ))}
+        }
+        Ok(())
+    }
+
+    /// Transaction apis and metadata fetching of rdkafka block until done or timeout,
+    /// they are run in the blocking thread pool to not stall other tasks of the runtime
+    async fn run_blocking<T, F>(&self, f: F) -> anyhow::Result<T>
+    where
+        T: Send + 'static,
+        F: FnOnce(FutureProducer) -> KafkaResult<T> + Send + 'static,
+    {
+        let producer = self.producer.clone();
+        Ok(tokio::task::spawn_blocking(move || f(producer)).await??)
+    }
+
+    async fn send_rows_with_checkpoint(
+        &mut self,
+        data: Vec<DtItem>,
+        checkpoint: &Position,
+    ) -> anyhow::Result<()> {
+        let mut data = self.message_builder.filter_raw_items(data);
+        call_batch_fn!(self, data, Self::send_raw);
+
+        let message = KafkaMessage::new(
+            self.checkpoint_topic.clone(),
+            self.transactional_id.clone().into_bytes(),
+            checkpoint.to_string().into_bytes(),
+        );
+        self.send(&[message]).await
+    }
+
+    async fn send_avro(
+        &mut self,
+        data: &mut [RowData],
+        sinked_count: usize,
+        batch_size: usize,
+    ) -> anyhow::Result<()> {
+        let start_time = Instant::now();
+        let data = &mut data[sinked_count..sinked_count + batch_size];
+        let data_size: usize = data.iter().map(|i| i.data_size).sum();
+
+        let messages = self.message_builder.build_avro_messages(data).await?;
+        self.send(&messages).await?;
+
+        BaseSinker::update_batch_monitor(&mut self.monitor, batch_size, data_size, start_time)
+    }
+
+    async fn send_raw(
+        &mut self,
+        data: &mut [DtItem],
+        sinked_count: usize,
+        batch_size: usize,
+    ) -> anyhow::Result<()> {
+        let start_time = Instant::now();
+        let data = &mut data[sinked_count..sinked_count + batch_size];
+        let data_size: usize = data.iter().map(|i| i.dt_data.get_data_size()).sum();
+
+        let messages = self.message_builder.build_raw_messages(data).await?;
+        self.send(&messages).await?;
+
+        BaseSinker::update_batch_monitor(&mut self.monitor, batch_size, data_size, start_time)
+    }
+
+    async fn send(&mut self, messages: &[KafkaMessage]) -> anyhow::Result<()> {
+        let mut partitions = Vec::with_capacity(messages.len());
+        for m in messages.iter() {
+            let partition_count = match m.partition {
+                KafkaPartition::Hash(_) => self.get_partition_count(&m.topic).await?,
+                _ => None,
             };
-            futures.push(delivery_status);
+            partitions.push(m.partition.resolve(partition_count));
         }
 
-        // This loop will wait until all delivery statuses have been received.
-        for future in futures {
-            if let Err(err) = future.await {
-                bail!(format!("failed in kafka producer, error: {:?}", err));
+        let queue_timeout = Duration::from_secs(self.queue_timeout_secs);
+        // all messages are enqueued in order first, then wait for the delivery results
+        let futures = messages.iter().zip(partitions).map(|(m, partition)| {
+            let mut record = FutureRecord::<[u8], [u8]>::to(&m.topic);
+            if !m.key.is_empty() {
+                record = record.key(m.key.as_slice());
+            }
+            if !m.payload.is_empty() {
+                record = record.payload(m.payload.as_slice());
+            }
+            if let Some(partition) = partition {
+                record = record.partition(partition);
+            }
+            if !m.headers.is_empty() {
+                let mut headers = OwnedHeaders::new();
+                for (key, value) in m.headers.iter() {
+                    headers = headers.insert(Header {
+                        key: key.as_str(),
+                        value: Some(value.as_str()),
+                    });
+                }
+                record = record.headers(headers);
+            }
+            self.producer.send(record, queue_timeout)
+        });
+
+        for result in join_all(futures).await {
+            if let Err((err, _)) = result {
+                bail! {Error::SinkerError(format!(
+                    "failed in kafka producer, error: {:?}",
+                    err
+                ))}
             }
         }
+        Ok(())
+    }
 
-        BaseSinker::update_batch_monitor(&mut self.monitor, batch_size, data_size, start_time)
+    async fn get_partition_count(&mut self, topic: &str) -> anyhow::Result<Option<i32>> {
+        if let Some(count) = self.partition_counts.get(topic) {
+            return Ok(Some(*count));
+        }
+
+        let timeout = Duration::from_secs(self.queue_timeout_secs);
+        let topic_name = topic.to_string();
+        let count = self
+            .run_blocking(move |producer| {
+                let metadata = producer
+                    .client()
+                    .fetch_metadata(Some(&topic_name), timeout)?;
+                Ok(metadata
+                    .topics()
+                    .iter()
+                    .find(|t| t.name() == topic_name)
+                    .map(|t| t.partitions().len() as i32)
+                    .filter(|count| *count > 0))
+            })
+            .await?;
+        if let Some(count) = count {
+            self.partition_counts.insert(topic.to_string(), count);
+        }
+        Ok(count)
     }
 }
@@ -1,2 +1,3 @@
 pub mod mongo_checker;
 pub mod mongo_sinker;
+pub mod mongo_to_rdb_converter;
@@ -1,4 +1,5 @@
 use std::{
+    collections::HashMap,
     sync::{Arc, Mutex},
     time::Instant,
 };
@@ -13,7 +14,8 @@ use mongodb::{
 use dt_common::{log_error, monitor::monitor::Monitor};
 
 use dt_common::meta::{
-    col_value::ColValue, mongo::mongo_constant::MongoConstants, row_data::RowData,
+    adaptor::mongo_col_value_convertor::MongoColValueConvertor, col_value::ColValue,
+    mongo::mongo_constant::MongoConstants, rdb_meta_manager::RdbMetaManager, row_data::RowData,
     row_type::RowType,
 };
 
@@ -25,6 +27,9 @@ pub struct MongoSinker {
     pub batch_size: usize,
     pub mongo_client: Client,
     pub monitor: Arc<Mutex<Monitor>>,
+    // only for rows from MySQL / Postgres, converted to documents with _id from id_cols
+    pub extractor_meta_manager: Option<RdbMetaManager>,
+    pub reverse_router: RdbRouter,
 }
 
 #[async_trait]
@@ -34,6 +39,10 @@ impl Sinker for MongoSinker {
             return Ok(());
         }
 
+        if self.extractor_meta_manager.is_some() {
+            data = self.convert_rdb_row_datas(data).await?;
+        }
+
         if !batch {
             self.serial_sink(data).await?;
         } else {
@@ -51,12 +60,83 @@ impl Sinker for MongoSinker {
     }
 
     async fn close(&mut self) -> anyhow::Result<()> {
+        if let Some(meta_manager) = &self.extractor_meta_manager {
+            meta_manager.close().await?;
+        }
         self.mongo_client.clone().shutdown().await;
         Ok(())
     }
 }
 
 impl MongoSinker {
+    /// Converts rows from MySQL / Postgres to rows with MongoDoc, the same as those from Mongo extractors.
+    async fn convert_rdb_row_datas(&mut self, data: Vec<RowData>) -> anyhow::Result<Vec<RowData>> {
+        let meta_manager = self.extractor_meta_manager.as_mut().unwrap();
+        let mut res = Vec::with_capacity(data.len());
+
+        for row_data in data {
+            // rows have been routed by extractor, tb_meta is fetched by the source schema and tb
+            let (src_schema, src_tb) = self
+                .reverse_router
+                .get_tb_map(&row_data.schema, &row_data.tb);
+            let (src_schema, src_tb) = (src_schema.to_string(), src_tb.to_string());
+            let tb_meta = meta_manager.get_tb_meta(&src_schema, &src_tb).await?;
+            let col_map = self.router.get_col_map(&src_schema, &src_tb);
+
+            let new_row_data = |row_type, before: Option<Document>, after: Option<Document>| {
+                let to_col_values = |doc| {
+                    HashMap::from([(MongoConstants::DOC.to_string(), ColValue::MongoDoc(doc))])
+                };
+                RowData {
+                    schema: row_data.schema.clone(),
+                    tb: row_data.tb.clone(),
+                    row_type,
+                    before: before.map(to_col_values),
+                    after: after.map(to_col_values),
+                    data_size: row_data.data_size,
+                }
+            };
+
+            match row_data.row_type {
+                RowType::Insert => {
+                    let after = row_data.after.as_ref().unwrap();
+                    let doc = MongoColValueConvertor::to_doc(after, tb_meta, col_map);
+                    res.push(new_row_data(RowType::Insert, None, Some(doc)));
+                }
+
+                RowType::Delete => {
+                    let before = row_data.before.as_ref().unwrap();
+                    let id = MongoColValueConvertor::to_id(before, tb_meta, col_map);
+                    let query_doc = doc! {MongoConstants::ID: id};
+                    res.push(new_row_data(RowType::Delete, Some(query_doc), None));
+                }
+
+                RowType::Update => {
+                    let before = row_data.before.as_ref().unwrap();
+                    let after = row_data.after.as_ref().unwrap();
+                    let id = MongoColValueConvertor::to_id(before, tb_meta, col_map);
+                    let mut doc = MongoColValueConvertor::to_doc(after, tb_meta, col_map);
+                    let query_doc = doc! {MongoConstants::ID: id.clone()};
+
+                    if doc.get(MongoConstants::ID) == Some(&id) {
+                        doc.remove(MongoConstants::ID);
+                        let update_doc = doc! {MongoConstants::SET: doc};
+                        res.push(new_row_data(
+                            RowType::Update,
+                            Some(query_doc),
+                            Some(update_doc),
+                        ));
+                    } else {
+                        // _id is immutable, delete the old document and insert the new one
+                        res.push(new_row_data(RowType::Delete, Some(query_doc), None));
+                        res.push(new_row_data(RowType::Insert, None, Some(doc)));
+                    }
+                }
+            }
+        }
+        Ok(res)
+    }
+
     async fn serial_sink(&mut self, mut data: Vec<RowData>) -> anyhow::Result<()> {
         let start_time = Instant::now();
         let mut data_size = 0;
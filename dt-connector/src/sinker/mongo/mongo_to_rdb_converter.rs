@@ -0,0 +1,125 @@
+use std::collections::HashMap;
+
+use dt_common::meta::{
+    adaptor::mongo_doc_flattener::MongoDocFlattener, col_value::ColValue,
+    mongo::mongo_constant::MongoConstants, rdb_tb_meta::RdbTbMeta, row_data::RowData,
+    row_type::RowType,
+};
+use mongodb::{
+    bson::{doc, Document},
+    Client,
+};
+
+use crate::rdb_router::RdbRouter;
+
+/// Converts rows from Mongo extractors for MySQL / Postgres sinkers,
+/// missing target tables are created from documents sampled in the source collections.
+#[derive(Clone)]
+pub struct MongoToRdbConverter {
+    pub flattener: MongoDocFlattener,
+    pub src_mongo_client: Client,
+    // col_map of router: {field_path: col}
+    pub router: RdbRouter,
+    pub reverse_router: RdbRouter,
+    pub sample_size: usize,
+}
+
+impl MongoToRdbConverter {
+    pub async fn convert_row_data(
+        &self,
+        row_data: &RowData,
+        tb_meta: &RdbTbMeta,
+    ) -> anyhow::Result<Option<RowData>> {
+        // rows have been routed by extractor, col_map is got by the source db and collection
+        let (src_db, src_tb) = self
+            .reverse_router
+            .get_tb_map(&row_data.schema, &row_data.tb);
+        let col_map = self.router.get_col_map(src_db, src_tb);
+
+        if let Some(diff_doc) = Self::get_doc(&row_data.after, MongoConstants::DIFF_DOC) {
+            if self.flattener.need_full_doc(diff_doc, col_map) {
+                let full_row_data = match self.get_full_doc(src_db, src_tb, row_data).await? {
+                    Some(doc) => RowData {
+                        schema: row_data.schema.clone(),
+                        tb: row_data.tb.clone(),
+                        row_type: RowType::Insert,
+                        before: None,
+                        after: Some(HashMap::from([(
+                            MongoConstants::DOC.to_string(),
+                            ColValue::MongoDoc(doc),
+                        )])),
+                        data_size: row_data.data_size,
+                    },
+                    // removed in source, the delete will come later
+                    None => return Ok(None),
+                };
+                return Ok(self
+                    .flattener
+                    .flatten_row_data(&full_row_data, col_map, tb_meta));
+            }
+        }
+        Ok(self.flattener.flatten_row_data(row_data, col_map, tb_meta))
+    }
+
+    /// Looks up the current document in source by _id of a diff Update,
+    /// the document may be newer than the Update, the later changes will be applied again.
+    async fn get_full_doc(
+        &self,
+        src_db: &str,
+        src_tb: &str,
+        row_data: &RowData,
+    ) -> anyhow::Result<Option<Document>> {
+        let Some(id) = Self::get_doc(&row_data.before, MongoConstants::DOC)
+            .and_then(|doc| doc.get(MongoConstants::ID))
+        else {
+            return Ok(None);
+        };
+        let collection = self
+            .src_mongo_client
+            .database(src_db)
+            .collection::<Document>(src_tb);
+        Ok(collection
+            .find_one(doc! {MongoConstants::ID: id.clone()}, None)
+            .await?)
+    }
+
+    fn get_doc<'a>(
+        col_values: &'a Option<HashMap<String, ColValue>>,
+        key: &str,
+    ) -> Option<&'a Document> {
+        match col_values.as_ref().and_then(|v| v.get(key)) {
+            Some(ColValue::MongoDoc(doc)) => Some(doc),
+            _ => None,
+        }
+    }
+
+    pub async fn get_create_tb_sqls(&self, row_data: &RowData) -> anyhow::Result<Vec<String>> {
+        let (src_db, src_tb) = self
+            .reverse_router
+            .get_tb_map(&row_data.schema, &row_data.tb);
+        let collection = self
+            .src_mongo_client
+            .database(src_db)
+            .collection::<Document>(src_tb);
+
+        let pipeline = vec![doc! {"$sample": {"size": self.sample_size as i64}}];
+        let mut cursor = collection.aggregate(pipeline, None).await?;
+        let mut docs = Vec::new();
+        while cursor.advance().await? {
+            docs.push(cursor.deserialize_current()?);
+        }
+        // the current document may have been changed or removed in source
+        if let Some(ColValue::MongoDoc(doc)) = row_data
+            .after
+            .as_ref()
+            .and_then(|v| v.get(MongoConstants::DOC))
+        {
+            docs.push(doc.clone());
+        }
+
+        let col_map = self.router.get_col_map(src_db, src_tb);
+        Ok(self
+            .flattener
+            .get_create_tb_sqls(&row_data.schema, &row_data.tb, &docs, col_map))
+    }
+}
@@ -18,7 +18,13 @@ use crate::{
 };
 
 use dt_common::{
-    meta::struct_meta::struct_data::StructData, monitor::monitor::Monitor, rdb_filter::RdbFilter,
+    config::config_enums::DbType,
+    meta::{
+        adaptor::pg_to_mysql_col_value_convertor::PgToMysqlColValueConvertor,
+        struct_meta::struct_data::StructData,
+    },
+    monitor::monitor::Monitor,
+    rdb_filter::RdbFilter,
 };
 
 use dt_common::meta::{
@@ -35,6 +41,7 @@ pub struct MysqlChecker {
     pub batch_size: usize,
     pub monitor: Arc<Mutex<Monitor>>,
     pub filter: RdbFilter,
+    pub src_db_type: DbType,
 }
 
 #[async_trait]
@@ -44,6 +51,14 @@ impl Sinker for MysqlChecker {
             return Ok(());
         }
 
+        // convert src values to what they would be in target before comparing
+        if self.src_db_type == DbType::Pg {
+            for row_data in data.iter_mut() {
+                let tb_meta = self.meta_manager.get_tb_meta_by_row_data(row_data).await?;
+                PgToMysqlColValueConvertor::convert_row_data(row_data, tb_meta);
+            }
+        }
+
         if !batch {
             self.serial_check(data).await?;
         } else {
@@ -5,16 +5,22 @@ use std::{
 };
 
 use crate::{
-    call_batch_fn, close_conn_pool, data_marker::DataMarker, rdb_query_builder::RdbQueryBuilder,
-    rdb_router::RdbRouter, sinker::base_sinker::BaseSinker, Sinker,
+    call_batch_fn, close_conn_pool,
+    data_marker::DataMarker,
+    rdb_query_builder::RdbQueryBuilder,
+    rdb_router::RdbRouter,
+    sinker::{base_sinker::BaseSinker, mongo::mongo_to_rdb_converter::MongoToRdbConverter},
+    Sinker,
 };
 
 use anyhow::Context;
 use dt_common::{
+    config::config_enums::{ConflictPolicyEnum, DbType},
     log_error, log_info,
     meta::{
+        adaptor::pg_to_mysql_col_value_convertor::PgToMysqlColValueConvertor,
         dcl_meta::dcl_data::DclData,
-        ddl_meta::{ddl_data::DdlData, ddl_type::DdlType},
+        ddl_meta::{ddl_data::DdlData, ddl_translator::DdlTranslator, ddl_type::DdlType},
     },
     monitor::monitor::Monitor,
 };
@@ -40,6 +46,10 @@ pub struct MysqlSinker {
     pub monitor: Arc<Mutex<Monitor>>,
     pub data_marker: Option<Arc<RwLock<DataMarker>>>,
     pub replace: bool,
+    pub src_db_type: DbType,
+    pub ddl_conflict_policy: ConflictPolicyEnum,
+    // only for documents from Mongo
+    pub mongo_converter: Option<MongoToRdbConverter>,
 }
 
 #[async_trait]
@@ -49,6 +59,17 @@ impl Sinker for MysqlSinker {
             return Ok(());
         }
 
+        if self.src_db_type == DbType::Pg {
+            self.convert_pg_row_datas(&mut data).await?;
+        }
+
+        if self.mongo_converter.is_some() {
+            data = self.convert_mongo_row_datas(data).await?;
+            if data.is_empty() {
+                return Ok(());
+            }
+        }
+
         if !batch {
             self.serial_sink(&data).await?;
         } else {
@@ -68,10 +89,16 @@ impl Sinker for MysqlSinker {
 
     async fn sink_ddl(&mut self, data: Vec<DdlData>, _batch: bool) -> anyhow::Result<()> {
         for ddl_data in data {
-            let sql = ddl_data.to_sql();
-            let query = sqlx::query(&sql);
+            // ddl from Postgres is translated to MySQL dialect
+            let sqls = match DdlTranslator::translate(&ddl_data, &DbType::Mysql) {
+                Ok(sqls) => sqls,
+                Err(err) if self.ddl_conflict_policy == ConflictPolicyEnum::Ignore => {
+                    log_error!("ddl skipped: {}, error: {}", ddl_data.query, err);
+                    continue;
+                }
+                Err(err) => return Err(err),
+            };
             let (db, _tb) = ddl_data.get_schema_tb();
-            log_info!("sink ddl, db: {}, sql: {}", db, sql);
 
             // create a tmp connection with databse since sqlx conn pool does NOT support `USE db`
             let mut conn_options = MySqlConnectOptions::from_str(&self.url)?;
@@ -88,7 +115,10 @@ impl Sinker for MysqlSinker {
                 .max_connections(1)
                 .connect_with(conn_options)
                 .await?;
-            query.execute(&conn_pool).await?;
+            for sql in sqls {
+                log_info!("sink ddl, db: {}, sql: {}", db, sql);
+                sqlx::query(&sql).execute(&conn_pool).await?;
+            }
             conn_pool.close().await;
         }
         Ok(())
@@ -118,6 +148,43 @@ impl Sinker for MysqlSinker {
 }
 
 impl MysqlSinker {
+    async fn convert_pg_row_datas(&mut self, data: &mut [RowData]) -> anyhow::Result<()> {
+        for row_data in data.iter_mut() {
+            let tb_meta = self.meta_manager.get_tb_meta_by_row_data(row_data).await?;
+            PgToMysqlColValueConvertor::convert_row_data(row_data, tb_meta);
+        }
+        Ok(())
+    }
+
+    async fn convert_mongo_row_datas(
+        &mut self,
+        data: Vec<RowData>,
+    ) -> anyhow::Result<Vec<RowData>> {
+        let converter = self.mongo_converter.as_ref().unwrap();
+        let mut res = Vec::with_capacity(data.len());
+        for row_data in data {
+            if self
+                .meta_manager
+                .get_tb_meta_by_row_data(&row_data)
+                .await
+                .is_err()
+            {
+                for sql in converter.get_create_tb_sqls(&row_data).await? {
+                    log_info!("create table for documents: {}", sql);
+                    sqlx::query(&sql).execute(&self.conn_pool).await?;
+                }
+            }
+            let tb_meta = self.meta_manager.get_tb_meta_by_row_data(&row_data).await?;
+            if let Some(row_data) = converter
+                .convert_row_data(&row_data, &tb_meta.basic)
+                .await?
+            {
+                res.push(row_data);
+            }
+        }
+        Ok(res)
+    }
+
     async fn serial_sink(&mut self, data: &[RowData]) -> anyhow::Result<()> {
         let start_time = Instant::now();
         let mut data_size = 0;
@@ -6,7 +6,11 @@ use crate::{
 };
 
 use dt_common::{
-    config::config_enums::ConflictPolicyEnum, meta::struct_meta::struct_data::StructData,
+    config::config_enums::ConflictPolicyEnum,
+    meta::struct_meta::{
+        pg_to_mysql_converter::PgToMysqlConverter, statement::struct_statement::StructStatement,
+        struct_data::StructData,
+    },
     rdb_filter::RdbFilter,
 };
 
@@ -25,6 +29,7 @@ pub struct MysqlStructSinker {
 #[async_trait]
 impl Sinker for MysqlStructSinker {
     async fn sink_struct(&mut self, data: Vec<StructData>) -> anyhow::Result<()> {
+        let data = self.convert_pg_structs(data);
         BaseStructSinker::sink_structs(
             &DBConnPool::MySQL(self.conn_pool.clone()),
             &self.conflict_policy,
@@ -38,3 +43,41 @@ impl Sinker for MysqlStructSinker {
         return close_conn_pool!(self);
     }
 }
+
+impl MysqlStructSinker {
+    // structures from postgres source are converted to mysql structures
+    fn convert_pg_structs(&self, data: Vec<StructData>) -> Vec<StructData> {
+        let reverse_router = self.router.reverse();
+        data.into_iter()
+            .map(|i| {
+                let statement = match i.statement {
+                    StructStatement::PgCreateSchema(statement) => {
+                        StructStatement::MysqlCreateDatabase(PgToMysqlConverter::convert_schema(
+                            statement,
+                        ))
+                    }
+
+                    StructStatement::PgCreateTable(statement) => {
+                        let src_schema = reverse_router
+                            .get_tb_map(&statement.table.schema_name, &statement.table.table_name)
+                            .0
+                            .to_string();
+                        StructStatement::MysqlCreateTable(PgToMysqlConverter::convert_table(
+                            &src_schema,
+                            statement,
+                        ))
+                    }
+
+                    // roles and privileges of postgres are not migrated
+                    StructStatement::PgCreateRbac(_) => StructStatement::Unknown,
+
+                    statement => statement,
+                };
+                StructData {
+                    schema: i.schema,
+                    statement,
+                }
+            })
+            .collect()
+    }
+}
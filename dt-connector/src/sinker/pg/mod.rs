@@ -1,3 +1,4 @@
 pub mod pg_checker;
+pub mod pg_copy_encoder;
 pub mod pg_sinker;
 pub mod pg_struct_sinker;
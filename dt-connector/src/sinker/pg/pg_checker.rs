@@ -9,7 +9,10 @@ use dt_common::meta::{
     pg::pg_meta_manager::PgMetaManager,
     rdb_meta_manager::RdbMetaManager,
     row_data::RowData,
-    struct_meta::{statement::struct_statement::StructStatement, struct_data::StructData},
+    struct_meta::{
+        mysql_to_pg_converter::MysqlToPgConverter, statement::struct_statement::StructStatement,
+        struct_data::StructData,
+    },
 };
 use dt_common::{monitor::monitor::Monitor, rdb_filter::RdbFilter};
 use futures::TryStreamExt;
@@ -20,7 +23,9 @@ use crate::{
     meta_fetcher::pg::pg_struct_fetcher::PgStructFetcher,
     rdb_query_builder::RdbQueryBuilder,
     rdb_router::RdbRouter,
-    sinker::{base_checker::BaseChecker, base_sinker::BaseSinker},
+    sinker::{
+        base_checker::BaseChecker, base_sinker::BaseSinker, pg::pg_struct_sinker::PgStructSinker,
+    },
     Sinker,
 };
 
@@ -29,10 +34,12 @@ pub struct PgChecker {
     pub conn_pool: Pool<Postgres>,
     pub meta_manager: PgMetaManager,
     pub extractor_meta_manager: RdbMetaManager,
+    pub router: RdbRouter,
     pub reverse_router: RdbRouter,
     pub batch_size: usize,
     pub monitor: Arc<Mutex<Monitor>>,
     pub filter: RdbFilter,
+    pub converter: MysqlToPgConverter,
 }
 
 #[async_trait]
@@ -150,7 +157,10 @@ impl PgChecker {
         BaseSinker::update_batch_monitor(&mut self.monitor, batch_size, 0, start_time)
     }
 
-    async fn serial_check_struct(&mut self, mut data: Vec<StructData>) -> anyhow::Result<()> {
+    async fn serial_check_struct(&mut self, data: Vec<StructData>) -> anyhow::Result<()> {
+        // structures from mysql source are compared after being converted the same way as
+        // PgStructSinker does
+        let mut data = PgStructSinker::convert_mysql_structs(&self.converter, &self.router, data);
         for src_data in data.iter_mut() {
             let src_statement = &mut src_data.statement;
             let schema = match src_statement {
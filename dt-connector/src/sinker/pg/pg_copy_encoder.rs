@@ -0,0 +1,384 @@
+use std::str::FromStr;
+
+use anyhow::{bail, Context};
+use byteorder::{BigEndian, WriteBytesExt};
+use dt_common::{
+    config::config_enums::PgInsertMethod,
+    error::Error,
+    meta::{
+        col_value::ColValue,
+        pg::{pg_col_type::PgColType, pg_tb_meta::PgTbMeta, pg_value_type::PgValueType},
+        row_data::RowData,
+    },
+};
+
+// https://www.postgresql.org/docs/current/sql-copy.html#id-1.9.3.55.9.4
+const BINARY_SIGNATURE: &[u8] = b"PGCOPY\n\xff\r\n\0";
+const TEXT_NULL: &[u8] = b"\\N";
+
+/// Encodes rows into the COPY ... FROM STDIN payload of a table.
+pub struct PgCopyEncoder<'a> {
+    pub tb_meta: &'a PgTbMeta,
+    pub binary: bool,
+}
+
+impl<'a> PgCopyEncoder<'a> {
+    pub fn new(tb_meta: &'a PgTbMeta, insert_method: &PgInsertMethod) -> Self {
+        // binary COPY requires the exact wire format of each column,
+        // fall back to text for tables with columns we can not encode
+        let binary = *insert_method == PgInsertMethod::CopyBinary
+            && tb_meta
+                .basic
+                .cols
+                .iter()
+                .all(|col| tb_meta.get_col_type(col).is_ok_and(Self::support_binary));
+        Self { tb_meta, binary }
+    }
+
+    pub fn get_copy_sql(&self) -> String {
+        let cols: Vec<String> = self
+            .tb_meta
+            .basic
+            .cols
+            .iter()
+            .map(|col| format!(r#""{}""#, col))
+            .collect();
+        format!(
+            r#"COPY "{}"."{}"({}) FROM STDIN WITH (FORMAT {})"#,
+            self.tb_meta.basic.schema,
+            self.tb_meta.basic.tb,
+            cols.join(","),
+            if self.binary { "binary" } else { "text" }
+        )
+    }
+
+    pub fn encode(
+        &self,
+        data: &[RowData],
+        start_index: usize,
+        batch_size: usize,
+    ) -> anyhow::Result<Vec<u8>> {
+        let mut buf = Vec::new();
+
+        if self.binary {
+            buf.extend_from_slice(BINARY_SIGNATURE);
+            // flags + header extension length
+            buf.write_i32::<BigEndian>(0)?;
+            buf.write_i32::<BigEndian>(0)?;
+        }
+
+        for row_data in data.iter().skip(start_index).take(batch_size) {
+            let after = row_data.after.as_ref().unwrap();
+            if self.binary {
+                buf.write_i16::<BigEndian>(self.tb_meta.basic.cols.len() as i16)?;
+            }
+
+            for (i, col) in self.tb_meta.basic.cols.iter().enumerate() {
+                let col_type = self.tb_meta.get_col_type(col)?;
+                let col_value = after.get(col).unwrap_or(&ColValue::None);
+                if self.binary {
+                    Self::encode_binary(&mut buf, col_value, col_type)
+                        .with_context(|| format!("failed to encode col: [{}]", col))?;
+                } else {
+                    if i > 0 {
+                        buf.push(b'\t');
+                    }
+                    Self::encode_text(&mut buf, col_value, col_type);
+                }
+            }
+
+            if !self.binary {
+                buf.push(b'\n');
+            }
+        }
+
+        if self.binary {
+            // file trailer
+            buf.write_i16::<BigEndian>(-1)?;
+        }
+        Ok(buf)
+    }
+
+    fn support_binary(col_type: &PgColType) -> bool {
+        // types of extensions / users may have their own binary formats
+        if !col_type.is_builtin() {
+            return false;
+        }
+
+        // by the real type rather than value_type, e.g. xid / tid are PgValueType::String
+        // and oid is PgValueType::Int64, but their binary formats differ
+        matches!(
+            col_type.alias.as_str(),
+            "bool"
+                | "int2"
+                | "int4"
+                | "int8"
+                | "float4"
+                | "float8"
+                | "text"
+                | "varchar"
+                | "bpchar"
+                | "name"
+                | "json"
+                | "jsonb"
+                | "bytea"
+                | "uuid"
+        )
+    }
+
+    fn encode_text(buf: &mut Vec<u8>, col_value: &ColValue, col_type: &PgColType) {
+        let str = match col_value {
+            ColValue::None => {
+                buf.extend_from_slice(TEXT_NULL);
+                return;
+            }
+
+            ColValue::Blob(v) | ColValue::RawString(v)
+                if col_type.value_type == PgValueType::Bytes =>
+            {
+                // bytea hex format, the leading backslash needs to be escaped in text COPY
+                buf.extend_from_slice(b"\\\\x");
+                buf.extend_from_slice(hex::encode(v).as_bytes());
+                return;
+            }
+
+            ColValue::Json(v) => String::from_utf8_lossy(v).to_string(),
+            _ => col_value.to_option_string().unwrap(),
+        };
+
+        for b in str.bytes() {
+            match b {
+                b'\\' => buf.extend_from_slice(b"\\\\"),
+                b'\t' => buf.extend_from_slice(b"\\t"),
+                b'\n' => buf.extend_from_slice(b"\\n"),
+                b'\r' => buf.extend_from_slice(b"\\r"),
+                _ => buf.push(b),
+            }
+        }
+    }
+
+    fn encode_binary(
+        buf: &mut Vec<u8>,
+        col_value: &ColValue,
+        col_type: &PgColType,
+    ) -> anyhow::Result<()> {
+        if *col_value == ColValue::None {
+            buf.write_i32::<BigEndian>(-1)?;
+            return Ok(());
+        }
+
+        let mut field = Vec::new();
+        match col_type.alias.as_str() {
+            "bool" => {
+                let v = match col_value {
+                    ColValue::Bool(v) => *v,
+                    _ => matches!(Self::to_str(col_value)?.as_str(), "t" | "true" | "1"),
+                };
+                field.push(v as u8);
+            }
+
+            "int2" => field.write_i16::<BigEndian>(Self::parse(col_value)?)?,
+            "int4" => field.write_i32::<BigEndian>(Self::parse(col_value)?)?,
+            "int8" => field.write_i64::<BigEndian>(Self::parse(col_value)?)?,
+            "float4" => field.write_f32::<BigEndian>(Self::parse(col_value)?)?,
+            "float8" => field.write_f64::<BigEndian>(Self::parse(col_value)?)?,
+
+            "bytea" => match col_value {
+                ColValue::Blob(v) | ColValue::RawString(v) | ColValue::Json(v) => {
+                    field.extend_from_slice(v)
+                }
+                _ => field.extend_from_slice(Self::to_str(col_value)?.as_bytes()),
+            },
+
+            "uuid" => {
+                let uuid = uuid::Uuid::parse_str(&Self::to_str(col_value)?)?;
+                field.extend_from_slice(uuid.as_bytes());
+            }
+
+            "text" | "varchar" | "bpchar" | "name" | "json" | "jsonb" => {
+                // jsonb binary format: 1 byte version + json text
+                if col_type.alias == "jsonb" {
+                    field.push(1);
+                }
+                match col_value {
+                    ColValue::Json(v) | ColValue::RawString(v) => field.extend_from_slice(v),
+                    _ => field.extend_from_slice(Self::to_str(col_value)?.as_bytes()),
+                }
+            }
+
+            _ => bail! {Error::SinkerError(format!(
+                "binary copy not supported for col type: {}",
+                col_type.alias
+            ))},
+        }
+
+        buf.write_i32::<BigEndian>(field.len() as i32)?;
+        buf.extend_from_slice(&field);
+        Ok(())
+    }
+
+    fn to_str(col_value: &ColValue) -> anyhow::Result<String> {
+        col_value
+            .to_option_string()
+            .with_context(|| format!("unexpected col value: {:?}", col_value))
+    }
+
+    fn parse<T: FromStr>(col_value: &ColValue) -> anyhow::Result<T> {
+        let str = Self::to_str(col_value)?;
+        match str.parse::<T>() {
+            Ok(v) => Ok(v),
+            Err(_) => bail! {Error::SinkerError(format!(
+                "failed to parse col value: {} for binary copy",
+                str
+            ))},
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use dt_common::meta::{rdb_tb_meta::RdbTbMeta, row_type::RowType};
+
+    use super::*;
+
+    fn mock_col_type(value_type: PgValueType, alias: &str) -> PgColType {
+        PgColType {
+            value_type,
+            name: alias.to_string(),
+            alias: alias.to_string(),
+            oid: 0,
+            parent_oid: 0,
+            element_oid: 0,
+            category: String::new(),
+            enum_values: None,
+            schema: "pg_catalog".to_string(),
+        }
+    }
+
+    fn mock_tb_meta(cols: Vec<(&str, PgColType)>) -> PgTbMeta {
+        let basic = RdbTbMeta {
+            schema: "db1".to_string(),
+            tb: "tb1".to_string(),
+            cols: cols.iter().map(|i| i.0.to_string()).collect(),
+            ..Default::default()
+        };
+        PgTbMeta {
+            basic,
+            oid: 0,
+            col_type_map: cols.into_iter().map(|i| (i.0.to_string(), i.1)).collect(),
+        }
+    }
+
+    fn mock_row_data(after: Vec<(&str, ColValue)>) -> RowData {
+        let after: HashMap<String, ColValue> =
+            after.into_iter().map(|(k, v)| (k.to_string(), v)).collect();
+        RowData::new(
+            "db1".to_string(),
+            "tb1".to_string(),
+            RowType::Insert,
+            None,
+            Some(after),
+        )
+    }
+
+    #[test]
+    fn test_encode_text() {
+        let tb_meta = mock_tb_meta(vec![
+            ("id", mock_col_type(PgValueType::Int32, "int4")),
+            ("name", mock_col_type(PgValueType::String, "text")),
+            ("data", mock_col_type(PgValueType::Bytes, "bytea")),
+            ("ts", mock_col_type(PgValueType::Timestamp, "timestamp")),
+        ]);
+        let data = vec![mock_row_data(vec![
+            ("id", ColValue::Long(1)),
+            ("name", ColValue::String("a\tb\\c\nd".to_string())),
+            ("data", ColValue::Blob(vec![0x01, 0xab])),
+            ("ts", ColValue::None),
+        ])];
+
+        // falls back to text since timestamp is not supported by binary
+        let encoder = PgCopyEncoder::new(&tb_meta, &PgInsertMethod::CopyBinary);
+        assert!(!encoder.binary);
+        assert_eq!(
+            encoder.get_copy_sql(),
+            r#"COPY "db1"."tb1"("id","name","data","ts") FROM STDIN WITH (FORMAT text)"#
+        );
+
+        let buf = encoder.encode(&data, 0, 1).unwrap();
+        assert_eq!(
+            String::from_utf8(buf).unwrap(),
+            "1\ta\\tb\\\\c\\nd\t\\\\x01ab\t\\N\n"
+        );
+    }
+
+    #[test]
+    fn test_encode_binary() {
+        let tb_meta = mock_tb_meta(vec![
+            ("id", mock_col_type(PgValueType::Int16, "int2")),
+            ("doc", mock_col_type(PgValueType::JSON, "jsonb")),
+            ("flag", mock_col_type(PgValueType::Boolean, "bool")),
+        ]);
+        let data = vec![mock_row_data(vec![
+            ("id", ColValue::Short(2)),
+            ("doc", ColValue::Json2("{}".to_string())),
+            ("flag", ColValue::None),
+        ])];
+
+        let encoder = PgCopyEncoder::new(&tb_meta, &PgInsertMethod::CopyBinary);
+        assert!(encoder.binary);
+
+        let buf = encoder.encode(&data, 0, 1).unwrap();
+        let mut expected = BINARY_SIGNATURE.to_vec();
+        expected.extend_from_slice(&[0, 0, 0, 0, 0, 0, 0, 0]);
+        // col count
+        expected.extend_from_slice(&[0, 3]);
+        // id
+        expected.extend_from_slice(&[0, 0, 0, 2, 0, 2]);
+        // doc
+        expected.extend_from_slice(&[0, 0, 0, 3, 1, b'{', b'}']);
+        // flag
+        expected.extend_from_slice(&[0xff, 0xff, 0xff, 0xff]);
+        // trailer
+        expected.extend_from_slice(&[0xff, 0xff]);
+        assert_eq!(buf, expected);
+    }
+
+    #[test]
+    fn test_binary_fallback_by_real_type() {
+        // xid is PgValueType::String and oid is PgValueType::Int64,
+        // but their binary formats are neither text nor int8
+        let tb_meta = mock_tb_meta(vec![
+            ("id", mock_col_type(PgValueType::Int32, "int4")),
+            ("tx", mock_col_type(PgValueType::String, "xid")),
+            ("obj", mock_col_type(PgValueType::Int64, "oid")),
+        ]);
+        let data = vec![mock_row_data(vec![
+            ("id", ColValue::Long(1)),
+            ("tx", ColValue::String("1234".to_string())),
+            ("obj", ColValue::LongLong(16384)),
+        ])];
+
+        let encoder = PgCopyEncoder::new(&tb_meta, &PgInsertMethod::CopyBinary);
+        assert!(!encoder.binary);
+        assert_eq!(
+            encoder.get_copy_sql(),
+            r#"COPY "db1"."tb1"("id","tx","obj") FROM STDIN WITH (FORMAT text)"#
+        );
+        let buf = encoder.encode(&data, 0, 1).unwrap();
+        assert_eq!(String::from_utf8(buf).unwrap(), "1\t1234\t16384\n");
+
+        for (value_type, alias) in [(PgValueType::String, "xid"), (PgValueType::Int64, "oid")] {
+            let col_type = mock_col_type(value_type, alias);
+            assert!(!PgCopyEncoder::support_binary(&col_type));
+            let mut buf = Vec::new();
+            assert!(PgCopyEncoder::encode_binary(
+                &mut buf,
+                &ColValue::String("1234".into()),
+                &col_type
+            )
+            .is_err());
+        }
+    }
+}
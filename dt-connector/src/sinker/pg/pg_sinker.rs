@@ -1,23 +1,32 @@
 use std::{
+    collections::HashMap,
     str::FromStr,
     sync::{Arc, Mutex, RwLock},
     time::Instant,
 };
 
 use crate::{
-    call_batch_fn, close_conn_pool, data_marker::DataMarker, rdb_query_builder::RdbQueryBuilder,
-    rdb_router::RdbRouter, sinker::base_sinker::BaseSinker, Sinker,
+    call_batch_fn, close_conn_pool,
+    data_marker::DataMarker,
+    rdb_query_builder::RdbQueryBuilder,
+    rdb_router::RdbRouter,
+    sinker::{
+        base_sinker::BaseSinker, mongo::mongo_to_rdb_converter::MongoToRdbConverter,
+        pg::pg_copy_encoder::PgCopyEncoder,
+    },
+    Sinker,
 };
 
 use anyhow::Context;
 use dt_common::{
-    log_error, log_info,
-    meta::ddl_meta::{ddl_data::DdlData, ddl_type::DdlType},
+    config::config_enums::{ConflictPolicyEnum, DbType, PgInsertMethod},
+    log_debug, log_error, log_info,
+    meta::ddl_meta::{ddl_data::DdlData, ddl_translator::DdlTranslator, ddl_type::DdlType},
     monitor::monitor::Monitor,
 };
 use sqlx::{
     postgres::{PgConnectOptions, PgPoolOptions},
-    Executor, Pool, Postgres,
+    Executor, PgConnection, Pool, Postgres,
 };
 
 use dt_common::meta::{pg::pg_meta_manager::PgMetaManager, row_data::RowData, row_type::RowType};
@@ -34,8 +43,18 @@ pub struct PgSinker {
     pub monitor: Arc<Mutex<Monitor>>,
     pub data_marker: Option<Arc<RwLock<DataMarker>>>,
     pub replace: bool,
+    pub insert_method: PgInsertMethod,
+    // replication origin prefix -> single-session pool tagged with a replication origin,
+    // only used if data_marker is replication_origin
+    pub origin_conn_pools: HashMap<String, Pool<Postgres>>,
+    pub ddl_conflict_policy: ConflictPolicyEnum,
+    // only for documents from Mongo
+    pub mongo_converter: Option<MongoToRdbConverter>,
 }
 
+// max replication origins per data_origin_node, should be >= concurrent sinker sessions
+const MAX_REPLICATION_ORIGINS: usize = 1024;
+
 #[async_trait]
 impl Sinker for PgSinker {
     async fn sink_dml(&mut self, mut data: Vec<RowData>, batch: bool) -> anyhow::Result<()> {
@@ -43,13 +62,25 @@ impl Sinker for PgSinker {
             return Ok(());
         }
 
+        if self.mongo_converter.is_some() {
+            data = self.convert_mongo_row_datas(data).await?;
+            if data.is_empty() {
+                return Ok(());
+            }
+        }
+
         if !batch {
             self.serial_sink(&data).await?;
         } else {
             match data[0].row_type {
-                RowType::Insert => {
-                    call_batch_fn!(self, data, Self::batch_insert);
-                }
+                RowType::Insert => match self.insert_method {
+                    PgInsertMethod::Insert => {
+                        call_batch_fn!(self, data, Self::batch_insert);
+                    }
+                    _ => {
+                        call_batch_fn!(self, data, Self::batch_copy);
+                    }
+                },
                 RowType::Delete => {
                     call_batch_fn!(self, data, Self::batch_delete);
                 }
@@ -61,6 +92,16 @@ impl Sinker for PgSinker {
 
     async fn sink_ddl(&mut self, data: Vec<DdlData>, _batch: bool) -> anyhow::Result<()> {
         for ddl_data in data {
+            // ddl from MySQL is translated to Postgres dialect
+            let sqls = match DdlTranslator::translate(&ddl_data, &DbType::Pg) {
+                Ok(sqls) => sqls,
+                Err(err) if self.ddl_conflict_policy == ConflictPolicyEnum::Ignore => {
+                    log_error!("ddl skipped: {}, error: {}", ddl_data.query, err);
+                    continue;
+                }
+                Err(err) => return Err(err),
+            };
+
             let (schema, _tb) = ddl_data.get_schema_tb();
             let conn_options = PgConnectOptions::from_str(&self.url)?;
             let mut pool_options = PgPoolOptions::new().max_connections(1);
@@ -68,7 +109,12 @@ impl Sinker for PgSinker {
 
             if !schema.is_empty() {
                 match ddl_data.ddl_type {
-                    DdlType::CreateSchema | DdlType::DropSchema | DdlType::AlterSchema => {}
+                    DdlType::CreateSchema
+                    | DdlType::DropSchema
+                    | DdlType::AlterSchema
+                    | DdlType::CreateDatabase
+                    | DdlType::DropDatabase
+                    | DdlType::AlterDatabase => {}
                     _ => {
                         pool_options = pool_options.after_connect(move |conn, _meta| {
                             let sql = sql.clone();
@@ -81,12 +127,11 @@ impl Sinker for PgSinker {
                 }
             }
 
-            let sql = ddl_data.to_sql();
-            log_info!("sink ddl, schema: {}, sql: {}", schema, sql);
-
             let conn_pool = pool_options.connect_with(conn_options).await?;
-            let query = sqlx::query(&sql);
-            query.execute(&conn_pool).await?;
+            for sql in sqls {
+                log_info!("sink ddl, schema: {}, sql: {}", schema, sql);
+                sqlx::query(&sql).execute(&conn_pool).await?;
+            }
             conn_pool.close().await;
         }
         Ok(())
@@ -101,16 +146,50 @@ impl Sinker for PgSinker {
 
     async fn close(&mut self) -> anyhow::Result<()> {
         self.meta_manager.close().await?;
+        // origins are kept since pgoutput looks up their names while decoding the WAL
+        for (_, conn_pool) in self.origin_conn_pools.drain() {
+            conn_pool.close().await;
+        }
         return close_conn_pool!(self);
     }
 }
 
 impl PgSinker {
+    async fn convert_mongo_row_datas(
+        &mut self,
+        data: Vec<RowData>,
+    ) -> anyhow::Result<Vec<RowData>> {
+        let converter = self.mongo_converter.as_ref().unwrap();
+        let mut res = Vec::with_capacity(data.len());
+        for row_data in data {
+            if self
+                .meta_manager
+                .get_tb_meta_by_row_data(&row_data)
+                .await
+                .is_err()
+            {
+                for sql in converter.get_create_tb_sqls(&row_data).await? {
+                    log_info!("create table for documents: {}", sql);
+                    sqlx::query(&sql).execute(&self.conn_pool).await?;
+                }
+            }
+            let tb_meta = self.meta_manager.get_tb_meta_by_row_data(&row_data).await?;
+            if let Some(row_data) = converter
+                .convert_row_data(&row_data, &tb_meta.basic)
+                .await?
+            {
+                res.push(row_data);
+            }
+        }
+        Ok(res)
+    }
+
     async fn serial_sink(&mut self, data: &[RowData]) -> anyhow::Result<()> {
         let start_time = Instant::now();
         let mut data_size = 0;
 
-        let mut tx = self.conn_pool.begin().await?;
+        let conn_pool = self.get_conn_pool().await?;
+        let mut tx = conn_pool.begin().await?;
         if let Some(sql) = self.get_data_marker_sql() {
             sqlx::query(&sql)
                 .execute(&mut tx)
@@ -150,13 +229,14 @@ impl PgSinker {
             query_builder.get_batch_delete_query(data, start_index, batch_size)?;
         let query = query_builder.create_pg_query(&query_info);
 
+        let conn_pool = self.get_conn_pool().await?;
         if let Some(sql) = self.get_data_marker_sql() {
-            let mut tx = self.conn_pool.begin().await?;
+            let mut tx = conn_pool.begin().await?;
             sqlx::query(&sql).execute(&mut tx).await?;
             query.execute(&mut tx).await?;
             tx.commit().await?;
         } else {
-            query.execute(&self.conn_pool).await?;
+            query.execute(&conn_pool).await?;
         }
 
         BaseSinker::update_batch_monitor(&mut self.monitor, batch_size, data_size, start_time)
@@ -181,13 +261,14 @@ impl PgSinker {
             query_builder.get_batch_insert_query(data, start_index, batch_size, self.replace)?;
         let query = query_builder.create_pg_query(&query_info);
 
+        let conn_pool = self.get_conn_pool().await?;
         let exec_error = if let Some(sql) = self.get_data_marker_sql() {
-            let mut tx = self.conn_pool.begin().await?;
+            let mut tx = conn_pool.begin().await?;
             sqlx::query(&sql).execute(&mut tx).await?;
             query.execute(&mut tx).await?;
             tx.commit().await
         } else {
-            match query.execute(&self.conn_pool).await {
+            match query.execute(&conn_pool).await {
                 Err(e) => Err(e),
                 _ => Ok(()),
             }
@@ -207,9 +288,163 @@ impl PgSinker {
         BaseSinker::update_batch_monitor(&mut self.monitor, batch_size, data_size, start_time)
     }
 
+    async fn batch_copy(
+        &mut self,
+        data: &mut [RowData],
+        start_index: usize,
+        batch_size: usize,
+    ) -> anyhow::Result<()> {
+        let start_time = Instant::now();
+
+        let tb_meta = self
+            .meta_manager
+            .get_tb_meta_by_row_data(&data[0])
+            .await?
+            .to_owned();
+        let encoder = PgCopyEncoder::new(&tb_meta, &self.insert_method);
+        let copy_sql = encoder.get_copy_sql();
+        let data_size: usize = data
+            .iter()
+            .skip(start_index)
+            .take(batch_size)
+            .map(|i| i.data_size)
+            .sum();
+
+        let conn_pool = self.get_conn_pool().await?;
+        let data_marker_sql = self.get_data_marker_sql();
+        let exec_error = match encoder.encode(data, start_index, batch_size) {
+            Ok(payload) => Self::copy_in(&conn_pool, &copy_sql, payload, data_marker_sql).await,
+            Err(error) => Err(error),
+        };
+
+        if let Err(error) = exec_error {
+            log_error!(
+                "batch copy failed, will insert one by one, schema: {}, tb: {}, error: {}",
+                tb_meta.basic.schema,
+                tb_meta.basic.tb,
+                error.to_string()
+            );
+            let sub_data = &data[start_index..start_index + batch_size];
+            self.serial_sink(sub_data).await?;
+        }
+
+        BaseSinker::update_batch_monitor(&mut self.monitor, batch_size, data_size, start_time)
+    }
+
+    async fn copy_in(
+        conn_pool: &Pool<Postgres>,
+        copy_sql: &str,
+        payload: Vec<u8>,
+        data_marker_sql: Option<String>,
+    ) -> anyhow::Result<()> {
+        let mut tx = conn_pool.begin().await?;
+        if let Some(sql) = data_marker_sql {
+            sqlx::query(&sql).execute(&mut tx).await?;
+        }
+        let mut copy_in = tx.copy_in_raw(copy_sql).await?;
+        copy_in.send(payload).await?;
+        copy_in.finish().await?;
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn get_conn_pool(&mut self) -> anyhow::Result<Pool<Postgres>> {
+        let origin_prefix = match &self.data_marker {
+            Some(data_marker) => {
+                let data_marker = data_marker.read().unwrap();
+                if !data_marker.is_replication_origin() {
+                    return Ok(self.conn_pool.clone());
+                }
+                data_marker.get_replication_origin_prefix()
+            }
+            None => return Ok(self.conn_pool.clone()),
+        };
+
+        if let Some(conn_pool) = self.origin_conn_pools.get(&origin_prefix) {
+            return Ok(conn_pool.clone());
+        }
+
+        let conn_options = PgConnectOptions::from_str(&self.url)?;
+        let prefix = origin_prefix.clone();
+        let conn_pool = PgPoolOptions::new()
+            .max_connections(1)
+            .after_connect(move |conn, _meta| {
+                let prefix = prefix.clone();
+                Box::pin(async move {
+                    // disable foreign key checks
+                    conn.execute("SET session_replication_role = 'replica';")
+                        .await?;
+                    Self::setup_replication_origin(conn, &prefix).await
+                })
+            })
+            .connect_with(conn_options)
+            .await?;
+        self.origin_conn_pools
+            .insert(origin_prefix, conn_pool.clone());
+        Ok(conn_pool)
+    }
+
+    async fn setup_replication_origin(
+        conn: &mut PgConnection,
+        prefix: &str,
+    ) -> Result<(), sqlx::Error> {
+        // a replication origin can only be active in one session at a time,
+        // try the next one if it is taken by another sinker
+        let mut last_error = None;
+        for i in 0..MAX_REPLICATION_ORIGINS {
+            let origin_name = format!("{}{}", prefix, i);
+            let sql = format!(
+                "SELECT pg_replication_origin_create('{0}') WHERE pg_replication_origin_oid('{0}') IS NULL",
+                origin_name
+            );
+            // may fail if the origin was just created by another sinker, which is fine
+            if let Err(error) = conn.execute(sql.as_str()).await {
+                log_debug!(
+                    "create replication origin: {} failed, error: {}",
+                    origin_name,
+                    error
+                );
+            }
+
+            let sql = format!(
+                "SELECT pg_replication_origin_session_setup('{}')",
+                origin_name
+            );
+            match conn.execute(sql.as_str()).await {
+                Ok(_) => {
+                    log_info!(
+                        "sinker session setup with replication origin: {}",
+                        origin_name
+                    );
+                    return Ok(());
+                }
+                Err(error) => {
+                    // 55006: object_in_use, the origin is active in another session
+                    let in_use = error
+                        .as_database_error()
+                        .and_then(|e| e.code())
+                        .is_some_and(|code| code == "55006");
+                    if !in_use {
+                        return Err(error);
+                    }
+                    last_error = Some(error);
+                }
+            }
+        }
+
+        Err(sqlx::Error::Protocol(format!(
+            "failed to setup replication origin with prefix: {}, error: {:?}",
+            prefix, last_error
+        )))
+    }
+
     fn get_data_marker_sql(&self) -> Option<String> {
         if let Some(data_marker) = &self.data_marker {
             let data_marker = data_marker.read().unwrap();
+            // sessions are tagged with replication origins instead
+            if data_marker.is_replication_origin() {
+                return None;
+            }
             // CREATE TABLE ape_trans_pg.topo1 (
             //     data_origin_node varchar(255) NOT NULL,
             //     src_node varchar(255) NOT NULL,
@@ -6,7 +6,11 @@ use crate::{
 };
 
 use dt_common::{
-    config::config_enums::ConflictPolicyEnum, meta::struct_meta::struct_data::StructData,
+    config::config_enums::ConflictPolicyEnum,
+    meta::struct_meta::{
+        mysql_to_pg_converter::MysqlToPgConverter, statement::struct_statement::StructStatement,
+        struct_data::StructData,
+    },
     rdb_filter::RdbFilter,
 };
 
@@ -19,11 +23,13 @@ pub struct PgStructSinker {
     pub conflict_policy: ConflictPolicyEnum,
     pub filter: RdbFilter,
     pub router: RdbRouter,
+    pub converter: MysqlToPgConverter,
 }
 
 #[async_trait]
 impl Sinker for PgStructSinker {
     async fn sink_struct(&mut self, data: Vec<StructData>) -> anyhow::Result<()> {
+        let data = Self::convert_mysql_structs(&self.converter, &self.router, data);
         BaseStructSinker::sink_structs(
             &DBConnPool::PostgreSQL(self.conn_pool.clone()),
             &self.conflict_policy,
@@ -38,4 +44,47 @@ impl Sinker for PgStructSinker {
     }
 }
 
-impl PgStructSinker {}
+impl PgStructSinker {
+    // structures from mysql source are converted to postgres structures,
+    // also used by PgChecker to compare them with the target
+    pub fn convert_mysql_structs(
+        converter: &MysqlToPgConverter,
+        router: &RdbRouter,
+        data: Vec<StructData>,
+    ) -> Vec<StructData> {
+        let reverse_router = router.reverse();
+        // foreign key references are in source names
+        let route_tb = |db: &str, tb: &str| {
+            let (dst_db, dst_tb) = router.get_tb_map(db, tb);
+            (dst_db.to_string(), dst_tb.to_string())
+        };
+        data.into_iter()
+            .map(|i| {
+                let statement = match i.statement {
+                    StructStatement::MysqlCreateDatabase(statement) => {
+                        StructStatement::PgCreateSchema(MysqlToPgConverter::convert_database(
+                            statement,
+                        ))
+                    }
+
+                    StructStatement::MysqlCreateTable(statement) => {
+                        let (src_db, src_tb) = reverse_router.get_tb_map(
+                            &statement.table.database_name,
+                            &statement.table.table_name,
+                        );
+                        let (src_db, src_tb) = (src_db.to_string(), src_tb.to_string());
+                        StructStatement::PgCreateTable(
+                            converter.convert_table(&src_db, &src_tb, statement, route_tb),
+                        )
+                    }
+
+                    statement => statement,
+                };
+                StructData {
+                    schema: i.schema,
+                    statement,
+                }
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,7 @@
+// Generates the messages, server and client of proto/dt_stream.proto by the vendored protoc,
+// the proto file used by clients of other languages is the only definition of the service
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    std::env::set_var("PROTOC", protoc_bin_vendored::protoc_bin_path()?);
+    tonic_build::compile_protos("proto/dt_stream.proto")?;
+    Ok(())
+}
@@ -8,7 +8,10 @@ use std::{
 
 use async_trait::async_trait;
 use dt_common::{
-    config::sinker_config::SinkerConfig,
+    config::{
+        config_enums::{KafkaHeader, MessageFormat},
+        sinker_config::SinkerConfig,
+    },
     meta::{
         dcl_meta::dcl_data::DclData,
         ddl_meta::ddl_data::DdlData,
@@ -116,6 +119,9 @@ impl Pipeline for BasePipeline {
                 last_commit_position = position.to_owned();
             }
 
+            if last_checkpoint_time.elapsed().as_secs() >= self.checkpoint_interval_secs {
+                self.flush_sinkers().await?;
+            }
             last_checkpoint_time = self.record_checkpoint(
                 Some(last_checkpoint_time),
                 &last_received_position,
@@ -131,6 +137,7 @@ impl Pipeline for BasePipeline {
             TimeUtil::sleep_millis(1).await;
         }
 
+        self.flush_sinkers().await?;
         self.record_checkpoint(None, &last_received_position, &last_commit_position);
         Ok(())
     }
@@ -211,6 +218,17 @@ impl BasePipeline {
         Ok((count, last_received_position, last_commit_position))
     }
 
+    /// Checks the value of an authorization header against the bearer token,
+    /// compared in constant time so the token can not be guessed byte by byte from timings
+    pub fn check_bearer_token(authorization: Option<&str>, auth_token: &str) -> bool {
+        authorization
+            .and_then(|v| v.strip_prefix("Bearer "))
+            .is_some_and(|v| {
+                v.len() == auth_token.len()
+                    && openssl::memcmp::eq(v.as_bytes(), auth_token.as_bytes())
+            })
+    }
+
     pub fn fetch_raw(data: &[DtItem]) -> (Option<Position>, Option<Position>) {
         let mut last_received_position = Option::None;
         let mut last_commit_position = Option::None;
@@ -323,6 +341,22 @@ impl BasePipeline {
                     SinkerConfig::FoxlakePush { .. }
                     | SinkerConfig::FoxlakeMerge { .. }
                     | SinkerConfig::Foxlake { .. } => return SinkMethod::Raw,
+                    // json messages and position headers need the positions of rows,
+                    // transactions need the positions to be committed with,
+                    // and transaction metadata needs the commits
+                    SinkerConfig::Kafka {
+                        ref message_format,
+                        ref transactional_id,
+                        ref headers,
+                        ref transaction_topic,
+                        ..
+                    } if *message_format != MessageFormat::Avro
+                        || !transactional_id.is_empty()
+                        || headers.contains(&KafkaHeader::Position)
+                        || !transaction_topic.is_empty() =>
+                    {
+                        return SinkMethod::Raw
+                    }
                     _ => return SinkMethod::Dml,
                 },
                 DtData::Redis { .. } | DtData::Foxlake { .. } => return SinkMethod::Raw,
@@ -334,6 +368,14 @@ impl BasePipeline {
         SinkMethod::Raw
     }
 
+    // data buffered in sinkers (file) should be persisted before its position is committed
+    async fn flush_sinkers(&self) -> anyhow::Result<()> {
+        for sinker in self.sinkers.iter() {
+            sinker.lock().await.flush().await?;
+        }
+        Ok(())
+    }
+
     fn record_checkpoint(
         &self,
         last_checkpoint_time: Option<Instant>,
@@ -0,0 +1,94 @@
+// Messages generated from proto/dt_stream.proto, and their conversions from DtItem
+
+use std::collections::HashMap;
+
+use dt_common::meta::{
+    col_value::ColValue,
+    dt_data::{DtData, DtItem},
+};
+
+tonic::include_proto!("ape_dts.v1");
+
+impl Item {
+    /// Rows, ddls and commits, None for other items
+    pub fn from_dt_item(item: DtItem) -> Option<Self> {
+        let data = match item.dt_data {
+            DtData::Dml { mut row_data } => {
+                row_data.convert_raw_string();
+                let to_columns = |col_values: Option<HashMap<String, ColValue>>| {
+                    col_values.map(|col_values| Columns {
+                        values: col_values
+                            .iter()
+                            .map(|(col, value)| (col.clone(), Value::from(value)))
+                            .collect(),
+                    })
+                };
+                item::Data::Row(Row {
+                    row_type: row_data.row_type.to_string(),
+                    schema: row_data.schema,
+                    tb: row_data.tb,
+                    before: to_columns(row_data.before),
+                    after: to_columns(row_data.after),
+                })
+            }
+
+            DtData::Ddl { ddl_data } => item::Data::Ddl(Ddl {
+                db_type: ddl_data.db_type.to_string(),
+                schema: ddl_data.default_schema,
+                ddl_type: ddl_data.ddl_type.to_string(),
+                query: ddl_data.query,
+            }),
+
+            DtData::Commit { xid } => item::Data::Commit(Commit { xid }),
+
+            _ => return None,
+        };
+        Some(Self {
+            data: Some(data),
+            position: item.position.to_string(),
+        })
+    }
+}
+
+impl From<&ColValue> for Value {
+    fn from(value: &ColValue) -> Self {
+        use value::Kind;
+        let kind = match value {
+            ColValue::None => None,
+            ColValue::Bool(v) => Some(Kind::BoolValue(*v)),
+
+            ColValue::Tiny(v) => Some(Kind::IntValue(*v as i64)),
+            ColValue::Short(v) => Some(Kind::IntValue(*v as i64)),
+            ColValue::Long(v) => Some(Kind::IntValue(*v as i64)),
+            ColValue::LongLong(v) => Some(Kind::IntValue(*v)),
+            ColValue::Year(v) => Some(Kind::IntValue(*v as i64)),
+
+            ColValue::UnsignedTiny(v) => Some(Kind::UintValue(*v as u64)),
+            ColValue::UnsignedShort(v) => Some(Kind::UintValue(*v as u64)),
+            ColValue::UnsignedLong(v) => Some(Kind::UintValue(*v as u64)),
+            ColValue::UnsignedLongLong(v) => Some(Kind::UintValue(*v)),
+            ColValue::Bit(v) | ColValue::Set(v) => Some(Kind::UintValue(*v)),
+            ColValue::Enum(v) => Some(Kind::UintValue(*v as u64)),
+
+            ColValue::Float(v) => Some(Kind::DoubleValue(*v as f64)),
+            ColValue::Double(v) => Some(Kind::DoubleValue(*v)),
+
+            ColValue::Decimal(v)
+            | ColValue::Time(v)
+            | ColValue::Date(v)
+            | ColValue::DateTime(v)
+            | ColValue::Timestamp(v)
+            | ColValue::String(v)
+            | ColValue::Set2(v)
+            | ColValue::Enum2(v)
+            | ColValue::Json2(v) => Some(Kind::StringValue(v.clone())),
+            ColValue::Json3(v) => Some(Kind::StringValue(v.to_string())),
+            ColValue::MongoDoc(v) => Some(Kind::StringValue(v.to_string())),
+
+            ColValue::Blob(v) | ColValue::Json(v) | ColValue::RawString(v) => {
+                Some(Kind::BytesValue(v.clone()))
+            }
+        };
+        Self { kind }
+    }
+}
@@ -0,0 +1,539 @@
+use std::{
+    collections::BTreeMap,
+    net::ToSocketAddrs,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+};
+
+use anyhow::Context;
+use async_trait::async_trait;
+use dt_common::{
+    log_info, log_position, log_warn,
+    meta::{dt_queue::DtQueue, position::Position, syncer::Syncer},
+    monitor::{counter_type::CounterType, monitor::Monitor},
+    utils::time_util::TimeUtil,
+};
+use dt_parallelizer::base_parallelizer::BaseParallelizer;
+use futures::{channel::mpsc, FutureExt, SinkExt};
+use tonic::{transport::Server, Request, Response, Status, Streaming};
+
+use crate::{
+    base_pipeline::BasePipeline,
+    grpc_message::{
+        consume_request,
+        dt_stream_server::{DtStream, DtStreamServer},
+        Batch, ConsumeRequest, Item, Subscribe,
+    },
+    Pipeline,
+};
+
+type PositionInfo = (Option<Position>, Option<Position>);
+
+const DEFAULT_BATCH_SIZE: usize = 1000;
+const DEFAULT_MAX_UNACKED_BATCHES: u64 = 10;
+const WAIT_INTERVAL_MILLIS: u64 = 100;
+
+/// Serves the data in buffer by the bidirectional stream DtStream.Consume of
+/// proto/dt_stream.proto, only one stream is served at a time.
+#[derive(Clone)]
+pub struct GrpcServerPipeline {
+    pub buffer: Arc<DtQueue>,
+    pub syncer: Arc<Mutex<Syncer>>,
+    pub monitor: Arc<Mutex<Monitor>>,
+    pub grpc_host: String,
+    pub grpc_port: u64,
+    pub auth_token: String,
+
+    state: Arc<async_std::sync::Mutex<StreamState>>,
+    streaming: Arc<AtomicBool>,
+}
+
+/// Kept across streams, batches not acked by a closed stream are resent by the next one
+#[derive(Default)]
+struct StreamState {
+    sent_batch_id: u64,
+    acked_batch_id: u64,
+    // key: batch_id
+    pending_batches: BTreeMap<u64, (Batch, PositionInfo)>,
+}
+
+impl StreamState {
+    /// Positions of the batches acked
+    fn ack(&mut self, ack_batch_id: u64) -> Result<PositionInfo, Status> {
+        if ack_batch_id > self.sent_batch_id {
+            return Err(Status::invalid_argument(format!(
+                "ack batch_id: [{}] must <= sent_batch_id: [{}]",
+                ack_batch_id, self.sent_batch_id
+            )));
+        }
+
+        if ack_batch_id < self.acked_batch_id {
+            return Err(Status::invalid_argument(format!(
+                "ack batch_id: [{}] must >= acked_batch_id: [{}]",
+                ack_batch_id, self.acked_batch_id
+            )));
+        }
+
+        let mut acked_position_info = (None, None);
+        while let Some(entry) = self.pending_batches.first_entry() {
+            if *entry.key() > ack_batch_id {
+                break;
+            }
+            let (_, position_info) = entry.remove();
+            merge_position_info(&mut acked_position_info, position_info);
+        }
+        self.acked_batch_id = ack_batch_id;
+        Ok(acked_position_info)
+    }
+}
+
+impl GrpcServerPipeline {
+    pub fn new(
+        buffer: Arc<DtQueue>,
+        syncer: Arc<Mutex<Syncer>>,
+        monitor: Arc<Mutex<Monitor>>,
+        grpc_host: &str,
+        grpc_port: u64,
+        auth_token: &str,
+    ) -> Self {
+        Self {
+            buffer,
+            syncer,
+            monitor,
+            grpc_host: grpc_host.into(),
+            grpc_port,
+            auth_token: auth_token.into(),
+            state: Default::default(),
+            streaming: Default::default(),
+        }
+    }
+
+    async fn run_stream(
+        self,
+        mut inbound: Streaming<ConsumeRequest>,
+        subscribe: Subscribe,
+        mut outbound: mpsc::Sender<Result<Batch, Status>>,
+    ) {
+        if let Err(status) = self
+            .serve_stream(&mut inbound, subscribe, &mut outbound)
+            .await
+        {
+            log_warn!("grpc stream closed by error: {}", status);
+            let _ = outbound.send(Err(status)).await;
+        } else {
+            log_info!("grpc stream closed");
+        }
+        self.streaming.store(false, Ordering::SeqCst);
+    }
+
+    async fn serve_stream(
+        &self,
+        inbound: &mut Streaming<ConsumeRequest>,
+        subscribe: Subscribe,
+        outbound: &mut mpsc::Sender<Result<Batch, Status>>,
+    ) -> Result<(), Status> {
+        let batch_size = match subscribe.batch_size {
+            0 => DEFAULT_BATCH_SIZE,
+            n => n as usize,
+        };
+        let max_unacked_batches = match subscribe.max_unacked_batches {
+            0 => DEFAULT_MAX_UNACKED_BATCHES,
+            n => n as u64,
+        };
+        log_info!(
+            "grpc stream starts, batch_size: {}, max_unacked_batches: {}",
+            batch_size,
+            max_unacked_batches
+        );
+
+        // batches after last_sent_batch_id are sent by the previous stream but not acked
+        let mut last_sent_batch_id = self.state.lock().await.acked_batch_id;
+        loop {
+            // handle the acks already received without waiting
+            while let Some(request) = inbound.message().now_or_never() {
+                if !self
+                    .handle_request(request?, &mut last_sent_batch_id)
+                    .await?
+                {
+                    return Ok(());
+                }
+            }
+
+            // flow control: at most max_unacked_batches are sent but not acked
+            let next_batch = {
+                let mut state = self.state.lock().await;
+                if last_sent_batch_id - state.acked_batch_id < max_unacked_batches {
+                    self.next_batch(&mut state, last_sent_batch_id, batch_size)
+                        .await
+                } else {
+                    None
+                }
+            };
+
+            if let Some(batch) = next_batch {
+                last_sent_batch_id = batch.batch_id;
+                if outbound.send(Ok(batch)).await.is_err() {
+                    return Ok(());
+                }
+                continue;
+            }
+
+            tokio::select! {
+                request = inbound.message() => {
+                    if !self.handle_request(request?, &mut last_sent_batch_id).await? {
+                        return Ok(());
+                    }
+                }
+                _ = TimeUtil::sleep_millis(WAIT_INTERVAL_MILLIS) => {}
+            }
+        }
+    }
+
+    /// Returns false if the client closed the stream
+    async fn handle_request(
+        &self,
+        request: Option<ConsumeRequest>,
+        last_sent_batch_id: &mut u64,
+    ) -> Result<bool, Status> {
+        let Some(request) = request else {
+            return Ok(false);
+        };
+
+        match request.request {
+            Some(consume_request::Request::Ack(ack)) => {
+                let position_info = self.state.lock().await.ack(ack.batch_id)?;
+                // batches sent by the previous stream may be acked before resent
+                *last_sent_batch_id = (*last_sent_batch_id).max(ack.batch_id);
+                self.record_checkpoint(position_info);
+                Ok(true)
+            }
+            _ => Err(Status::invalid_argument(
+                "only ack is expected after subscribe",
+            )),
+        }
+    }
+
+    async fn next_batch(
+        &self,
+        state: &mut StreamState,
+        last_sent_batch_id: u64,
+        batch_size: usize,
+    ) -> Option<Batch> {
+        // resend the batches not acked by the previous stream
+        let mut unacked_batches = state.pending_batches.range(last_sent_batch_id + 1..);
+        if let Some((_, (batch, _))) = unacked_batches.next() {
+            return Some(batch.clone());
+        }
+
+        if self.buffer.is_empty() {
+            return None;
+        }
+
+        let mut parallelizer = BaseParallelizer {
+            monitor: self.monitor.clone(),
+            ..Default::default()
+        };
+        let data = parallelizer
+            .drain_by_count(&self.buffer, batch_size)
+            .await
+            .unwrap();
+        let position_info = BasePipeline::fetch_raw(&data);
+        if let Some(position) = &position_info.0 {
+            self.syncer.lock().unwrap().received_position = position.to_owned();
+        }
+
+        let items: Vec<Item> = data.into_iter().filter_map(Item::from_dt_item).collect();
+
+        // update monitor
+        {
+            let mut monitor = self.monitor.lock().unwrap();
+            monitor.add_counter(CounterType::BufferSize, self.buffer.len());
+            monitor.add_counter(CounterType::SinkedCount, items.len());
+        }
+
+        // positions of data not sent, e.g. heartbeats, are recorded with the last batch
+        if items.is_empty() {
+            match state.pending_batches.values_mut().next_back() {
+                Some((_, pending_position_info)) => {
+                    merge_position_info(pending_position_info, position_info)
+                }
+                None => self.record_checkpoint(position_info),
+            }
+            return None;
+        }
+
+        state.sent_batch_id += 1;
+        let batch = Batch {
+            batch_id: state.sent_batch_id,
+            items,
+            checkpoint_position: position_info
+                .1
+                .as_ref()
+                .map(|position| position.to_string())
+                .unwrap_or_default(),
+        };
+        state
+            .pending_batches
+            .insert(batch.batch_id, (batch.clone(), position_info));
+        Some(batch)
+    }
+
+    fn record_checkpoint(&self, position_info: PositionInfo) {
+        if let Some(current_position) = position_info.0 {
+            log_position!("current_position | {}", current_position.to_string());
+        }
+        if let Some(checkpoint_position) = position_info.1 {
+            log_position!("checkpoint_position | {}", checkpoint_position.to_string());
+            self.syncer.lock().unwrap().committed_position = checkpoint_position;
+        }
+    }
+}
+
+#[tonic::async_trait]
+impl DtStream for GrpcServerPipeline {
+    type ConsumeStream = mpsc::Receiver<Result<Batch, Status>>;
+
+    async fn consume(
+        &self,
+        request: Request<Streaming<ConsumeRequest>>,
+    ) -> Result<Response<Self::ConsumeStream>, Status> {
+        let mut inbound = request.into_inner();
+        let subscribe = match inbound.message().await? {
+            Some(ConsumeRequest {
+                request: Some(consume_request::Request::Subscribe(subscribe)),
+            }) => subscribe,
+            _ => {
+                return Err(Status::invalid_argument(
+                    "the first request should be subscribe",
+                ))
+            }
+        };
+
+        if self
+            .streaming
+            .compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst)
+            .is_err()
+        {
+            return Err(Status::failed_precondition(
+                "another stream is being consumed",
+            ));
+        }
+
+        let (outbound, receiver) = mpsc::channel(1);
+        tokio::spawn(self.clone().run_stream(inbound, subscribe, outbound));
+        Ok(Response::new(receiver))
+    }
+}
+
+#[async_trait]
+impl Pipeline for GrpcServerPipeline {
+    async fn stop(&mut self) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    async fn start(&mut self) -> anyhow::Result<()> {
+        let addr = format!("{}:{}", self.grpc_host, self.grpc_port);
+        let socket_addr = addr
+            .to_socket_addrs()?
+            .next()
+            .with_context(|| format!("invalid grpc server addr: [{}]", addr))?;
+
+        let auth_token = self.auth_token.clone();
+        let service = DtStreamServer::with_interceptor(self.clone(), move |request| {
+            authorize(&auth_token, request)
+        });
+
+        log_info!(
+            "grpc server starts, addr: {}, auth token: {}",
+            addr,
+            !self.auth_token.is_empty()
+        );
+        Server::builder()
+            .add_service(service)
+            .serve(socket_addr)
+            .await?;
+        Ok(())
+    }
+}
+
+/// Checks the bearer token in metadata if grpc_auth_token is set
+fn authorize(auth_token: &str, request: Request<()>) -> Result<Request<()>, Status> {
+    if auth_token.is_empty() {
+        return Ok(request);
+    }
+
+    let authorization = request
+        .metadata()
+        .get("authorization")
+        .and_then(|v| v.to_str().ok());
+    let authorized = BasePipeline::check_bearer_token(authorization, auth_token);
+    if authorized {
+        Ok(request)
+    } else {
+        Err(Status::unauthenticated("invalid auth token"))
+    }
+}
+
+fn merge_position_info(position_info: &mut PositionInfo, newer: PositionInfo) {
+    if newer.0.is_some() {
+        position_info.0 = newer.0;
+    }
+    if newer.1.is_some() {
+        position_info.1 = newer.1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{net::TcpListener, time::Duration};
+
+    use dt_common::meta::dt_data::{DtData, DtItem};
+    use tonic::{metadata::MetadataValue, transport::Endpoint, Code};
+
+    use super::*;
+    use crate::grpc_message::{dt_stream_client::DtStreamClient, item, Ack};
+
+    fn add_batch(state: &mut StreamState, offset: i64) {
+        let position = Position::Kafka {
+            topic: "test".into(),
+            partition: 0,
+            offset,
+            offsets: BTreeMap::new(),
+        };
+        state.sent_batch_id += 1;
+        let batch = Batch {
+            batch_id: state.sent_batch_id,
+            ..Default::default()
+        };
+        state
+            .pending_batches
+            .insert(state.sent_batch_id, (batch, (None, Some(position))));
+    }
+
+    #[test]
+    fn test_ack() {
+        let mut state = StreamState::default();
+        add_batch(&mut state, 1);
+        add_batch(&mut state, 2);
+        add_batch(&mut state, 3);
+
+        let (_, checkpoint_position) = state.ack(2).unwrap();
+        assert!(matches!(
+            checkpoint_position,
+            Some(Position::Kafka { offset: 2, .. })
+        ));
+        assert_eq!(state.acked_batch_id, 2);
+        assert_eq!(state.pending_batches.keys().collect::<Vec<_>>(), vec![&3]);
+
+        // acking the same batch again changes nothing
+        assert_eq!(state.ack(2).unwrap(), (None, None));
+        assert!(state.ack(1).is_err());
+        assert!(state.ack(4).is_err());
+
+        state.ack(3).unwrap();
+        assert!(state.pending_batches.is_empty());
+    }
+
+    fn commit_item(offset: i64) -> DtItem {
+        DtItem {
+            dt_data: DtData::Commit {
+                xid: offset.to_string(),
+            },
+            position: Position::Kafka {
+                topic: "test".into(),
+                partition: 0,
+                offset,
+                offsets: BTreeMap::new(),
+            },
+            data_origin_node: String::new(),
+        }
+    }
+
+    fn request(request: consume_request::Request) -> ConsumeRequest {
+        ConsumeRequest {
+            request: Some(request),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_consume_stream() {
+        let buffer = Arc::new(DtQueue::new(100, 0));
+        for offset in 1..=3 {
+            buffer.push(commit_item(offset)).await.unwrap();
+        }
+        let syncer = Arc::new(Mutex::new(Syncer::default()));
+        let monitor = Arc::new(Mutex::new(Monitor::new("pipeline", "", 1, 100, 1000)));
+        let port = TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+        let mut pipeline = GrpcServerPipeline::new(
+            buffer,
+            syncer.clone(),
+            monitor,
+            "127.0.0.1",
+            port as u64,
+            "my_token",
+        );
+        tokio::spawn(async move { pipeline.start().await.unwrap() });
+
+        let endpoint = Endpoint::from_shared(format!("http://127.0.0.1:{}", port)).unwrap();
+        let channel = loop {
+            match endpoint.connect().await {
+                Ok(channel) => break channel,
+                Err(_) => TimeUtil::sleep_millis(WAIT_INTERVAL_MILLIS).await,
+            }
+        };
+
+        // without the token
+        let (mut sender, receiver) = mpsc::channel(10);
+        let subscribe = consume_request::Request::Subscribe(Subscribe {
+            batch_size: 2,
+            max_unacked_batches: 1,
+        });
+        sender.send(request(subscribe.clone())).await.unwrap();
+        let mut client = DtStreamClient::new(channel.clone());
+        let status = client.consume(receiver).await.unwrap_err();
+        assert_eq!(status.code(), Code::Unauthenticated);
+
+        let token = MetadataValue::from_static("Bearer my_token");
+        let mut client = DtStreamClient::with_interceptor(channel, move |mut req: Request<()>| {
+            req.metadata_mut().insert("authorization", token.clone());
+            Ok(req)
+        });
+        let (mut sender, receiver) = mpsc::channel(10);
+        sender.send(request(subscribe)).await.unwrap();
+        let mut inbound = client.consume(receiver).await.unwrap().into_inner();
+
+        let batch = inbound.message().await.unwrap().unwrap();
+        assert_eq!(batch.batch_id, 1);
+        assert!(matches!(
+            batch.items.as_slice(),
+            [
+                Item { data: Some(item::Data::Commit(c1)), .. },
+                Item { data: Some(item::Data::Commit(c2)), .. },
+            ] if c1.xid == "1" && c2.xid == "2"
+        ));
+        assert_eq!(
+            batch.checkpoint_position,
+            commit_item(2).position.to_string()
+        );
+
+        // flow control: batch 2 is not sent before batch 1 is acked
+        let next = tokio::time::timeout(Duration::from_millis(500), inbound.message()).await;
+        assert!(next.is_err());
+
+        let ack = consume_request::Request::Ack(Ack { batch_id: 1 });
+        sender.send(request(ack)).await.unwrap();
+        let batch = inbound.message().await.unwrap().unwrap();
+        assert_eq!(batch.batch_id, 2);
+        assert_eq!(batch.items.len(), 1);
+        assert!(matches!(
+            syncer.lock().unwrap().committed_position,
+            Position::Kafka { offset: 2, .. }
+        ));
+    }
+}
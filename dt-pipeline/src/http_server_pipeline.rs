@@ -1,31 +1,42 @@
 use std::{
-    collections::HashMap,
-    sync::{
-        atomic::{AtomicU64, Ordering},
-        Arc, Mutex,
-    },
+    collections::{BTreeMap, HashMap, VecDeque},
+    sync::{Arc, Mutex},
+    time::Instant,
 };
 
-use anyhow::bail;
+use anyhow::{bail, Context};
 use async_trait::async_trait;
 use dt_common::{
-    log_position,
+    config::http_server_security_config::HttpServerSecurityConfig,
+    log_info, log_position,
     meta::{
-        avro::avro_converter::AvroConverter, dt_data::DtData, dt_queue::DtQueue,
-        position::Position, syncer::Syncer,
+        avro::avro_converter::AvroConverter,
+        col_value::ColValue,
+        dt_data::{DtData, DtItem},
+        dt_queue::DtQueue,
+        position::Position,
+        rdb_tb_meta::RdbTbMeta,
+        syncer::Syncer,
     },
     monitor::{counter_type::CounterType, monitor::Monitor},
+    utils::time_util::TimeUtil,
 };
 use dt_parallelizer::base_parallelizer::BaseParallelizer;
 use futures::executor::block_on;
+use openssl::ssl::{SslAcceptor, SslAcceptorBuilder, SslFiletype, SslMethod, SslVerifyMode};
 
 use crate::{base_pipeline::BasePipeline, Pipeline};
 
-use actix_web::{web, App, HttpResponse, HttpServer, Responder};
+use actix_web::{http::header, web, App, HttpRequest, HttpResponse, HttpServer, Responder};
 use serde::{Deserialize, Serialize};
 
 type PositionInfo = (Option<Position>, Option<Position>);
 
+const DEFAULT_CONSUMER: &str = "default";
+const WAIT_INTERVAL_MILLIS: u64 = 100;
+// long polls hold a connection each, so wait_secs of clients is capped
+const MAX_WAIT_SECS: u64 = 60;
+
 #[derive(Clone)]
 pub struct HttpServerPipeline {
     pub buffer: Arc<DtQueue>,
@@ -36,27 +47,188 @@ pub struct HttpServerPipeline {
     pub batch_sink_interval_secs: u64,
     pub http_host: String,
     pub http_port: u64,
+    pub security_config: HttpServerSecurityConfig,
+    // 0: sessions never expire
+    pub session_timeout_secs: u64,
+
+    sessions: Arc<async_std::sync::Mutex<ConsumerSessions>>,
+}
+
+/// Consumers share the data in buffer, each batch is sent to one consumer.
+/// Positions of a batch are recorded after it and all batches before it are acked.
+#[derive(Default)]
+struct ConsumerSessions {
+    // key: consumer
+    sessions: HashMap<String, ConsumerSession>,
+    // sequence of batches of all consumers, in the order of data
+    last_seq: u64,
+    // key: seq, value: (positions of the batch, acked)
+    pending_ack_positions: BTreeMap<u64, (PositionInfo, bool)>,
+    // (seq, data) of unacked batches of expired sessions, sent to other consumers first
+    requeued_data: VecDeque<(u64, Vec<DtItem>)>,
+}
 
-    acked_batch_id: Arc<AtomicU64>,
-    sent_batch_id: Arc<AtomicU64>,
-    pending_ack_data: Arc<async_std::sync::Mutex<HashMap<u64, FetchResp>>>,
-    pending_ack_positions: Arc<async_std::sync::Mutex<HashMap<u64, PositionInfo>>>,
+struct ConsumerSession {
+    acked_batch_id: u64,
+    sent_batch_id: u64,
+    // key: batch_id, value: (seq, dml / ddl items encoded in the format of each fetch)
+    pending_ack_data: HashMap<u64, (u64, Vec<DtItem>)>,
+    last_active_time: Instant,
+}
+
+impl Default for ConsumerSession {
+    fn default() -> Self {
+        Self {
+            acked_batch_id: 0,
+            sent_batch_id: 0,
+            pending_ack_data: HashMap::new(),
+            last_active_time: Instant::now(),
+        }
+    }
+}
+
+impl ConsumerSessions {
+    fn get_session(&mut self, consumer: &str) -> &mut ConsumerSession {
+        let session = self.sessions.entry(consumer.to_string()).or_default();
+        session.last_active_time = Instant::now();
+        session
+    }
+
+    /// Sessions without requests for timeout_secs are removed,
+    /// their unacked batches are requeued in the order of data
+    fn expire_sessions(&mut self, timeout_secs: u64) {
+        if timeout_secs == 0 {
+            return;
+        }
+
+        let expired: Vec<String> = self
+            .sessions
+            .iter()
+            .filter(|(_, s)| s.last_active_time.elapsed().as_secs() >= timeout_secs)
+            .map(|(consumer, _)| consumer.clone())
+            .collect();
+        for consumer in expired {
+            let session = self.sessions.remove(&consumer).unwrap();
+            log_info!(
+                "http consumer: [{}] expired, requeued batches: {}",
+                consumer,
+                session.pending_ack_data.len()
+            );
+            self.requeued_data
+                .extend(session.pending_ack_data.into_values());
+        }
+        self.requeued_data
+            .make_contiguous()
+            .sort_by_key(|(seq, _)| *seq);
+    }
+
+    /// A requeued batch is sent as a new batch of the consumer, regardless of batch_size
+    fn send_requeued(&mut self, consumer: &str) -> Option<(u64, Vec<DtItem>)> {
+        let (seq, data) = self.requeued_data.pop_front()?;
+        let session = self.get_session(consumer);
+        session.sent_batch_id += 1;
+        session
+            .pending_ack_data
+            .insert(session.sent_batch_id, (seq, data.clone()));
+        Some((session.sent_batch_id, data))
+    }
+
+    fn ack(&mut self, consumer: &str, ack_batch_id: u64) -> anyhow::Result<()> {
+        let session = self.get_session(consumer);
+        if ack_batch_id > session.sent_batch_id {
+            bail!(format!(
+                "ack_batch_id: [{}] must <= sent_batch_id: [{}]",
+                ack_batch_id, session.sent_batch_id
+            ));
+        }
+
+        if ack_batch_id < session.acked_batch_id {
+            bail!(format!(
+                "ack_batch_id: [{}] must >= acked_batch_id : [{}]",
+                ack_batch_id, session.acked_batch_id
+            ));
+        }
+
+        let mut acked_seqs = Vec::new();
+        session.pending_ack_data.retain(|&batch_id, (seq, _)| {
+            if batch_id <= ack_batch_id {
+                acked_seqs.push(*seq);
+            }
+            batch_id > ack_batch_id
+        });
+        session.acked_batch_id = ack_batch_id;
+
+        for seq in acked_seqs {
+            if let Some((_, acked)) = self.pending_ack_positions.get_mut(&seq) {
+                *acked = true;
+            }
+        }
+        record_checkpoint(self.pop_acked_positions());
+        Ok(())
+    }
+
+    /// Positions of the continuous acked batches from the oldest one
+    fn pop_acked_positions(&mut self) -> PositionInfo {
+        let mut max_acked_position_info = (None, None);
+        while let Some(entry) = self.pending_ack_positions.first_entry() {
+            if !entry.get().1 {
+                break;
+            }
+            let (position_info, _) = entry.remove();
+            if position_info.0.is_some() {
+                max_acked_position_info.0 = position_info.0;
+            }
+            if position_info.1.is_some() {
+                max_acked_position_info.1 = position_info.1;
+            }
+        }
+        max_acked_position_info
+    }
+}
+
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+enum FetchFormat {
+    // FetchResp, items encoded by AvroConverter
+    #[default]
+    Avro,
+    // JsonFetchResp
+    Json,
+}
+
+#[derive(Deserialize)]
+struct InfoParams {
+    #[serde(default = "default_consumer")]
+    consumer: String,
 }
 
 #[derive(Deserialize)]
 struct FetchNewParams {
     batch_size: usize,
     ack_batch_id: Option<u64>,
+    #[serde(default)]
+    format: FetchFormat,
+    #[serde(default = "default_consumer")]
+    consumer: String,
+    // long poll, wait until the buffer is not empty, at most wait_secs, capped by MAX_WAIT_SECS
+    #[serde(default)]
+    wait_secs: u64,
 }
 
 #[derive(Deserialize)]
 struct FetchOldParams {
     old_batch_id: u64,
+    #[serde(default)]
+    format: FetchFormat,
+    #[serde(default = "default_consumer")]
+    consumer: String,
 }
 
 #[derive(Deserialize)]
 struct AckReq {
     ack_batch_id: u64,
+    #[serde(default = "default_consumer")]
+    consumer: String,
 }
 
 #[derive(Serialize)]
@@ -70,12 +242,58 @@ struct FetchResp {
     batch_id: u64,
 }
 
+#[derive(Serialize)]
+struct JsonFetchResp {
+    data: Vec<JsonItem>,
+    batch_id: u64,
+    // tables of the dml items in data, only if with_field_defs
+    schemas: Vec<JsonTbSchema>,
+}
+
+#[derive(Serialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+enum JsonItem {
+    Dml {
+        schema: String,
+        tb: String,
+        row_type: String,
+        before: Option<HashMap<String, ColValue>>,
+        after: Option<HashMap<String, ColValue>>,
+        position: Position,
+    },
+    Ddl {
+        db_type: String,
+        schema: String,
+        ddl_type: String,
+        query: String,
+        position: Position,
+    },
+}
+
+#[derive(Serialize)]
+struct JsonTbSchema {
+    schema: String,
+    tb: String,
+    cols: Vec<JsonColDef>,
+    id_cols: Vec<String>,
+}
+
+#[derive(Serialize)]
+struct JsonColDef {
+    name: String,
+    column_type: String,
+}
+
 #[derive(Serialize)]
 struct InfoResp {
     acked_batch_id: u64,
     sent_batch_id: u64,
 }
 
+fn default_consumer() -> String {
+    DEFAULT_CONSUMER.into()
+}
+
 impl HttpServerPipeline {
     #![allow(clippy::too_many_arguments)]
     pub fn new(
@@ -87,6 +305,8 @@ impl HttpServerPipeline {
         batch_sink_interval_secs: u64,
         http_host: &str,
         http_port: u64,
+        security_config: HttpServerSecurityConfig,
+        session_timeout_secs: u64,
     ) -> Self {
         Self {
             buffer,
@@ -97,12 +317,38 @@ impl HttpServerPipeline {
             batch_sink_interval_secs,
             http_host: http_host.into(),
             http_port,
-            acked_batch_id: Default::default(),
-            sent_batch_id: Default::default(),
-            pending_ack_data: Default::default(),
-            pending_ack_positions: Default::default(),
+            security_config,
+            session_timeout_secs,
+            sessions: Default::default(),
         }
     }
+
+    fn create_ssl_acceptor(&self) -> anyhow::Result<Option<SslAcceptorBuilder>> {
+        let config = &self.security_config;
+        if !config.is_tls() {
+            return Ok(None);
+        }
+
+        let mut builder = SslAcceptor::mozilla_intermediate(SslMethod::tls())?;
+        builder
+            .set_private_key_file(&config.tls_key_file, SslFiletype::PEM)
+            .with_context(|| format!("invalid http_tls_key_file: [{}]", config.tls_key_file))?;
+        builder
+            .set_certificate_chain_file(&config.tls_cert_file)
+            .with_context(|| format!("invalid http_tls_cert_file: [{}]", config.tls_cert_file))?;
+        if !config.tls_client_ca_file.is_empty() {
+            builder
+                .set_ca_file(&config.tls_client_ca_file)
+                .with_context(|| {
+                    format!(
+                        "invalid http_tls_client_ca_file: [{}]",
+                        config.tls_client_ca_file
+                    )
+                })?;
+            builder.set_verify(SslVerifyMode::PEER | SslVerifyMode::FAIL_IF_NO_PEER_CERT);
+        }
+        Ok(Some(builder))
+    }
 }
 
 #[async_trait]
@@ -113,44 +359,108 @@ impl Pipeline for HttpServerPipeline {
 
     async fn start(&mut self) -> anyhow::Result<()> {
         let app_data = self.clone();
-        block_on(
-            HttpServer::new(move || {
-                App::new()
-                    .app_data(web::Data::new(app_data.clone()))
-                    .service(web::resource("/info").route(web::get().to(info)))
-                    .service(web::resource("/fetch_new").route(web::get().to(fetch_new)))
-                    .service(web::resource("/fetch_old").route(web::get().to(fetch_old)))
-                    .service(web::resource("/ack").route(web::post().to(ack)))
-            })
-            .bind(format!("{}:{}", self.http_host, self.http_port))
-            .unwrap()
-            .run(),
-        )
-        .unwrap();
+        let server = HttpServer::new(move || {
+            App::new()
+                .app_data(web::Data::new(app_data.clone()))
+                .service(web::resource("/info").route(web::get().to(info)))
+                .service(web::resource("/fetch_new").route(web::get().to(fetch_new)))
+                .service(web::resource("/fetch_old").route(web::get().to(fetch_old)))
+                .service(web::resource("/ack").route(web::post().to(ack)))
+        });
+
+        let addr = format!("{}:{}", self.http_host, self.http_port);
+        let server = match self.create_ssl_acceptor()? {
+            Some(builder) => server.bind_openssl(&addr, builder)?,
+            None => server.bind(&addr)?,
+        };
+        log_info!(
+            "http server starts, addr: {}, https: {}, mtls: {}, auth token: {}",
+            addr,
+            self.security_config.is_tls(),
+            !self.security_config.tls_client_ca_file.is_empty(),
+            !self.security_config.auth_token.is_empty()
+        );
+        block_on(server.run())?;
+        Ok(())
+    }
+}
+
+/// Checks the bearer token if http_auth_token is set
+fn authorize(req: &HttpRequest, pipeline: &HttpServerPipeline) -> Result<(), HttpResponse> {
+    let auth_token = &pipeline.security_config.auth_token;
+    if auth_token.is_empty() {
+        return Ok(());
+    }
+
+    let authorization = req
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok());
+    let authorized = BasePipeline::check_bearer_token(authorization, auth_token);
+    if authorized {
         Ok(())
+    } else {
+        Err(HttpResponse::Unauthorized()
+            .insert_header((header::WWW_AUTHENTICATE, "Bearer"))
+            .finish())
     }
 }
 
-async fn info(pipeline: web::Data<HttpServerPipeline>) -> impl Responder {
+async fn info(
+    req: HttpRequest,
+    query: web::Query<InfoParams>,
+    pipeline: web::Data<HttpServerPipeline>,
+) -> impl Responder {
+    if let Err(response) = authorize(&req, &pipeline) {
+        return response;
+    }
+
+    let sessions = pipeline.sessions.lock().await;
+    let (acked_batch_id, sent_batch_id) = sessions
+        .sessions
+        .get(&query.consumer)
+        .map(|s| (s.acked_batch_id, s.sent_batch_id))
+        .unwrap_or_default();
     send_response(&InfoResp {
-        acked_batch_id: pipeline.acked_batch_id.load(Ordering::Acquire),
-        sent_batch_id: pipeline.sent_batch_id.load(Ordering::Acquire),
+        acked_batch_id,
+        sent_batch_id,
     })
 }
 
 async fn fetch_new(
+    req: HttpRequest,
     query: web::Query<FetchNewParams>,
     pipeline: web::Data<HttpServerPipeline>,
 ) -> impl Responder {
+    if let Err(response) = authorize(&req, &pipeline) {
+        return response;
+    }
+
     if let Some(ack_batch_id) = query.ack_batch_id {
-        if let Err(err) = do_ack(ack_batch_id, &pipeline).await {
+        let mut sessions = pipeline.sessions.lock().await;
+        if let Err(err) = sessions.ack(&query.consumer, ack_batch_id) {
             return HttpResponse::BadRequest().body(err.to_string());
         }
     }
 
-    let mut pending_ack_data = pipeline.pending_ack_data.lock().await;
-    let mut pending_ack_positions = pipeline.pending_ack_positions.lock().await;
-    let sent_batch_id = pipeline.sent_batch_id.load(Ordering::Acquire);
+    {
+        let mut sessions = pipeline.sessions.lock().await;
+        sessions.expire_sessions(pipeline.session_timeout_secs);
+        if let Some((batch_id, data)) = sessions.send_requeued(&query.consumer) {
+            drop(sessions);
+            return send_fetch_response(&pipeline, batch_id, &data, &query.format).await;
+        }
+    }
+
+    // long poll
+    let start_time = Instant::now();
+    let wait_secs = query.wait_secs.min(MAX_WAIT_SECS);
+    while pipeline.buffer.is_empty() && start_time.elapsed().as_secs() < wait_secs {
+        TimeUtil::sleep_millis(WAIT_INTERVAL_MILLIS).await;
+    }
+
+    // data are drained in the lock, so seqs of batches are in the order of data
+    let mut sessions = pipeline.sessions.lock().await;
 
     // get data from buffer
     let mut parallelizer = BaseParallelizer {
@@ -163,158 +473,240 @@ async fn fetch_new(
         .unwrap();
     let (last_received_position, last_commit_position) = BasePipeline::fetch_raw(&data);
 
-    // data -> avro response
-    let mut response = FetchResp {
-        batch_id: sent_batch_id + 1,
-        data: Vec::new(),
-    };
-
-    let mut avro_converter = pipeline.avro_converter.clone();
-    for i in data {
-        match i.dt_data {
-            DtData::Dml { row_data } => {
-                let payload = avro_converter
-                    .row_data_to_avro_value(row_data)
-                    .await
-                    .unwrap();
-                response.data.push(payload);
-            }
+    let data: Vec<DtItem> = data
+        .into_iter()
+        .filter(|i| matches!(i.dt_data, DtData::Dml { .. } | DtData::Ddl { .. }))
+        .collect();
 
-            DtData::Ddl { ddl_data } => {
-                let payload = avro_converter
-                    .ddl_data_to_avro_value(ddl_data)
-                    .await
-                    .unwrap();
-                response.data.push(payload);
-            }
+    // update monitor
+    {
+        let mut monitor = pipeline.monitor.lock().unwrap();
+        monitor.add_counter(CounterType::BufferSize, pipeline.buffer.len());
+        monitor.add_counter(CounterType::SinkedCount, data.len());
+    }
 
-            _ => {}
+    // positions of batches without data, e.g. heartbeats, need no ack
+    if last_received_position.is_some() || last_commit_position.is_some() || !data.is_empty() {
+        sessions.last_seq += 1;
+        let seq = sessions.last_seq;
+        sessions.pending_ack_positions.insert(
+            seq,
+            (
+                (last_received_position, last_commit_position),
+                data.is_empty(),
+            ),
+        );
+        if data.is_empty() {
+            record_checkpoint(sessions.pop_acked_positions());
+        } else {
+            let session = sessions.get_session(&query.consumer);
+            session
+                .pending_ack_data
+                .insert(session.sent_batch_id + 1, (seq, data.clone()));
         }
     }
 
-    // update monitor
-    let mut monitor = pipeline.monitor.lock().unwrap();
-    monitor.add_counter(CounterType::BufferSize, pipeline.buffer.len());
-    monitor.add_counter(CounterType::SinkedCount, response.data.len());
-
-    // update pending_ack_data & pending_ack_positions
-    let batch_id = response.batch_id;
-    pipeline.sent_batch_id.store(batch_id, Ordering::Release);
-    if !response.data.is_empty() {
-        pending_ack_data.insert(batch_id, response);
-        pending_ack_positions.insert(batch_id, (last_received_position, last_commit_position));
-        send_response(pending_ack_data.get(&batch_id).unwrap())
-    } else {
-        send_response(&response)
-    }
+    let session = sessions.get_session(&query.consumer);
+    session.sent_batch_id += 1;
+    let batch_id = session.sent_batch_id;
+    drop(sessions);
+    send_fetch_response(&pipeline, batch_id, &data, &query.format).await
 }
 
 async fn fetch_old(
+    req: HttpRequest,
     query: web::Query<FetchOldParams>,
     pipeline: web::Data<HttpServerPipeline>,
 ) -> impl Responder {
-    let acked_batch_id = pipeline.acked_batch_id.load(Ordering::Acquire);
-    let sent_batch_id = pipeline.sent_batch_id.load(Ordering::Acquire);
+    if let Err(response) = authorize(&req, &pipeline) {
+        return response;
+    }
+
+    let mut sessions = pipeline.sessions.lock().await;
+    let Some(session) = sessions.sessions.get_mut(&query.consumer) else {
+        // never fetched, or expired and its batches requeued
+        return HttpResponse::BadRequest().body(format!("unknown consumer: [{}]", query.consumer));
+    };
+    session.last_active_time = Instant::now();
     let old_batch_id = query.old_batch_id;
 
-    if old_batch_id > sent_batch_id {
+    if old_batch_id > session.sent_batch_id {
         return HttpResponse::BadRequest().body(format!(
             "old_batch_id: [{}] must <= sent_batch_id: [{}]",
-            old_batch_id, sent_batch_id
+            old_batch_id, session.sent_batch_id
         ));
     }
 
-    if old_batch_id <= acked_batch_id {
+    if old_batch_id <= session.acked_batch_id {
         return HttpResponse::BadRequest().body(format!(
             "old_batch_id: [{}] must > acked_batch_id: [{}]",
-            old_batch_id, acked_batch_id
+            old_batch_id, session.acked_batch_id
         ));
     }
 
-    if let Some(response) = pipeline.pending_ack_data.lock().await.get(&old_batch_id) {
-        send_response(response)
-    } else {
-        // should never happen
-        send_response(&FetchResp::default())
-    }
+    // batches without data are not kept
+    let data = session
+        .pending_ack_data
+        .get(&old_batch_id)
+        .map(|(_, data)| data.clone())
+        .unwrap_or_default();
+    drop(sessions);
+    send_fetch_response(&pipeline, old_batch_id, &data, &query.format).await
 }
 
-async fn ack(data: web::Json<AckReq>, pipeline: web::Data<HttpServerPipeline>) -> impl Responder {
-    if let Err(err) = do_ack(data.ack_batch_id, &pipeline).await {
+async fn ack(
+    req: HttpRequest,
+    data: web::Json<AckReq>,
+    pipeline: web::Data<HttpServerPipeline>,
+) -> impl Responder {
+    if let Err(response) = authorize(&req, &pipeline) {
+        return response;
+    }
+
+    let mut sessions = pipeline.sessions.lock().await;
+    if let Err(err) = sessions.ack(&data.consumer, data.ack_batch_id) {
         return HttpResponse::BadRequest().body(err.to_string());
     }
     send_response(&AckResp {
-        acked_batch_id: pipeline.acked_batch_id.load(Ordering::Acquire),
+        acked_batch_id: data.ack_batch_id,
     })
 }
 
-fn send_response<T: Serialize>(response: &T) -> HttpResponse {
-    match serde_json::to_string(response) {
-        Ok(json) => HttpResponse::Ok()
-            .content_type("application/json")
-            .body(json),
-        Err(_) => HttpResponse::InternalServerError().finish(),
-    }
+async fn send_fetch_response(
+    pipeline: &HttpServerPipeline,
+    batch_id: u64,
+    data: &[DtItem],
+    format: &FetchFormat,
+) -> HttpResponse {
+    let result = match format {
+        FetchFormat::Avro => build_avro_response(pipeline, batch_id, data)
+            .await
+            .map(|response| send_response(&response)),
+        FetchFormat::Json => build_json_response(&pipeline.avro_converter, batch_id, data)
+            .await
+            .map(|response| send_response(&response)),
+    };
+    result.unwrap_or_else(|err| HttpResponse::InternalServerError().body(err.to_string()))
 }
 
-async fn do_ack(ack_batch_id: u64, pipeline: &web::Data<HttpServerPipeline>) -> anyhow::Result<()> {
-    let acked_batch_id = pipeline.acked_batch_id.load(Ordering::Acquire);
-    let sent_batch_id = pipeline.sent_batch_id.load(Ordering::Acquire);
+async fn build_avro_response(
+    pipeline: &HttpServerPipeline,
+    batch_id: u64,
+    data: &[DtItem],
+) -> anyhow::Result<FetchResp> {
+    let mut response = FetchResp {
+        batch_id,
+        data: Vec::new(),
+    };
 
-    if ack_batch_id > sent_batch_id {
-        bail!(format!(
-            "ack_batch_id: [{}] must <= sent_batch_id: [{}]",
-            ack_batch_id, sent_batch_id
-        ));
-    }
+    let mut avro_converter = pipeline.avro_converter.clone();
+    for i in data {
+        match &i.dt_data {
+            DtData::Dml { row_data } => {
+                let payload = avro_converter
+                    .row_data_to_avro_value(row_data.clone())
+                    .await?;
+                response.data.push(payload);
+            }
 
-    if ack_batch_id < acked_batch_id {
-        bail!(format!(
-            "ack_batch_id: [{}] must >= acked_batch_id : [{}]",
-            ack_batch_id, acked_batch_id
-        ));
+            DtData::Ddl { ddl_data } => {
+                let payload = avro_converter
+                    .ddl_data_to_avro_value(ddl_data.clone())
+                    .await?;
+                response.data.push(payload);
+            }
+
+            _ => {}
+        }
     }
+    Ok(response)
+}
 
-    let mut pending_ack_data = pipeline.pending_ack_data.lock().await;
-    refresh_appending_ack_data(&mut pending_ack_data, ack_batch_id);
+async fn build_json_response(
+    avro_converter: &AvroConverter,
+    batch_id: u64,
+    data: &[DtItem],
+) -> anyhow::Result<JsonFetchResp> {
+    let mut response = JsonFetchResp {
+        batch_id,
+        data: Vec::new(),
+        schemas: Vec::new(),
+    };
 
-    let mut pending_ack_positions = pipeline.pending_ack_positions.lock().await;
-    let max_acked_position_info =
-        refresh_appending_ack_positions(&mut pending_ack_positions, ack_batch_id);
+    let mut avro_converter = avro_converter.clone();
+    for i in data {
+        match &i.dt_data {
+            DtData::Dml { row_data } => {
+                let mut row_data = row_data.clone();
+                row_data.convert_raw_string();
+
+                let exists = response
+                    .schemas
+                    .iter()
+                    .any(|s| s.schema == row_data.schema && s.tb == row_data.tb);
+                if avro_converter.with_field_defs && !exists {
+                    if let Some(meta_manager) = avro_converter.meta_manager.as_mut() {
+                        let tb_meta = meta_manager
+                            .get_tb_meta(&row_data.schema, &row_data.tb)
+                            .await?;
+                        response.schemas.push(to_json_tb_schema(tb_meta));
+                    }
+                }
+
+                response.data.push(JsonItem::Dml {
+                    row_type: row_data.row_type.to_string(),
+                    schema: row_data.schema,
+                    tb: row_data.tb,
+                    before: row_data.before,
+                    after: row_data.after,
+                    position: i.position.clone(),
+                });
+            }
 
-    record_checkpoint(max_acked_position_info);
-    pipeline
-        .acked_batch_id
-        .store(ack_batch_id, Ordering::Release);
-    Ok(())
+            DtData::Ddl { ddl_data } => {
+                response.data.push(JsonItem::Ddl {
+                    db_type: ddl_data.db_type.to_string(),
+                    schema: ddl_data.default_schema.clone(),
+                    ddl_type: ddl_data.ddl_type.to_string(),
+                    query: ddl_data.query.clone(),
+                    position: i.position.clone(),
+                });
+            }
+
+            _ => {}
+        }
+    }
+    Ok(response)
 }
 
-fn refresh_appending_ack_data(
-    pending_ack_data: &mut async_std::sync::MutexGuard<'_, HashMap<u64, FetchResp>>,
-    ack_batch_id: u64,
-) {
-    pending_ack_data.retain(|&batch_id, _| batch_id > ack_batch_id);
+fn to_json_tb_schema(tb_meta: &RdbTbMeta) -> JsonTbSchema {
+    let cols = tb_meta
+        .cols
+        .iter()
+        .map(|col| JsonColDef {
+            name: col.clone(),
+            column_type: tb_meta
+                .col_origin_type_map
+                .get(col)
+                .cloned()
+                .unwrap_or_default(),
+        })
+        .collect();
+    JsonTbSchema {
+        schema: tb_meta.schema.clone(),
+        tb: tb_meta.tb.clone(),
+        cols,
+        id_cols: tb_meta.id_cols.clone(),
+    }
 }
 
-fn refresh_appending_ack_positions(
-    pending_ack_positions: &mut async_std::sync::MutexGuard<'_, HashMap<u64, PositionInfo>>,
-    ack_batch_id: u64,
-) -> PositionInfo {
-    let mut max_acked_batch_id = 0;
-    let mut max_acked_position_info = (None, None);
-    for (batch_id, position_info) in pending_ack_positions.iter() {
-        if *batch_id <= ack_batch_id && *batch_id >= max_acked_batch_id {
-            max_acked_batch_id = *batch_id;
-            if let Some(last_received_position) = &position_info.0 {
-                max_acked_position_info.0 = Some(last_received_position.to_owned());
-            }
-            if let Some(last_commit_position) = &position_info.1 {
-                max_acked_position_info.1 = Some(last_commit_position.to_owned());
-            }
-        }
+fn send_response<T: Serialize>(response: &T) -> HttpResponse {
+    match serde_json::to_string(response) {
+        Ok(json) => HttpResponse::Ok()
+            .content_type("application/json")
+            .body(json),
+        Err(_) => HttpResponse::InternalServerError().finish(),
     }
-    pending_ack_positions.retain(|&batch_id, _| batch_id > ack_batch_id);
-    max_acked_position_info
 }
 
 fn record_checkpoint(position_info: PositionInfo) {
@@ -325,3 +717,201 @@ fn record_checkpoint(position_info: PositionInfo) {
         log_position!("checkpoint_position | {}", checkpoint_position.to_string());
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use dt_common::meta::{
+        ddl_meta::{ddl_data::DdlData, ddl_type::DdlType},
+        row_data::RowData,
+        row_type::RowType,
+    };
+    use serde_json::json;
+    use std::time::Duration;
+
+    use super::*;
+
+    fn add_batch(sessions: &mut ConsumerSessions, consumer: &str, offset: i64) {
+        let position = Position::Kafka {
+            topic: "test".into(),
+            partition: 0,
+            offset,
+            offsets: BTreeMap::new(),
+        };
+        sessions.last_seq += 1;
+        let seq = sessions.last_seq;
+        sessions
+            .pending_ack_positions
+            .insert(seq, ((None, Some(position)), false));
+        let session = sessions.sessions.entry(consumer.into()).or_default();
+        session.sent_batch_id += 1;
+        session
+            .pending_ack_data
+            .insert(session.sent_batch_id, (seq, Vec::new()));
+    }
+
+    #[test]
+    fn test_ack_multi_consumers() {
+        let mut sessions = ConsumerSessions::default();
+        add_batch(&mut sessions, "c1", 1);
+        add_batch(&mut sessions, "c2", 2);
+        add_batch(&mut sessions, "c1", 3);
+
+        // the batch of c1 before is not acked
+        sessions.ack("c2", 1).unwrap();
+        assert_eq!(sessions.pending_ack_positions.len(), 3);
+
+        sessions.ack("c1", 1).unwrap();
+        assert_eq!(
+            sessions.pending_ack_positions.keys().collect::<Vec<_>>(),
+            vec![&3]
+        );
+        assert_eq!(sessions.sessions["c1"].acked_batch_id, 1);
+        assert_eq!(sessions.sessions["c1"].pending_ack_data.len(), 1);
+
+        assert!(sessions.ack("c1", 3).is_err());
+        assert!(sessions.ack("c2", 0).is_err());
+        sessions.ack("c1", 2).unwrap();
+        assert!(sessions.pending_ack_positions.is_empty());
+    }
+
+    #[test]
+    fn test_requeue_expired_sessions() {
+        let mut sessions = ConsumerSessions::default();
+        add_batch(&mut sessions, "c1", 1);
+        add_batch(&mut sessions, "c2", 2);
+        add_batch(&mut sessions, "c1", 3);
+
+        // disabled
+        sessions.expire_sessions(0);
+        assert_eq!(sessions.sessions.len(), 2);
+
+        let session = sessions.sessions.get_mut("c1").unwrap();
+        session.last_active_time = Instant::now() - Duration::from_secs(10);
+        sessions.expire_sessions(5);
+        assert!(!sessions.sessions.contains_key("c1"));
+        assert_eq!(
+            sessions
+                .requeued_data
+                .iter()
+                .map(|(seq, _)| *seq)
+                .collect::<Vec<_>>(),
+            vec![1, 3]
+        );
+
+        // requeued batches are sent as new batches of c2 and keep their seqs
+        assert_eq!(sessions.send_requeued("c2").unwrap().0, 2);
+        assert_eq!(sessions.send_requeued("c2").unwrap().0, 3);
+        assert!(sessions.send_requeued("c2").is_none());
+        sessions.ack("c2", 3).unwrap();
+        assert!(sessions.pending_ack_positions.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_build_json_response() {
+        let position = Position::Kafka {
+            topic: "test".into(),
+            partition: 0,
+            offset: 1,
+            offsets: BTreeMap::new(),
+        };
+        let row_data = RowData::new(
+            "db_1".into(),
+            "tb_1".into(),
+            RowType::Insert,
+            None,
+            Some(HashMap::from([
+                ("id".to_string(), ColValue::Long(1)),
+                ("name".to_string(), ColValue::RawString(b"a".to_vec())),
+            ])),
+        );
+        let ddl_data = DdlData {
+            default_schema: "db_1".into(),
+            query: "TRUNCATE TABLE tb_1".into(),
+            ddl_type: DdlType::TruncateTable,
+            ..Default::default()
+        };
+        let data = vec![
+            DtItem {
+                dt_data: DtData::Dml { row_data },
+                position: position.clone(),
+                data_origin_node: String::new(),
+            },
+            DtItem {
+                dt_data: DtData::Ddl { ddl_data },
+                position,
+                data_origin_node: String::new(),
+            },
+        ];
+
+        let response = build_json_response(&AvroConverter::new(None, true), 3, &data)
+            .await
+            .unwrap();
+        let position = json!({"type": "Kafka", "topic": "test", "partition": 0, "offset": 1});
+        assert_eq!(
+            serde_json::to_value(&response).unwrap(),
+            json!({
+                "data": [
+                    {
+                        "type": "dml",
+                        "schema": "db_1",
+                        "tb": "tb_1",
+                        "row_type": "insert",
+                        "before": null,
+                        "after": {"id": 1, "name": "a"},
+                        "position": position.clone(),
+                    },
+                    {
+                        "type": "ddl",
+                        "db_type": "mysql",
+                        "schema": "db_1",
+                        "ddl_type": "truncate_table",
+                        "query": "TRUNCATE TABLE tb_1",
+                        "position": position,
+                    },
+                ],
+                "batch_id": 3,
+                // no meta manager to get table schemas
+                "schemas": [],
+            })
+        );
+    }
+
+    #[test]
+    fn test_to_json_tb_schema() {
+        let tb_meta = RdbTbMeta {
+            schema: "db_1".into(),
+            tb: "tb_1".into(),
+            cols: vec!["id".into(), "name".into()],
+            col_origin_type_map: HashMap::from([
+                ("id".to_string(), "int".to_string()),
+                ("name".to_string(), "varchar(255)".to_string()),
+            ]),
+            id_cols: vec!["id".into()],
+            ..Default::default()
+        };
+        assert_eq!(
+            serde_json::to_value(to_json_tb_schema(&tb_meta)).unwrap(),
+            json!({
+                "schema": "db_1",
+                "tb": "tb_1",
+                "cols": [
+                    {"name": "id", "column_type": "int"},
+                    {"name": "name", "column_type": "varchar(255)"},
+                ],
+                "id_cols": ["id"],
+            })
+        );
+    }
+
+    #[test]
+    fn test_check_bearer_token() {
+        let check = BasePipeline::check_bearer_token;
+        assert!(check(Some("Bearer my_token"), "my_token"));
+        assert!(!check(Some("Bearer my_tokem"), "my_token"));
+        // tokens of different lengths
+        assert!(!check(Some("Bearer my"), "my_token"));
+        assert!(!check(Some("Bearer my_token_2"), "my_token"));
+        assert!(!check(Some("my_token"), "my_token"));
+        assert!(!check(None, "my_token"));
+    }
+}
@@ -1,4 +1,6 @@
 pub mod base_pipeline;
+pub mod grpc_message;
+pub mod grpc_server_pipeline;
 pub mod http_server_pipeline;
 pub mod lua_processor;
 
@@ -1,5 +1,6 @@
 use std::{
     cmp,
+    collections::BTreeMap,
     str::FromStr,
     sync::{atomic::AtomicBool, Arc, Mutex},
 };
@@ -20,9 +21,9 @@ use dt_common::{
 };
 use dt_common::{
     meta::{
-        avro::avro_converter::AvroConverter, mongo::mongo_cdc_source::MongoCdcSource,
-        pg::pg_meta_manager::PgMetaManager, redis::redis_statistic_type::RedisStatisticType,
-        syncer::Syncer,
+        avro::avro_converter::AvroConverter, json::json_decoder::JsonDecoder,
+        mongo::mongo_cdc_source::MongoCdcSource, pg::pg_meta_manager::PgMetaManager,
+        redis::redis_statistic_type::RedisStatisticType, syncer::Syncer,
     },
     utils::redis_util::RedisUtil,
 };
@@ -31,6 +32,7 @@ use dt_connector::{
     extractor::{
         base_extractor::BaseExtractor,
         extractor_monitor::ExtractorMonitor,
+        file::{file_decoder::FileDecoder, file_snapshot_extractor::FileSnapshotExtractor},
         foxlake::foxlake_s3_extractor::FoxlakeS3Extractor,
         kafka::kafka_extractor::KafkaExtractor,
         mongo::{
@@ -43,8 +45,9 @@ use dt_connector::{
             mysql_struct_extractor::MysqlStructExtractor,
         },
         pg::{
-            pg_cdc_extractor::PgCdcExtractor, pg_check_extractor::PgCheckExtractor,
-            pg_snapshot_extractor::PgSnapshotExtractor, pg_struct_extractor::PgStructExtractor,
+            pg_cdc_extractor::PgCdcExtractor, pg_cdc_stream_buffer::PgCdcStreamBuffer,
+            pg_check_extractor::PgCheckExtractor, pg_snapshot_extractor::PgSnapshotExtractor,
+            pg_struct_extractor::PgStructExtractor,
         },
         redis::{
             redis_client::RedisClient, redis_psync_extractor::RedisPsyncExtractor,
@@ -55,6 +58,7 @@ use dt_connector::{
         resumer::{cdc_resumer::CdcResumer, snapshot_resumer::SnapshotResumer},
     },
     rdb_router::RdbRouter,
+    schema_registry_client::SchemaRegistryClient,
     Extractor,
 };
 
@@ -244,10 +248,17 @@ impl ExtractorUtil {
                 ddl_meta_tb,
                 start_time_utc,
                 end_time_utc,
+                streaming,
+                slot_check_interval_secs,
+                slot_lag_warn_mb,
+                slot_lag_limit_mb,
+                slot_limit_policy,
+                drop_slot_on_finish,
             } => {
                 let conn_pool = TaskUtil::create_pg_conn_pool(&url, 2, enable_sqlx_log).await?;
                 let meta_manager = PgMetaManager::new(conn_pool.clone()).await?;
                 base_extractor.time_filter = TimeFilter::new(&start_time_utc, &end_time_utc)?;
+                let stream_buffer = PgCdcStreamBuffer::new(&slot_name);
                 let extractor = PgCdcExtractor {
                     meta_manager,
                     filter,
@@ -262,6 +273,13 @@ impl ExtractorUtil {
                     heartbeat_interval_secs,
                     heartbeat_tb,
                     ddl_meta_tb,
+                    streaming,
+                    slot_check_interval_secs,
+                    slot_lag_warn_mb,
+                    slot_lag_limit_mb,
+                    slot_limit_policy,
+                    drop_slot_on_finish,
+                    stream_buffer,
                     resumer: cdc_resumer,
                     base_extractor,
                 };
@@ -466,24 +484,40 @@ impl ExtractorUtil {
             ExtractorConfig::Kafka {
                 url,
                 group,
-                topic,
-                partition,
+                topics,
+                partitions,
+                subscribe,
                 offset,
                 ack_interval_secs,
+                message_format,
+                schema_registry_url,
+                security_config,
             } => {
                 let meta_manager = TaskUtil::create_rdb_meta_manager(config).await?;
                 let avro_converter = AvroConverter::new(meta_manager, false);
+                let json_decoder = JsonDecoder::new(message_format.clone());
+                let schema_registry_client = if schema_registry_url.is_empty() {
+                    None
+                } else {
+                    Some(SchemaRegistryClient::new(&schema_registry_url)?)
+                };
                 let extractor = KafkaExtractor {
                     url,
                     group,
-                    topic,
-                    partition,
+                    topics,
+                    partitions,
+                    subscribe,
                     offset,
                     ack_interval_secs,
+                    message_format,
                     avro_converter,
+                    json_decoder,
+                    schema_registry_client,
+                    security_config,
                     syncer,
                     resumer: cdc_resumer,
                     base_extractor,
+                    offsets: BTreeMap::new(),
                 };
                 Box::new(extractor)
             }
@@ -507,6 +541,38 @@ impl ExtractorUtil {
                 };
                 Box::new(extractor)
             }
+
+            ExtractorConfig::FileSnapshot {
+                url,
+                schema,
+                tb,
+                file_format,
+                csv_delimiter,
+                csv_has_header,
+                s3_config,
+                batch_size,
+            } => {
+                let s3_client = TaskUtil::create_optional_s3_client(&s3_config);
+                // rows are typed by the target tables
+                let sinker_meta_manager = TaskUtil::create_rdb_meta_manager(config).await?;
+                let extractor = FileSnapshotExtractor {
+                    url,
+                    decoder: FileDecoder {
+                        file_format,
+                        csv_delimiter,
+                        csv_has_header,
+                    },
+                    s3_client,
+                    s3_config,
+                    sinker_meta_manager,
+                    base_extractor,
+                    resumer: snapshot_resumer,
+                    batch_size,
+                    schema,
+                    tb,
+                };
+                Box::new(extractor)
+            }
         };
         Ok(extractor)
     }
@@ -20,7 +20,7 @@ use dt_common::{
         task_config::TaskConfig,
     },
     error::Error,
-    log_finished, log_info,
+    log_finished, log_info, log_warn,
     meta::{avro::avro_converter::AvroConverter, dt_queue::DtQueue},
     monitor::{group_monitor::GroupMonitor, monitor::Monitor, FlushableMonitor},
     rdb_filter::RdbFilter,
@@ -32,13 +32,17 @@ use dt_common::{
 };
 use dt_connector::{
     data_marker::DataMarker,
-    extractor::resumer::{cdc_resumer::CdcResumer, snapshot_resumer::SnapshotResumer},
+    extractor::{
+        file::file_snapshot_extractor::FileSnapshotExtractor,
+        resumer::{cdc_resumer::CdcResumer, snapshot_resumer::SnapshotResumer},
+    },
     rdb_router::RdbRouter,
+    sequence_syncer::SequenceSyncer,
     Sinker,
 };
 use dt_pipeline::{
-    base_pipeline::BasePipeline, http_server_pipeline::HttpServerPipeline,
-    lua_processor::LuaProcessor, Pipeline,
+    base_pipeline::BasePipeline, grpc_server_pipeline::GrpcServerPipeline,
+    http_server_pipeline::HttpServerPipeline, lua_processor::LuaProcessor, Pipeline,
 };
 
 use log4rs::config::RawConfig;
@@ -99,7 +103,8 @@ impl TaskRunner {
             | ExtractorConfig::MysqlSnapshot { url, .. }
             | ExtractorConfig::PgSnapshot { url, .. }
             | ExtractorConfig::MongoSnapshot { url, .. }
-            | ExtractorConfig::FoxlakeS3 { url, .. } => {
+            | ExtractorConfig::FoxlakeS3 { url, .. }
+            | ExtractorConfig::FileSnapshot { url, .. } => {
                 self.start_multi_task(url, &router, &snapshot_resumer, &cdc_resumer)
                     .await?
             }
@@ -116,6 +121,19 @@ impl TaskRunner {
             }
         };
 
+        // cutover, sync sequence values after all data synced
+        if self
+            .config
+            .sequence
+            .as_ref()
+            .is_some_and(|config| config.sync_on_finish)
+        {
+            if let Some(mut sequence_syncer) = self.create_sequence_syncer(&router).await? {
+                sequence_syncer.sync().await?;
+                sequence_syncer.close().await?;
+            }
+        }
+
         log_finished!("task finished");
         Ok(())
     }
@@ -131,7 +149,7 @@ impl TaskRunner {
         let mut filter = RdbFilter::from_config(&self.config.filter, db_type)?;
 
         let mut pending_tbs = VecDeque::new();
-        let schemas = TaskUtil::list_schemas(url, db_type).await?;
+        let schemas = self.list_schemas(url).await?;
         for (index, schema) in schemas.iter().enumerate() {
             let do_global_structs = index == schemas.len() - 1;
 
@@ -164,7 +182,7 @@ impl TaskRunner {
             }
 
             // find pending tables
-            let tbs = TaskUtil::list_tbs(url, schema, db_type).await?;
+            let tbs = self.list_tbs(url, schema).await?;
             for tb in tbs.iter() {
                 if snapshot_resumer.check_finished(schema, tb) {
                     log_info!("schema: {}, tb: {}, already finished", schema, tb);
@@ -250,6 +268,28 @@ impl TaskRunner {
         Ok(())
     }
 
+    async fn list_schemas(&self, url: &str) -> anyhow::Result<Vec<String>> {
+        match &self.config.extractor {
+            // files of a table are in {url}/{schema}/{tb}/
+            ExtractorConfig::FileSnapshot { s3_config, .. } => {
+                let s3_client = TaskUtil::create_optional_s3_client(s3_config);
+                FileSnapshotExtractor::list_dirs(&s3_client, s3_config, url).await
+            }
+            _ => TaskUtil::list_schemas(url, &self.config.extractor_basic.db_type).await,
+        }
+    }
+
+    async fn list_tbs(&self, url: &str, schema: &str) -> anyhow::Result<Vec<String>> {
+        match &self.config.extractor {
+            ExtractorConfig::FileSnapshot { s3_config, .. } => {
+                let s3_client = TaskUtil::create_optional_s3_client(s3_config);
+                let dir = format!("{}/{}", url.trim_end_matches('/'), schema);
+                FileSnapshotExtractor::list_dirs(&s3_client, s3_config, &dir).await
+            }
+            _ => TaskUtil::list_tbs(url, schema, &self.config.extractor_basic.db_type).await,
+        }
+    }
+
     async fn spawn_single_task(
         self,
         schema: &str,
@@ -311,6 +351,25 @@ impl TaskRunner {
                 batch_size: *batch_size,
             },
 
+            ExtractorConfig::FileSnapshot {
+                url,
+                file_format,
+                csv_delimiter,
+                csv_has_header,
+                s3_config,
+                batch_size,
+                ..
+            } => ExtractorConfig::FileSnapshot {
+                url: url.clone(),
+                schema: schema.into(),
+                tb: tb.into(),
+                file_format: file_format.clone(),
+                csv_delimiter: *csv_delimiter,
+                csv_has_header: *csv_has_header,
+                s3_config: s3_config.clone(),
+                batch_size: *batch_size,
+            },
+
             _ => {
                 bail! {Error::ConfigError("unsupported extractor config".into())};
             }
@@ -375,6 +434,7 @@ impl TaskRunner {
             ExtractorConfig::MysqlSnapshot { db, tb, .. } => format!("{}.{}", db, tb),
             ExtractorConfig::PgSnapshot { schema, tb, .. } => format!("{}.{}", schema, tb),
             ExtractorConfig::MongoSnapshot { db, tb, .. } => format!("{}.{}", db, tb),
+            ExtractorConfig::FileSnapshot { schema, tb, .. } => format!("{}.{}", schema, tb),
             _ => String::new(),
         };
 
@@ -452,6 +512,8 @@ impl TaskRunner {
 
         // do pre operations before task starts
         self.pre_single_task(sinker_data_marker).await?;
+        self.start_sequence_sync(extractor_config, router, shut_down.clone())
+            .await?;
 
         // start threads
         let f1 = tokio::spawn(async move {
@@ -482,7 +544,10 @@ impl TaskRunner {
             ExtractorConfig::MysqlSnapshot { db, tb, .. }
             | ExtractorConfig::MongoSnapshot { db, tb, .. } => (db.to_owned(), tb.to_owned()),
             ExtractorConfig::PgSnapshot { schema, tb, .. }
-            | ExtractorConfig::FoxlakeS3 { schema, tb, .. } => (schema.to_owned(), tb.to_owned()),
+            | ExtractorConfig::FoxlakeS3 { schema, tb, .. }
+            | ExtractorConfig::FileSnapshot { schema, tb, .. } => {
+                (schema.to_owned(), tb.to_owned())
+            }
             _ => (String::new(), String::new()),
         };
         if !tb.is_empty() {
@@ -577,6 +642,20 @@ impl TaskRunner {
                     self.config.pipeline.batch_sink_interval_secs,
                     &self.config.pipeline.http_host,
                     self.config.pipeline.http_port,
+                    self.config.pipeline.http_security_config.clone(),
+                    self.config.pipeline.http_session_timeout_secs,
+                );
+                Ok(Box::new(pipeline))
+            }
+
+            PipelineType::GrpcServer => {
+                let pipeline = GrpcServerPipeline::new(
+                    buffer,
+                    syncer,
+                    monitor,
+                    &self.config.pipeline.grpc_host,
+                    self.config.pipeline.grpc_port,
+                    &self.config.pipeline.grpc_auth_token,
                 );
                 Ok(Box::new(pipeline))
             }
@@ -686,6 +765,89 @@ impl TaskRunner {
         }
     }
 
+    async fn create_sequence_syncer(
+        &self,
+        router: &RdbRouter,
+    ) -> anyhow::Result<Option<SequenceSyncer>> {
+        let (src_db_type, dst_db_type) = (
+            &self.config.extractor_basic.db_type,
+            &self.config.sinker_basic.db_type,
+        );
+        let (src_url, dst_url) = (
+            &self.config.extractor_basic.url,
+            &self.config.sinker_basic.url,
+        );
+        let enable_sqlx_log = TaskUtil::check_enable_sqlx_log(&self.config.runtime.log_level);
+
+        let mut sequence_syncer = SequenceSyncer {
+            src_mysql_conn_pool: None,
+            dst_mysql_conn_pool: None,
+            src_pg_conn_pool: None,
+            dst_pg_conn_pool: None,
+            filter: RdbFilter::from_config(&self.config.filter, src_db_type)?,
+            router: router.clone(),
+        };
+        match (src_db_type, dst_db_type) {
+            (DbType::Mysql, DbType::Mysql) => {
+                sequence_syncer.src_mysql_conn_pool =
+                    Some(TaskUtil::create_mysql_conn_pool(src_url, 1, enable_sqlx_log).await?);
+                sequence_syncer.dst_mysql_conn_pool =
+                    Some(TaskUtil::create_mysql_conn_pool(dst_url, 1, enable_sqlx_log).await?);
+            }
+
+            (DbType::Pg, DbType::Pg) => {
+                sequence_syncer.src_pg_conn_pool =
+                    Some(TaskUtil::create_pg_conn_pool(src_url, 1, enable_sqlx_log).await?);
+                sequence_syncer.dst_pg_conn_pool =
+                    Some(TaskUtil::create_pg_conn_pool(dst_url, 1, enable_sqlx_log).await?);
+            }
+
+            _ => {
+                log_warn!(
+                    "sequence sync not supported from: {} to: {}",
+                    src_db_type,
+                    dst_db_type
+                );
+                return Ok(None);
+            }
+        }
+        Ok(Some(sequence_syncer))
+    }
+
+    async fn start_sequence_sync(
+        &self,
+        extractor_config: &ExtractorConfig,
+        router: &RdbRouter,
+        shut_down: Arc<AtomicBool>,
+    ) -> anyhow::Result<()> {
+        let interval_secs = match &self.config.sequence {
+            Some(config) if config.sync_interval_secs > 0 => config.sync_interval_secs,
+            _ => return Ok(()),
+        };
+        // only cdc tasks keep the target in sync continuously
+        if !matches!(
+            extractor_config,
+            ExtractorConfig::MysqlCdc { .. } | ExtractorConfig::PgCdc { .. }
+        ) {
+            return Ok(());
+        }
+
+        let Some(mut sequence_syncer) = self.create_sequence_syncer(router).await? else {
+            return Ok(());
+        };
+        tokio::spawn(async move {
+            while !shut_down.load(Ordering::Acquire) {
+                TimeUtil::sleep_millis(interval_secs * 1000).await;
+                if let Err(error) = sequence_syncer.sync().await {
+                    log_error!("sequence sync failed, error: {}", error);
+                }
+            }
+            sequence_syncer.close().await
+        });
+        log_info!("sequence sync started, interval_secs: {}", interval_secs);
+        Ok(())
+    }
+
     async fn pre_single_task(&self, sinker_data_marker: Option<DataMarker>) -> anyhow::Result<()> {
         // create heartbeat table
         let schema_tb = match &self.config.extractor {
@@ -760,6 +922,11 @@ impl TaskRunner {
 
         // create data marker table
         if let Some(data_marker) = sinker_data_marker {
+            // replication origins are created by sinker sessions
+            if data_marker.is_replication_origin() {
+                return Ok(());
+            }
+
             match &self.config.sinker {
                 SinkerConfig::Mysql { url, .. } => {
                     let db_sql = format!(
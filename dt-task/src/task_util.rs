@@ -363,4 +363,13 @@ impl TaskUtil {
 
         S3Client::new_with(rusoto_core::HttpClient::new().unwrap(), credentials, region)
     }
+
+    /// For targets / sources which may be either local files or files in s3
+    pub fn create_optional_s3_client(s3_config: &S3Config) -> Option<S3Client> {
+        if s3_config.bucket.is_empty() {
+            None
+        } else {
+            Some(Self::create_s3_client(s3_config))
+        }
+    }
 }
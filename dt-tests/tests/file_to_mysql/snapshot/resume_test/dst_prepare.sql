@@ -0,0 +1,11 @@
+DROP DATABASE IF EXISTS test_db_1;
+
+CREATE DATABASE test_db_1;
+
+CREATE TABLE test_db_1.one_pk_no_uk (id int, value varchar(255), PRIMARY KEY (id));
+
+CREATE TABLE test_db_1.resume_table (id int, value varchar(255), PRIMARY KEY (id));
+
+CREATE TABLE test_db_1.finished_table (id int, value varchar(255), PRIMARY KEY (id));
+
+CREATE TABLE test_db_1.no_position_table (id int, value varchar(255), PRIMARY KEY (id));
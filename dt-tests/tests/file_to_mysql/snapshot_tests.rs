@@ -0,0 +1,28 @@
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use dt_common::config::config_enums::DbType;
+    use serial_test::serial;
+
+    use crate::test_runner::test_base::TestBase;
+
+    #[tokio::test]
+    #[serial]
+    async fn snapshot_resume_test() {
+        let mut dst_expected_counts = HashMap::new();
+        // resume from 1.csv, 2 rows extracted
+        dst_expected_counts.insert("test_db_1.one_pk_no_uk", 4);
+        // resume from 2.csv, 1 row extracted
+        dst_expected_counts.insert("test_db_1.resume_table", 1);
+        dst_expected_counts.insert("test_db_1.finished_table", 0);
+        dst_expected_counts.insert("test_db_1.no_position_table", 6);
+
+        TestBase::run_snapshot_test_and_check_dst_count(
+            "file_to_mysql/snapshot/resume_test",
+            &DbType::Mysql,
+            dst_expected_counts,
+        )
+        .await;
+    }
+}
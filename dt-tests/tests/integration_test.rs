@@ -1,18 +1,26 @@
+mod file_to_mysql;
 mod log_reader;
 mod mongo_to_mongo;
+mod mongo_to_mysql;
 mod mysql_to_clickhouse;
 mod mysql_to_doris;
+mod mysql_to_elasticsearch;
+mod mysql_to_file;
+mod mysql_to_file_to_mysql;
 mod mysql_to_foxlake;
 mod mysql_to_kafka_to_mysql;
+mod mysql_to_mongo;
 mod mysql_to_mysql;
 mod mysql_to_mysql_case_sensitive;
 mod mysql_to_mysql_lua;
+mod mysql_to_pg;
 mod mysql_to_redis;
 mod mysql_to_starrocks;
 mod mysql_to_tidb;
 mod pg_to_clickhouse;
 mod pg_to_doris;
 mod pg_to_kafka_to_pg;
+mod pg_to_mysql;
 mod pg_to_pg;
 mod pg_to_pg_lua;
 mod pg_to_starrocks;
@@ -0,0 +1,21 @@
+use test_db_1
+
+db.tb_1.insertOne({ "_id": 1, "name": "a", "age": 1, "addr": { "city": "a", "geo": { "lat": 1 } }, "tags": ["x", "y", "z"] });
+db.tb_1.insertOne({ "_id": 2, "name": "b", "age": 2, "addr": { "city": "b" }, "tags": [{ "k": "a" }, { "k": "b" }] });
+
+-- set and unset in the same update
+db.tb_1.updateOne({ "_id": 1 }, { "$set": { "name": "a_1" }, "$unset": { "age": "" } });
+
+-- nested fields
+db.tb_1.updateOne({ "_id": 1 }, { "$set": { "addr.city": "a_1", "addr.geo.lng": 2 } });
+db.tb_1.updateOne({ "_id": 1 }, { "$unset": { "addr.geo.lat": "" } });
+
+-- array elements
+db.tb_1.updateOne({ "_id": 1 }, { "$set": { "tags.0": "x_1" } });
+db.tb_1.updateOne({ "_id": 2 }, { "$set": { "tags.1.k": "b_1" } });
+db.tb_1.updateOne({ "_id": 1 }, { "$push": { "tags": "w" } });
+
+-- array truncated
+db.tb_1.updateOne({ "_id": 1 }, { "$pop": { "tags": 1 } });
+db.tb_1.updateOne({ "_id": 1 }, { "$pull": { "tags": "y" } });
+db.tb_1.updateOne({ "_id": 2 }, { "$pop": { "tags": -1 }, "$unset": { "age": "" } });
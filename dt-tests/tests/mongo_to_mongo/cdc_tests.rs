@@ -10,6 +10,12 @@ mod test {
         TestBase::run_mongo_cdc_test("mongo_to_mongo/cdc/op_log_test", 3000, 3000).await;
     }
 
+    #[tokio::test]
+    #[serial]
+    async fn cdc_op_log_diff_test() {
+        TestBase::run_mongo_cdc_test("mongo_to_mongo/cdc/op_log_diff_test", 3000, 3000).await;
+    }
+
     #[tokio::test]
     #[serial]
     async fn cdc_change_steram_test() {
@@ -0,0 +1,30 @@
+use test_db_1
+
+-- insert
+db.tb_1.insertOne({ "name": "a", "age": "1", "addr": { "city": "c_1" } });
+db.tb_1.insertOne({ "name": "b", "age": "2", "addr": { "city": "c_2" } });
+db.tb_1.insertOne({ "name": "c", "age": "3", "tags": ["x", "y"] });
+db.tb_1.insertOne({ "name": "d", "age": "4" });
+db.tb_1.insertOne({ "name": "e", "age": "5" });
+
+db.tb_2.insertMany([{ "name": "a", "age": "1", "_id": "1" }, { "name": "b", "age": "2", "_id": "2" }, { "name": "c", "age": "3" }]);
+
+-- set, u
+db.tb_1.updateOne({ "age" : "4" }, { "$set": { "name" : "d_1" } });
+db.tb_1.updateOne({ "age" : "1" }, { "$set": { "addr.city" : "c_3" } });
+
+db.tb_2.updateOne({ "age" : "1" }, { "$set": { "name" : "a_1" } });
+
+-- set, i
+db.tb_1.updateOne({ "age" : "5" }, { "$set": { "salary" : 100 } });
+
+-- unset, d
+db.tb_1.updateOne({ "age" : "2" }, { "$unset": { "addr" : "" } });
+
+-- inc, u
+db.tb_1.updateOne({ "age" : "5" }, { "$inc": { "salary" : 100 } });
+
+-- delete
+db.tb_1.deleteOne({ "name": "c", "age": "3" });
+
+db.tb_2.deleteMany({ "age": "2" });
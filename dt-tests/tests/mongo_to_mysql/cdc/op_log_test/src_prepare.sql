@@ -0,0 +1,6 @@
+use test_db_1
+
+db.dropDatabase();
+
+db.createCollection("tb_1");
+db.createCollection("tb_2");
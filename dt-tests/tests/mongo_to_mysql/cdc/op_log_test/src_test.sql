@@ -0,0 +1,26 @@
+use test_db_1
+
+-- insert
+db.tb_1.insertOne({ "_id": "1", "name": "a", "addr": { "city": "c_1" }, "attrs": { "k": "v_1" }, "tags": ["x"] });
+db.tb_1.insertOne({ "_id": "2", "name": "b", "addr": { "city": "c_2" }, "attrs": { "k": "v_2" }, "tags": ["y"] });
+
+db.tb_2.insertOne({ "_id": "1", "name": "a", "info": { "level": 1, "note": "n_1" } });
+db.tb_2.insertOne({ "_id": "2", "name": "b", "info": { "level": 2, "note": "n_2" } });
+
+-- nested field mapped to a col
+db.tb_1.updateOne({ "_id" : "1" }, { "$set": { "addr.city" : "c_3" } });
+
+-- nested field inside a col stored as JSON
+db.tb_1.updateOne({ "_id" : "1" }, { "$set": { "attrs.k" : "v_3" } });
+db.tb_1.updateOne({ "_id" : "2" }, { "$unset": { "attrs.k" : "" } });
+db.tb_1.updateOne({ "_id" : "1" }, { "$push": { "tags" : "z" } });
+db.tb_1.updateOne({ "_id" : "2" }, { "$set": { "tags.0" : "y_1" } });
+
+-- nested field without col_map
+db.tb_2.updateOne({ "_id" : "1" }, { "$set": { "info.level" : 3 } });
+db.tb_2.updateOne({ "_id" : "2" }, { "$unset": { "info.note" : "" } });
+db.tb_2.updateOne({ "_id" : "2" }, { "$inc": { "info.level" : 10 } });
+
+-- delete
+db.tb_1.deleteOne({ "_id": "2" });
+db.tb_2.deleteOne({ "_id": "2" });
@@ -0,0 +1,19 @@
+#[cfg(test)]
+mod test {
+
+    use serial_test::serial;
+
+    use crate::test_runner::test_base::TestBase;
+
+    #[tokio::test]
+    #[serial]
+    async fn cdc_basic_test() {
+        TestBase::run_mongo_rdb_cdc_test("mongo_to_mysql/cdc/basic_test", 3000, 2000).await;
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn cdc_op_log_test() {
+        TestBase::run_mongo_rdb_cdc_test("mongo_to_mysql/cdc/op_log_test", 3000, 2000).await;
+    }
+}
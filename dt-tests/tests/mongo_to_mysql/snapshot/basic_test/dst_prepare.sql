@@ -0,0 +1 @@
+drop database if exists test_db_1;
@@ -0,0 +1,12 @@
+use test_db_1
+
+-- scalar, nested and array fields
+db.tb_1.insertOne({ "name": "a", "age": 1, "score": 1.5, "vip": true });
+db.tb_1.insertOne({ "name": "b", "age": 2, "tags": ["x", "y"], "addr": { "city": "c_1", "zip": "z_1" } });
+db.tb_1.insertOne({ "name": "c", "age": 3, "tags": [], "addr": { "city": "c_2", "geo": [1, 2] } });
+-- missing and null fields
+db.tb_1.insertOne({ "name": "d" });
+db.tb_1.insertOne({ "name": null, "age": null });
+
+-- custom defined _id
+db.tb_2.insertMany([{ "name": "a", "age": "1", "_id": "1" }, { "name": "b", "age": "2", "_id": "2" }, { "name": "c", "age": "3" }]);
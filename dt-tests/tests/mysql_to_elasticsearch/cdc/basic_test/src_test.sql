@@ -0,0 +1,13 @@
+INSERT INTO test_db_1.one_pk_no_uk VALUES (1,1,1,2,2,3,3,4,4,5,5,123456.1234,1234.12,12345.123,x'0000000000000000','2022-01-02 03:04:05.123456','03:04:05.123456','2022-01-02',2022,'2022-01-02 03:04:05.123456','ab','cd','ef','gh','ij','kl','mn','op',x'0123456789ABCDEF',x'0123456789ABCDEF',x'0123456789ABCDEF',x'0123456789ABCDEF','x-small','c','{"中文":"😀"}');
+INSERT INTO test_db_1.one_pk_no_uk VALUES (2,2,2,20,20,30,30,40,40,50,50,654321.4321,4321.21,54321.321,x'FFFFFFFFFFFFFFFF','2021-02-01 04:05:06.654321','04:05:06.654321','2012-02-01',2021,'2021-02-01 04:05:06.654321','1','2','3','4','5','6','7','8',x'0123456789ABCDEF',x'0123456789ABCDEF',x'0123456789ABCDEF',x'0123456789ABCDEF','x-small','c','{"a":"b"}');
+INSERT INTO test_db_1.one_pk_no_uk VALUES (3, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL);
+INSERT INTO test_db_1.multi_pk VALUES (1, 'a', 'v_1'), (1, 'b', 'v_2'), (2, 'a', NULL);
+
+UPDATE test_db_1.one_pk_no_uk SET tinyint_col=2, tinyint_col_unsigned=2, smallint_col=20, smallint_col_unsigned=20, mediumint_col=30, mediumint_col_unsigned=30, int_col=40, int_col_unsigned=40, bigint_col=50, bigint_col_unsigned=50, decimal_col=654321.4321, float_col=4321.21, double_col=54321.321, bit_col=x'FFFFFFFFFFFFFFFF', datetime_col='2021-02-01 04:05:06.654321', time_col='04:05:06.654321', date_col='2012-02-01', year_col=2021, timestamp_col='2021-02-01 04:05:06.654321', char_col='1', varchar_col='2', binary_col='3', varbinary_col='4', tinytext_col='5', text_col='6', mediumtext_col='7', longtext_col='8', tinyblob_col=x'ABCDEF0123456789', blob_col=x'ABCDEF0123456789', mediumblob_col=x'ABCDEF0123456789', longblob_col=x'ABCDEF0123456789', enum_col='small', set_col='b', json_col='{"a":"b"}' WHERE pk=1;
+UPDATE test_db_1.one_pk_no_uk SET tinyint_col=1, tinyint_col_unsigned=1, smallint_col=2, smallint_col_unsigned=2, mediumint_col=3, mediumint_col_unsigned=3, int_col=4, int_col_unsigned=4, bigint_col=5, bigint_col_unsigned=5, decimal_col=123456.1234, float_col=1234.12, double_col=12345.123, bit_col=x'0000000000000000', datetime_col='2022-01-02 03:04:05.123456', time_col='03:04:05.123456', date_col='2022-01-02', year_col=2022, timestamp_col='2022-01-02 03:04:05.123456', char_col='ab', varchar_col='cd', binary_col='ef', varbinary_col='gh', tinytext_col='ij', text_col='kl', mediumtext_col='mn', longtext_col='op', tinyblob_col=x'0123456789ABCDEF', blob_col=x'0123456789ABCDEF', mediumblob_col=x'0123456789ABCDEF', longblob_col=x'0123456789ABCDEF', enum_col='x-small', set_col='c', json_col='{"中文":"😀"}' WHERE pk=2;
+-- update keys
+UPDATE test_db_1.multi_pk SET value='v_3' WHERE pk_1=2;
+UPDATE test_db_1.multi_pk SET pk_2='c' WHERE pk_1=1 AND pk_2='b';
+
+DELETE FROM test_db_1.one_pk_no_uk;
+DELETE FROM test_db_1.multi_pk WHERE pk_1=1;
\ No newline at end of file
@@ -0,0 +1,2 @@
+DELETE /dst_test_db_1.dst_one_pk
+DELETE /dst_test_db_1.dst_multi_pk
@@ -0,0 +1,6 @@
+DROP DATABASE IF EXISTS test_db_1;
+
+CREATE DATABASE test_db_1;
+
+CREATE TABLE test_db_1.one_pk (pk int, value varchar(255), PRIMARY KEY (pk));
+CREATE TABLE test_db_1.multi_pk (pk_1 int, pk_2 varchar(64), value varchar(255), PRIMARY KEY (pk_1, pk_2));
@@ -0,0 +1,10 @@
+INSERT INTO test_db_1.one_pk VALUES (1, 'v_1'), (2, 'v_2'), (3, NULL);
+INSERT INTO test_db_1.multi_pk VALUES (1, 'a', 'v_1'), (1, 'b', 'v_2'), (2, 'a', NULL);
+
+UPDATE test_db_1.one_pk SET value='v_3' WHERE pk=3;
+-- update keys
+UPDATE test_db_1.one_pk SET pk=4 WHERE pk=2;
+UPDATE test_db_1.multi_pk SET pk_2='c' WHERE pk_1=1 AND pk_2='b';
+
+DELETE FROM test_db_1.one_pk WHERE pk=1;
+DELETE FROM test_db_1.multi_pk WHERE pk_1=1;
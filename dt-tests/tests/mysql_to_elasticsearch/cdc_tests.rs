@@ -0,0 +1,27 @@
+#[cfg(test)]
+mod test {
+
+    use serial_test::serial;
+
+    use crate::test_runner::rdb_elasticsearch_test_runner::RdbElasticsearchTestRunner;
+
+    #[tokio::test]
+    #[serial]
+    async fn cdc_basic_test() {
+        let runner = RdbElasticsearchTestRunner::new("mysql_to_elasticsearch/cdc/basic_test")
+            .await
+            .unwrap();
+        runner.run_cdc_test(3000, 3000).await.unwrap();
+        runner.close().await.unwrap();
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn cdc_route_test() {
+        let runner = RdbElasticsearchTestRunner::new("mysql_to_elasticsearch/cdc/route_test")
+            .await
+            .unwrap();
+        runner.run_cdc_test(3000, 3000).await.unwrap();
+        runner.close().await.unwrap();
+    }
+}
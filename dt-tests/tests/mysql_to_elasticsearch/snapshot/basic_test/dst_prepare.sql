@@ -0,0 +1,2 @@
+DELETE /test_db_1.one_pk_no_uk
+DELETE /test_db_1.multi_pk
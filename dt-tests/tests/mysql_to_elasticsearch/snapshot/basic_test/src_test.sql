@@ -0,0 +1,5 @@
+INSERT INTO test_db_1.one_pk_no_uk VALUES (1,1,1,2,2,3,3,4,4,5,5,123456.1234,1234.12,12345.123,x'0000000000000000','2022-01-02 03:04:05.123456','03:04:05.123456','2022-01-02',2022,'2022-01-02 03:04:05.123456','ab','cd','ef','gh','ij','kl','mn','op',x'0123456789ABCDEF',x'0123456789ABCDEF',x'0123456789ABCDEF',x'0123456789ABCDEF','x-small','c','{"中文":"😀"}');
+INSERT INTO test_db_1.one_pk_no_uk VALUES (2,2,2,20,20,30,30,40,40,50,50,654321.4321,4321.21,54321.321,x'FFFFFFFFFFFFFFFF','2021-02-01 04:05:06.654321','04:05:06.654321','2012-02-01',2021,'2021-02-01 04:05:06.654321','1','2','3','4','5','6','7','8',x'0123456789ABCDEF',x'0123456789ABCDEF',x'0123456789ABCDEF',x'0123456789ABCDEF','x-small','c','{"a":"b"}');
+INSERT INTO test_db_1.one_pk_no_uk VALUES (3, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL);
+
+INSERT INTO test_db_1.multi_pk VALUES (1, 'a', 'v_1'), (1, 'b', 'v_2'), (2, 'a', NULL);
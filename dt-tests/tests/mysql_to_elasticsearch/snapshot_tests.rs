@@ -0,0 +1,17 @@
+#[cfg(test)]
+mod test {
+
+    use serial_test::serial;
+
+    use crate::test_runner::rdb_elasticsearch_test_runner::RdbElasticsearchTestRunner;
+
+    #[tokio::test]
+    #[serial]
+    async fn snapshot_basic_test() {
+        let runner = RdbElasticsearchTestRunner::new("mysql_to_elasticsearch/snapshot/basic_test")
+            .await
+            .unwrap();
+        runner.run_snapshot_test().await.unwrap();
+        runner.close().await.unwrap();
+    }
+}
@@ -0,0 +1,13 @@
+INSERT INTO test_db_1.one_pk_no_uk VALUES (1, 5, 'ab', 123456.1234, '2022-01-02 03:04:05.123456', '2022-01-02', x'0123456789ABCDEF');
+INSERT INTO test_db_1.one_pk_no_uk VALUES (2, -5, 'a,b "c"', -654321.4321, '2021-02-01 04:05:06.654321', '2012-02-01', x'ABCDEF0123456789');
+INSERT INTO test_db_1.one_pk_no_uk VALUES (3, NULL, NULL, NULL, NULL, NULL, NULL);
+INSERT INTO test_db_1.multi_pk VALUES (1, 'a', 'v_1'), (1, 'b', 'v_2'), (2, 'a', NULL);
+
+UPDATE test_db_1.one_pk_no_uk SET bigint_col=6, varchar_col='cd', blob_col=NULL WHERE id=1;
+UPDATE test_db_1.one_pk_no_uk SET bigint_col=NULL, decimal_col=1.1 WHERE id=3;
+-- update keys
+UPDATE test_db_1.multi_pk SET value='v_3' WHERE pk_1=2;
+UPDATE test_db_1.multi_pk SET pk_2='c' WHERE pk_1=1 AND pk_2='b';
+
+DELETE FROM test_db_1.one_pk_no_uk WHERE id=2;
+DELETE FROM test_db_1.multi_pk WHERE pk_1=1 AND pk_2='a';
@@ -0,0 +1,17 @@
+#[cfg(test)]
+mod test {
+
+    use serial_test::serial;
+
+    use crate::test_runner::rdb_file_test_runner::RdbFileTestRunner;
+
+    #[tokio::test]
+    #[serial]
+    async fn cdc_basic_test() {
+        let runner = RdbFileTestRunner::new("mysql_to_file/cdc/basic_test")
+            .await
+            .unwrap();
+        runner.run_cdc_test(3000, 3000).await.unwrap();
+        runner.close().await.unwrap();
+    }
+}
@@ -0,0 +1,4 @@
+INSERT INTO test_db_1.one_pk_no_uk VALUES (1, 5, 'ab', 123456.1234, '2022-01-02 03:04:05.123456', '2022-01-02', x'0123456789ABCDEF');
+INSERT INTO test_db_1.one_pk_no_uk VALUES (2, -5, 'a,b "c"', -654321.4321, '2021-02-01 04:05:06.654321', '2012-02-01', x'ABCDEF0123456789');
+INSERT INTO test_db_1.one_pk_no_uk VALUES (3, NULL, NULL, NULL, NULL, NULL, NULL);
+INSERT INTO test_db_1.multi_pk VALUES (1, 'a', 'v_1'), (1, 'b', 'v_2'), (2, 'a', NULL);
@@ -0,0 +1,7 @@
+DROP DATABASE IF EXISTS test_db_1;
+
+CREATE DATABASE test_db_1;
+
+CREATE TABLE test_db_1.one_pk_no_uk (id int, bigint_col bigint, varchar_col varchar(255), decimal_col decimal(10,4), datetime_col datetime(6), date_col date, blob_col blob, PRIMARY KEY (id));
+
+CREATE TABLE test_db_1.multi_pk (pk_1 int, pk_2 varchar(64), value varchar(255), PRIMARY KEY (pk_1, pk_2));
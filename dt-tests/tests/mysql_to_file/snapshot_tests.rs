@@ -0,0 +1,31 @@
+#[cfg(test)]
+mod test {
+
+    use serial_test::serial;
+
+    use crate::test_runner::rdb_file_test_runner::RdbFileTestRunner;
+
+    #[tokio::test]
+    #[serial]
+    async fn snapshot_csv_test() {
+        run_test("mysql_to_file/snapshot/csv_test").await;
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn snapshot_jsonl_test() {
+        run_test("mysql_to_file/snapshot/jsonl_test").await;
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn snapshot_parquet_test() {
+        run_test("mysql_to_file/snapshot/parquet_test").await;
+    }
+
+    async fn run_test(test_dir: &str) {
+        let runner = RdbFileTestRunner::new(test_dir).await.unwrap();
+        runner.run_snapshot_test().await.unwrap();
+        runner.close().await.unwrap();
+    }
+}
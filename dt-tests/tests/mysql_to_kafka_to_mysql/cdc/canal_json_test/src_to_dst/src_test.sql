@@ -0,0 +1,8 @@
+INSERT INTO test_db_1.one_pk_no_uk VALUES (1, 2, 123456.1234, 12345.123, '2022-01-02 03:04:05.123456', '2022-01-02', 'abc', x'0123456789ABCDEF', x'0123456789ABCDEF', '{"k": "v"}');
+INSERT INTO test_db_1.one_pk_no_uk VALUES (2, 20, -654321.4321, 54321.321, '2021-02-01 04:05:06.654321', '2012-02-01', '中文', x'ABCDEF0123456789', x'ABCDEF0123456789', '[1, 2]');
+INSERT INTO test_db_1.one_pk_no_uk VALUES (3, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL);
+
+UPDATE test_db_1.one_pk_no_uk SET f_1 = 200, f_6 = 'def', f_7 = x'00FF' WHERE f_0 = 2;
+UPDATE test_db_1.one_pk_no_uk SET f_2 = 1.5, f_8 = x'FF00' WHERE f_0 = 3;
+
+DELETE FROM test_db_1.one_pk_no_uk WHERE f_0 = 1;
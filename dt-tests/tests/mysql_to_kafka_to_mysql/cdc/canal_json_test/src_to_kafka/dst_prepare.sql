@@ -0,0 +1 @@
+create topic test_canal_json
\ No newline at end of file
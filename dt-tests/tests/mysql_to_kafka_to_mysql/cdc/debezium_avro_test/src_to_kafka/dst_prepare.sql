@@ -0,0 +1 @@
+create topic test_debezium_avro
\ No newline at end of file
@@ -0,0 +1 @@
+create topic test_debezium_json
\ No newline at end of file
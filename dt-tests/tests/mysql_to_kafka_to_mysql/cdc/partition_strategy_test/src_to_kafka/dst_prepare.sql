@@ -0,0 +1,2 @@
+create topic test_partition_1 3
+create topic test_partition_2 2
\ No newline at end of file
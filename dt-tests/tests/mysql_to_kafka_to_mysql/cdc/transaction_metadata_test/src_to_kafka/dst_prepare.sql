@@ -0,0 +1,3 @@
+create topic test_transaction_metadata_data
+create topic test_transaction_metadata
+create topic test_schema_changes
\ No newline at end of file
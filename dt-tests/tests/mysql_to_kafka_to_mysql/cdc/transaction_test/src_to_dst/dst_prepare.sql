@@ -0,0 +1,5 @@
+DROP DATABASE IF EXISTS test_db_1;
+
+CREATE DATABASE test_db_1;
+
+CREATE TABLE test_db_1.one_pk_no_uk ( f_0 int, f_1 bigint DEFAULT NULL, f_2 decimal(10,4) DEFAULT NULL, f_3 double DEFAULT NULL, f_4 datetime(6) DEFAULT NULL, f_5 date DEFAULT NULL, f_6 varchar(255) DEFAULT NULL, f_7 varbinary(255) DEFAULT NULL, f_8 blob, f_9 json DEFAULT NULL, PRIMARY KEY (f_0) ) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4;
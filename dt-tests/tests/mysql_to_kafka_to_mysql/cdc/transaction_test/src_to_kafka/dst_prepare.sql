@@ -0,0 +1,2 @@
+create topic test_transaction
+create topic test_checkpoint 1 cleanup.policy=compact
\ No newline at end of file
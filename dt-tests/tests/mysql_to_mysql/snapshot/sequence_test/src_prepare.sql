@@ -0,0 +1,7 @@
+DROP DATABASE IF EXISTS test_db_1;
+
+CREATE DATABASE test_db_1;
+
+CREATE TABLE test_db_1.tb_1(id int AUTO_INCREMENT PRIMARY KEY, value int);
+
+CREATE TABLE test_db_1.tb_2(id bigint AUTO_INCREMENT PRIMARY KEY, value int);
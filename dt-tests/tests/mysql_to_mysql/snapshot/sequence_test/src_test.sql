@@ -0,0 +1,8 @@
+INSERT INTO test_db_1.tb_1(value) VALUES (1), (2), (3), (4), (5);
+
+-- AUTO_INCREMENT is ahead of max(id), the target would be max(id) + 1 without sync
+DELETE FROM test_db_1.tb_1 WHERE id > 3;
+
+INSERT INTO test_db_1.tb_2(id, value) VALUES (1000, 1);
+
+DELETE FROM test_db_1.tb_2;
@@ -98,4 +98,10 @@ mod test {
         // tb_parallel_size=3
         TestBase::run_snapshot_test("mysql_to_mysql/snapshot/tb_parallel_test").await;
     }
+
+    #[tokio::test]
+    #[serial]
+    async fn snapshot_sequence_test() {
+        TestBase::run_sequence_test("mysql_to_mysql/snapshot/sequence_test", "test_db_1").await;
+    }
 }
@@ -0,0 +1,15 @@
+DROP SCHEMA IF EXISTS test_db_1 CASCADE;
+DROP SCHEMA IF EXISTS test_db_2 CASCADE;
+
+CREATE SCHEMA test_db_1;
+
+CREATE TABLE test_db_1.tb_1 (id integer NOT NULL, f_1 integer, PRIMARY KEY (id));
+
+CREATE TABLE test_db_1.index_tb_1 (id integer NOT NULL, f_1 integer, f_2 character varying(64), PRIMARY KEY (id));
+
+CREATE TABLE test_db_1.truncate_tb_1 (id integer NOT NULL, f_1 integer, PRIMARY KEY (id));
+INSERT INTO test_db_1.truncate_tb_1 VALUES (1, 1);
+
+CREATE TABLE test_db_1.rename_tb_1 (id integer NOT NULL, f_1 integer, PRIMARY KEY (id));
+
+CREATE TABLE test_db_1.drop_tb_1 (id integer NOT NULL, f_1 integer, PRIMARY KEY (id));
@@ -0,0 +1,15 @@
+DROP DATABASE IF EXISTS test_db_1;
+DROP DATABASE IF EXISTS test_db_2;
+
+CREATE DATABASE test_db_1;
+
+CREATE TABLE test_db_1.tb_1 (id int NOT NULL, f_1 int, PRIMARY KEY (id));
+
+CREATE TABLE test_db_1.index_tb_1 (id int NOT NULL, f_1 int, f_2 varchar(64), PRIMARY KEY (id));
+
+CREATE TABLE test_db_1.truncate_tb_1 (id int NOT NULL, f_1 int, PRIMARY KEY (id));
+INSERT INTO test_db_1.truncate_tb_1 VALUES (1, 1);
+
+CREATE TABLE test_db_1.rename_tb_1 (id int NOT NULL, f_1 int, PRIMARY KEY (id));
+
+CREATE TABLE test_db_1.drop_tb_1 (id int NOT NULL, f_1 int, PRIMARY KEY (id));
@@ -0,0 +1,57 @@
+INSERT INTO test_db_1.tb_1 VALUES (1, 1);
+
+-- add column
+ALTER TABLE test_db_1.tb_1 ADD COLUMN f_2 int DEFAULT NULL, ADD COLUMN f_3 varchar(64) NOT NULL DEFAULT 'abc';
+
+INSERT INTO test_db_1.tb_1 VALUES (2, 2, 2, 'a''b');
+INSERT INTO test_db_1.tb_1 (id, f_1) VALUES (3, 3);
+
+-- modify / change column
+ALTER TABLE test_db_1.tb_1 MODIFY f_1 bigint;
+ALTER TABLE test_db_1.tb_1 CHANGE COLUMN f_2 f_4 varchar(32);
+
+INSERT INTO test_db_1.tb_1 VALUES (4, 4, '4', 'd');
+
+-- column default
+ALTER TABLE test_db_1.tb_1 ALTER COLUMN f_4 SET DEFAULT 'x';
+
+INSERT INTO test_db_1.tb_1 (id, f_1) VALUES (5, 5);
+
+-- drop column
+ALTER TABLE test_db_1.tb_1 DROP COLUMN f_3;
+
+INSERT INTO test_db_1.tb_1 VALUES (6, 6, '6');
+
+-- indexes
+ALTER TABLE test_db_1.index_tb_1 ADD INDEX idx_f_1 (f_1), ADD UNIQUE KEY uk_f_2 (f_2);
+CREATE INDEX idx_f_1_f_2 ON test_db_1.index_tb_1 (f_1, f_2);
+ALTER TABLE test_db_1.index_tb_1 RENAME INDEX idx_f_1 TO idx_f_1_new;
+ALTER TABLE test_db_1.index_tb_1 DROP INDEX idx_f_1_new;
+DROP INDEX idx_f_1_f_2 ON test_db_1.index_tb_1;
+
+INSERT INTO test_db_1.index_tb_1 VALUES (1, 1, 'a'), (2, 1, 'b');
+
+-- truncate table
+TRUNCATE TABLE test_db_1.truncate_tb_1;
+
+INSERT INTO test_db_1.truncate_tb_1 VALUES (2, 2);
+
+-- create table
+CREATE TABLE test_db_1.create_tb_1 (id int NOT NULL AUTO_INCREMENT, f_1 varchar(64) DEFAULT 'a' COMMENT 'f_1 comment', f_2 int NOT NULL DEFAULT 0, PRIMARY KEY (id), KEY idx_f_1 (f_1)) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4;
+
+INSERT INTO test_db_1.create_tb_1 (f_1, f_2) VALUES ('a', 1), (NULL, 2);
+
+-- rename table
+RENAME TABLE test_db_1.rename_tb_1 TO test_db_1.dst_rename_tb_1;
+
+INSERT INTO test_db_1.dst_rename_tb_1 VALUES (1, 1);
+
+-- drop table
+DROP TABLE test_db_1.drop_tb_1;
+
+-- create database
+CREATE DATABASE test_db_2;
+
+CREATE TABLE test_db_2.tb_1 (id int NOT NULL, f_1 int, PRIMARY KEY (id));
+
+INSERT INTO test_db_2.tb_1 VALUES (1, 1);
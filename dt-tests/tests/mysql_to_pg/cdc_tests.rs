@@ -0,0 +1,13 @@
+#[cfg(test)]
+mod test {
+
+    use serial_test::serial;
+
+    use crate::test_runner::test_base::TestBase;
+
+    #[tokio::test]
+    #[serial]
+    async fn cdc_ddl_test() {
+        TestBase::run_ddl_test("mysql_to_pg/cdc/ddl_test", 3000, 5000).await;
+    }
+}
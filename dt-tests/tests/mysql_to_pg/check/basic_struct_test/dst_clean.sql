@@ -0,0 +1 @@
+drop schema if exists struct_check_test_1 CASCADE;
\ No newline at end of file
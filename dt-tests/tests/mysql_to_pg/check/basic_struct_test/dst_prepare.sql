@@ -0,0 +1,11 @@
+drop schema if exists struct_check_test_1 CASCADE;
+
+create schema struct_check_test_1;
+
+CREATE TABLE struct_check_test_1.match_tb (id integer NOT NULL, name character varying(64), amount numeric(30,0), created timestamp(0) without time zone, tiny_col smallint, big_col numeric(20,0), doc jsonb, PRIMARY KEY (id));
+
+-- not match: missing
+-- CREATE TABLE struct_check_test_1.not_match_missing (id integer NOT NULL, PRIMARY KEY (id));
+
+-- not match: column
+CREATE TABLE struct_check_test_1.not_match_column (id integer NOT NULL, name character varying(32), PRIMARY KEY (id));
@@ -0,0 +1 @@
+drop database if exists struct_check_test_1;
\ No newline at end of file
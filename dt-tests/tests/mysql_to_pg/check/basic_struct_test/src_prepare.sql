@@ -0,0 +1,12 @@
+drop database if exists struct_check_test_1;
+
+create database struct_check_test_1;
+
+-- match, amount is mapped to numeric(30,0) by col_type_map
+CREATE TABLE struct_check_test_1.match_tb (id int NOT NULL, name varchar(64), amount decimal(10,2), created datetime, tiny_col tinyint, big_col bigint unsigned, doc json, PRIMARY KEY (id));
+
+-- not match: missing
+CREATE TABLE struct_check_test_1.not_match_missing (id int NOT NULL, PRIMARY KEY (id));
+
+-- not match: column
+CREATE TABLE struct_check_test_1.not_match_column (id int NOT NULL, name varchar(64), PRIMARY KEY (id));
@@ -0,0 +1,13 @@
+#[cfg(test)]
+mod test {
+
+    use serial_test::serial;
+
+    use crate::test_runner::test_base::TestBase;
+
+    #[tokio::test]
+    #[serial]
+    async fn check_struct_basic_test() {
+        TestBase::run_check_test("mysql_to_pg/check/basic_struct_test").await;
+    }
+}
@@ -0,0 +1,3 @@
+mod cdc_tests;
+mod check_tests;
+mod struct_tests;
@@ -0,0 +1,2 @@
+DROP SCHEMA IF EXISTS test_db_1 CASCADE;
+DROP SCHEMA IF EXISTS dst_test_db_2 CASCADE;
@@ -0,0 +1,74 @@
+DROP DATABASE IF EXISTS test_db_1;
+DROP DATABASE IF EXISTS test_db_2;
+
+CREATE DATABASE test_db_1;
+CREATE DATABASE test_db_2;
+
+```
+CREATE TABLE test_db_1.full_column_type (
+    id bigint unsigned NOT NULL AUTO_INCREMENT,
+    tinyint_col tinyint DEFAULT 1,
+    tinyint_col_unsigned tinyint unsigned,
+    smallint_col smallint DEFAULT NULL,
+    smallint_col_unsigned smallint unsigned DEFAULT NULL,
+    mediumint_col mediumint DEFAULT NULL,
+    mediumint_col_unsigned mediumint unsigned DEFAULT NULL,
+    int_col int NOT NULL DEFAULT 0,
+    int_col_unsigned int unsigned DEFAULT NULL,
+    bigint_col bigint DEFAULT NULL,
+    bigint_col_unsigned bigint unsigned DEFAULT NULL,
+    decimal_col decimal(10,4) DEFAULT '0.0000',
+    float_col float(6,2) DEFAULT NULL,
+    double_col double(8,3) DEFAULT NULL,
+    bit_col bit(64) DEFAULT NULL,
+    datetime_col datetime(6) DEFAULT CURRENT_TIMESTAMP(6),
+    time_col time(6) DEFAULT NULL,
+    date_col date DEFAULT NULL,
+    year_col year DEFAULT NULL,
+    timestamp_col timestamp(3) NULL DEFAULT CURRENT_TIMESTAMP(3) ON UPDATE CURRENT_TIMESTAMP(3),
+    char_col char(255) DEFAULT NULL,
+    varchar_col varchar(255) DEFAULT 'it''s' COMMENT 'varchar comment',
+    binary_col binary(255) DEFAULT NULL,
+    varbinary_col varbinary(255) DEFAULT NULL,
+    tinytext_col tinytext,
+    text_col text,
+    mediumtext_col mediumtext,
+    longtext_col longtext,
+    tinyblob_col tinyblob,
+    blob_col blob,
+    mediumblob_col mediumblob,
+    longblob_col longblob,
+    enum_col enum('x-small','small','medium','large','x-large') DEFAULT 'small',
+    set_col set('a','b','c','d','e') DEFAULT NULL,
+    json_col json DEFAULT NULL,
+    PRIMARY KEY (id),
+    UNIQUE KEY uk_int_col (int_col),
+    KEY idx_varchar_char (varchar_col, char_col)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COMMENT='full column type';
+```
+
+```
+CREATE TABLE test_db_1.check_pk_cols_order (
+    col_1 INT,
+    col_2 INT,
+    pk_3 INT,
+    pk_1 INT,
+    col_3 INT,
+    pk_2 INT,
+    PRIMARY KEY(pk_1, pk_2, pk_3)
+);
+```
+
+CREATE TABLE test_db_1.fk_parent (id INT PRIMARY KEY);
+
+```
+CREATE TABLE test_db_1.fk_child (
+    id INT PRIMARY KEY,
+    parent_id INT,
+    amount bigint unsigned,
+    CONSTRAINT fk_child_parent FOREIGN KEY (parent_id) REFERENCES test_db_1.fk_parent (id),
+    CONSTRAINT chk_amount CHECK (amount < 1000000)
+);
+```
+
+CREATE TABLE test_db_2.router_test_1 (pk int, col_1 int, PRIMARY KEY(pk));
@@ -0,0 +1,16 @@
+DROP SCHEMA IF EXISTS test_db_1 CASCADE;
+DROP SCHEMA IF EXISTS test_db_2 CASCADE;
+CREATE SCHEMA test_db_1;
+CREATE SCHEMA test_db_2;
+
+CREATE TABLE test_db_1.numeric_table (pk SERIAL PRIMARY KEY, si SMALLINT, i INTEGER, bi BIGINT, r REAL, db DOUBLE PRECISION, d DECIMAL(10,2), n NUMERIC, b BOOLEAN, csh MONEY);
+
+CREATE TABLE test_db_1.string_table (pk INT PRIMARY KEY, vc VARCHAR(10), ch CHAR(4), t TEXT, ba BYTEA, bs BIT(8), bv BIT VARYING(16), u UUID, ip INET, c CIDR, m MACADDR);
+
+CREATE TABLE test_db_1.time_table (pk INT PRIMARY KEY, ts TIMESTAMP, ts_ms TIMESTAMP(3), tz TIMESTAMPTZ, d DATE, t TIME, t_ms TIME(3), it INTERVAL, ttz TIMETZ);
+
+CREATE TABLE test_db_1.json_table (pk BIGINT GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY, j JSON, jb JSONB, int_array INT[], text_array TEXT[], nested_array INT[][]);
+
+CREATE TABLE test_db_2.router_test_1 (pk INT PRIMARY KEY, col_1 INT DEFAULT 0, col_2 VARCHAR(10) DEFAULT 'abc', created_at TIMESTAMP DEFAULT now());
+CREATE INDEX idx_col_1 ON test_db_2.router_test_1(col_1);
+CREATE UNIQUE INDEX uk_col_2 ON test_db_2.router_test_1(col_2);
@@ -0,0 +1,55 @@
+INSERT INTO test_db_1.tb_1 VALUES (1, 1);
+
+-- add column
+ALTER TABLE test_db_1.tb_1 ADD COLUMN f_2 int, ADD COLUMN IF NOT EXISTS f_3 varchar(64) NOT NULL DEFAULT 'abc';
+
+INSERT INTO test_db_1.tb_1 VALUES (2, 2, 2, 'a''b');
+INSERT INTO test_db_1.tb_1 (id, f_1) VALUES (3, 3);
+
+-- rename column
+ALTER TABLE test_db_1.tb_1 RENAME COLUMN f_2 TO f_4;
+
+INSERT INTO test_db_1.tb_1 VALUES (4, 4, 4, 'd');
+
+-- column default
+ALTER TABLE test_db_1.tb_1 ALTER COLUMN f_4 SET DEFAULT 1, ALTER COLUMN f_3 DROP DEFAULT;
+
+INSERT INTO test_db_1.tb_1 (id, f_1, f_3) VALUES (5, 5, 'e');
+
+-- drop column
+ALTER TABLE test_db_1.tb_1 DROP COLUMN IF EXISTS f_3;
+
+INSERT INTO test_db_1.tb_1 VALUES (6, 6, 6);
+
+-- indexes and constraints
+CREATE INDEX idx_f_1 ON test_db_1.index_tb_1 (f_1);
+CREATE UNIQUE INDEX uk_f_2 ON test_db_1.index_tb_1 USING btree (f_2 DESC);
+ALTER TABLE test_db_1.index_tb_1 ADD CONSTRAINT uk_f_1_f_2 UNIQUE (f_1, f_2);
+ALTER TABLE test_db_1.index_tb_1 DROP CONSTRAINT uk_f_1_f_2;
+
+INSERT INTO test_db_1.index_tb_1 VALUES (1, 1, 'a'), (2, 1, 'b');
+
+-- truncate table
+TRUNCATE TABLE test_db_1.truncate_tb_1;
+
+INSERT INTO test_db_1.truncate_tb_1 VALUES (2, 2);
+
+-- create table
+CREATE TABLE test_db_1.create_tb_1 (id serial PRIMARY KEY, f_1 varchar(64) DEFAULT 'a', f_2 int NOT NULL DEFAULT 0, CONSTRAINT uk_create_f_1 UNIQUE (f_1));
+
+INSERT INTO test_db_1.create_tb_1 (f_1, f_2) VALUES ('a', 1), (NULL, 2);
+
+-- rename table
+ALTER TABLE test_db_1.rename_tb_1 RENAME TO dst_rename_tb_1;
+
+INSERT INTO test_db_1.dst_rename_tb_1 VALUES (1, 1);
+
+-- drop table
+DROP TABLE test_db_1.drop_tb_1;
+
+-- create schema
+CREATE SCHEMA test_db_2;
+
+CREATE TABLE test_db_2.tb_1 (id int PRIMARY KEY, f_1 int);
+
+INSERT INTO test_db_2.tb_1 VALUES (1, 1);
@@ -0,0 +1,19 @@
+#[cfg(test)]
+mod test {
+
+    use serial_test::serial;
+
+    use crate::test_runner::test_base::TestBase;
+
+    #[tokio::test]
+    #[serial]
+    async fn cdc_basic_test() {
+        TestBase::run_cdc_test("pg_to_mysql/cdc/basic_test", 3000, 5000).await;
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn cdc_ddl_test() {
+        TestBase::run_ddl_test("pg_to_mysql/cdc/ddl_test", 3000, 5000).await;
+    }
+}
@@ -0,0 +1,17 @@
+INSERT INTO test_db_1.numeric_table (si, i, bi, r, db, d, n, b, csh) VALUES (1, 123456, 1234567890123, 3.3, 4.44, 12345678.91, 22.2222, true, '$1234.11');
+INSERT INTO test_db_1.numeric_table (si, i, bi, r, db, d, n, b, csh) VALUES (-32768, -2147483648, -9223372036854775808, -3.3, -4.44, -12345678.91, -22.2222, false, '($1234.11)');
+INSERT INTO test_db_1.numeric_table (si, i, bi, r, db, d, n, b, csh) VALUES (NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL);
+
+INSERT INTO test_db_1.string_table VALUES (1, 'žš', 'abcd', 'some text', E'\\000\\001\\002'::bytea, B'10101010', B'101', 'a0eebc99-9c0b-4ef8-bb6d-6bb9bd380a11', '192.168.2.0/12', '192.168.100.128/25', '08:00:2b:01:02:03');
+INSERT INTO test_db_1.string_table VALUES (2, 'a''b"c\d', 'ab', '中文', '', B'00000000', B'', NULL, '::1', '::/0', NULL);
+INSERT INTO test_db_1.string_table VALUES (3, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL);
+
+INSERT INTO test_db_1.time_table VALUES (1, '2016-11-04 13:51:30.123456', '2016-11-04 13:51:30.123', '2016-11-04 13:51:30.123456+02:00', '2016-11-04', '13:51:30', '13:51:30.123', 'P1Y2M3DT4H5M6.78S', '13:51:30.123789+02:00');
+INSERT INTO test_db_1.time_table VALUES (2, '1970-01-01 00:00:00', '2038-01-19 03:14:07.999', '2016-11-04 23:51:30-08:00', '1000-01-01', '00:00:00', '23:59:59.999', '-1 day', '00:00:00+00');
+INSERT INTO test_db_1.time_table VALUES (3, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL);
+
+INSERT INTO test_db_1.json_table (j, jb, int_array, text_array, nested_array) VALUES ('{"bar": "baz", "num": 1.5}', '{"bar": "baz", "arr": [1, 2]}', '{1,2,3}', '{"one","two, three","NULL",NULL}', '{{1,2},{3,4}}');
+INSERT INTO test_db_1.json_table (j, jb, int_array, text_array, nested_array) VALUES ('[]', 'null', '{}', '{"a\"b","c\\d"}', NULL);
+INSERT INTO test_db_1.json_table (j, jb, int_array, text_array, nested_array) VALUES (NULL, NULL, NULL, NULL, NULL);
+
+INSERT INTO test_db_2.router_test_1 (pk, col_1, col_2) VALUES (1, 1, 'a'), (2, NULL, NULL);
@@ -0,0 +1,54 @@
+INSERT INTO test_db_1.geometric_table VALUES (1, '(1.5,2.5)', '[(0,0),(1,1)]', '((0,0),(1,1),(2,0))', '(1,1),(0,0)', '((0,0),(0,1),(1,1),(1,0))', '{1,-1,0}', '<(1,1),5>');
+INSERT INTO test_db_1.geometric_table VALUES (2, '(-1.25,1e-10)', '[(1,2),(3,4)]', '[(0,0),(1,1),(2,0)]', '(3,3),(1,1)', '((1,1),(2,3),(4,1))', '{2,3,4}', '<(0,0),0.5>');
+INSERT INTO test_db_1.geometric_table VALUES (3, NULL, NULL, NULL, NULL, NULL, NULL, NULL);
+
+INSERT INTO test_db_1.geometric_array_table VALUES (1, ARRAY['(1,2)'::point, '(3,4)'::point], ARRAY['[(0,0),(1,1)]'::lseg], ARRAY['((0,0),(1,1))'::path], ARRAY['(1,1),(0,0)'::box, '(2,2),(1,1)'::box], ARRAY['((0,0),(0,1),(1,1))'::polygon], ARRAY['{1,-1,0}'::line], ARRAY['<(1,1),5>'::circle]);
+INSERT INTO test_db_1.geometric_array_table VALUES (2, '{}', '{}', '{}', '{}', '{}', '{}', '{}');
+INSERT INTO test_db_1.geometric_array_table VALUES (3, NULL, NULL, NULL, NULL, NULL, NULL, NULL);
+
+INSERT INTO test_db_1.misc_table VALUES (1, '<doc><item id="1">abc &amp; def</item></doc>', '08:00:2b:01:02:03:04:05', '$1,234.56', B'10101', B'101', 'a fat cat sat on a mat', 'fat & rat', '$.a[*] ? (@ > 2)', 'some_name', 'x');
+INSERT INTO test_db_1.misc_table VALUES (2, 'plain text content', '08-00-2b-01-02-03-04-05', '-$0.01', B'00000', B'', '', 'a | b', '$', '', ' ');
+INSERT INTO test_db_1.misc_table VALUES (3, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL);
+
+INSERT INTO test_db_1.misc_array_table VALUES (1, ARRAY['<a>1</a>'::xml, '<b/>'::xml], ARRAY['{"a": [1, 2]}'::json, 'null'::json], ARRAY['{"b": {"c": "d,e"}}'::jsonb], ARRAY['192.168.1.1/24'::inet, '::1'::inet], ARRAY['10.0.0.0/8'::cidr], ARRAY['08:00:2b:01:02:03'::macaddr], ARRAY['08:00:2b:01:02:03:04:05'::macaddr8], ARRAY['a0eebc99-9c0b-4ef8-bb6d-6bb9bd380a11'::uuid], ARRAY[1.5, -0.000001, 12345678901234567890.123]::numeric[], ARRAY['$1.00'::money], ARRAY['\x0001ff'::bytea, '\x'::bytea], ARRAY[B'101', B'010']::bit(3)[], ARRAY[B'1', B'101010']::varbit[], ARRAY['10:20:30.123456'::time], ARRAY['10:20:30+08'::timetz], ARRAY['1 year 2 mons 3 days 04:05:06'::interval]);
+INSERT INTO test_db_1.misc_array_table VALUES (2, '{}', ARRAY[NULL]::json[], '{}', '{}', '{}', '{}', '{}', '{}', '{}', '{}', ARRAY[NULL]::bytea[], '{}', '{}', '{}', '{}', '{}');
+INSERT INTO test_db_1.misc_array_table VALUES (3, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL);
+
+INSERT INTO test_db_1.range_table VALUES (1, '[1,10)', '[-9223372036854775808,0]', '(1.5,2.5]', '[2020-01-01 00:00:00,2020-12-31 23:59:59.999999)', '[2020-01-01 00:00:00+08,infinity)', '[2020-01-01,2021-01-01)', ARRAY['[1,2)'::int4range, 'empty'::int4range], ARRAY['[2020-01-01,)'::daterange]);
+INSERT INTO test_db_1.range_table VALUES (2, 'empty', '(,)', '[0,0]', '(,)', 'empty', '(,2020-01-01]', '{}', '{}');
+INSERT INTO test_db_1.range_table VALUES (3, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL);
+
+INSERT INTO test_db_1.multirange_table VALUES (1, '{[1,3), [5,7)}', '{[1,2]}', '{(1.1,2.2], [3,4)}', '{[2020-01-01 00:00:00,2020-02-01 00:00:00)}', '{[2020-01-01 00:00:00+00,2020-02-01 00:00:00+00)}', '{[2020-01-01,2020-02-01), [2020-03-01,)}');
+INSERT INTO test_db_1.multirange_table VALUES (2, '{}', '{}', '{}', '{}', '{}', '{}');
+INSERT INTO test_db_1.multirange_table VALUES (3, NULL, NULL, NULL, NULL, NULL, NULL);
+
+INSERT INTO test_db_1.extension_table VALUES (1, 'SRID=4326;POINT(-71.064544 42.28787)', 'SRID=4326;LINESTRING(-72.1260 42.45, -72.123 42.1546)', '"a"=>"1", "b"=>NULL, "c,d"=>"e=>f"', ARRAY['POINT(1 2)'::geometry, 'POLYGON((0 0,1 0,1 1,0 1,0 0))'::geometry], ARRAY['"k"=>"v"'::hstore]);
+INSERT INTO test_db_1.extension_table VALUES (2, 'POINT EMPTY', 'POINT(0 0)', '', '{}', '{}');
+INSERT INTO test_db_1.extension_table VALUES (3, NULL, NULL, NULL, NULL, NULL);
+
+INSERT INTO test_db_1.domain_enum_table VALUES (1, 'abc', 'happy', ARRAY['sad', 'ok']::test_db_1."Mood"[]);
+INSERT INTO test_db_1.domain_enum_table VALUES (2, '', 'sad', '{}');
+INSERT INTO test_db_1.domain_enum_table VALUES (3, NULL, NULL, NULL);
+
+INSERT INTO test_db_1.no_pk_table VALUES (1, '{"a": 1}', '<a/>', '(1,1),(0,0)', '<(0,0),1>', '((0,0),(0,1),(1,1))');
+INSERT INTO test_db_1.no_pk_table VALUES (2, '{"a": 1}', '<a/>', '(2,2),(0,0)', '<(0,0),2>', '((0,0),(0,2),(2,2))');
+
+UPDATE test_db_1.geometric_table SET c_point = '(3,4)', c_box = '(2,2),(1,1)', c_circle = '<(2,2),1>' WHERE pk = 1;
+UPDATE test_db_1.geometric_array_table SET c_polygon = ARRAY['((1,1),(1,2),(2,2))'::polygon] WHERE pk = 2;
+UPDATE test_db_1.misc_table SET c_xml = '<doc/>', c_bit = B'11111', c_money = '$0.00' WHERE pk = 1;
+UPDATE test_db_1.misc_array_table SET c_json = ARRAY['{"updated": true}'::json] WHERE pk = 1;
+UPDATE test_db_1.range_table SET c_int4range = '[2,3)', c_daterange = 'empty' WHERE pk = 1;
+UPDATE test_db_1.multirange_table SET c_int4multirange = '{[10,20)}' WHERE pk = 2;
+UPDATE test_db_1.extension_table SET c_geometry = 'POINT(5 5)', c_hstore = '"x"=>"y"' WHERE pk = 1;
+UPDATE test_db_1.domain_enum_table SET c_mood = 'ok', c_short_text = 'updated' WHERE pk = 2;
+UPDATE test_db_1.no_pk_table SET c_int = 10 WHERE c_int = 1;
+
+DELETE FROM test_db_1.geometric_table WHERE pk = 2;
+DELETE FROM test_db_1.geometric_array_table WHERE pk = 3;
+DELETE FROM test_db_1.misc_table WHERE pk = 2;
+DELETE FROM test_db_1.misc_array_table WHERE pk = 2;
+DELETE FROM test_db_1.range_table WHERE pk = 2;
+DELETE FROM test_db_1.multirange_table WHERE pk = 3;
+DELETE FROM test_db_1.extension_table WHERE pk = 2;
+DELETE FROM test_db_1.domain_enum_table WHERE pk = 3;
+DELETE FROM test_db_1.no_pk_table WHERE c_int = 2;
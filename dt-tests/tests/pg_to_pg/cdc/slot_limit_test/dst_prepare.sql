@@ -0,0 +1,5 @@
+DROP SCHEMA IF EXISTS test_db_1 CASCADE;
+
+CREATE SCHEMA test_db_1;
+
+CREATE TABLE test_db_1.tb_1(id int PRIMARY KEY, value text);
@@ -0,0 +1,10 @@
+DROP SCHEMA IF EXISTS test_db_1 CASCADE;
+
+CREATE SCHEMA test_db_1;
+
+CREATE TABLE test_db_1.tb_1(id int PRIMARY KEY, value text);
+
+SELECT pg_drop_replication_slot(slot_name) FROM pg_catalog.pg_replication_slots WHERE slot_name = 'ape_test_slot_limit';
+
+-- created before the test data, so the wal of them is retained by the slot
+SELECT pg_create_logical_replication_slot('ape_test_slot_limit', 'pgoutput');
@@ -0,0 +1,2 @@
+-- about 5 mb wal, more than slot_lag_limit_mb
+INSERT INTO test_db_1.tb_1 SELECT i, repeat('a', 1000) FROM generate_series(1, 5000) AS i;
@@ -0,0 +1,5 @@
+DROP SCHEMA IF EXISTS streaming_test_db CASCADE;
+
+CREATE SCHEMA streaming_test_db;
+
+CREATE TABLE streaming_test_db.tb_1(id int PRIMARY KEY, value varchar(255));
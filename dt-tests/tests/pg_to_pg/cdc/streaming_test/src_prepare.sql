@@ -0,0 +1,10 @@
+DROP SCHEMA IF EXISTS streaming_test_db CASCADE;
+
+CREATE SCHEMA streaming_test_db;
+
+CREATE TABLE streaming_test_db.tb_1(id int PRIMARY KEY, value varchar(255));
+
+-- the minimum value, so large transactions are streamed before commit
+ALTER SYSTEM SET logical_decoding_work_mem = '64kB';
+
+SELECT pg_reload_conf();
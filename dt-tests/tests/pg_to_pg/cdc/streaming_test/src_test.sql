@@ -0,0 +1,30 @@
+-- large transaction
+INSERT INTO streaming_test_db.tb_1 SELECT i, repeat('a', 100) FROM generate_series(1, 10000) AS i;
+
+-- large transaction with an aborted subtransaction
+```
+DO $$
+BEGIN
+    INSERT INTO streaming_test_db.tb_1 SELECT i, repeat('b', 100) FROM generate_series(10001, 20000) AS i;
+    BEGIN
+        INSERT INTO streaming_test_db.tb_1 SELECT i, repeat('c', 100) FROM generate_series(20001, 30000) AS i;
+        RAISE EXCEPTION 'rollback subtransaction';
+    EXCEPTION WHEN OTHERS THEN
+        NULL;
+    END;
+    UPDATE streaming_test_db.tb_1 SET value = repeat('d', 100) WHERE id <= 5000;
+END $$;
+```
+
+-- aborted large transaction
+```
+DO $$
+BEGIN
+    INSERT INTO streaming_test_db.tb_1 SELECT i, repeat('e', 100) FROM generate_series(30001, 40000) AS i;
+    RAISE EXCEPTION 'rollback transaction';
+EXCEPTION WHEN OTHERS THEN
+    NULL;
+END $$;
+```
+
+DELETE FROM streaming_test_db.tb_1 WHERE id > 15000;
@@ -11,6 +11,18 @@ mod test {
         TestBase::run_cdc_test("pg_to_pg/cdc/basic_test", 3000, 9000).await;
     }
 
+    #[tokio::test]
+    #[serial]
+    async fn cdc_all_types_test() {
+        TestBase::run_cdc_test("pg_to_pg/cdc/all_types_test", 3000, 9000).await;
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn cdc_streaming_test() {
+        TestBase::run_cdc_test("pg_to_pg/cdc/streaming_test", 3000, 10000).await;
+    }
+
     #[tokio::test]
     #[serial]
     async fn cdc_postgis_test() {
@@ -88,6 +100,18 @@ mod test {
         .await;
     }
 
+    #[tokio::test]
+    #[serial]
+    async fn cycle_replication_origin_test() {
+        RdbCycleTestRunner::run_cycle_cdc_test(
+            "pg_to_pg/cdc/cycle_replication_origin_test",
+            2000,
+            4000,
+            &vec![],
+        )
+        .await;
+    }
+
     #[tokio::test]
     #[serial]
     async fn cycle_star_test() {
@@ -130,6 +154,12 @@ mod test {
         .await;
     }
 
+    #[tokio::test]
+    #[serial]
+    async fn cdc_slot_limit_test() {
+        TestBase::run_slot_limit_test("pg_to_pg/cdc/slot_limit_test", 10000).await;
+    }
+
     #[tokio::test]
     #[serial]
     async fn cdc_heartbeat_test() {
@@ -0,0 +1,28 @@
+CREATE EXTENSION IF NOT EXISTS hstore;
+CREATE EXTENSION IF NOT EXISTS postgis;
+
+DROP SCHEMA IF EXISTS test_db_1 CASCADE;
+CREATE SCHEMA test_db_1;
+
+CREATE DOMAIN test_db_1.positive_int AS integer CHECK (VALUE > 0);
+CREATE DOMAIN test_db_1.short_text AS varchar(16);
+CREATE TYPE test_db_1."Mood" AS ENUM ('sad', 'ok', 'happy');
+
+CREATE TABLE test_db_1.geometric_table(pk serial, c_point point, c_lseg lseg, c_path path, c_box box, c_polygon polygon, c_line line, c_circle circle, PRIMARY KEY(pk));
+
+CREATE TABLE test_db_1.geometric_array_table(pk serial, c_point point[], c_lseg lseg[], c_path path[], c_box box[], c_polygon polygon[], c_line line[], c_circle circle[], PRIMARY KEY(pk));
+
+CREATE TABLE test_db_1.misc_table(pk serial, c_xml xml, c_macaddr8 macaddr8, c_money money, c_bit bit(5), c_varbit varbit(8), c_tsvector tsvector, c_tsquery tsquery, c_jsonpath jsonpath, c_name name, c_char "char", PRIMARY KEY(pk));
+
+CREATE TABLE test_db_1.misc_array_table(pk serial, c_xml xml[], c_json json[], c_jsonb jsonb[], c_inet inet[], c_cidr cidr[], c_macaddr macaddr[], c_macaddr8 macaddr8[], c_uuid uuid[], c_numeric numeric[], c_money money[], c_bytea bytea[], c_bit bit(3)[], c_varbit varbit[], c_time time[], c_timetz timetz[], c_interval interval[], PRIMARY KEY(pk));
+
+CREATE TABLE test_db_1.range_table(pk serial, c_int4range int4range, c_int8range int8range, c_numrange numrange, c_tsrange tsrange, c_tstzrange tstzrange, c_daterange daterange, c_int4range_array int4range[], c_daterange_array daterange[], PRIMARY KEY(pk));
+
+CREATE TABLE test_db_1.multirange_table(pk serial, c_int4multirange int4multirange, c_int8multirange int8multirange, c_nummultirange nummultirange, c_tsmultirange tsmultirange, c_tstzmultirange tstzmultirange, c_datemultirange datemultirange, PRIMARY KEY(pk));
+
+CREATE TABLE test_db_1.extension_table(pk serial, c_geometry geometry, c_geography geography, c_hstore hstore, c_geometry_array geometry[], c_hstore_array hstore[], PRIMARY KEY(pk));
+
+CREATE TABLE test_db_1.domain_enum_table(pk test_db_1.positive_int, c_short_text test_db_1.short_text, c_mood test_db_1."Mood", c_mood_array test_db_1."Mood"[], PRIMARY KEY(pk));
+
+-- no primary key, rows are identified by all columns
+CREATE TABLE test_db_1.no_pk_table(c_int int, c_json json, c_xml xml, c_box box, c_circle circle, c_polygon polygon);
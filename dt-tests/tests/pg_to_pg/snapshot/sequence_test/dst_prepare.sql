@@ -0,0 +1,11 @@
+DROP SCHEMA IF EXISTS test_db_1 CASCADE;
+
+CREATE SCHEMA test_db_1;
+
+CREATE TABLE test_db_1.serial_table(id serial PRIMARY KEY, value int);
+
+CREATE TABLE test_db_1.identity_table(id int GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY, value int);
+
+CREATE SEQUENCE test_db_1.standalone_seq;
+
+CREATE SEQUENCE test_db_1."quote""seq";
@@ -0,0 +1,10 @@
+INSERT INTO test_db_1.serial_table(value) SELECT generate_series(1, 5);
+
+-- the sequence is ahead of max(id)
+DELETE FROM test_db_1.serial_table WHERE id > 3;
+
+INSERT INTO test_db_1.identity_table(value) VALUES (1), (2);
+
+SELECT setval('test_db_1.standalone_seq', 100);
+
+SELECT setval('test_db_1."quote""seq"', 200);
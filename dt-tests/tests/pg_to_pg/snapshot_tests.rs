@@ -40,6 +40,24 @@ mod test {
         TestBase::run_snapshot_test("pg_to_pg/snapshot/postgis_array_test").await;
     }
 
+    #[tokio::test]
+    #[serial]
+    async fn snapshot_all_types_test() {
+        TestBase::run_snapshot_test("pg_to_pg/snapshot/all_types_test").await;
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn snapshot_copy_text_test() {
+        TestBase::run_snapshot_test("pg_to_pg/snapshot/copy_text_test").await;
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn snapshot_copy_binary_test() {
+        TestBase::run_snapshot_test("pg_to_pg/snapshot/copy_binary_test").await;
+    }
+
     #[tokio::test]
     #[serial]
     async fn snapshot_charset_test() {
@@ -92,4 +110,10 @@ mod test {
     async fn snapshot_route_test() {
         TestBase::run_snapshot_test("pg_to_pg/snapshot/route_test").await;
     }
+
+    #[tokio::test]
+    #[serial]
+    async fn snapshot_sequence_test() {
+        TestBase::run_sequence_test("pg_to_pg/snapshot/sequence_test", "test_db_1").await;
+    }
 }
@@ -137,6 +137,12 @@ impl TestConfigUtil {
                 update_configs.push((EXTRACTOR.to_string(), "file_path".to_string(), file_path));
             }
 
+            // extractor/url, the local root directory of files
+            ExtractorConfig::FileSnapshot { url, .. } => {
+                let file_dir = format!("{}/{}", project_root, url);
+                update_configs.push((EXTRACTOR.to_string(), "url".to_string(), file_dir));
+            }
+
             _ => {}
         }
 
@@ -157,6 +163,12 @@ impl TestConfigUtil {
                 ));
             }
 
+            // sinker/url, the local root directory of files
+            SinkerConfig::File { url, .. } => {
+                let file_dir = format!("{}/{}", project_root, url);
+                update_configs.push((SINKER.to_string(), "url".to_string(), file_dir));
+            }
+
             // sinker/statistic_log_dir
             SinkerConfig::RedisStatistic {
                 statistic_log_dir, ..
@@ -0,0 +1,103 @@
+use std::{
+    collections::HashMap,
+    sync::{Mutex, Once},
+};
+
+use actix_web::{web, App, HttpResponse, HttpServer};
+use serde_json::{json, Value};
+
+static START: Once = Once::new();
+
+/// In-memory stand-in of Confluent Schema Registry, only the apis used by ape-dts are served:
+///     POST /subjects/{subject}/versions
+///     GET /schemas/ids/{id}
+///     PUT /config/{subject}
+/// Compatibility levels are recorded but not checked.
+#[derive(Default)]
+struct Registry {
+    // schema -> id, ids of identical schemas are shared by subjects
+    ids: HashMap<String, u32>,
+    schemas: HashMap<u32, String>,
+    subjects: HashMap<String, Vec<u32>>,
+    compatibilities: HashMap<String, String>,
+}
+
+pub struct MockSchemaRegistry {}
+
+impl MockSchemaRegistry {
+    /// Starts the registry in a background thread, only once for all tests
+    pub fn start(url: &str) {
+        let addr = url
+            .trim_start_matches("http://")
+            .trim_end_matches('/')
+            .to_string();
+        START.call_once(move || {
+            std::thread::spawn(move || {
+                let registry = web::Data::new(Mutex::new(Registry::default()));
+                actix_web::rt::System::new().block_on(async move {
+                    HttpServer::new(move || {
+                        App::new()
+                            .app_data(registry.clone())
+                            .route("/subjects/{subject}/versions", web::post().to(register))
+                            .route("/schemas/ids/{id}", web::get().to(get_schema))
+                            .route("/config/{subject}", web::put().to(set_config))
+                    })
+                    .bind(addr)
+                    .unwrap()
+                    .run()
+                    .await
+                    .unwrap()
+                });
+            });
+        });
+    }
+}
+
+async fn register(
+    registry: web::Data<Mutex<Registry>>,
+    subject: web::Path<String>,
+    body: web::Bytes,
+) -> HttpResponse {
+    let Some(schema) = parse_body(&body, "schema") else {
+        return HttpResponse::UnprocessableEntity().finish();
+    };
+
+    let mut registry = registry.lock().unwrap();
+    let next_id = registry.ids.len() as u32 + 1;
+    let id = *registry.ids.entry(schema.clone()).or_insert(next_id);
+    registry.schemas.insert(id, schema);
+    let versions = registry.subjects.entry(subject.into_inner()).or_default();
+    if !versions.contains(&id) {
+        versions.push(id);
+    }
+    HttpResponse::Ok().json(json!({ "id": id }))
+}
+
+async fn get_schema(registry: web::Data<Mutex<Registry>>, id: web::Path<u32>) -> HttpResponse {
+    match registry.lock().unwrap().schemas.get(&id) {
+        Some(schema) => HttpResponse::Ok().json(json!({ "schema": schema })),
+        None => HttpResponse::NotFound().finish(),
+    }
+}
+
+async fn set_config(
+    registry: web::Data<Mutex<Registry>>,
+    subject: web::Path<String>,
+    body: web::Bytes,
+) -> HttpResponse {
+    let Some(compatibility) = parse_body(&body, "compatibility") else {
+        return HttpResponse::UnprocessableEntity().finish();
+    };
+
+    registry
+        .lock()
+        .unwrap()
+        .compatibilities
+        .insert(subject.into_inner(), compatibility.clone());
+    HttpResponse::Ok().json(json!({ "compatibility": compatibility }))
+}
+
+fn parse_body(body: &[u8], key: &str) -> Option<String> {
+    let json: Value = serde_json::from_slice(body).ok()?;
+    json[key].as_str().map(|v| v.to_string())
+}
@@ -1,13 +1,19 @@
 pub mod base_test_runner;
 pub mod check_util;
+pub mod mock_schema_registry;
 pub mod mongo_check_test_runner;
+pub mod mongo_rdb_test_runner;
 pub mod mongo_test_runner;
 pub mod precheck_test_runner;
 pub mod rdb_check_test_runner;
 pub mod rdb_clickhouse_test_runner;
 pub mod rdb_cycle_test_runner;
+pub mod rdb_elasticsearch_test_runner;
+pub mod rdb_file_rdb_test_runner;
+pub mod rdb_file_test_runner;
 pub mod rdb_kafka_rdb_test_runner;
 pub mod rdb_lua_test_runner;
+pub mod rdb_mongo_test_runner;
 pub mod rdb_redis_test_runner;
 pub mod rdb_sql_test_runner;
 pub mod rdb_starrocks_test_runner;
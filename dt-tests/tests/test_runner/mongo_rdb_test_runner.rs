@@ -0,0 +1,196 @@
+use std::collections::HashMap;
+
+use anyhow::bail;
+use dt_common::{
+    config::{config_enums::DbType, sinker_config::SinkerConfig, task_config::TaskConfig},
+    error::Error,
+    meta::{adaptor::mongo_doc_flattener::MongoDocFlattener, col_value::ColValue},
+    utils::time_util::TimeUtil,
+};
+use dt_connector::rdb_router::RdbRouter;
+use dt_task::task_util::TaskUtil;
+use sqlx::{MySql, Pool};
+
+use crate::test_runner::rdb_util::RdbUtil;
+
+use super::{
+    base_test_runner::BaseTestRunner,
+    mongo_test_runner::{MongoTestRunner, SRC},
+};
+
+pub struct MongoRdbTestRunner {
+    mongo_runner: MongoTestRunner,
+    mysql_conn_pool: Pool<MySql>,
+    flattener: MongoDocFlattener,
+    router: RdbRouter,
+}
+
+impl MongoRdbTestRunner {
+    pub async fn new(relative_test_dir: &str) -> anyhow::Result<Self> {
+        let mongo_runner = MongoTestRunner::new(relative_test_dir).await?;
+        let config = TaskConfig::new(&mongo_runner.base.task_config_file).unwrap();
+
+        let (mysql_conn_pool, doc_col) = match config.sinker {
+            SinkerConfig::Mysql {
+                url, mongo_doc_col, ..
+            } => (
+                TaskUtil::create_mysql_conn_pool(&url, 1, false).await?,
+                mongo_doc_col,
+            ),
+            _ => {
+                bail! {Error::ConfigError("unsupported sinker config".into())};
+            }
+        };
+        let flattener = MongoDocFlattener {
+            db_type: DbType::Mysql,
+            doc_col,
+        };
+        let router = RdbRouter::from_config(&config.router, &DbType::Mongo)?;
+
+        Ok(Self {
+            mongo_runner,
+            mysql_conn_pool,
+            flattener,
+            router,
+        })
+    }
+
+    pub async fn close(&self) -> anyhow::Result<()> {
+        self.mysql_conn_pool.close().await;
+        Ok(())
+    }
+
+    pub async fn run_snapshot_test(&self) -> anyhow::Result<()> {
+        self.prepare().await?;
+        self.mongo_runner.execute_test_sqls().await?;
+        self.base().start_task().await?;
+
+        for db in self.get_src_dbs() {
+            self.compare_db_data(&db).await?;
+        }
+        Ok(())
+    }
+
+    pub async fn run_cdc_test(&self, start_millis: u64, parse_millis: u64) -> anyhow::Result<()> {
+        self.prepare().await?;
+
+        let task = self.base().spawn_task().await?;
+        TimeUtil::sleep_millis(start_millis).await;
+
+        let src_sqls = MongoTestRunner::slice_sqls_by_db(&self.base().src_test_sqls);
+        for (db, sqls) in src_sqls.iter() {
+            let (src_insert_sqls, src_update_sqls, src_delete_sqls) =
+                MongoTestRunner::slice_sqls_by_type(sqls);
+            for sqls in [src_insert_sqls, src_update_sqls, src_delete_sqls] {
+                self.mongo_runner.execute_src_dmls(db, &sqls).await?;
+                TimeUtil::sleep_millis(parse_millis).await;
+                self.compare_db_data(db).await?;
+            }
+        }
+
+        self.base().abort_task(&task).await
+    }
+
+    fn base(&self) -> &BaseTestRunner {
+        &self.mongo_runner.base
+    }
+
+    fn get_src_dbs(&self) -> Vec<String> {
+        MongoTestRunner::slice_sqls_by_db(&self.base().src_test_sqls)
+            .into_keys()
+            .collect()
+    }
+
+    async fn prepare(&self) -> anyhow::Result<()> {
+        // src_prepare.sql: mongo commands, dst_prepare.sql: mysql sqls
+        self.mongo_runner.execute_prepare_sqls().await?;
+        RdbUtil::execute_sqls_mysql(&self.mysql_conn_pool, &self.base().dst_prepare_sqls).await
+    }
+
+    async fn compare_db_data(&self, db: &str) -> anyhow::Result<()> {
+        for tb in self.mongo_runner.list_tb(db, SRC).await {
+            self.compare_tb_data(db, &tb).await?;
+        }
+        Ok(())
+    }
+
+    async fn compare_tb_data(&self, db: &str, tb: &str) -> anyhow::Result<()> {
+        let src_data = self.mongo_runner.fetch_data(db, tb, SRC).await;
+        let (dst_db, dst_tb) = self.router.get_tb_map(db, tb);
+        let col_map = self.router.get_col_map(db, tb);
+        let dst_db_tb = (dst_db.to_string(), dst_tb.to_string());
+
+        // the target table is created by the first document
+        if src_data.is_empty()
+            && RdbUtil::get_tb_meta_mysql(&self.mysql_conn_pool, &dst_db_tb)
+                .await
+                .is_err()
+        {
+            return Ok(());
+        }
+
+        let dst_data =
+            RdbUtil::fetch_data_mysql(&self.mysql_conn_pool, None, &dst_db_tb, "").await?;
+        println!(
+            "compare tb data, db: {}, tb: {}, src count: {}, dst count: {}",
+            db,
+            tb,
+            src_data.len(),
+            dst_data.len()
+        );
+        assert_eq!(src_data.len(), dst_data.len());
+
+        let id_col = col_map
+            .and_then(|v| v.get("_id"))
+            .map_or("_id".to_string(), |v| v.to_lowercase());
+        let mut dst_rows = HashMap::new();
+        for row_data in dst_data {
+            let after = row_data.after.unwrap();
+            let id = after.get(&id_col).unwrap().to_option_string().unwrap();
+            dst_rows.insert(id, after);
+        }
+
+        for src_doc in src_data.values() {
+            let src_row: HashMap<String, ColValue> = self
+                .flattener
+                .flatten_doc(src_doc, col_map)
+                .into_iter()
+                .map(|(col, col_value)| (col.to_lowercase(), col_value))
+                .collect();
+            let id = src_row.get(&id_col).unwrap().to_option_string().unwrap();
+            let dst_row = dst_rows.get(&id).unwrap();
+            for (col, src_col_value) in src_row.iter() {
+                let dst_col_value = dst_row.get(col).unwrap_or(&ColValue::None);
+                assert!(
+                    Self::col_value_eq(src_col_value, dst_col_value),
+                    "col: {}, src_value: {:?}, dst_value: {:?}",
+                    col,
+                    src_col_value,
+                    dst_col_value
+                );
+            }
+        }
+        Ok(())
+    }
+
+    fn col_value_eq(src: &ColValue, dst: &ColValue) -> bool {
+        // bool is stored as TINYINT(1) in MySQL
+        let to_option_string = |v: &ColValue| match v {
+            ColValue::Bool(v) => Some((*v as u8).to_string()),
+            _ => v.to_option_string(),
+        };
+        let (src, dst) = (to_option_string(src), to_option_string(dst));
+        if src == dst {
+            return true;
+        }
+        // json is reformatted by target
+        match (src, dst) {
+            (Some(src), Some(dst)) => {
+                let src: Option<serde_json::Value> = serde_json::from_str(&src).ok();
+                let dst: Option<serde_json::Value> = serde_json::from_str(&dst).ok();
+                src.is_some() && src == dst
+            }
+            _ => false,
+        }
+    }
+}
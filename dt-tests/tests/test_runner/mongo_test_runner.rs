@@ -218,18 +218,19 @@ impl MongoTestRunner {
 
     pub async fn execute_prepare_sqls(&self) -> anyhow::Result<()> {
         let src_mongo_client = self.src_mongo_client.as_ref().unwrap();
-        let dst_mongo_client = self.dst_mongo_client.as_ref().unwrap();
-
         let src_sqls = Self::slice_sqls_by_db(&self.base.src_prepare_sqls);
-        let dst_sqls = Self::slice_sqls_by_db(&self.base.dst_prepare_sqls);
-
         for (db, sqls) in src_sqls.iter() {
             self.execute_ddls(src_mongo_client, db, sqls).await?;
             self.execute_dmls(src_mongo_client, db, sqls).await?;
         }
-        for (db, sqls) in dst_sqls.iter() {
-            self.execute_ddls(dst_mongo_client, db, sqls).await?;
-            self.execute_dmls(dst_mongo_client, db, sqls).await?;
+
+        // target may not be mongo
+        if let Some(dst_mongo_client) = self.dst_mongo_client.as_ref() {
+            let dst_sqls = Self::slice_sqls_by_db(&self.base.dst_prepare_sqls);
+            for (db, sqls) in dst_sqls.iter() {
+                self.execute_ddls(dst_mongo_client, db, sqls).await?;
+                self.execute_dmls(dst_mongo_client, db, sqls).await?;
+            }
         }
         Ok(())
     }
@@ -251,6 +252,11 @@ impl MongoTestRunner {
         Ok(())
     }
 
+    pub async fn execute_src_dmls(&self, db: &str, sqls: &[String]) -> anyhow::Result<()> {
+        self.execute_dmls(self.src_mongo_client.as_ref().unwrap(), db, sqls)
+            .await
+    }
+
     async fn execute_ddls(&self, client: &Client, db: &str, sqls: &[String]) -> anyhow::Result<()> {
         for sql in sqls.iter() {
             if sql.contains("dropDatabase") {
@@ -430,7 +436,7 @@ impl MongoTestRunner {
         results
     }
 
-    fn slice_sqls_by_db(sqls: &[String]) -> HashMap<String, Vec<String>> {
+    pub fn slice_sqls_by_db(sqls: &[String]) -> HashMap<String, Vec<String>> {
         let mut db = String::new();
         let mut sliced_sqls: HashMap<String, Vec<String>> = HashMap::new();
         for sql in sqls.iter() {
@@ -448,7 +454,7 @@ impl MongoTestRunner {
         sliced_sqls
     }
 
-    fn slice_sqls_by_type(sqls: &[String]) -> (Vec<String>, Vec<String>, Vec<String>) {
+    pub fn slice_sqls_by_type(sqls: &[String]) -> (Vec<String>, Vec<String>, Vec<String>) {
         let mut insert_sqls = Vec::new();
         let mut update_sqls = Vec::new();
         let mut delete_sqls = Vec::new();
@@ -134,6 +134,10 @@ impl RdbCycleTestRunner {
 
         assert!(self.base.compare_data_for_tbs(&db_tbs, &db_tbs).await?);
 
+        // no marker table if sinker sessions are tagged with replication origins
+        if data_marker.is_replication_origin() {
+            return Ok(());
+        }
         self.check_data_marker_data(expect_tx_count_map).await
     }
 
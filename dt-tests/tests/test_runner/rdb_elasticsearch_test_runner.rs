@@ -0,0 +1,208 @@
+use std::collections::HashMap;
+
+use dt_common::utils::time_util::TimeUtil;
+use dt_connector::sinker::elasticsearch::{
+    elasticsearch_sinker::ElasticsearchSinker,
+    elasticsearch_struct_sinker::ElasticsearchStructSinker,
+};
+use dt_task::task_runner::TaskRunner;
+use reqwest::{Client, Method, StatusCode};
+use serde_json::Value;
+use url::Url;
+
+use super::{
+    base_test_runner::BaseTestRunner,
+    rdb_test_runner::{RdbTestRunner, SRC},
+    rdb_util::RdbUtil,
+};
+
+pub struct RdbElasticsearchTestRunner {
+    rdb_test_runner: RdbTestRunner,
+    client: Client,
+    // http://host:port
+    url: String,
+    username: String,
+    password: String,
+}
+
+impl RdbElasticsearchTestRunner {
+    pub async fn new(relative_test_dir: &str) -> anyhow::Result<Self> {
+        let rdb_test_runner = RdbTestRunner::new(relative_test_dir).await?;
+
+        let url_info = Url::parse(&rdb_test_runner.base.get_config().sinker_basic.url)?;
+        let url = format!(
+            "{}://{}:{}",
+            url_info.scheme(),
+            url_info.host_str().unwrap(),
+            url_info.port_or_known_default().unwrap()
+        );
+        Ok(Self {
+            rdb_test_runner,
+            client: Client::new(),
+            url,
+            username: url_info.username().to_string(),
+            password: url_info.password().unwrap_or("").to_string(),
+        })
+    }
+
+    pub async fn close(&self) -> anyhow::Result<()> {
+        self.rdb_test_runner.close().await
+    }
+
+    pub async fn run_snapshot_test(&self) -> anyhow::Result<()> {
+        self.prepare_task().await?;
+        self.rdb_test_runner.execute_test_sqls().await?;
+        self.rdb_test_runner.base.start_task().await?;
+        self.compare_data_for_tbs().await
+    }
+
+    pub async fn run_cdc_test(&self, start_millis: u64, parse_millis: u64) -> anyhow::Result<()> {
+        let runner = &self.rdb_test_runner;
+        self.prepare_task().await?;
+
+        let task = runner.spawn_cdc_task(start_millis, parse_millis).await?;
+
+        let (src_insert_sqls, src_update_sqls, src_delete_sqls) =
+            RdbTestRunner::split_dml_sqls(&runner.base.src_test_sqls);
+        for sqls in [src_insert_sqls, src_update_sqls, src_delete_sqls] {
+            if sqls.is_empty() {
+                continue;
+            }
+            runner.execute_src_sqls(&sqls).await?;
+            TimeUtil::sleep_millis(parse_millis).await;
+            self.compare_data_for_tbs().await?;
+        }
+
+        runner.base.wait_task_finish(&task).await
+    }
+
+    async fn prepare_task(&self) -> anyhow::Result<()> {
+        let base = &self.rdb_test_runner.base;
+        self.rdb_test_runner
+            .execute_src_sqls(&base.src_prepare_sqls)
+            .await?;
+
+        // dst_prepare.sql: DELETE /test_db_1.tb_1
+        for sql in base.dst_prepare_sqls.iter() {
+            let (method, path) = sql.split_once(' ').unwrap();
+            let method = Method::from_bytes(method.as_bytes())?;
+            self.request(method, path.trim()).await?;
+        }
+
+        // create indexes with mappings if needed
+        if !base.struct_task_config_file.is_empty() {
+            TaskRunner::new(&base.struct_task_config_file)?
+                .start_task(BaseTestRunner::get_enable_log4rs())
+                .await?;
+            self.compare_mappings_for_tbs().await?;
+        }
+        Ok(())
+    }
+
+    async fn compare_mappings_for_tbs(&self) -> anyhow::Result<()> {
+        let (src_db_tbs, dst_db_tbs) = self.rdb_test_runner.get_compare_db_tbs()?;
+        for (src_db_tb, dst_db_tb) in src_db_tbs.iter().zip(dst_db_tbs.iter()) {
+            let src_tb_meta = RdbUtil::get_tb_meta_mysql(
+                self.rdb_test_runner.src_conn_pool_mysql.as_ref().unwrap(),
+                src_db_tb,
+            )
+            .await?;
+            let expect_mappings =
+                ElasticsearchStructSinker::get_mappings(Some(&src_tb_meta), None)?;
+
+            let index = ElasticsearchSinker::get_index(&dst_db_tb.0, &dst_db_tb.1);
+            let (_, response) = self
+                .request(Method::GET, &format!("/{}/_mapping", index))
+                .await?;
+            let dst_mappings = &response[&index]["mappings"];
+            println!(
+                "compare mappings, index: {}, expect: {}, dst: {}",
+                index, expect_mappings, dst_mappings
+            );
+            assert_eq!(&expect_mappings, dst_mappings);
+        }
+        Ok(())
+    }
+
+    async fn compare_data_for_tbs(&self) -> anyhow::Result<()> {
+        let (src_db_tbs, dst_db_tbs) = self.rdb_test_runner.get_compare_db_tbs()?;
+        for (src_db_tb, dst_db_tb) in src_db_tbs.iter().zip(dst_db_tbs.iter()) {
+            self.compare_tb_data(src_db_tb, dst_db_tb).await?;
+        }
+        Ok(())
+    }
+
+    async fn compare_tb_data(
+        &self,
+        src_db_tb: &(String, String),
+        dst_db_tb: &(String, String),
+    ) -> anyhow::Result<()> {
+        let src_data = self.rdb_test_runner.fetch_data(src_db_tb, SRC).await?;
+        let src_tb_meta = RdbUtil::get_tb_meta_mysql(
+            self.rdb_test_runner.src_conn_pool_mysql.as_ref().unwrap(),
+            src_db_tb,
+        )
+        .await?;
+
+        let index = ElasticsearchSinker::get_index(&dst_db_tb.0, &dst_db_tb.1);
+        let dst_docs = self.fetch_docs(&index).await?;
+        println!(
+            "compare tb data, src_tb: {:?}, index: {}, src count: {}, dst count: {}",
+            src_db_tb,
+            index,
+            src_data.len(),
+            dst_docs.len()
+        );
+        assert_eq!(src_data.len(), dst_docs.len());
+
+        // router: col_map
+        let router = &self.rdb_test_runner.router;
+        let id_cols = ElasticsearchSinker::route_id_cols(
+            &src_tb_meta.basic.id_cols,
+            router.get_col_map(&src_db_tb.0, &src_db_tb.1),
+        );
+        for row_data in src_data.into_iter() {
+            let row_data = router.route_row(row_data);
+            let after = row_data.after.as_ref().unwrap();
+            let id = ElasticsearchSinker::get_doc_id(after, &id_cols);
+            let src_doc = ElasticsearchSinker::to_doc(after);
+            let dst_doc = dst_docs.get(&id);
+            assert_eq!(Some(&src_doc), dst_doc, "doc id: {}", id);
+        }
+        Ok(())
+    }
+
+    async fn fetch_docs(&self, index: &str) -> anyhow::Result<HashMap<String, Value>> {
+        let mut docs = HashMap::new();
+        // make written documents visible for search
+        let (status_code, _) = self
+            .request(Method::POST, &format!("/{}/_refresh", index))
+            .await?;
+        if status_code == StatusCode::NOT_FOUND {
+            return Ok(docs);
+        }
+
+        let (_, response) = self
+            .request(Method::GET, &format!("/{}/_search?size=10000", index))
+            .await?;
+        for hit in response["hits"]["hits"].as_array().unwrap() {
+            let id = hit["_id"].as_str().unwrap().to_string();
+            docs.insert(id, hit["_source"].clone());
+        }
+        Ok(docs)
+    }
+
+    async fn request(&self, method: Method, path: &str) -> anyhow::Result<(StatusCode, Value)> {
+        let mut request = self.client.request(method, format!("{}{}", self.url, path));
+        if !self.username.is_empty() {
+            request = request.basic_auth(&self.username, Some(&self.password));
+        }
+        let response = request.send().await?;
+        let status_code = response.status();
+        let text = response.text().await?;
+        Ok((
+            status_code,
+            serde_json::from_str(&text).unwrap_or(Value::Null),
+        ))
+    }
+}
@@ -0,0 +1,68 @@
+use std::path::Path;
+
+use dt_common::config::{sinker_config::SinkerConfig, task_config::TaskConfig};
+
+use super::{base_test_runner::BaseTestRunner, rdb_test_runner::RdbTestRunner};
+
+/// This is used for test cases: rdb(src) -> files -> rdb(dst).
+/// There are 2 tasks running:
+///     rdb(src) -> files
+///     files -> rdb(dst)
+/// And we need another dummy task runner to compare rdb(src) and rdb(dst)
+///     rdb(src) -> rdb(dst)
+pub struct RdbFileRdbTestRunner {
+    src_to_dst_runner: RdbTestRunner,
+    src_to_file_runner: BaseTestRunner,
+    file_to_dst_runner: BaseTestRunner,
+}
+
+impl RdbFileRdbTestRunner {
+    pub async fn new(relative_test_dir: &str) -> anyhow::Result<Self> {
+        let src_to_dst_runner =
+            RdbTestRunner::new(&format!("{}/src_to_dst", relative_test_dir)).await?;
+        let src_to_file_runner =
+            BaseTestRunner::new(&format!("{}/src_to_file", relative_test_dir)).await?;
+        let file_to_dst_runner =
+            BaseTestRunner::new(&format!("{}/file_to_dst", relative_test_dir)).await?;
+        Ok(Self {
+            src_to_dst_runner,
+            src_to_file_runner,
+            file_to_dst_runner,
+        })
+    }
+
+    pub async fn close(&self) -> anyhow::Result<()> {
+        self.src_to_dst_runner.close().await
+    }
+
+    pub async fn run_snapshot_test(&self) -> anyhow::Result<()> {
+        self.src_to_dst_runner.execute_prepare_sqls().await?;
+        self.prepare_files()?;
+
+        // prepare src data
+        self.src_to_dst_runner.execute_test_sqls().await?;
+
+        // src -> files -> dst
+        self.src_to_file_runner.start_task().await?;
+        self.file_to_dst_runner.start_task().await?;
+
+        // compare data
+        let (src_db_tbs, dst_db_tbs) = self.src_to_dst_runner.get_compare_db_tbs()?;
+        assert!(
+            self.src_to_dst_runner
+                .compare_data_for_tbs(&src_db_tbs, &dst_db_tbs)
+                .await?
+        );
+        Ok(())
+    }
+
+    fn prepare_files(&self) -> anyhow::Result<()> {
+        let config = TaskConfig::new(&self.src_to_file_runner.task_config_file)?;
+        if let SinkerConfig::File { url, .. } = config.sinker {
+            if Path::new(&url).exists() {
+                std::fs::remove_dir_all(&url)?;
+            }
+        }
+        Ok(())
+    }
+}
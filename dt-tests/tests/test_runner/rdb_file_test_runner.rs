@@ -0,0 +1,228 @@
+use std::{collections::HashMap, fs::File, path::Path};
+
+use anyhow::bail;
+use dt_common::{
+    config::{config_enums::FileFormat, sinker_config::SinkerConfig, task_config::TaskConfig},
+    error::Error,
+};
+use dt_connector::sinker::file::file_encoder::FileEncoder;
+use parquet::{
+    file::reader::{FileReader, SerializedFileReader},
+    record::Field,
+};
+use serde_json::Value;
+
+use super::{
+    rdb_test_runner::{RdbTestRunner, SRC},
+    rdb_util::RdbUtil,
+};
+
+type FileRow = HashMap<String, Option<String>>;
+
+pub struct RdbFileTestRunner {
+    rdb_test_runner: RdbTestRunner,
+    // local root directory of files
+    url: String,
+    file_format: FileFormat,
+    csv_delimiter: u8,
+    op_col: String,
+    ts_col: String,
+}
+
+impl RdbFileTestRunner {
+    pub async fn new(relative_test_dir: &str) -> anyhow::Result<Self> {
+        let rdb_test_runner = RdbTestRunner::new(relative_test_dir).await?;
+        let config = TaskConfig::new(&rdb_test_runner.base.task_config_file)?;
+        match config.sinker {
+            SinkerConfig::File {
+                url,
+                file_format,
+                csv_delimiter,
+                op_col,
+                ts_col,
+                ..
+            } => Ok(Self {
+                rdb_test_runner,
+                url,
+                file_format,
+                csv_delimiter,
+                op_col,
+                ts_col,
+            }),
+            _ => bail! {Error::ConfigError("unsupported sinker config".into())},
+        }
+    }
+
+    pub async fn close(&self) -> anyhow::Result<()> {
+        self.rdb_test_runner.close().await
+    }
+
+    pub async fn run_snapshot_test(&self) -> anyhow::Result<()> {
+        self.prepare_task().await?;
+        self.rdb_test_runner.execute_test_sqls().await?;
+        self.rdb_test_runner.base.start_task().await?;
+        self.compare_data_for_tbs().await
+    }
+
+    pub async fn run_cdc_test(&self, start_millis: u64, parse_millis: u64) -> anyhow::Result<()> {
+        let runner = &self.rdb_test_runner;
+        self.prepare_task().await?;
+
+        // files are written when the task finishes
+        let task = runner.spawn_cdc_task(start_millis, parse_millis).await?;
+        runner.execute_src_sqls(&runner.base.src_test_sqls).await?;
+        runner.base.wait_task_finish(&task).await?;
+        self.compare_data_for_tbs().await
+    }
+
+    async fn prepare_task(&self) -> anyhow::Result<()> {
+        let base = &self.rdb_test_runner.base;
+        self.rdb_test_runner
+            .execute_src_sqls(&base.src_prepare_sqls)
+            .await?;
+        if Path::new(&self.url).exists() {
+            std::fs::remove_dir_all(&self.url)?;
+        }
+        Ok(())
+    }
+
+    async fn compare_data_for_tbs(&self) -> anyhow::Result<()> {
+        let (src_db_tbs, dst_db_tbs) = self.rdb_test_runner.get_compare_db_tbs()?;
+        for (src_db_tb, dst_db_tb) in src_db_tbs.iter().zip(dst_db_tbs.iter()) {
+            self.compare_tb_data(src_db_tb, dst_db_tb).await?;
+        }
+        Ok(())
+    }
+
+    async fn compare_tb_data(
+        &self,
+        src_db_tb: &(String, String),
+        dst_db_tb: &(String, String),
+    ) -> anyhow::Result<()> {
+        let src_data = self.rdb_test_runner.fetch_data(src_db_tb, SRC).await?;
+        let src_tb_meta = RdbUtil::get_tb_meta_mysql(
+            self.rdb_test_runner.src_conn_pool_mysql.as_ref().unwrap(),
+            src_db_tb,
+        )
+        .await?;
+        let id_cols = &src_tb_meta.basic.id_cols;
+
+        let mut src_rows = HashMap::new();
+        for row_data in src_data.iter() {
+            let row: FileRow = row_data
+                .after
+                .as_ref()
+                .unwrap()
+                .iter()
+                .map(|(col, v)| (col.clone(), self.normalize(FileEncoder::to_string_value(v))))
+                .collect();
+            src_rows.insert(Self::get_id(&row, id_cols), row);
+        }
+
+        // files are named by creation time, replay rows in order
+        let mut dst_rows = HashMap::new();
+        for mut row in self.read_tb_files(dst_db_tb)? {
+            let op = row.remove(&self.op_col).flatten();
+            row.remove(&self.ts_col);
+            let id = Self::get_id(&row, id_cols);
+            if op.as_deref() == Some("D") {
+                dst_rows.remove(&id);
+            } else {
+                dst_rows.insert(id, row);
+            }
+        }
+
+        println!(
+            "compare tb data, src_tb: {:?}, dst_tb: {:?}, src count: {}, dst count: {}",
+            src_db_tb,
+            dst_db_tb,
+            src_rows.len(),
+            dst_rows.len()
+        );
+        assert_eq!(src_rows, dst_rows);
+        Ok(())
+    }
+
+    fn get_id(row: &FileRow, id_cols: &[String]) -> String {
+        let ids: Vec<&Option<String>> = id_cols.iter().map(|col| &row[col]).collect();
+        format!("{:?}", ids)
+    }
+
+    fn normalize(&self, value: Option<String>) -> Option<String> {
+        // null is written as empty field in csv
+        match self.file_format {
+            FileFormat::Csv => Some(value.unwrap_or_default()),
+            _ => value,
+        }
+    }
+
+    fn read_tb_files(&self, dst_db_tb: &(String, String)) -> anyhow::Result<Vec<FileRow>> {
+        let dir = format!("{}/{}/{}", self.url, dst_db_tb.0, dst_db_tb.1);
+        let mut rows = Vec::new();
+        if !Path::new(&dir).exists() {
+            return Ok(rows);
+        }
+
+        let mut file_paths: Vec<_> = std::fs::read_dir(&dir)?
+            .map(|entry| entry.unwrap().path())
+            .collect();
+        file_paths.sort();
+        for file_path in file_paths {
+            match self.file_format {
+                FileFormat::Csv => {
+                    let mut reader = csv::ReaderBuilder::new()
+                        .delimiter(self.csv_delimiter)
+                        .from_path(&file_path)?;
+                    let headers = reader.headers()?.clone();
+                    for record in reader.records() {
+                        let row = headers
+                            .iter()
+                            .zip(record?.iter())
+                            .map(|(col, v)| (col.to_string(), Some(v.to_string())))
+                            .collect();
+                        rows.push(row);
+                    }
+                }
+
+                FileFormat::Jsonl => {
+                    for line in std::fs::read_to_string(&file_path)?.lines() {
+                        let doc: serde_json::Map<String, Value> = serde_json::from_str(line)?;
+                        let row = doc
+                            .into_iter()
+                            .map(|(col, v)| {
+                                let v = match v {
+                                    Value::Null => None,
+                                    Value::String(v) => Some(v),
+                                    _ => Some(v.to_string()),
+                                };
+                                (col, v)
+                            })
+                            .collect();
+                        rows.push(row);
+                    }
+                }
+
+                FileFormat::Parquet => {
+                    let reader = SerializedFileReader::new(File::open(&file_path)?)?;
+                    for record in reader.get_row_iter(None)? {
+                        let row = record?
+                            .get_column_iter()
+                            .map(|(col, v)| {
+                                let v = match v {
+                                    Field::Null => None,
+                                    Field::Str(v) => Some(v.clone()),
+                                    // the same as binary values in text formats
+                                    Field::Bytes(v) => Some(hex::encode(v.data())),
+                                    _ => Some(v.to_string()),
+                                };
+                                (col.clone(), v)
+                            })
+                            .collect();
+                        rows.push(row);
+                    }
+                }
+            }
+        }
+        Ok(rows)
+    }
+}
@@ -3,6 +3,7 @@ use std::time::Duration;
 use crate::test_config_util::TestConfigUtil;
 
 use super::base_test_runner::BaseTestRunner;
+use super::mock_schema_registry::MockSchemaRegistry;
 use super::rdb_test_runner::RdbTestRunner;
 use dt_common::config::sinker_config::SinkerConfig;
 use dt_common::config::task_config::TaskConfig;
@@ -128,15 +129,32 @@ impl RdbKafkaRdbTestRunner {
     }
 
     async fn prepare_kafka(&self) -> anyhow::Result<()> {
-        let mut topics: Vec<&str> = vec![];
-        let re = Regex::new(r"create topic ([\w\W]+)").unwrap();
+        // create topic {topic} [{partition_count}] [{config}={value} ...]
+        let mut topics: Vec<(&str, i32, Vec<(&str, &str)>)> = vec![];
+        let re = Regex::new(r"create topic ([^\s]+)(\s+(\d+))?((\s+[^\s=]+=[^\s]+)*)").unwrap();
         for sql in self.src_to_kafka_runner.dst_prepare_sqls.iter() {
             let cap = re.captures(sql).unwrap();
-            topics.push(cap.get(1).unwrap().as_str());
+            let partition_count = cap.get(3).map_or(1, |v| v.as_str().parse().unwrap());
+            let configs = cap.get(4).map_or(vec![], |v| {
+                v.as_str()
+                    .split_whitespace()
+                    .filter_map(|kv| kv.split_once('='))
+                    .collect()
+            });
+            topics.push((cap.get(1).unwrap().as_str(), partition_count, configs));
         }
 
         let config = TaskConfig::new(&self.src_to_kafka_runner.task_config_file).unwrap();
-        if let SinkerConfig::Kafka { url, .. } = config.sinker {
+        if let SinkerConfig::Kafka {
+            url,
+            schema_registry_url,
+            ..
+        } = config.sinker
+        {
+            if !schema_registry_url.is_empty() {
+                MockSchemaRegistry::start(&schema_registry_url);
+            }
+
             let check_topic_exist = |meta: &Metadata, topic: &str| -> bool {
                 for exist_topic in meta.topics() {
                     if exist_topic.name() == topic && !exist_topic.partitions().is_empty() {
@@ -148,7 +166,7 @@ impl RdbKafkaRdbTestRunner {
 
             let admin_client = Self::create_kafka_admin_client(&url);
             let consumer: BaseConsumer = Self::create_kafka_base_consumer(&url);
-            for topic in topics.iter() {
+            for (topic, partition_count, configs) in topics {
                 // delete_topic/create_topic may fail
                 let mut meta = consumer.fetch_metadata(Some(topic), Duration::from_secs(10))?;
                 while check_topic_exist(&meta, topic) {
@@ -158,7 +176,7 @@ impl RdbKafkaRdbTestRunner {
                 }
 
                 while !check_topic_exist(&meta, topic) {
-                    Self::create_topic(&admin_client, topic).await;
+                    Self::create_topic(&admin_client, topic, partition_count, &configs).await;
                     meta = consumer.fetch_metadata(Some(topic), Duration::from_secs(10))?;
                     TimeUtil::sleep_millis(100).await;
                     println!("kafka topic: [{}] is NOT ready", topic);
@@ -182,8 +200,16 @@ impl RdbKafkaRdbTestRunner {
         config.create().unwrap()
     }
 
-    async fn create_topic(client: &AdminClient<DefaultClientContext>, topic: &str) {
-        let topic = NewTopic::new(topic, 1, TopicReplication::Fixed(1));
+    async fn create_topic(
+        client: &AdminClient<DefaultClientContext>,
+        topic: &str,
+        partition_count: i32,
+        configs: &[(&str, &str)],
+    ) {
+        let mut topic = NewTopic::new(topic, partition_count, TopicReplication::Fixed(1));
+        for (key, value) in configs {
+            topic = topic.set(*key, *value);
+        }
         client
             .create_topics(&[topic], &AdminOptions::new())
             .await
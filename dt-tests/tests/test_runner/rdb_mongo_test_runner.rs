@@ -0,0 +1,168 @@
+use anyhow::bail;
+use dt_common::{
+    config::{config_enums::DbType, sinker_config::SinkerConfig, task_config::TaskConfig},
+    error::Error,
+    meta::{
+        adaptor::mongo_col_value_convertor::MongoColValueConvertor,
+        mongo::mongo_constant::MongoConstants,
+    },
+    utils::time_util::TimeUtil,
+};
+use dt_connector::rdb_router::RdbRouter;
+use dt_task::task_util::TaskUtil;
+use mongodb::{
+    bson::{doc, Document},
+    Client,
+};
+use sqlx::{MySql, Pool};
+
+use crate::test_runner::rdb_util::RdbUtil;
+
+use super::{base_test_runner::BaseTestRunner, rdb_test_runner::RdbTestRunner};
+
+pub struct RdbMongoTestRunner {
+    pub base: BaseTestRunner,
+    mysql_conn_pool: Pool<MySql>,
+    mongo_client: Client,
+    router: RdbRouter,
+}
+
+impl RdbMongoTestRunner {
+    pub async fn new(relative_test_dir: &str) -> anyhow::Result<Self> {
+        let base = BaseTestRunner::new(relative_test_dir).await.unwrap();
+        let config = TaskConfig::new(&base.task_config_file).unwrap();
+
+        let mysql_conn_pool =
+            TaskUtil::create_mysql_conn_pool(&config.extractor_basic.url, 1, false).await?;
+        let mongo_client = match config.sinker {
+            SinkerConfig::Mongo { url, app_name, .. } => {
+                TaskUtil::create_mongo_client(&url, &app_name).await?
+            }
+            _ => {
+                bail! {Error::ConfigError("unsupported sinker config".into())};
+            }
+        };
+        let router = RdbRouter::from_config(&config.router, &DbType::Mysql)?;
+
+        Ok(Self {
+            base,
+            mysql_conn_pool,
+            mongo_client,
+            router,
+        })
+    }
+
+    pub async fn close(&self) -> anyhow::Result<()> {
+        self.mysql_conn_pool.close().await;
+        Ok(())
+    }
+
+    pub async fn run_snapshot_test(&mut self) -> anyhow::Result<()> {
+        self.prepare().await?;
+        RdbUtil::execute_sqls_mysql(&self.mysql_conn_pool, &self.base.src_test_sqls).await?;
+        self.base.start_task().await?;
+
+        let db_tbs = RdbTestRunner::get_compare_db_tbs_from_sqls(
+            &DbType::Mysql,
+            &self.base.src_prepare_sqls,
+        )?;
+        self.compare_data_for_tbs(&db_tbs).await
+    }
+
+    pub async fn run_cdc_test(
+        &mut self,
+        start_millis: u64,
+        parse_millis: u64,
+    ) -> anyhow::Result<()> {
+        self.prepare().await?;
+
+        let task = self.base.spawn_task().await?;
+        TimeUtil::sleep_millis(start_millis).await;
+
+        let mut src_insert_sqls = Vec::new();
+        let mut src_update_sqls = Vec::new();
+        let mut src_delete_sqls = Vec::new();
+        for sql in self.base.src_test_sqls.iter() {
+            if sql.to_lowercase().starts_with("insert") {
+                src_insert_sqls.push(sql.clone());
+            } else if sql.to_lowercase().starts_with("update") {
+                src_update_sqls.push(sql.clone());
+            } else {
+                src_delete_sqls.push(sql.clone());
+            }
+        }
+
+        let db_tbs = RdbTestRunner::get_compare_db_tbs_from_sqls(
+            &DbType::Mysql,
+            &self.base.src_prepare_sqls,
+        )?;
+
+        for sqls in [src_insert_sqls, src_update_sqls, src_delete_sqls] {
+            RdbUtil::execute_sqls_mysql(&self.mysql_conn_pool, &sqls).await?;
+            TimeUtil::sleep_millis(parse_millis).await;
+            self.compare_data_for_tbs(&db_tbs).await?;
+        }
+
+        self.base.abort_task(&task).await
+    }
+
+    async fn prepare(&self) -> anyhow::Result<()> {
+        RdbUtil::execute_sqls_mysql(&self.mysql_conn_pool, &self.base.src_prepare_sqls).await?;
+
+        // dst_prepare.sql: use db_1, db.dropDatabase();
+        let mut db = String::new();
+        for sql in self.base.dst_prepare_sqls.iter() {
+            if let Some(use_db) = sql.strip_prefix("use ") {
+                db = use_db.trim().to_string();
+            } else if sql.contains("dropDatabase") {
+                self.mongo_client.database(&db).drop(None).await?;
+            }
+        }
+        Ok(())
+    }
+
+    async fn compare_data_for_tbs(&self, db_tbs: &Vec<(String, String)>) -> anyhow::Result<()> {
+        for db_tb in db_tbs {
+            self.compare_tb_data(db_tb).await?;
+        }
+        Ok(())
+    }
+
+    async fn compare_tb_data(&self, db_tb: &(String, String)) -> anyhow::Result<()> {
+        let tb_meta = RdbUtil::get_tb_meta_mysql(&self.mysql_conn_pool, db_tb).await?;
+        let src_data = RdbUtil::fetch_data_mysql(&self.mysql_conn_pool, None, db_tb, "").await?;
+
+        let (dst_db, dst_tb) = self.router.get_tb_map(&db_tb.0, &db_tb.1);
+        let col_map = self.router.get_col_map(&db_tb.0, &db_tb.1);
+        let collection = self
+            .mongo_client
+            .database(dst_db)
+            .collection::<Document>(dst_tb);
+
+        let dst_count = collection.count_documents(None, None).await?;
+        println!(
+            "compare tb data, db: {}, tb: {}, src count: {}, dst count: {}",
+            db_tb.0,
+            db_tb.1,
+            src_data.len(),
+            dst_count
+        );
+        assert_eq!(src_data.len() as u64, dst_count);
+
+        for row_data in src_data {
+            // rows are routed the same way as by the task before being converted
+            let row_data = self.router.route_row(row_data);
+            let src_doc = MongoColValueConvertor::to_doc(
+                row_data.after.as_ref().unwrap(),
+                &tb_meta.basic,
+                col_map,
+            );
+            let id = src_doc.get(MongoConstants::ID).unwrap().clone();
+            let dst_doc = collection
+                .find_one(doc! {MongoConstants::ID: id}, None)
+                .await?;
+            assert_eq!(Some(src_doc), dst_doc);
+        }
+        Ok(())
+    }
+}
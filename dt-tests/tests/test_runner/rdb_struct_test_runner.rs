@@ -1,11 +1,16 @@
 use dt_common::{
-    config::{config_enums::DbType, task_config::TaskConfig},
-    meta::ddl_meta::{ddl_parser::DdlParser, ddl_statement::DdlStatement},
+    config::{config_enums::DbType, sinker_config::SinkerConfig, task_config::TaskConfig},
+    meta::{
+        ddl_meta::{ddl_parser::DdlParser, ddl_statement::DdlStatement},
+        struct_meta::mysql_to_pg_converter::MysqlToPgConverter,
+    },
 };
 use dt_connector::meta_fetcher::{
     mysql::mysql_struct_check_fetcher::MysqlStructCheckFetcher,
     pg::pg_struct_check_fetcher::PgStructCheckFetcher,
 };
+use futures::TryStreamExt;
+use sqlx::Row;
 use std::collections::{HashMap, HashSet};
 
 use super::{base_test_runner::BaseTestRunner, rdb_test_runner::RdbTestRunner};
@@ -200,6 +205,84 @@ impl RdbStructTestRunner {
         Ok(())
     }
 
+    /// compares dst pg columns with src mysql columns converted by MysqlToPgConverter
+    pub async fn run_mysql_to_pg_struct_test(&mut self) -> anyhow::Result<()> {
+        self.base.execute_prepare_sqls().await?;
+        self.base.base.start_task().await?;
+
+        let config = TaskConfig::new(&self.base.base.task_config_file).unwrap();
+        let converter = match config.sinker {
+            SinkerConfig::PgStruct { col_type_map, .. } => {
+                MysqlToPgConverter::from_config(&col_type_map)?
+            }
+            _ => MysqlToPgConverter::default(),
+        };
+
+        let src_conn_pool = self.base.src_conn_pool_mysql.as_ref().unwrap().clone();
+        let dst_check_fetcher = PgStructCheckFetcher {
+            conn_pool: self.base.dst_conn_pool_pg.as_mut().unwrap().clone(),
+        };
+
+        let (src_db_tbs, dst_db_tbs) = self.base.get_compare_db_tbs().unwrap();
+        for i in 0..src_db_tbs.len() {
+            let (src_db, src_tb) = &src_db_tbs[i];
+            let (dst_schema, dst_tb) = &dst_db_tbs[i];
+            println!(
+                "comparing src table: {}.{} with dst table: {}.{}\n",
+                src_db, src_tb, dst_schema, dst_tb
+            );
+
+            let sql = format!(
+                "SELECT COLUMN_NAME, COLUMN_TYPE, IS_NULLABLE, EXTRA FROM information_schema.columns
+                WHERE TABLE_SCHEMA = '{}' AND TABLE_NAME = '{}' ORDER BY ORDINAL_POSITION",
+                src_db, src_tb
+            );
+            let mut src_columns = Vec::new();
+            let mut rows = sqlx::query(&sql).fetch(&src_conn_pool);
+            while let Some(row) = rows.try_next().await? {
+                let col: String = row.try_get("COLUMN_NAME")?;
+                let col_type: String = row.try_get("COLUMN_TYPE")?;
+                let is_nullable: String = row.try_get("IS_NULLABLE")?;
+                let extra: String = row.try_get("EXTRA")?;
+                src_columns.push((col, col_type, is_nullable == "YES", extra));
+            }
+
+            let dst_table = dst_check_fetcher.fetch_table(dst_schema, dst_tb).await?;
+            assert_eq!(src_columns.len(), dst_table.columns.len());
+
+            let col_type_overrides = converter
+                .col_type_map
+                .get(&(src_db.to_string(), src_tb.to_string()));
+            for (j, (col, col_type, is_nullable, extra)) in src_columns.iter().enumerate() {
+                let dst_column = &dst_table.columns[j];
+                let auto_increment = extra.to_lowercase().contains("auto_increment");
+
+                let mut expect_type = MysqlToPgConverter::get_pg_col_type(col_type);
+                if auto_increment && expect_type == "numeric(20,0)" {
+                    expect_type = "bigint".into();
+                }
+                if let Some(override_type) = col_type_overrides.and_then(|i| i.get(col)) {
+                    expect_type = override_type.clone();
+                }
+
+                println!(
+                    "column: {}, src type: {}, dst column: {:?}",
+                    col, col_type, dst_column
+                );
+                assert_eq!(dst_column.get("attname"), Some(col));
+                assert_eq!(dst_column.get("format_type"), Some(&expect_type));
+                assert_eq!(
+                    dst_column.get("attnotnull").map(|i| i.as_str()),
+                    Some(if *is_nullable { "false" } else { "true" })
+                );
+                if auto_increment {
+                    assert_eq!(dst_column.get("attidentity").map(|i| i.as_str()), Some("d"));
+                }
+            }
+        }
+        Ok(())
+    }
+
     pub async fn run_struct_test_without_check(&mut self) -> anyhow::Result<()> {
         self.base.execute_prepare_sqls().await?;
         self.base.base.start_task().await
@@ -1,23 +1,40 @@
-use std::{collections::HashSet, str::FromStr};
+use std::{
+    collections::HashSet,
+    str::FromStr,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    time::Instant,
+};
+
+use anyhow::bail;
 
 use chrono::{Duration, Utc};
 use dt_common::{
     config::{
-        config_enums::DbType, config_token_parser::ConfigTokenParser,
-        extractor_config::ExtractorConfig, meta_center_config::MetaCenterConfig,
-        sinker_config::SinkerConfig, task_config::TaskConfig,
+        config_enums::{DbType, PgSlotLimitPolicy},
+        config_token_parser::ConfigTokenParser,
+        extractor_config::ExtractorConfig,
+        meta_center_config::MetaCenterConfig,
+        sinker_config::SinkerConfig,
+        task_config::TaskConfig,
     },
     meta::{ddl_meta::ddl_type::DdlType, time::dt_utc_time::DtNaiveTime},
+    monitor::{counter_type::CounterType, monitor::Monitor},
     rdb_filter::RdbFilter,
     utils::{sql_util::SqlUtil, time_util::TimeUtil},
 };
 
 use dt_common::meta::{
-    col_value::ColValue, ddl_meta::ddl_parser::DdlParser,
-    mysql::mysql_meta_manager::MysqlMetaManager, row_data::RowData,
+    adaptor::pg_to_mysql_col_value_convertor::PgToMysqlColValueConvertor, col_value::ColValue,
+    ddl_meta::ddl_parser::DdlParser, mysql::mysql_meta_manager::MysqlMetaManager,
+    row_data::RowData,
 };
 use dt_connector::{
-    meta_fetcher::mysql::mysql_struct_check_fetcher::MysqlStructCheckFetcher, rdb_router::RdbRouter,
+    extractor::pg::pg_slot_monitor::PgSlotMonitor,
+    meta_fetcher::mysql::mysql_struct_check_fetcher::MysqlStructCheckFetcher,
+    rdb_router::RdbRouter,
 };
 use dt_task::{task_runner::TaskRunner, task_util::TaskUtil};
 
@@ -157,6 +174,76 @@ impl RdbTestRunner {
         Ok(())
     }
 
+    /// Runs a snapshot task with [sequence] sync_on_finish=true, then the sequences (pg) /
+    /// auto_increment counters (mysql) of the target should be the same as the source
+    pub async fn run_sequence_test(&self, schema: &str) -> anyhow::Result<()> {
+        self.run_snapshot_test(false).await?;
+
+        if let (Some(src_pool), Some(dst_pool)) = (&self.src_conn_pool_pg, &self.dst_conn_pool_pg) {
+            let src_sequences = Self::fetch_pg_sequences(src_pool, schema).await?;
+            let dst_sequences = Self::fetch_pg_sequences(dst_pool, schema).await?;
+            assert!(!src_sequences.is_empty());
+            assert_eq!(src_sequences, dst_sequences);
+
+            // inserts on the target won't collide with migrated values
+            for (sequence, last_value) in src_sequences {
+                let sequence = format!(
+                    "{}.{}",
+                    SqlUtil::quote_identifier(schema, &DbType::Pg),
+                    SqlUtil::quote_identifier(&sequence, &DbType::Pg)
+                );
+                let next_value: i64 = query("SELECT nextval($1::regclass)")
+                    .bind(&sequence)
+                    .fetch_one(dst_pool)
+                    .await?
+                    .try_get(0)?;
+                assert_eq!(Some(next_value - 1), last_value);
+            }
+        }
+
+        if let (Some(src_pool), Some(dst_pool)) =
+            (&self.src_conn_pool_mysql, &self.dst_conn_pool_mysql)
+        {
+            let src_counters = Self::fetch_mysql_auto_increments(src_pool, schema).await?;
+            let dst_counters = Self::fetch_mysql_auto_increments(dst_pool, schema).await?;
+            assert!(!src_counters.is_empty());
+            assert_eq!(src_counters, dst_counters);
+        }
+        Ok(())
+    }
+
+    async fn fetch_pg_sequences(
+        conn_pool: &Pool<Postgres>,
+        schema: &str,
+    ) -> anyhow::Result<Vec<(String, Option<i64>)>> {
+        let sql = "SELECT sequencename, last_value FROM pg_catalog.pg_sequences
+            WHERE schemaname = $1 ORDER BY sequencename";
+        let mut sequences = Vec::new();
+        for row in query(sql).bind(schema).fetch_all(conn_pool).await? {
+            sequences.push((row.try_get(0)?, row.try_get(1)?));
+        }
+        Ok(sequences)
+    }
+
+    async fn fetch_mysql_auto_increments(
+        conn_pool: &Pool<MySql>,
+        schema: &str,
+    ) -> anyhow::Result<Vec<(String, u64)>> {
+        let mut conn = conn_pool.acquire().await?;
+        // mysql 8.0 caches table statistics, the variable does not exist in 5.7
+        let _ = query("SET SESSION information_schema_stats_expiry = 0")
+            .execute(&mut *conn)
+            .await;
+
+        let sql = "SELECT TABLE_NAME, AUTO_INCREMENT FROM information_schema.TABLES
+            WHERE TABLE_SCHEMA = ? AND AUTO_INCREMENT IS NOT NULL ORDER BY TABLE_NAME";
+        let mut counters = Vec::new();
+        for row in query(sql).bind(schema).fetch_all(&mut *conn).await? {
+            counters.push((row.try_get(0)?, row.try_get(1)?));
+        }
+        Ok(counters)
+    }
+
     pub async fn run_ddl_test(&self, start_millis: u64, parse_millis: u64) -> anyhow::Result<()> {
         self.execute_prepare_sqls().await?;
 
@@ -413,6 +500,64 @@ impl RdbTestRunner {
         Ok(())
     }
 
+    /// The slot is created and fed with wal before the task starts,
+    /// so it exceeds slot_lag_limit_mb at the first check
+    pub async fn run_slot_limit_test(&self, parse_millis: u64) -> anyhow::Result<()> {
+        let ExtractorConfig::PgCdc {
+            slot_name,
+            slot_lag_limit_mb,
+            ..
+        } = self.config.extractor.clone()
+        else {
+            bail!("slot limit test only supports pg cdc");
+        };
+        let conn_pool = self.src_conn_pool_pg.clone().unwrap();
+
+        self.execute_prepare_sqls().await?;
+        self.execute_src_sqls(&self.base.src_test_sqls).await?;
+
+        // counters
+        let monitor = Arc::new(Mutex::new(Monitor::new("extractor", "", 1, 100, 1)));
+        let mut slot_monitor = PgSlotMonitor {
+            conn_pool: conn_pool.clone(),
+            slot_name: slot_name.clone(),
+            check_interval_secs: 1,
+            lag_warn_mb: 0,
+            lag_limit_mb: slot_lag_limit_mb,
+            limit_policy: PgSlotLimitPolicy::None,
+            monitor: monitor.clone(),
+            limit_reached: Arc::new(AtomicBool::new(false)),
+        };
+        slot_monitor.check().await?;
+        let restart_lag =
+            monitor.lock().unwrap().no_window_counters[&CounterType::SlotRestartLagBytes].value;
+        assert!(restart_lag >= slot_lag_limit_mb as usize * 1024 * 1024);
+        assert!(!slot_monitor.limit_reached.load(Ordering::Acquire));
+
+        // policy: fail, the task stops before end_time_utc, and the slot is kept
+        // even if drop_slot_on_finish is set, so the task can resume after restart
+        self.update_cdc_task_config(0, parse_millis).await?;
+        let start_time = Instant::now();
+        assert!(self.base.start_task().await.is_err());
+        assert!(start_time.elapsed().as_millis() < parse_millis as u128);
+        assert!(Self::slot_exists(&conn_pool, &slot_name).await?);
+
+        // policy: drop_slot
+        slot_monitor.limit_policy = PgSlotLimitPolicy::DropSlot;
+        slot_monitor.check().await?;
+        assert!(slot_monitor.limit_reached.load(Ordering::Acquire));
+        assert!(!Self::slot_exists(&conn_pool, &slot_name).await?);
+        Ok(())
+    }
+
+    async fn slot_exists(conn_pool: &Pool<Postgres>, slot_name: &str) -> anyhow::Result<bool> {
+        let sql = format!(
+            "SELECT 1 FROM pg_catalog.pg_replication_slots WHERE slot_name = '{}'",
+            slot_name
+        );
+        Ok(query(&sql).fetch_optional(conn_pool).await?.is_some())
+    }
+
     pub async fn update_cdc_task_config(
         &self,
         start_millis: u64,
@@ -600,8 +745,20 @@ impl RdbTestRunner {
         src_db_tb: &(String, String),
         dst_db_tb: &(String, String),
     ) -> anyhow::Result<bool> {
-        let src_data = self.fetch_data(src_db_tb, SRC).await?;
+        let mut src_data = self.fetch_data(src_db_tb, SRC).await?;
         let dst_data = self.fetch_data(dst_db_tb, DST).await?;
+
+        // pg -> mysql: convert src values to what they would be in target before comparing
+        if self.get_db_type(SRC) == DbType::Pg {
+            if let Some(pool) = &self.dst_conn_pool_mysql {
+                let mut meta_manager = MysqlMetaManager::new(pool.clone()).await?;
+                let tb_meta = meta_manager.get_tb_meta(&dst_db_tb.0, &dst_db_tb.1).await?;
+                for row_data in src_data.iter_mut() {
+                    PgToMysqlColValueConvertor::convert_row_data(row_data, tb_meta);
+                }
+            }
+        }
+
         println!(
             "comparing row data for src_tb: {:?}, dst_tb: {:?}, src_data count: {}, dst_data count: {}",
             src_db_tb,
@@ -7,9 +7,10 @@ use futures::executor::block_on;
 use crate::test_runner::rdb_test_runner::DST;
 
 use super::{
-    mongo_check_test_runner::MongoCheckTestRunner, mongo_test_runner::MongoTestRunner,
-    precheck_test_runner::PrecheckTestRunner, rdb_check_test_runner::RdbCheckTestRunner,
-    rdb_kafka_rdb_test_runner::RdbKafkaRdbTestRunner, rdb_lua_test_runner::RdbLuaTestRunner,
+    mongo_check_test_runner::MongoCheckTestRunner, mongo_rdb_test_runner::MongoRdbTestRunner,
+    mongo_test_runner::MongoTestRunner, precheck_test_runner::PrecheckTestRunner,
+    rdb_check_test_runner::RdbCheckTestRunner, rdb_kafka_rdb_test_runner::RdbKafkaRdbTestRunner,
+    rdb_lua_test_runner::RdbLuaTestRunner, rdb_mongo_test_runner::RdbMongoTestRunner,
     rdb_redis_test_runner::RdbRedisTestRunner, rdb_sql_test_runner::RdbSqlTestRunner,
     rdb_starrocks_test_runner::RdbStarRocksTestRunner, rdb_struct_test_runner::RdbStructTestRunner,
     rdb_test_runner::RdbTestRunner, redis_statistic_runner::RedisStatisticTestRunner,
@@ -26,6 +27,12 @@ impl TestBase {
         runner.close().await.unwrap();
     }
 
+    pub async fn run_sequence_test(test_dir: &str, schema: &str) {
+        let runner = RdbTestRunner::new(test_dir).await.unwrap();
+        runner.run_sequence_test(schema).await.unwrap();
+        runner.close().await.unwrap();
+    }
+
     pub async fn run_snapshot_test_and_check_dst_count(
         test_dir: &str,
         db_type: &DbType,
@@ -97,6 +104,12 @@ impl TestBase {
         runner.close().await.unwrap();
     }
 
+    pub async fn run_slot_limit_test(test_dir: &str, parse_millis: u64) {
+        let runner = RdbTestRunner::new(test_dir).await.unwrap();
+        runner.run_slot_limit_test(parse_millis).await.unwrap();
+        runner.close().await.unwrap();
+    }
+
     pub async fn run_ddl_test(test_dir: &str, start_millis: u64, parse_millis: u64) {
         let runner = RdbTestRunner::new(test_dir).await.unwrap();
         runner
@@ -194,6 +207,21 @@ impl TestBase {
         runner.run_review_test().await.unwrap();
     }
 
+    pub async fn run_mongo_rdb_snapshot_test(test_dir: &str) {
+        let runner = MongoRdbTestRunner::new(test_dir).await.unwrap();
+        runner.run_snapshot_test().await.unwrap();
+        runner.close().await.unwrap();
+    }
+
+    pub async fn run_mongo_rdb_cdc_test(test_dir: &str, start_millis: u64, parse_millis: u64) {
+        let runner = MongoRdbTestRunner::new(test_dir).await.unwrap();
+        runner
+            .run_cdc_test(start_millis, parse_millis)
+            .await
+            .unwrap();
+        runner.close().await.unwrap();
+    }
+
     pub async fn run_redis_snapshot_test(test_dir: &str) {
         let mut runner = RedisTestRunner::new_default(test_dir).await.unwrap();
         runner.run_snapshot_test().await.unwrap();
@@ -258,6 +286,13 @@ impl TestBase {
         runner.close().await.unwrap();
     }
 
+    pub async fn run_mysql_to_pg_struct_test(test_dir: &str) {
+        let mut runner = RdbStructTestRunner::new(test_dir).await.unwrap();
+        runner.run_mysql_to_pg_struct_test().await.unwrap();
+        runner.base.execute_clean_sqls().await.unwrap();
+        runner.close().await.unwrap();
+    }
+
     pub async fn run_precheck_test(
         test_dir: &str,
         ignore_check_items: &HashSet<String>,
@@ -310,6 +345,21 @@ impl TestBase {
         runner.close().await.unwrap();
     }
 
+    pub async fn run_rdb_mongo_cdc_test(test_dir: &str, start_millis: u64, parse_millis: u64) {
+        let mut runner = RdbMongoTestRunner::new(test_dir).await.unwrap();
+        runner
+            .run_cdc_test(start_millis, parse_millis)
+            .await
+            .unwrap();
+        runner.close().await.unwrap();
+    }
+
+    pub async fn run_rdb_mongo_snapshot_test(test_dir: &str) {
+        let mut runner = RdbMongoTestRunner::new(test_dir).await.unwrap();
+        runner.run_snapshot_test().await.unwrap();
+        runner.close().await.unwrap();
+    }
+
     pub async fn run_rdb_starrocks_cdc_test(test_dir: &str, start_millis: u64, parse_millis: u64) {
         let runner = RdbStarRocksTestRunner::new(test_dir).await.unwrap();
         runner